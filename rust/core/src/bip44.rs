@@ -0,0 +1,129 @@
+use crate::RawKey;
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+
+pub use bip39::Mnemonic;
+
+type HmacSha512 = Hmac<Sha512>;
+
+const HARDENED: u32 = 0x8000_0000;
+const BIP44_PURPOSE: u32 = 44;
+/// Registered coin type of Nano in SLIP-44
+pub const NANO_COIN_TYPE: u32 = 165;
+
+/// A node of a SLIP-10 ed25519 key tree.
+/// Only hardened derivation is defined for ed25519
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Slip10Node {
+    pub key: RawKey,
+    pub chain_code: RawKey,
+}
+
+impl Slip10Node {
+    pub fn new(key: RawKey, chain_code: RawKey) -> Self {
+        Self { key, chain_code }
+    }
+
+    pub fn master(seed: &[u8]) -> Self {
+        Self::hmac(b"ed25519 seed", &[seed])
+    }
+
+    /// Derives the hardened child `index'`
+    pub fn derive(&self, index: u32) -> Self {
+        Self::hmac(
+            self.chain_code.as_bytes(),
+            &[&[0], self.key.as_bytes(), &(index | HARDENED).to_be_bytes()],
+        )
+    }
+
+    pub fn derive_path(&self, path: &[u32]) -> Self {
+        path.iter()
+            .fold(self.clone(), |node, index| node.derive(*index))
+    }
+
+    fn hmac(key: &[u8], data: &[&[u8]]) -> Self {
+        let mut mac = HmacSha512::new_from_slice(key).unwrap();
+        for d in data {
+            mac.update(d);
+        }
+        let result = mac.finalize().into_bytes();
+        Self {
+            key: RawKey::from_bytes(result[..32].try_into().unwrap()),
+            chain_code: RawKey::from_bytes(result[32..].try_into().unwrap()),
+        }
+    }
+}
+
+/// The node `m/44'/165'` of which all Nano accounts are hardened children
+pub fn bip44_account_node(mnemonic: &Mnemonic, passphrase: &str) -> Slip10Node {
+    Slip10Node::master(&mnemonic.to_seed(passphrase)).derive_path(&[BIP44_PURPOSE, NANO_COIN_TYPE])
+}
+
+/// Private key of the account at the path `m/44'/165'/index'`
+pub fn bip44_key(mnemonic: &Mnemonic, passphrase: &str, index: u32) -> RawKey {
+    bip44_account_node(mnemonic, passphrase).derive(index).key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Account, PublicKey};
+
+    // Test vector 1 for ed25519 of the SLIP-10 specification
+    #[test]
+    fn slip10_test_vector() {
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let master = Slip10Node::master(&seed);
+        assert_eq!(
+            master,
+            Slip10Node::new(
+                RawKey::decode_hex(
+                    "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7"
+                )
+                .unwrap(),
+                RawKey::decode_hex(
+                    "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb"
+                )
+                .unwrap()
+            )
+        );
+        assert_eq!(
+            master.derive(0),
+            Slip10Node::new(
+                RawKey::decode_hex(
+                    "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3"
+                )
+                .unwrap(),
+                RawKey::decode_hex(
+                    "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69"
+                )
+                .unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn nano_bip44_test_vector() {
+        let mnemonic = Mnemonic::parse("edge defense waste choose enrich upon flee junk siren film clown finish luggage leader kid quick brick print evidence swap drill paddle truly occur").unwrap();
+        let key = bip44_key(&mnemonic, "some password", 0);
+        assert_eq!(
+            key,
+            RawKey::decode_hex("3BE4FC2EF3F3B7374E6FC4FB6E7BB153F8A2998B3B3DAB50853EABE128024143")
+                .unwrap()
+        );
+        let account = Account::from(PublicKey::try_from(&key).unwrap());
+        assert_eq!(
+            account.encode_account(),
+            "nano_1pu7p5n3ghq1i1p4rhmek41f5add1uh34xpb94nkbxe8g4a6x1p69emk8y1d"
+        );
+    }
+
+    #[test]
+    fn derive_path() {
+        let master = Slip10Node::master(&[1, 2, 3]);
+        assert_eq!(
+            master.derive_path(&[44, 165]),
+            master.derive(44).derive(165)
+        );
+    }
+}
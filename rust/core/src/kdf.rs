@@ -1,30 +1,174 @@
 use crate::RawKey;
 use argon2::{Variant, Version};
+use serde::{Deserialize, Serialize};
+
+/// Parameters of an Argon2id key derivation
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Argon2idParams {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl Argon2idParams {
+    pub const MAX_MEMORY_KIB: u32 = 1024 * 1024;
+    pub const MAX_ITERATIONS: u32 = 32;
+    pub const MAX_PARALLELISM: u32 = 16;
+
+    /// Cheap parameters for tests and the dev network
+    pub fn new_dev() -> Self {
+        Self {
+            memory_kib: 8,
+            iterations: 1,
+            parallelism: 1,
+        }
+    }
+
+    /// The parameters may come from an untrusted wallet backup, so they
+    /// are bounded to keep the derivation from exhausting memory or time
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.parallelism == 0 || self.parallelism > Self::MAX_PARALLELISM {
+            bail!(
+                "Argon2id parallelism must be between 1 and {}",
+                Self::MAX_PARALLELISM
+            );
+        }
+        if self.iterations == 0 || self.iterations > Self::MAX_ITERATIONS {
+            bail!(
+                "Argon2id iterations must be between 1 and {}",
+                Self::MAX_ITERATIONS
+            );
+        }
+        let min_memory = 8 * self.parallelism;
+        if self.memory_kib < min_memory || self.memory_kib > Self::MAX_MEMORY_KIB {
+            bail!(
+                "Argon2id memory must be between {} and {} KiB",
+                min_memory,
+                Self::MAX_MEMORY_KIB
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Default for Argon2idParams {
+    fn default() -> Self {
+        Self {
+            memory_kib: 64 * 1024,
+            iterations: 3,
+            parallelism: 1,
+        }
+    }
+}
 
 /// Key derivation function
 #[derive(Clone)]
 pub struct KeyDerivationFunction {
     kdf_work: u32,
+    time_cost: u32,
+    lanes: u32,
+    variant: Variant,
+    version: Version,
 }
 
 impl KeyDerivationFunction {
+    /// The Argon2d variant which is used for encrypting the wallet keys
     pub fn new(kdf_work: u32) -> Self {
-        Self { kdf_work }
+        Self {
+            kdf_work,
+            time_cost: 1,
+            lanes: 1,
+            variant: Variant::Argon2d,
+            version: Version::Version10,
+        }
+    }
+
+    pub fn argon2id(params: &Argon2idParams) -> anyhow::Result<Self> {
+        params.validate()?;
+        Ok(Self {
+            kdf_work: params.memory_kib,
+            time_cost: params.iterations,
+            lanes: params.parallelism,
+            variant: Variant::Argon2id,
+            version: Version::Version13,
+        })
     }
 
-    pub fn hash_password(&self, password: &str, salt: &[u8; 32]) -> RawKey {
+    pub fn hash_password(&self, password: &str, salt: &[u8; 32]) -> anyhow::Result<RawKey> {
         let config = argon2::Config {
             hash_length: 32,
-            lanes: 1,
+            lanes: self.lanes,
             mem_cost: self.kdf_work,
             thread_mode: argon2::ThreadMode::Sequential,
-            time_cost: 1,
-            variant: Variant::Argon2d,
-            version: Version::Version10,
+            time_cost: self.time_cost,
+            variant: self.variant,
+            version: self.version,
             ..Default::default()
         };
 
-        let hash = argon2::hash_raw(password.as_bytes(), salt, &config).unwrap();
-        RawKey::from_bytes(hash.as_slice().try_into().unwrap())
+        let hash = argon2::hash_raw(password.as_bytes(), salt, &config)?;
+        Ok(RawKey::from_bytes(hash.as_slice().try_into()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn argon2id_differs_from_legacy_kdf() {
+        let salt = [1; 32];
+        let legacy = KeyDerivationFunction::new(8)
+            .hash_password("password", &salt)
+            .unwrap();
+        let argon2id = KeyDerivationFunction::argon2id(&Argon2idParams::new_dev())
+            .unwrap()
+            .hash_password("password", &salt)
+            .unwrap();
+        assert_ne!(legacy, argon2id);
+        assert_eq!(
+            argon2id,
+            KeyDerivationFunction::argon2id(&Argon2idParams::new_dev())
+                .unwrap()
+                .hash_password("password", &salt)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn reject_out_of_range_params() {
+        let valid = Argon2idParams::new_dev();
+        assert!(valid.validate().is_ok());
+        assert!(Argon2idParams::default().validate().is_ok());
+
+        let params = [
+            Argon2idParams {
+                memory_kib: Argon2idParams::MAX_MEMORY_KIB + 1,
+                ..valid.clone()
+            },
+            Argon2idParams {
+                memory_kib: 7,
+                ..valid.clone()
+            },
+            Argon2idParams {
+                iterations: 0,
+                ..valid.clone()
+            },
+            Argon2idParams {
+                iterations: Argon2idParams::MAX_ITERATIONS + 1,
+                ..valid.clone()
+            },
+            Argon2idParams {
+                parallelism: 0,
+                ..valid.clone()
+            },
+            Argon2idParams {
+                parallelism: 2,
+                ..valid.clone()
+            },
+        ];
+        for p in params {
+            assert!(KeyDerivationFunction::argon2id(&p).is_err(), "{:?}", p);
+        }
     }
 }
@@ -70,8 +70,17 @@ mod unchecked_info;
 pub use unchecked_info::{UncheckedInfo, UncheckedKey};
 
 mod kdf;
-pub use kdf::KeyDerivationFunction;
+pub use kdf::{Argon2idParams, KeyDerivationFunction};
+
+mod rep_details;
+pub use rep_details::{RepDetails, RepTelemetry, REP_ACTIVITY_HOURS, REP_LATENCY_BUCKETS};
+
+mod bip44;
+pub use bip44::{bip44_account_node, bip44_key, Mnemonic, Slip10Node, NANO_COIN_TYPE};
+
+mod wallets_error;
 use utils::{BufferWriter, Deserialize, Serialize, Stream};
+pub use wallets_error::WalletsError;
 
 use std::{
     fmt::{Debug, Display, Write},
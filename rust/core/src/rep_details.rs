@@ -0,0 +1,383 @@
+use crate::{
+    utils::{BufferWriter, Deserialize, MemoryStream, Serialize, Stream, StreamExt},
+    PublicKey,
+};
+use std::net::SocketAddrV6;
+
+/// Upper bounds in milliseconds of the vote latency histogram buckets.
+/// The last bucket collects everything slower
+pub const REP_LATENCY_BUCKETS: [u64; 8] = [100, 250, 500, 1_000, 2_000, 5_000, 10_000, u64::MAX];
+
+const MAX_NODE_IDS: usize = 4;
+const MAX_ENDPOINTS: usize = 4;
+const ACTIVITY_WORDS: usize = 12;
+/// Number of hours for which the vote activity is remembered (32 days)
+pub const REP_ACTIVITY_HOURS: u64 = (ACTIVITY_WORDS * 64) as u64;
+const SERIALIZATION_VERSION: u8 = 1;
+
+/// Summary of the last telemetry received from a representative's node
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RepTelemetry {
+    /// Seconds since epoch at which the telemetry was received
+    pub received: u64,
+    pub node_id: PublicKey,
+    pub protocol_version: u8,
+    pub major_version: u8,
+    pub minor_version: u8,
+    pub patch_version: u8,
+    pub pre_release_version: u8,
+    pub maker: u8,
+    pub block_count: u64,
+    pub cemented_count: u64,
+    pub peer_count: u32,
+    pub uptime: u64,
+}
+
+impl Serialize for RepTelemetry {
+    fn serialize(&self, writer: &mut dyn BufferWriter) {
+        writer.write_u64_be_safe(self.received);
+        self.node_id.serialize(writer);
+        writer.write_u8_safe(self.protocol_version);
+        writer.write_u8_safe(self.major_version);
+        writer.write_u8_safe(self.minor_version);
+        writer.write_u8_safe(self.patch_version);
+        writer.write_u8_safe(self.pre_release_version);
+        writer.write_u8_safe(self.maker);
+        writer.write_u64_be_safe(self.block_count);
+        writer.write_u64_be_safe(self.cemented_count);
+        writer.write_u32_be_safe(self.peer_count);
+        writer.write_u64_be_safe(self.uptime);
+    }
+}
+
+impl Deserialize for RepTelemetry {
+    type Target = Self;
+
+    fn deserialize(stream: &mut dyn Stream) -> anyhow::Result<Self> {
+        Ok(Self {
+            received: stream.read_u64_be()?,
+            node_id: PublicKey::deserialize(stream)?,
+            protocol_version: stream.read_u8()?,
+            major_version: stream.read_u8()?,
+            minor_version: stream.read_u8()?,
+            patch_version: stream.read_u8()?,
+            pre_release_version: stream.read_u8()?,
+            maker: stream.read_u8()?,
+            block_count: stream.read_u64_be()?,
+            cemented_count: stream.read_u64_be()?,
+            peer_count: stream.read_u32_be()?,
+            uptime: stream.read_u64_be()?,
+        })
+    }
+}
+
+/// Everything that was observed about a representative over time.
+/// All timestamps are seconds since epoch
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RepDetails {
+    /// Node ids which the representative's votes arrived from, most recent last
+    pub node_ids: Vec<PublicKey>,
+    /// Endpoints which the representative's votes arrived from, most recent last
+    pub endpoints: Vec<SocketAddrV6>,
+    pub first_seen: u64,
+    pub last_seen: u64,
+    pub vote_count: u64,
+    /// Number of votes per latency bucket, see `REP_LATENCY_BUCKETS`
+    pub latency_histogram: [u64; REP_LATENCY_BUCKETS.len()],
+    pub telemetry: Option<RepTelemetry>,
+    /// Bit i is set if a vote was observed in the hour `activity_hour - i`
+    activity: [u64; ACTIVITY_WORDS],
+    activity_hour: u64,
+}
+
+impl RepDetails {
+    pub fn new(now: u64) -> Self {
+        Self {
+            first_seen: now,
+            last_seen: now,
+            activity_hour: now / 3600,
+            ..Default::default()
+        }
+    }
+
+    pub fn observe_vote(
+        &mut self,
+        now: u64,
+        node_id: Option<PublicKey>,
+        endpoint: Option<SocketAddrV6>,
+        latency_ms: Option<u64>,
+    ) {
+        self.last_seen = self.last_seen.max(now);
+        self.vote_count += 1;
+        if let Some(node_id) = node_id {
+            move_to_back(&mut self.node_ids, node_id, MAX_NODE_IDS);
+        }
+        if let Some(endpoint) = endpoint {
+            move_to_back(&mut self.endpoints, endpoint, MAX_ENDPOINTS);
+        }
+        if let Some(latency) = latency_ms {
+            let bucket = REP_LATENCY_BUCKETS
+                .iter()
+                .position(|bound| latency <= *bound)
+                .unwrap_or(REP_LATENCY_BUCKETS.len() - 1);
+            self.latency_histogram[bucket] += 1;
+        }
+        self.mark_active(now / 3600);
+    }
+
+    pub fn current_node_id(&self) -> Option<&PublicKey> {
+        self.node_ids.last()
+    }
+
+    pub fn latency_samples(&self) -> u64 {
+        self.latency_histogram.iter().sum()
+    }
+
+    /// Upper bound in milliseconds of the bucket which contains the given percentile
+    pub fn latency_percentile(&self, percentile: u8) -> Option<u64> {
+        let samples = self.latency_samples();
+        if samples == 0 {
+            return None;
+        }
+        let rank = (samples * percentile.min(100) as u64).div_ceil(100).max(1);
+        let mut seen = 0;
+        for (i, count) in self.latency_histogram.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return Some(REP_LATENCY_BUCKETS[i]);
+            }
+        }
+        REP_LATENCY_BUCKETS.last().cloned()
+    }
+
+    /// Percentage of the hours within the last `window_hours` in which the
+    /// representative voted. Hours before it was first seen don't count
+    pub fn uptime(&self, window_hours: u64, now: u64) -> f64 {
+        let now_hour = now / 3600;
+        let window = window_hours
+            .min(REP_ACTIVITY_HOURS)
+            .min(now_hour.saturating_sub(self.first_seen / 3600) + 1);
+        if window == 0 {
+            return 0.0;
+        }
+        let offset = now_hour.saturating_sub(self.activity_hour);
+        let active = (offset..window)
+            .filter(|i| self.is_active(i - offset))
+            .count();
+        active as f64 * 100.0 / window as f64
+    }
+
+    fn mark_active(&mut self, hour: u64) {
+        if hour > self.activity_hour {
+            self.shift_activity(hour - self.activity_hour);
+            self.activity_hour = hour;
+        }
+        let bit = self.activity_hour - hour;
+        if bit < REP_ACTIVITY_HOURS {
+            self.activity[(bit / 64) as usize] |= 1 << (bit % 64);
+        }
+    }
+
+    fn is_active(&self, bit: u64) -> bool {
+        bit < REP_ACTIVITY_HOURS && self.activity[(bit / 64) as usize] & (1 << (bit % 64)) != 0
+    }
+
+    fn shift_activity(&mut self, hours: u64) {
+        if hours >= REP_ACTIVITY_HOURS {
+            self.activity = [0; ACTIVITY_WORDS];
+            return;
+        }
+        let words = (hours / 64) as usize;
+        let bits = hours % 64;
+        for i in (0..ACTIVITY_WORDS).rev() {
+            let mut value = 0;
+            if i >= words {
+                value = self.activity[i - words] << bits;
+                if bits > 0 && i > words {
+                    value |= self.activity[i - words - 1] >> (64 - bits);
+                }
+            }
+            self.activity[i] = value;
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut stream = MemoryStream::new();
+        self.serialize(&mut stream);
+        stream.to_vec()
+    }
+
+    pub fn new_test_instance() -> Self {
+        let mut details = Self::new(1_700_000_000);
+        details.observe_vote(
+            1_700_000_000,
+            Some(PublicKey::from(1)),
+            Some("[::ffff:10.0.0.1]:7075".parse().unwrap()),
+            Some(120),
+        );
+        details.telemetry = Some(RepTelemetry {
+            received: 1_700_000_001,
+            node_id: PublicKey::from(1),
+            protocol_version: 21,
+            major_version: 27,
+            block_count: 100,
+            cemented_count: 90,
+            peer_count: 5,
+            ..Default::default()
+        });
+        details
+    }
+}
+
+fn move_to_back<T: PartialEq>(items: &mut Vec<T>, item: T, max: usize) {
+    items.retain(|i| *i != item);
+    items.push(item);
+    if items.len() > max {
+        items.remove(0);
+    }
+}
+
+impl Serialize for RepDetails {
+    fn serialize(&self, writer: &mut dyn BufferWriter) {
+        writer.write_u8_safe(SERIALIZATION_VERSION);
+        writer.write_u8_safe(self.node_ids.len() as u8);
+        for node_id in &self.node_ids {
+            node_id.serialize(writer);
+        }
+        writer.write_u8_safe(self.endpoints.len() as u8);
+        for endpoint in &self.endpoints {
+            writer.write_bytes_safe(&endpoint.ip().octets());
+            writer.write_bytes_safe(&endpoint.port().to_be_bytes());
+        }
+        writer.write_u64_be_safe(self.first_seen);
+        writer.write_u64_be_safe(self.last_seen);
+        writer.write_u64_be_safe(self.vote_count);
+        for count in &self.latency_histogram {
+            writer.write_u64_be_safe(*count);
+        }
+        writer.write_u64_be_safe(self.activity_hour);
+        for word in &self.activity {
+            writer.write_u64_be_safe(*word);
+        }
+        match &self.telemetry {
+            Some(telemetry) => {
+                writer.write_u8_safe(1);
+                telemetry.serialize(writer);
+            }
+            None => writer.write_u8_safe(0),
+        }
+    }
+}
+
+impl Deserialize for RepDetails {
+    type Target = Self;
+
+    fn deserialize(stream: &mut dyn Stream) -> anyhow::Result<Self> {
+        let version = stream.read_u8()?;
+        if version != SERIALIZATION_VERSION {
+            bail!("unsupported rep details version: {}", version);
+        }
+        let mut details = RepDetails::default();
+        for _ in 0..stream.read_u8()? {
+            details.node_ids.push(PublicKey::deserialize(stream)?);
+        }
+        for _ in 0..stream.read_u8()? {
+            let mut ip = [0; 16];
+            stream.read_bytes(&mut ip, 16)?;
+            let mut port = [0; 2];
+            stream.read_bytes(&mut port, 2)?;
+            details
+                .endpoints
+                .push(SocketAddrV6::new(ip.into(), u16::from_be_bytes(port), 0, 0));
+        }
+        details.first_seen = stream.read_u64_be()?;
+        details.last_seen = stream.read_u64_be()?;
+        details.vote_count = stream.read_u64_be()?;
+        for count in details.latency_histogram.iter_mut() {
+            *count = stream.read_u64_be()?;
+        }
+        details.activity_hour = stream.read_u64_be()?;
+        for word in details.activity.iter_mut() {
+            *word = stream.read_u64_be()?;
+        }
+        if stream.read_u8()? != 0 {
+            details.telemetry = Some(RepTelemetry::deserialize(stream)?);
+        }
+        Ok(details)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::BufferReader;
+
+    const HOUR: u64 = 3600;
+    const START: u64 = 1_700_000_000 / HOUR * HOUR;
+
+    #[test]
+    fn serialization() {
+        let details = RepDetails::new_test_instance();
+        let bytes = details.to_bytes();
+        let mut stream = BufferReader::new(&bytes);
+        assert_eq!(RepDetails::deserialize(&mut stream).unwrap(), details);
+    }
+
+    #[test]
+    fn keep_most_recent_node_ids() {
+        let mut details = RepDetails::new(START);
+        for i in 0..6 {
+            details.observe_vote(START, Some(PublicKey::from(i)), None, None);
+        }
+        details.observe_vote(START, Some(PublicKey::from(3)), None, None);
+        assert_eq!(
+            details.node_ids,
+            vec![
+                PublicKey::from(2),
+                PublicKey::from(4),
+                PublicKey::from(5),
+                PublicKey::from(3)
+            ]
+        );
+        assert_eq!(details.current_node_id(), Some(&PublicKey::from(3)));
+        assert_eq!(details.vote_count, 7);
+    }
+
+    #[test]
+    fn latency_percentiles() {
+        let mut details = RepDetails::new(START);
+        assert_eq!(details.latency_percentile(50), None);
+        for latency in [10, 20, 30, 200, 3000] {
+            details.observe_vote(START, None, None, Some(latency));
+        }
+        assert_eq!(details.latency_samples(), 5);
+        assert_eq!(details.latency_percentile(50), Some(100));
+        assert_eq!(details.latency_percentile(80), Some(250));
+        assert_eq!(details.latency_percentile(99), Some(5_000));
+    }
+
+    #[test]
+    fn uptime() {
+        let mut details = RepDetails::new(START);
+        details.observe_vote(START, None, None, None);
+        assert_eq!(details.uptime(24, START), 100.0);
+
+        // votes in 2 of 4 hours
+        details.observe_vote(START + 2 * HOUR, None, None, None);
+        assert_eq!(details.uptime(24, START + 3 * HOUR), 50.0);
+        assert_eq!(details.uptime(2, START + 3 * HOUR), 50.0);
+        assert_eq!(details.uptime(1, START + 3 * HOUR), 0.0);
+    }
+
+    #[test]
+    fn uptime_forgets_old_hours() {
+        let mut details = RepDetails::new(START);
+        details.observe_vote(START, None, None, None);
+        details.observe_vote(START + 100 * HOUR, None, None, None);
+        assert_eq!(details.uptime(24, START + 100 * HOUR), 100.0 / 24.0);
+
+        details.observe_vote(START + 1000 * HOUR, None, None, None);
+        let uptime = details.uptime(REP_ACTIVITY_HOURS, START + 1000 * HOUR);
+        assert_eq!(uptime, 100.0 / REP_ACTIVITY_HOURS as f64);
+    }
+}
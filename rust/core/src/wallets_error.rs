@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(FromPrimitive, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WalletsError {
+    None,
+    Generic,
+    WalletNotFound,
+    WalletLocked,
+    AccountNotFound,
+    InvalidPassword,
+    BadPublicKey,
+    NoMnemonic,
+}
+
+impl WalletsError {
+    pub const ALL: [WalletsError; 8] = [
+        WalletsError::None,
+        WalletsError::Generic,
+        WalletsError::WalletNotFound,
+        WalletsError::WalletLocked,
+        WalletsError::AccountNotFound,
+        WalletsError::InvalidPassword,
+        WalletsError::BadPublicKey,
+        WalletsError::NoMnemonic,
+    ];
+}
+
+impl fmt::Display for WalletsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let error_message = match self {
+            WalletsError::None => "No error",
+            WalletsError::Generic => "Unknown error",
+            WalletsError::WalletNotFound => "Wallet not found",
+            WalletsError::WalletLocked => "Wallet is locked",
+            WalletsError::AccountNotFound => "Account not found in wallet",
+            WalletsError::InvalidPassword => "Invalid password",
+            WalletsError::BadPublicKey => "Bad public key",
+            WalletsError::NoMnemonic => "Wallet has no mnemonic",
+        };
+        write!(f, "{}", error_message)
+    }
+}
+
+impl std::error::Error for WalletsError {}
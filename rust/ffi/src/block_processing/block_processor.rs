@@ -45,11 +45,11 @@ pub extern "C" fn rsn_block_processor_add_blocking(
     status: &mut u8,
 ) -> bool {
     match handle.add_blocking(Arc::clone(block), BlockSource::from_u8(source).unwrap()) {
-        Some(i) => {
+        Ok(i) => {
             *status = i as u8;
             true
         }
-        None => false,
+        Err(_) => false,
     }
 }
 
@@ -472,6 +472,11 @@ impl TryFrom<&NodeConfigDto> for NodeConfig {
             local_block_broadcaster: (&value.local_block_broadcaster).into(),
             confirming_set: (&value.confirming_set).into(),
             monitor: (&value.monitor).into(),
+            wallet_backup: Default::default(),
+            external_signer: Default::default(),
+            election_trace: Default::default(),
+            cdc: Default::default(),
+            persistent_unchecked: Default::default(),
         };
 
         Ok(cfg)
@@ -575,6 +580,7 @@ impl From<&PriorityBucketConfigDto> for PriorityBucketConfig {
             max_blocks: value.max_blocks,
             reserved_elections: value.reserved_elections,
             max_elections: value.max_elections,
+            buckets: Vec::new(),
         }
     }
 }
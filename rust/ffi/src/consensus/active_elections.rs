@@ -289,7 +289,7 @@ pub unsafe extern "C" fn rsn_active_transactions_vote(
 ) -> *mut VoteResultMapHandle {
     let result = handle
         .vote_router
-        .vote(vote, VoteSource::from_u8(source).unwrap());
+        .vote(vote, VoteSource::from_u8(source).unwrap(), None);
     VoteResultMapHandle::new(&result)
 }
 
@@ -308,6 +308,7 @@ pub unsafe extern "C" fn rsn_active_transactions_vote2(
         timestamp,
         &BlockHash::from_ptr(block_hash),
         VoteSource::from_u8(vote_source).unwrap(),
+        None,
     ) as u8
 }
 /*
@@ -60,6 +60,7 @@ impl From<&VoteCacheConfigDto> for VoteCacheConfig {
             max_size: value.max_size,
             max_voters: value.max_voters,
             age_cutoff: Duration::from_secs(value.age_cutoff_s),
+            ..Default::default()
         }
     }
 }
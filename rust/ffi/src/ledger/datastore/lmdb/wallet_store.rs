@@ -180,7 +180,7 @@ pub unsafe extern "C" fn rsn_lmdb_wallet_store_derive_key(
     password: *const c_char,
 ) {
     let password = CStr::from_ptr(password).to_str().unwrap();
-    let key = (*handle).0.derive_key((*txn).as_txn(), password);
+    let key = (*handle).0.derive_key((*txn).as_txn(), password).unwrap();
     key.copy_bytes(prv);
 }
 
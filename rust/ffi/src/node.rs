@@ -312,7 +312,7 @@ pub unsafe extern "C" fn rsn_node_vote(
     vote: &VoteHandle,
     hash: *const u8,
 ) -> u8 {
-    let result = handle.0.vote_router.vote(vote, VoteSource::Live);
+    let result = handle.0.vote_router.vote(vote, VoteSource::Live, None);
     result
         .get(&BlockHash::from_ptr(hash))
         .cloned()
@@ -34,6 +34,6 @@ pub unsafe extern "C" fn rsn_kdf_phs(
 ) {
     let password = CStr::from_ptr(password).to_str().unwrap();
     let salt = slice::from_raw_parts(salt, 32).try_into().unwrap();
-    let key = (*handle).0.hash_password(password, salt);
+    let key = (*handle).0.hash_password(password, salt).unwrap();
     key.copy_bytes(result);
 }
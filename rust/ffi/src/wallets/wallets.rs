@@ -96,7 +96,8 @@ pub unsafe extern "C" fn rsn_lmdb_wallets_create(
             Arc::clone(stats),
             protocol,
         ),
-    );
+    )
+    .expect("could not create wallets");
     wallets.initialize().expect("could not create wallet");
     let wallets = Arc::new(wallets);
     wallets.initialize2();
@@ -43,6 +43,7 @@ impl<'a> BlockInserter<'a> {
         self.set_block_sideband();
         self.ledger.store.block.put(self.txn, self.block);
         self.update_account();
+        self.update_account_history_index();
         self.delete_old_pending_info();
         self.insert_new_pending_info();
         self.update_representative_cache();
@@ -70,6 +71,10 @@ impl<'a> BlockInserter<'a> {
         );
     }
 
+    fn update_account_history_index(&mut self) {
+        self.ledger.index_account_history(self.txn, self.block);
+    }
+
     fn delete_old_pending_info(&mut self) {
         if let Some(key) = &self.instructions.delete_pending {
             self.ledger.store.pending.del(self.txn, key);
@@ -28,6 +28,7 @@ impl<'a> RollbackInstructionsExecutor<'a> {
         self.update_pending_table();
         self.update_account_table();
         self.update_block_table();
+        self.update_account_history_index();
         self.roll_back_representative_cache();
         self.ledger
             .store
@@ -51,6 +52,15 @@ impl<'a> RollbackInstructionsExecutor<'a> {
         }
     }
 
+    fn update_account_history_index(&mut self) {
+        self.ledger.unindex_account_history(
+            self.txn,
+            &self.instructions.account,
+            self.instructions.block_height,
+            self.instructions.block_timestamp,
+        );
+    }
+
     fn update_account_table(&mut self) {
         self.ledger.update_account(
             self.txn,
@@ -1,5 +1,6 @@
-use rsnano_core::{AccountInfo, BlockEnum, BlockHash};
+use rsnano_core::{AccountInfo, BlockEnum, BlockHash, ConfirmationHeightInfo};
 use rsnano_store_lmdb::LmdbWriteTransaction;
+use std::sync::atomic::Ordering;
 
 use crate::Ledger;
 
@@ -12,6 +13,7 @@ pub(crate) struct BlockRollbackPerformer<'a> {
     ledger: &'a Ledger,
     pub txn: &'a mut LmdbWriteTransaction,
     pub rolled_back: Vec<BlockEnum>,
+    allow_cemented: bool,
 }
 
 impl<'a> BlockRollbackPerformer<'a> {
@@ -20,9 +22,16 @@ impl<'a> BlockRollbackPerformer<'a> {
             ledger,
             txn,
             rolled_back: Vec::new(),
+            allow_cemented: false,
         }
     }
 
+    /// Cemented blocks get rolled back too. The confirmation heights are lowered accordingly
+    pub(crate) fn allow_cemented(mut self) -> Self {
+        self.allow_cemented = true;
+        self
+    }
+
     pub(crate) fn roll_back(mut self, block_hash: &BlockHash) -> anyhow::Result<Vec<BlockEnum>> {
         self.roll_back_block_and_successors(block_hash)?;
         Ok(self.rolled_back)
@@ -38,6 +47,9 @@ impl<'a> BlockRollbackPerformer<'a> {
     }
 
     fn roll_back_head_block(&mut self, head_block: BlockEnum) -> Result<(), anyhow::Error> {
+        if self.allow_cemented {
+            self.uncement(&head_block);
+        }
         let planner =
             RollbackPlannerFactory::new(self.ledger, self.txn, &head_block).create_planner()?;
         let step = planner.roll_back_head_block()?;
@@ -58,6 +70,42 @@ impl<'a> BlockRollbackPerformer<'a> {
         }
     }
 
+    fn uncement(&mut self, head_block: &BlockEnum) {
+        let account = head_block.account();
+        let Some(info) = self
+            .ledger
+            .store
+            .confirmation_height
+            .get(self.txn, &account)
+        else {
+            return;
+        };
+
+        let height = head_block.sideband().unwrap().height;
+        if height > info.height {
+            return;
+        }
+
+        let new_height = height - 1;
+        if new_height == 0 {
+            self.ledger
+                .store
+                .confirmation_height
+                .del(self.txn, &account);
+        } else {
+            self.ledger.store.confirmation_height.put(
+                self.txn,
+                &account,
+                &ConfirmationHeightInfo::new(new_height, head_block.previous()),
+            );
+        }
+        self.ledger
+            .store
+            .cache
+            .cemented_count
+            .fetch_sub(info.height - new_height, Ordering::SeqCst);
+    }
+
     fn block_exists(&self, block_hash: &BlockHash) -> bool {
         self.ledger.any().block_exists(self.txn, block_hash)
     }
@@ -13,6 +13,8 @@ pub(crate) enum RollbackStep {
 pub(crate) struct RollbackInstructions {
     pub block_hash: BlockHash,
     pub block_sub_type: BlockSubType,
+    pub block_height: u64,
+    pub block_timestamp: u64,
     pub account: Account,
     pub remove_pending: Option<PendingKey>,
     pub add_pending: Option<(PendingKey, PendingInfo)>,
@@ -51,6 +53,8 @@ impl<'a> RollbackPlanner<'a> {
 
         let instructions = RollbackInstructions {
             block_hash: self.head_block.hash(),
+            block_height: self.head_block.sideband().unwrap().height,
+            block_timestamp: self.head_block.sideband().unwrap().timestamp,
             account: self.account,
             old_account_info: self.current_account_info.clone(),
             new_representative: self.previous_representative,
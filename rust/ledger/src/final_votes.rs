@@ -0,0 +1,247 @@
+use crate::{LedgerSetAny, LedgerSetConfirmed};
+use rsnano_core::{Account, BlockHash, QualifiedRoot, Root};
+use rsnano_store_lmdb::{LmdbStore, Transaction};
+
+/// A final vote of this node together with what the ledger knows about its root
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct FinalVoteEntry {
+    pub qualified_root: QualifiedRoot,
+    /// The block this node has finally voted for
+    pub hash: BlockHash,
+    /// The account the root belongs to. None if the previous block is unknown
+    pub account: Option<Account>,
+    /// The block which is cemented at this root, if any
+    pub cemented: Option<BlockHash>,
+}
+
+impl FinalVoteEntry {
+    /// A different block than the finally voted one was cemented at this root
+    pub fn is_conflicting(&self) -> bool {
+        self.cemented.is_some_and(|cemented| cemented != self.hash)
+    }
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct FinalVoteFilter {
+    pub root: Option<Root>,
+    /// Inclusive lower bound of the account the root belongs to
+    pub account_start: Option<Account>,
+    /// Inclusive upper bound of the account the root belongs to
+    pub account_end: Option<Account>,
+    pub count: Option<usize>,
+}
+
+impl FinalVoteFilter {
+    fn filters_accounts(&self) -> bool {
+        self.account_start.is_some() || self.account_end.is_some()
+    }
+
+    fn matches_account(&self, account: Option<Account>) -> bool {
+        if !self.filters_accounts() {
+            return true;
+        }
+        let Some(account) = account else {
+            return false;
+        };
+        self.account_start.map_or(true, |start| account >= start)
+            && self.account_end.map_or(true, |end| account <= end)
+    }
+}
+
+/// Inspects the final votes this node has generated
+pub struct FinalVotes<'a> {
+    store: &'a LmdbStore,
+}
+
+impl<'a> FinalVotes<'a> {
+    pub fn new(store: &'a LmdbStore) -> Self {
+        Self { store }
+    }
+
+    pub fn list(&self, tx: &dyn Transaction, filter: &FinalVoteFilter) -> Vec<FinalVoteEntry> {
+        let max_count = filter.count.unwrap_or(usize::MAX);
+        let mut result = Vec::new();
+
+        let mut it = match &filter.root {
+            Some(root) => self
+                .store
+                .final_vote
+                .begin_at_root(tx, &QualifiedRoot::new(*root, BlockHash::zero())),
+            None => self.store.final_vote.begin(tx),
+        };
+
+        while let Some((qualified_root, hash)) = it.current() {
+            if result.len() >= max_count {
+                break;
+            }
+            if filter.root.is_some_and(|root| root != qualified_root.root) {
+                break;
+            }
+
+            let account = self.root_account(tx, qualified_root);
+            if filter.matches_account(account) {
+                result.push(self.entry(tx, qualified_root.clone(), *hash, account));
+            }
+            it.next();
+        }
+
+        result
+    }
+
+    /// Returns all final votes for the given root
+    pub fn at_root(&self, tx: &dyn Transaction, root: &Root) -> Vec<FinalVoteEntry> {
+        self.store
+            .final_vote
+            .get_qualified(tx, root)
+            .into_iter()
+            .map(|(qualified_root, hash)| {
+                let account = self.root_account(tx, &qualified_root);
+                self.entry(tx, qualified_root, hash, account)
+            })
+            .collect()
+    }
+
+    fn entry(
+        &self,
+        tx: &dyn Transaction,
+        qualified_root: QualifiedRoot,
+        hash: BlockHash,
+        account: Option<Account>,
+    ) -> FinalVoteEntry {
+        let cemented = LedgerSetAny::new(self.store)
+            .block_successor_by_qualified_root(tx, &qualified_root)
+            .filter(|successor| LedgerSetConfirmed::new(self.store).block_exists(tx, successor));
+
+        FinalVoteEntry {
+            qualified_root,
+            hash,
+            account,
+            cemented,
+        }
+    }
+
+    fn root_account(
+        &self,
+        tx: &dyn Transaction,
+        qualified_root: &QualifiedRoot,
+    ) -> Option<Account> {
+        if qualified_root.previous.is_zero() {
+            // The root of an open block is the account itself
+            Some(qualified_root.root.into())
+        } else {
+            LedgerSetAny::new(self.store).block_account(tx, &qualified_root.previous)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Ledger;
+    use rsnano_core::{ConfirmationHeightInfo, TestAccountChain};
+
+    #[test]
+    fn empty() {
+        let ledger = Ledger::new_null();
+        let tx = ledger.read_txn();
+        assert!(ledger
+            .final_votes()
+            .list(&tx, &FinalVoteFilter::default())
+            .is_empty());
+    }
+
+    #[test]
+    fn final_vote_for_cemented_block() {
+        let mut chain = TestAccountChain::new_opened_chain();
+        let send = chain.add_legacy_send().clone();
+        let root = send.qualified_root();
+        let ledger = Ledger::new_null_builder()
+            .blocks(chain.blocks())
+            .account_info(&chain.account(), &chain.account_info())
+            .confirmation_height(
+                &chain.account(),
+                &ConfirmationHeightInfo::new(chain.height(), chain.frontier()),
+            )
+            .final_vote(&root, &send.hash())
+            .finish();
+        let tx = ledger.read_txn();
+
+        let entries = ledger.final_votes().at_root(&tx, &root.root);
+
+        assert_eq!(
+            entries,
+            vec![FinalVoteEntry {
+                qualified_root: root,
+                hash: send.hash(),
+                account: Some(chain.account()),
+                cemented: Some(send.hash()),
+            }]
+        );
+        assert_eq!(entries[0].is_conflicting(), false);
+    }
+
+    #[test]
+    fn conflicting_cemented_block() {
+        let mut chain = TestAccountChain::new_opened_chain();
+        let send = chain.add_legacy_send().clone();
+        let root = send.qualified_root();
+        let ledger = Ledger::new_null_builder()
+            .blocks(chain.blocks())
+            .account_info(&chain.account(), &chain.account_info())
+            .confirmation_height(
+                &chain.account(),
+                &ConfirmationHeightInfo::new(chain.height(), chain.frontier()),
+            )
+            .final_vote(&root, &BlockHash::from(42))
+            .finish();
+        let tx = ledger.read_txn();
+
+        let entries = ledger.final_votes().at_root(&tx, &root.root);
+
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].is_conflicting());
+    }
+
+    #[test]
+    fn unconfirmed_root_is_not_conflicting() {
+        let mut chain = TestAccountChain::new_opened_chain();
+        let send = chain.add_legacy_send().clone();
+        let root = send.qualified_root();
+        let ledger = Ledger::new_null_builder()
+            .blocks(chain.blocks())
+            .account_info(&chain.account(), &chain.account_info())
+            .final_vote(&root, &BlockHash::from(42))
+            .finish();
+        let tx = ledger.read_txn();
+
+        let entries = ledger.final_votes().at_root(&tx, &root.root);
+
+        assert_eq!(entries[0].cemented, None);
+        assert_eq!(entries[0].is_conflicting(), false);
+    }
+
+    #[test]
+    fn filter_by_account() {
+        let mut chain = TestAccountChain::new_opened_chain();
+        let send = chain.add_legacy_send().clone();
+        let ledger = Ledger::new_null_builder()
+            .blocks(chain.blocks())
+            .account_info(&chain.account(), &chain.account_info())
+            .final_vote(&send.qualified_root(), &send.hash())
+            .final_vote(&QualifiedRoot::new_test_instance(), &BlockHash::from(1))
+            .finish();
+        let tx = ledger.read_txn();
+
+        let entries = ledger.final_votes().list(
+            &tx,
+            &FinalVoteFilter {
+                account_start: Some(chain.account()),
+                account_end: Some(chain.account()),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].hash, send.hash());
+    }
+}
@@ -0,0 +1,86 @@
+use crate::RepresentativeBlockFinder;
+use rsnano_core::{Account, Amount, BlockEnum, PublicKey};
+use rsnano_store_lmdb::{LmdbStore, Transaction};
+
+/// Finds the confirmed block of an account chain at a given height or point in time.
+/// The block is looked up in the account history index, which must be complete.
+/// Points in time are found by a binary search over the heights.
+pub struct HistoricalBlockFinder<'a> {
+    txn: &'a dyn Transaction,
+    store: &'a LmdbStore,
+}
+
+impl<'a> HistoricalBlockFinder<'a> {
+    pub fn new(txn: &'a dyn Transaction, store: &'a LmdbStore) -> Self {
+        Self { txn, store }
+    }
+
+    /// The confirmed block at the given height of the account chain
+    pub fn at_height(&self, account: &Account, height: u64) -> Option<BlockEnum> {
+        let confirmed = self.store.confirmation_height.get(self.txn, account)?;
+        if height == 0 || height > confirmed.height {
+            return None;
+        }
+        self.indexed_block(account, height)
+    }
+
+    /// The last confirmed block of the account chain which was inserted
+    /// at or before the given local timestamp
+    pub fn at_timestamp(&self, account: &Account, timestamp: u64) -> Option<BlockEnum> {
+        let confirmed = self.store.confirmation_height.get(self.txn, account)?;
+        let mut result = None;
+        let (mut low, mut high) = (1, confirmed.height);
+        while low <= high {
+            let middle = low + (high - low) / 2;
+            let block = self.indexed_block(account, middle)?;
+            if block.sideband().unwrap().timestamp <= timestamp {
+                result = Some(block);
+                low = middle + 1;
+            } else {
+                high = middle - 1;
+            }
+        }
+        result
+    }
+
+    /// The representative the account delegated to with the given block
+    pub fn representative(&self, block: &BlockEnum) -> Option<PublicKey> {
+        let rep_block_hash =
+            RepresentativeBlockFinder::new(self.txn, self.store).find_rep_block(block.hash());
+        self.store
+            .block
+            .get(self.txn, &rep_block_hash)?
+            .representative_field()
+    }
+
+    /// The confirmed weight which was delegated to the representative at the given local
+    /// timestamp by at most `count` of the accounts which ever delegated to it, starting
+    /// with `start`. Returns the weight and the account to continue with, if there is one
+    pub fn weight_at(
+        &self,
+        representative: &PublicKey,
+        timestamp: u64,
+        start: &Account,
+        count: usize,
+    ) -> (Amount, Option<Account>) {
+        let mut weight = Amount::zero();
+        let mut delegators =
+            self.store
+                .account_history
+                .iter_delegators(self.txn, representative, start);
+        for account in delegators.by_ref().take(count) {
+            let Some(block) = self.at_timestamp(&account, timestamp) else {
+                continue;
+            };
+            if self.representative(&block) == Some(*representative) {
+                weight += block.balance();
+            }
+        }
+        (weight, delegators.next())
+    }
+
+    fn indexed_block(&self, account: &Account, height: u64) -> Option<BlockEnum> {
+        let hash = self.store.account_history.get(self.txn, account, height)?;
+        self.store.block.get(self.txn, &hash)
+    }
+}
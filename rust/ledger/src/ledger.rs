@@ -3,22 +3,25 @@ use crate::{
     block_cementer::BlockCementer,
     block_insertion::{BlockInserter, BlockValidatorFactory},
     ledger_set_confirmed::LedgerSetConfirmed,
-    BlockRollbackPerformer, GenerateCacheFlags, LedgerConstants, LedgerSetAny, RepWeightCache,
-    RepWeightsUpdater, RepresentativeBlockFinder, WriteGuard, WriteQueue,
+    BlockRollbackPerformer, FinalVotes, GenerateCacheFlags, HistoricalBlockFinder, LedgerConstants,
+    LedgerSetAny, RepWeightCache, RepWeightsUpdater, RepresentativeBlockFinder, WriteGuard,
+    WriteQueue,
 };
 use rand::{thread_rng, Rng};
 use rsnano_core::{
     utils::{seconds_since_epoch, ContainerInfoComponent},
     Account, AccountInfo, Amount, BlockEnum, BlockHash, BlockSubType, ConfirmationHeightInfo,
-    DependentBlocks, Epoch, Link, PendingInfo, PendingKey, PublicKey, Root,
+    DependentBlocks, Epoch, Link, PendingInfo, PendingKey, PublicKey, QualifiedRoot, Root,
 };
 use rsnano_store_lmdb::{
-    ConfiguredAccountDatabaseBuilder, ConfiguredBlockDatabaseBuilder,
-    ConfiguredConfirmationHeightDatabaseBuilder, ConfiguredPeersDatabaseBuilder,
-    ConfiguredPendingDatabaseBuilder, ConfiguredPrunedDatabaseBuilder, LedgerCache,
-    LmdbAccountStore, LmdbBlockStore, LmdbConfirmationHeightStore, LmdbEnv, LmdbFinalVoteStore,
+    AccountHistoryIndexState, ConfiguredAccountDatabaseBuilder, ConfiguredBlockDatabaseBuilder,
+    ConfiguredConfirmationHeightDatabaseBuilder, ConfiguredFinalVoteDatabaseBuilder,
+    ConfiguredPeersDatabaseBuilder, ConfiguredPendingDatabaseBuilder,
+    ConfiguredPrunedDatabaseBuilder, LedgerCache, LmdbAccountHistoryStore, LmdbAccountStore,
+    LmdbBlockStore, LmdbConfirmationHeightStore, LmdbDelegatorStore, LmdbEnv, LmdbFinalVoteStore,
     LmdbOnlineWeightStore, LmdbPeerStore, LmdbPendingStore, LmdbPrunedStore, LmdbReadTransaction,
-    LmdbRepWeightStore, LmdbStore, LmdbVersionStore, LmdbWriteTransaction, Transaction,
+    LmdbRepDetailsStore, LmdbRepWeightStore, LmdbStore, LmdbVersionStore, LmdbWriteTransaction,
+    Transaction,
 };
 use std::{
     collections::{HashMap, VecDeque},
@@ -77,6 +80,8 @@ pub struct Ledger {
     pub constants: LedgerConstants,
     pub observer: Arc<dyn LedgerObserver>,
     pruning: AtomicBool,
+    account_history_index: AtomicBool,
+    account_history_index_complete: AtomicBool,
     pub write_queue: Arc<WriteQueue>,
 }
 
@@ -87,6 +92,7 @@ pub struct NullLedgerBuilder {
     pruned: ConfiguredPrunedDatabaseBuilder,
     peers: ConfiguredPeersDatabaseBuilder,
     confirmation_height: ConfiguredConfirmationHeightDatabaseBuilder,
+    final_votes: ConfiguredFinalVoteDatabaseBuilder,
     min_rep_weight: Amount,
 }
 
@@ -99,6 +105,7 @@ impl NullLedgerBuilder {
             pruned: ConfiguredPrunedDatabaseBuilder::new(),
             peers: ConfiguredPeersDatabaseBuilder::new(),
             confirmation_height: ConfiguredConfirmationHeightDatabaseBuilder::new(),
+            final_votes: ConfiguredFinalVoteDatabaseBuilder::new(),
             min_rep_weight: Amount::zero(),
         }
     }
@@ -142,6 +149,11 @@ impl NullLedgerBuilder {
         self
     }
 
+    pub fn final_vote(mut self, root: &QualifiedRoot, hash: &BlockHash) -> Self {
+        self.final_votes = self.final_votes.final_vote(root, hash);
+        self
+    }
+
     pub fn finish(self) -> Ledger {
         let env = Arc::new(
             LmdbEnv::new_null_with()
@@ -151,6 +163,7 @@ impl NullLedgerBuilder {
                 .configured_database(self.pruned.build())
                 .configured_database(self.confirmation_height.build())
                 .configured_database(self.peers.build())
+                .configured_database(self.final_votes.build())
                 .build(),
         );
 
@@ -158,6 +171,8 @@ impl NullLedgerBuilder {
             cache: Arc::new(LedgerCache::new()),
             env: env.clone(),
             account: Arc::new(LmdbAccountStore::new(env.clone()).unwrap()),
+            account_history: Arc::new(LmdbAccountHistoryStore::new(env.clone()).unwrap()),
+            delegators: Arc::new(LmdbDelegatorStore::new(env.clone()).unwrap()),
             block: Arc::new(LmdbBlockStore::new(env.clone()).unwrap()),
             confirmation_height: Arc::new(LmdbConfirmationHeightStore::new(env.clone()).unwrap()),
             final_vote: Arc::new(LmdbFinalVoteStore::new(env.clone()).unwrap()),
@@ -166,6 +181,7 @@ impl NullLedgerBuilder {
             pending: Arc::new(LmdbPendingStore::new(env.clone()).unwrap()),
             pruned: Arc::new(LmdbPrunedStore::new(env.clone()).unwrap()),
             rep_weight: Arc::new(LmdbRepWeightStore::new(env.clone()).unwrap()),
+            rep_details: Arc::new(LmdbRepDetailsStore::new(env.clone()).unwrap()),
             version: Arc::new(LmdbVersionStore::new(env.clone()).unwrap()),
         };
         Ledger::new(
@@ -209,10 +225,13 @@ impl Ledger {
             constants,
             observer: Arc::new(NullLedgerObserver::new()),
             pruning: AtomicBool::new(false),
+            account_history_index: AtomicBool::new(false),
+            account_history_index_complete: AtomicBool::new(false),
             write_queue: Arc::new(WriteQueue::new(false)),
         };
 
         ledger.initialize(&GenerateCacheFlags::new())?;
+        ledger.load_account_history_index_state();
 
         Ok(ledger)
     }
@@ -311,6 +330,9 @@ impl Ledger {
                 epoch: Epoch::Epoch0,
             },
         );
+        self.store
+            .delegators
+            .put(txn, &genesis_account.into(), &genesis_account);
         self.store
             .rep_weight
             .put(txn, genesis_account.into(), Amount::MAX);
@@ -342,6 +364,46 @@ impl Ledger {
         LedgerSetConfirmed::new(&self.store)
     }
 
+    pub fn final_votes(&self) -> FinalVotes {
+        FinalVotes::new(&self.store)
+    }
+
+    /// Looks up confirmed blocks of account chains by height or local timestamp.
+    /// Returns None until the account history index is complete
+    pub fn historical_blocks<'a>(
+        &'a self,
+        txn: &'a dyn Transaction,
+    ) -> Option<HistoricalBlockFinder<'a>> {
+        if !self.account_history_index_complete() {
+            return None;
+        }
+        Some(HistoricalBlockFinder::new(txn, &self.store))
+    }
+
+    /// The confirmed weight which was delegated to the representative at the given local
+    /// timestamp. Only the accounts which ever delegated to the representative are checked.
+    /// They are checked in pages with a new read transaction each, so that a representative
+    /// with many delegators doesn't keep a reader open for long.
+    /// Returns None if the account history index isn't complete
+    pub fn representative_weight_at(
+        &self,
+        representative: &PublicKey,
+        timestamp: u64,
+    ) -> Option<Amount> {
+        const PAGE_SIZE: usize = 1_000;
+        let mut weight = Amount::zero();
+        let mut start = Some(Account::zero());
+        while let Some(start_account) = start {
+            let txn = self.read_txn();
+            let finder = self.historical_blocks(&txn)?;
+            let (page_weight, next) =
+                finder.weight_at(representative, timestamp, &start_account, PAGE_SIZE);
+            weight += page_weight;
+            start = next;
+        }
+        Some(weight)
+    }
+
     pub fn pruning_enabled(&self) -> bool {
         self.pruning.load(Ordering::SeqCst)
     }
@@ -350,6 +412,147 @@ impl Ledger {
         self.pruning.store(true, Ordering::SeqCst);
     }
 
+    /// New blocks are added to the account history index. The index may
+    /// still be incomplete while the existing blocks are being indexed
+    pub fn account_history_index_enabled(&self) -> bool {
+        self.account_history_index.load(Ordering::SeqCst)
+    }
+
+    /// All blocks are indexed, so that lookups can use the account history index
+    pub fn account_history_index_complete(&self) -> bool {
+        self.account_history_index_complete.load(Ordering::SeqCst)
+    }
+
+    fn load_account_history_index_state(&self) {
+        let state = self.store.account_history.state(&self.read_txn());
+        self.account_history_index.store(
+            state != AccountHistoryIndexState::Disabled,
+            Ordering::SeqCst,
+        );
+        self.account_history_index_complete.store(
+            state == AccountHistoryIndexState::Complete,
+            Ordering::SeqCst,
+        );
+    }
+
+    /// Enables the account history index. The enabled state is stored in the ledger,
+    /// so the index stays enabled until it is dropped explicitly. The existing blocks
+    /// are indexed by calling `build_account_history_index` until it returns false
+    pub fn enable_account_history_index(&self) {
+        let mut txn = self.rw_txn();
+        if self.store.account_history.state(&txn) == AccountHistoryIndexState::Disabled {
+            self.store.account_history.clear(&mut txn);
+            self.store.account_history.set_state(
+                &mut txn,
+                AccountHistoryIndexState::Building {
+                    next_account: Account::zero(),
+                },
+            );
+        }
+        self.account_history_index.store(true, Ordering::SeqCst);
+    }
+
+    /// Disables the account history index and removes its entries
+    pub fn drop_account_history_index(&self) {
+        let mut txn = self.rw_txn();
+        self.account_history_index.store(false, Ordering::SeqCst);
+        self.account_history_index_complete
+            .store(false, Ordering::SeqCst);
+        self.store.account_history.clear(&mut txn);
+        self.store
+            .account_history
+            .set_state(&mut txn, AccountHistoryIndexState::Disabled);
+    }
+
+    /// Indexes the chains of the next batch of accounts which weren't indexed yet.
+    /// Returns false if there is nothing left to index
+    pub fn build_account_history_index(&self) -> bool {
+        // Small batches keep the write transaction short, because the node keeps running meanwhile
+        const BATCH_SIZE: usize = 1_000;
+        let mut txn = self.rw_txn();
+        let AccountHistoryIndexState::Building { next_account } =
+            self.store.account_history.state(&txn)
+        else {
+            return false;
+        };
+
+        let batch: Vec<_> = self
+            .store
+            .account
+            .iter_range(&txn, next_account..)
+            .take(BATCH_SIZE)
+            .map(|(account, info)| (account, info.head))
+            .collect();
+
+        for (account, head) in &batch {
+            // Walk backwards from the head, so that pruned chains are indexed too
+            let mut hash = *head;
+            while let Some(block) = self.store.block.get(&txn, &hash) {
+                let sideband = block.sideband().unwrap();
+                self.store.account_history.put(
+                    &mut txn,
+                    account,
+                    sideband.height,
+                    sideband.timestamp,
+                    &hash,
+                );
+                if let Some(representative) = block.representative_field() {
+                    self.store
+                        .account_history
+                        .put_delegation(&mut txn, &representative, account);
+                }
+                hash = block.previous();
+            }
+        }
+
+        let state = match batch.last() {
+            Some((last, _)) if batch.len() == BATCH_SIZE => match last.inc() {
+                Some(next_account) => AccountHistoryIndexState::Building { next_account },
+                None => AccountHistoryIndexState::Complete,
+            },
+            _ => AccountHistoryIndexState::Complete,
+        };
+        self.store.account_history.set_state(&mut txn, state);
+        txn.commit();
+
+        let complete = state == AccountHistoryIndexState::Complete;
+        self.account_history_index_complete
+            .store(complete, Ordering::SeqCst);
+        !complete
+    }
+
+    pub(crate) fn index_account_history(&self, txn: &mut LmdbWriteTransaction, block: &BlockEnum) {
+        if self.account_history_index_enabled() {
+            let sideband = block.sideband().unwrap();
+            self.store.account_history.put(
+                txn,
+                &block.account(),
+                sideband.height,
+                sideband.timestamp,
+                &block.hash(),
+            );
+            if let Some(representative) = block.representative_field() {
+                self.store
+                    .account_history
+                    .put_delegation(txn, &representative, &block.account());
+            }
+        }
+    }
+
+    pub(crate) fn unindex_account_history(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        account: &Account,
+        height: u64,
+        timestamp: u64,
+    ) {
+        if self.account_history_index_enabled() {
+            self.store
+                .account_history
+                .del(txn, account, height, timestamp);
+        }
+    }
+
     pub fn bootstrap_weight_max_blocks(&self) -> u64 {
         self.rep_weights.bootstrap_weight_max_blocks()
     }
@@ -482,6 +685,7 @@ impl Ledger {
         old_info: &AccountInfo,
         new_info: &AccountInfo,
     ) {
+        self.update_delegators(txn, account, old_info, new_info);
         if !new_info.head.is_zero() {
             if old_info.head.is_zero() && new_info.open_block == new_info.head {
                 self.store
@@ -505,6 +709,30 @@ impl Ledger {
         }
     }
 
+    /// Keeps the representative -> account index in sync with the account table
+    fn update_delegators(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        account: &Account,
+        old_info: &AccountInfo,
+        new_info: &AccountInfo,
+    ) {
+        let was_delegating = !old_info.head.is_zero();
+        let is_delegating = !new_info.head.is_zero();
+        let representative_changed = old_info.representative != new_info.representative;
+
+        if was_delegating && (!is_delegating || representative_changed) {
+            self.store
+                .delegators
+                .del(txn, &old_info.representative, account);
+        }
+        if is_delegating && (!was_delegating || representative_changed) {
+            self.store
+                .delegators
+                .put(txn, &new_info.representative, account);
+        }
+    }
+
     pub fn pruning_action(
         &self,
         txn: &mut LmdbWriteTransaction,
@@ -518,6 +746,13 @@ impl Ledger {
         while !hash.is_zero() && hash != genesis_hash {
             if let Some(block) = self.any().get_block(txn, &hash) {
                 assert!(self.confirmed().block_exists_or_pruned(txn, &hash));
+                let sideband = block.sideband().unwrap();
+                self.unindex_account_history(
+                    txn,
+                    &sideband.account,
+                    sideband.height,
+                    sideband.timestamp,
+                );
                 self.store.block.del(txn, &hash);
                 self.store.pruned.put(txn, &hash);
                 hash = block.previous();
@@ -556,6 +791,28 @@ impl Ledger {
         BlockRollbackPerformer::new(self, txn).roll_back(block)
     }
 
+    /// Rolls back the block and all blocks which depend on it, even if they are cemented.
+    /// The confirmation heights of the affected accounts are lowered accordingly and
+    /// the final votes for the roots of the rolled back blocks are cleared, so that the
+    /// node can vote on these roots again.
+    /// This is meant for offline disaster recovery, while no node is running on the ledger.
+    pub fn rollback_cemented(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        block: &BlockHash,
+    ) -> anyhow::Result<Vec<BlockEnum>> {
+        if *block == self.constants.genesis.hash() {
+            bail!("The genesis block cannot be rolled back");
+        }
+        let rolled_back = BlockRollbackPerformer::new(self, txn)
+            .allow_cemented()
+            .roll_back(block)?;
+        for block in &rolled_back {
+            self.store.final_vote.del(txn, &block.root());
+        }
+        Ok(rolled_back)
+    }
+
     /// Returns the latest block with representative information
     pub fn representative_block_hash(&self, txn: &dyn Transaction, hash: &BlockHash) -> BlockHash {
         let hash = RepresentativeBlockFinder::new(txn, self.store.as_ref()).find_rep_block(*hash);
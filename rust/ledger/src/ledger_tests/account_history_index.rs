@@ -0,0 +1,120 @@
+use crate::{
+    ledger_constants::LEDGER_CONSTANTS_STUB, ledger_tests::LedgerContext, Ledger, RepWeightCache,
+    DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH,
+};
+use rsnano_core::{Amount, PublicKey};
+use rsnano_store_lmdb::AccountHistoryIndexState;
+use std::sync::Arc;
+
+#[test]
+fn disabled_by_default() {
+    let ctx = LedgerContext::empty();
+    let txn = ctx.ledger.read_txn();
+
+    assert!(!ctx.ledger.account_history_index_enabled());
+    assert_eq!(ctx.ledger.store.account_history.count(&txn), 0);
+}
+
+#[test]
+fn enabling_builds_index() {
+    let ctx = LedgerContext::empty();
+
+    ctx.ledger.enable_account_history_index();
+    assert!(ctx.ledger.account_history_index_enabled());
+    assert!(!ctx.ledger.account_history_index_complete());
+    while ctx.ledger.build_account_history_index() {}
+
+    let txn = ctx.ledger.read_txn();
+    assert!(ctx.ledger.account_history_index_complete());
+    assert_eq!(
+        ctx.ledger.store.account_history.state(&txn),
+        AccountHistoryIndexState::Complete
+    );
+    assert_eq!(
+        ctx.ledger
+            .store
+            .account_history
+            .get(&txn, &DEV_GENESIS_ACCOUNT, 1),
+        Some(*DEV_GENESIS_HASH)
+    );
+}
+
+#[test]
+fn enabling_again_keeps_index() {
+    let ctx = LedgerContext::empty();
+    ctx.ledger.enable_account_history_index();
+    while ctx.ledger.build_account_history_index() {}
+
+    ctx.ledger.enable_account_history_index();
+
+    assert!(ctx.ledger.account_history_index_complete());
+    assert_eq!(ctx.ledger.build_account_history_index(), false);
+}
+
+#[test]
+fn insert_and_rollback_update_index() {
+    let ctx = LedgerContext::empty();
+    ctx.ledger.enable_account_history_index();
+    while ctx.ledger.build_account_history_index() {}
+    let mut txn = ctx.ledger.rw_txn();
+    let genesis = ctx.genesis_block_factory();
+
+    let mut change = genesis
+        .legacy_change(&txn)
+        .representative(PublicKey::from(1))
+        .build();
+    ctx.ledger.process(&mut txn, &mut change).unwrap();
+
+    let history = &ctx.ledger.store.account_history;
+    assert_eq!(
+        history.get(&txn, &DEV_GENESIS_ACCOUNT, 2),
+        Some(change.hash())
+    );
+    let timestamp = change.sideband().unwrap().timestamp;
+    assert_eq!(
+        history.first_height_since(&txn, &DEV_GENESIS_ACCOUNT, timestamp),
+        Some(2)
+    );
+
+    ctx.ledger.rollback(&mut txn, &change.hash()).unwrap();
+
+    assert_eq!(history.get(&txn, &DEV_GENESIS_ACCOUNT, 2), None);
+    assert_eq!(history.count(&txn), 1);
+}
+
+#[test]
+fn dropping_clears_index() {
+    let ctx = LedgerContext::empty();
+    ctx.ledger.enable_account_history_index();
+    while ctx.ledger.build_account_history_index() {}
+
+    ctx.ledger.drop_account_history_index();
+
+    let txn = ctx.ledger.read_txn();
+    assert!(!ctx.ledger.account_history_index_enabled());
+    assert!(!ctx.ledger.account_history_index_complete());
+    assert_eq!(ctx.ledger.store.account_history.count(&txn), 0);
+    assert_eq!(
+        ctx.ledger.store.account_history.state(&txn),
+        AccountHistoryIndexState::Disabled
+    );
+}
+
+#[test]
+fn enabled_state_is_persisted() {
+    let ctx = LedgerContext::empty();
+    ctx.ledger.enable_account_history_index();
+
+    let reopened = Ledger::new(
+        ctx.ledger.store.clone(),
+        LEDGER_CONSTANTS_STUB.clone(),
+        Amount::zero(),
+        Arc::new(RepWeightCache::new()),
+    )
+    .unwrap();
+
+    assert!(reopened.account_history_index_enabled());
+    assert!(!reopened.account_history_index_complete());
+    while reopened.build_account_history_index() {}
+    assert!(reopened.account_history_index_complete());
+}
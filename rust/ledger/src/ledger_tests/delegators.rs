@@ -0,0 +1,92 @@
+use crate::{
+    ledger_constants::DEV_GENESIS_PUB_KEY,
+    ledger_tests::{helpers::setup_legacy_open_block, LedgerContext},
+    DEV_GENESIS_ACCOUNT,
+};
+use rsnano_core::{Account, PublicKey};
+
+#[test]
+fn genesis_delegates_to_itself() {
+    let ctx = LedgerContext::empty();
+    let txn = ctx.ledger.read_txn();
+
+    assert_eq!(
+        ctx.ledger
+            .store
+            .delegators
+            .iter(&txn, &DEV_GENESIS_PUB_KEY, &Account::zero())
+            .collect::<Vec<_>>(),
+        vec![*DEV_GENESIS_ACCOUNT]
+    );
+}
+
+#[test]
+fn open_adds_delegator() {
+    let ctx = LedgerContext::empty();
+    let mut txn = ctx.ledger.rw_txn();
+
+    let open = setup_legacy_open_block(&ctx, &mut txn);
+
+    let representative = open.open_block.representative_field().unwrap();
+    assert!(ctx
+        .ledger
+        .store
+        .delegators
+        .exists(&txn, &representative, &open.destination.account()));
+}
+
+#[test]
+fn change_moves_delegator() {
+    let ctx = LedgerContext::empty();
+    let mut txn = ctx.ledger.rw_txn();
+    let genesis = ctx.genesis_block_factory();
+    let new_representative = PublicKey::from(1);
+
+    let mut change = genesis
+        .legacy_change(&txn)
+        .representative(new_representative)
+        .build();
+    ctx.ledger.process(&mut txn, &mut change).unwrap();
+
+    let delegators = &ctx.ledger.store.delegators;
+    assert!(!delegators.exists(&txn, &DEV_GENESIS_PUB_KEY, &DEV_GENESIS_ACCOUNT));
+    assert!(delegators.exists(&txn, &new_representative, &DEV_GENESIS_ACCOUNT));
+}
+
+#[test]
+fn rollback_restores_delegators() {
+    let ctx = LedgerContext::empty();
+    let mut txn = ctx.ledger.rw_txn();
+    let genesis = ctx.genesis_block_factory();
+    let new_representative = PublicKey::from(1);
+
+    let mut change = genesis
+        .legacy_change(&txn)
+        .representative(new_representative)
+        .build();
+    ctx.ledger.process(&mut txn, &mut change).unwrap();
+    ctx.ledger.rollback(&mut txn, &change.hash()).unwrap();
+
+    let delegators = &ctx.ledger.store.delegators;
+    assert!(delegators.exists(&txn, &DEV_GENESIS_PUB_KEY, &DEV_GENESIS_ACCOUNT));
+    assert!(!delegators.exists(&txn, &new_representative, &DEV_GENESIS_ACCOUNT));
+    assert_eq!(delegators.count(&txn, &new_representative), 0);
+}
+
+#[test]
+fn rollback_of_open_removes_delegator() {
+    let ctx = LedgerContext::empty();
+    let mut txn = ctx.ledger.rw_txn();
+    let open = setup_legacy_open_block(&ctx, &mut txn);
+    let representative = open.open_block.representative_field().unwrap();
+
+    ctx.ledger
+        .rollback(&mut txn, &open.open_block.hash())
+        .unwrap();
+
+    assert!(!ctx.ledger.store.delegators.exists(
+        &txn,
+        &representative,
+        &open.destination.account()
+    ));
+}
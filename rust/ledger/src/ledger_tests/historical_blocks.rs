@@ -0,0 +1,105 @@
+use crate::{
+    ledger_tests::LedgerContext, DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH, DEV_GENESIS_PUB_KEY,
+};
+use rsnano_core::{Amount, BlockEnum, PublicKey};
+
+/// Processes a confirmed and an unconfirmed send from the genesis account
+fn setup(ctx: &LedgerContext) -> (BlockEnum, BlockEnum) {
+    let mut txn = ctx.ledger.rw_txn();
+    let genesis = ctx.genesis_block_factory();
+
+    let mut send1 = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send1).unwrap();
+    ctx.ledger.confirm(&mut txn, send1.hash());
+
+    let mut send2 = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send2).unwrap();
+    txn.commit();
+
+    (send1, send2)
+}
+
+/// Creates a ledger with a complete account history index
+fn indexed_ledger() -> LedgerContext {
+    let ctx = LedgerContext::empty();
+    ctx.ledger.enable_account_history_index();
+    while ctx.ledger.build_account_history_index() {}
+    ctx
+}
+
+#[test]
+fn requires_complete_index() {
+    let ctx = LedgerContext::empty();
+    let available = || {
+        ctx.ledger
+            .historical_blocks(&ctx.ledger.read_txn())
+            .is_some()
+    };
+    assert!(!available());
+
+    ctx.ledger.enable_account_history_index();
+    assert!(!available());
+
+    while ctx.ledger.build_account_history_index() {}
+    assert!(available());
+}
+
+#[test]
+fn at_height() {
+    let ctx = indexed_ledger();
+    let (send1, _) = setup(&ctx);
+    let txn = ctx.ledger.read_txn();
+    let finder = ctx.ledger.historical_blocks(&txn).unwrap();
+
+    assert_eq!(finder.at_height(&DEV_GENESIS_ACCOUNT, 0), None);
+    assert_eq!(
+        finder.at_height(&DEV_GENESIS_ACCOUNT, 1).map(|b| b.hash()),
+        Some(*DEV_GENESIS_HASH)
+    );
+    assert_eq!(finder.at_height(&DEV_GENESIS_ACCOUNT, 2), Some(send1));
+    assert_eq!(finder.at_height(&DEV_GENESIS_ACCOUNT, 3), None);
+}
+
+#[test]
+fn at_timestamp() {
+    let ctx = indexed_ledger();
+    let (send1, send2) = setup(&ctx);
+    let txn = ctx.ledger.read_txn();
+    let finder = ctx.ledger.historical_blocks(&txn).unwrap();
+
+    let timestamp = send2.sideband().unwrap().timestamp;
+    assert_eq!(
+        finder.at_timestamp(&DEV_GENESIS_ACCOUNT, timestamp),
+        Some(send1.clone())
+    );
+    assert_eq!(
+        finder.at_timestamp(&DEV_GENESIS_ACCOUNT, u64::MAX),
+        Some(send1)
+    );
+}
+
+#[test]
+fn representative_and_weight_at() {
+    let ctx = indexed_ledger();
+    let mut txn = ctx.ledger.rw_txn();
+    let genesis = ctx.genesis_block_factory();
+    let mut change = genesis.change(&txn).build();
+    ctx.ledger.process(&mut txn, &mut change).unwrap();
+    ctx.ledger.confirm(&mut txn, change.hash());
+    let new_rep = change.representative_field().unwrap();
+
+    let finder = ctx.ledger.historical_blocks(&txn).unwrap();
+    let genesis_block = finder.at_height(&DEV_GENESIS_ACCOUNT, 1).unwrap();
+
+    assert_eq!(
+        finder.representative(&genesis_block),
+        Some(*DEV_GENESIS_PUB_KEY)
+    );
+    assert_eq!(finder.representative(&change), Some(new_rep));
+    txn.commit();
+
+    let weight_at = |rep| ctx.ledger.representative_weight_at(rep, u64::MAX);
+    assert_eq!(weight_at(&new_rep), Some(Amount::MAX));
+    assert_eq!(weight_at(&DEV_GENESIS_PUB_KEY), Some(Amount::zero()));
+    assert_eq!(weight_at(&PublicKey::from(42)), Some(Amount::zero()));
+}
@@ -11,9 +11,13 @@ use rsnano_core::{
     DEV_GENESIS_KEY, GXRB_RATIO,
 };
 
+mod account_history_index;
+mod delegators;
 mod empty_ledger;
+mod historical_blocks;
 mod pruning;
 mod receivable_iteration;
+mod rollback_cemented;
 mod rollback_legacy_change;
 mod rollback_legacy_receive;
 mod rollback_legacy_send;
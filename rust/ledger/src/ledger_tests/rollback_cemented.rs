@@ -0,0 +1,112 @@
+use super::LedgerContext;
+use crate::{ledger_tests::AccountBlockFactory, DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH};
+use rsnano_core::ConfirmationHeightInfo;
+
+#[test]
+fn rollback_refuses_cemented_blocks() {
+    let ctx = LedgerContext::empty();
+    let mut txn = ctx.ledger.rw_txn();
+    let genesis = ctx.genesis_block_factory();
+
+    let mut send = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    ctx.ledger.confirm(&mut txn, send.hash());
+
+    assert!(ctx.ledger.rollback(&mut txn, &send.hash()).is_err());
+    assert!(ctx.ledger.store.block.exists(&txn, &send.hash()));
+}
+
+#[test]
+fn rollback_cemented_lowers_confirmation_height() {
+    let ctx = LedgerContext::empty();
+    let mut txn = ctx.ledger.rw_txn();
+    let genesis = ctx.genesis_block_factory();
+
+    let mut send1 = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send1).unwrap();
+    let mut send2 = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send2).unwrap();
+    ctx.ledger.confirm(&mut txn, send2.hash());
+
+    let rolled_back = ctx
+        .ledger
+        .rollback_cemented(&mut txn, &send1.hash())
+        .unwrap();
+
+    assert_eq!(rolled_back, vec![send2, send1]);
+    assert_eq!(
+        ctx.ledger
+            .store
+            .confirmation_height
+            .get(&txn, &DEV_GENESIS_ACCOUNT),
+        Some(ConfirmationHeightInfo::new(1, *DEV_GENESIS_HASH))
+    );
+    assert_eq!(ctx.ledger.cemented_count(), 1);
+}
+
+#[test]
+fn rollback_cemented_rolls_back_dependent_accounts() {
+    let ctx = LedgerContext::empty();
+    let mut txn = ctx.ledger.rw_txn();
+    let genesis = ctx.genesis_block_factory();
+    let destination = AccountBlockFactory::new(&ctx.ledger);
+
+    let mut send = genesis.send(&txn).link(destination.account()).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    let mut open = destination.open(&txn, send.hash()).build();
+    ctx.ledger.process(&mut txn, &mut open).unwrap();
+    ctx.ledger.confirm(&mut txn, open.hash());
+
+    let rolled_back = ctx
+        .ledger
+        .rollback_cemented(&mut txn, &send.hash())
+        .unwrap();
+
+    assert_eq!(rolled_back, vec![open, send]);
+    assert_eq!(
+        ctx.ledger
+            .store
+            .confirmation_height
+            .get(&txn, &destination.account()),
+        None
+    );
+    assert_eq!(ctx.ledger.store.account.count(&txn), 1);
+    assert_eq!(ctx.ledger.cemented_count(), 1);
+}
+
+#[test]
+fn rollback_cemented_clears_final_votes() {
+    let ctx = LedgerContext::empty();
+    let mut txn = ctx.ledger.rw_txn();
+    let genesis = ctx.genesis_block_factory();
+
+    let mut send = genesis.send(&txn).build();
+    ctx.ledger.process(&mut txn, &mut send).unwrap();
+    ctx.ledger.confirm(&mut txn, send.hash());
+    ctx.ledger
+        .store
+        .final_vote
+        .put(&mut txn, &send.qualified_root(), &send.hash());
+
+    ctx.ledger
+        .rollback_cemented(&mut txn, &send.hash())
+        .unwrap();
+
+    assert!(ctx
+        .ledger
+        .store
+        .final_vote
+        .get(&txn, send.root())
+        .is_empty());
+}
+
+#[test]
+fn genesis_cannot_be_rolled_back() {
+    let ctx = LedgerContext::empty();
+    let mut txn = ctx.ledger.rw_txn();
+
+    assert!(ctx
+        .ledger
+        .rollback_cemented(&mut txn, &DEV_GENESIS_HASH)
+        .is_err());
+}
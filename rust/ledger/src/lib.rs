@@ -8,7 +8,9 @@ mod block_cementer;
 mod block_insertion;
 mod block_rollback;
 mod dependent_blocks_finder;
+mod final_votes;
 mod generate_cache_flags;
+mod historical_block_finder;
 mod ledger;
 mod ledger_constants;
 mod ledger_context;
@@ -24,7 +26,9 @@ mod ledger_tests;
 
 pub(crate) use block_rollback::BlockRollbackPerformer;
 pub use dependent_blocks_finder::*;
+pub use final_votes::*;
 pub use generate_cache_flags::GenerateCacheFlags;
+pub use historical_block_finder::HistoricalBlockFinder;
 pub use ledger::*;
 pub use ledger_constants::{
     LedgerConstants, DEV_GENESIS, DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH, DEV_GENESIS_PUB_KEY,
@@ -9,6 +9,7 @@ pub enum Writer {
     ConfirmationHeight,
     BlockProcessor,
     Pruning,
+    AccountHistoryIndex,
     VotingFinal,
     Testing, // Used in tests to emulate a write lock
 }
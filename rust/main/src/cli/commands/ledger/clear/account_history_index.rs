@@ -0,0 +1,36 @@
+use crate::cli::get_path;
+use anyhow::Result;
+use clap::{ArgGroup, Parser};
+use rsnano_store_lmdb::{AccountHistoryIndexState, LmdbAccountHistoryStore, LmdbEnv};
+use std::sync::Arc;
+
+#[derive(Parser)]
+#[command(group = ArgGroup::new("input")
+    .args(&["data_path", "network"]))]
+pub(crate) struct AccountHistoryIndexArgs {
+    /// Uses the supplied path as the data directory
+    #[arg(long, group = "input")]
+    data_path: Option<String>,
+    /// Uses the supplied network (live, test, beta or dev)
+    #[arg(long, group = "input")]
+    network: Option<String>,
+}
+
+impl AccountHistoryIndexArgs {
+    pub(crate) fn account_history_index(&self) -> Result<()> {
+        let path = get_path(&self.data_path, &self.network).join("data.ldb");
+
+        let env = Arc::new(LmdbEnv::new(&path)?);
+
+        let account_history_store = LmdbAccountHistoryStore::new(env.clone())?;
+
+        let mut txn = env.tx_begin_write();
+
+        account_history_store.clear(&mut txn);
+        account_history_store.set_state(&mut txn, AccountHistoryIndexState::Disabled);
+
+        println!("The account history index was dropped and disabled");
+
+        Ok(())
+    }
+}
@@ -1,9 +1,9 @@
 use crate::cli::get_path;
-use anyhow::Result;
+use anyhow::{bail, Result};
 use clap::{ArgGroup, Parser};
 use rsnano_core::Root;
-use rsnano_store_lmdb::{LmdbEnv, LmdbFinalVoteStore};
-use std::sync::Arc;
+use rsnano_ledger::FinalVotes;
+use rsnano_store_lmdb::LmdbStore;
 
 #[derive(Parser)]
 #[command(group = ArgGroup::new("input1")
@@ -12,7 +12,8 @@ use std::sync::Arc;
 #[command(group = ArgGroup::new("input2")
     .args(&["data_path", "network"]))]
 pub(crate) struct FinalVoteArgs {
-    /// Clears the supplied final vote
+    /// Clears the final votes for the supplied root. Refuses to do so if a
+    /// different block than the finally voted one was cemented at that root
     #[arg(long, group = "input1")]
     root: Option<String>,
     /// Clears all final votes (not recommended)
@@ -30,18 +31,30 @@ impl FinalVoteArgs {
     pub(crate) fn final_vote(&self) -> Result<()> {
         let path = get_path(&self.data_path, &self.network).join("data.ldb");
 
-        let env = Arc::new(LmdbEnv::new(&path)?);
+        let store = LmdbStore::open(&path).build()?;
 
-        let final_vote_store = LmdbFinalVoteStore::new(env.clone())?;
-
-        let mut txn = env.tx_begin_write();
+        let mut txn = store.tx_begin_write();
 
         if let Some(root) = &self.root {
             let root_decoded = Root::decode_hex(root)?;
-            final_vote_store.del(&mut txn, &root_decoded);
+            let entries = FinalVotes::new(&store).at_root(&txn, &root_decoded);
+            if entries.is_empty() {
+                bail!("No final vote found for root {}", root_decoded);
+            }
+
+            if let Some(conflict) = entries.iter().find(|e| e.is_conflicting()) {
+                bail!(
+                    "Refusing to clear final vote: block {} is cemented at root {}, but the final vote is for block {}",
+                    conflict.cemented.unwrap(),
+                    root_decoded,
+                    conflict.hash
+                );
+            }
+
+            store.final_vote.del(&mut txn, &root_decoded);
             println!("Successfully cleared final vote");
         } else {
-            final_vote_store.clear(&mut txn);
+            store.final_vote.clear(&mut txn);
             println!("All final votes were cleared from the database");
         }
 
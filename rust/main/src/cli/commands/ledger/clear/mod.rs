@@ -1,3 +1,4 @@
+use account_history_index::AccountHistoryIndexArgs;
 use anyhow::Result;
 use clap::{CommandFactory, Parser, Subcommand};
 use confirmation_height::ConfirmationHeightArgs;
@@ -5,6 +6,7 @@ use final_vote::FinalVoteArgs;
 use online_weight::OnlineWeightArgs;
 use peers::PeersArgs;
 
+pub(crate) mod account_history_index;
 pub(crate) mod confirmation_height;
 pub(crate) mod final_vote;
 pub(crate) mod online_weight;
@@ -20,6 +22,8 @@ pub(crate) enum ClearSubcommands {
     Peers(PeersArgs),
     /// Clears the confirmation height of accounts
     ConfirmationHeight(ConfirmationHeightArgs),
+    /// Drops the account history index and disables it until a node is started with --enable-account-history-index
+    AccountHistoryIndex(AccountHistoryIndexArgs),
 }
 
 #[derive(Parser)]
@@ -35,6 +39,7 @@ impl ClearCommand {
             Some(ClearSubcommands::ConfirmationHeight(args)) => args.confirmation_height()?,
             Some(ClearSubcommands::OnlineWeight(args)) => args.online_weight()?,
             Some(ClearSubcommands::Peers(args)) => args.peers()?,
+            Some(ClearSubcommands::AccountHistoryIndex(args)) => args.account_history_index()?,
             None => ClearCommand::command().print_long_help()?,
         }
 
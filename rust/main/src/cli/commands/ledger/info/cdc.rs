@@ -0,0 +1,52 @@
+use crate::cli::get_path;
+use anyhow::Result;
+use clap::{ArgGroup, Parser};
+use rsnano_node::cdc::read_cdc_entries;
+
+#[derive(Parser)]
+#[command(group = ArgGroup::new("input")
+    .args(&["data_path", "network"]))]
+pub(crate) struct CdcArgs {
+    /// Displays the entries starting at the supplied sequence number
+    #[arg(long, default_value_t = 0)]
+    from: u64,
+    /// Displays at most the supplied number of entries
+    #[arg(long, default_value_t = 1000)]
+    count: usize,
+    /// Uses the supplied path as the data directory
+    #[arg(long, group = "input")]
+    data_path: Option<String>,
+    /// Uses the supplied network (live, test, beta or dev)
+    #[arg(long, group = "input")]
+    network: Option<String>,
+}
+
+impl CdcArgs {
+    pub(crate) fn cdc(&self) -> Result<()> {
+        let path = get_path(&self.data_path, &self.network).join("cdc");
+
+        let read = read_cdc_entries(&path, self.from, self.count)?;
+        if read.first_available > self.from {
+            println!(
+                "Entries before sequence {} were already deleted\n",
+                read.first_available
+            );
+        }
+        for entry in read.entries {
+            let sideband = entry.block.sideband().unwrap();
+            println!(
+                "sequence: {} hash: {} account: {} height: {} subtype: {:?} amount: {} confirmation_root: {}",
+                entry.sequence,
+                entry.block.hash(),
+                sideband.account.encode_account(),
+                sideband.height,
+                entry.subtype,
+                entry.amount.to_string_dec(),
+                entry.confirmation_root
+            );
+            println!("{} \n", entry.block.to_json()?);
+        }
+
+        Ok(())
+    }
+}
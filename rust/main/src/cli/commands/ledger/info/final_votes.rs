@@ -0,0 +1,78 @@
+use crate::cli::get_path;
+use anyhow::Result;
+use clap::{ArgGroup, Parser};
+use rsnano_core::{Account, Root};
+use rsnano_ledger::{FinalVoteFilter, FinalVotes};
+use rsnano_store_lmdb::LmdbStore;
+
+#[derive(Parser)]
+#[command(group = ArgGroup::new("input")
+    .args(&["data_path", "network"]))]
+pub(crate) struct FinalVotesArgs {
+    /// Only displays the final votes for the supplied root
+    #[arg(long)]
+    root: Option<String>,
+    /// Only displays final votes for accounts greater than or equal to the supplied account
+    #[arg(long)]
+    account_start: Option<String>,
+    /// Only displays final votes for accounts less than or equal to the supplied account
+    #[arg(long)]
+    account_end: Option<String>,
+    /// Displays at most the supplied number of final votes
+    #[arg(long)]
+    count: Option<usize>,
+    /// Uses the supplied path as the data directory
+    #[arg(long, group = "input")]
+    data_path: Option<String>,
+    /// Uses the supplied network (live, test, beta or dev)
+    #[arg(long, group = "input")]
+    network: Option<String>,
+}
+
+impl FinalVotesArgs {
+    pub(crate) fn final_votes(&self) -> Result<()> {
+        let path = get_path(&self.data_path, &self.network).join("data.ldb");
+
+        let filter = FinalVoteFilter {
+            root: self.root.as_ref().map(Root::decode_hex).transpose()?,
+            account_start: self
+                .account_start
+                .as_ref()
+                .map(Account::decode_account)
+                .transpose()?,
+            account_end: self
+                .account_end
+                .as_ref()
+                .map(Account::decode_account)
+                .transpose()?,
+            count: self.count,
+        };
+
+        let store = LmdbStore::open(&path).build()?;
+        let txn = store.tx_begin_read();
+
+        for entry in FinalVotes::new(&store).list(&txn, &filter) {
+            let account = entry
+                .account
+                .map(|a| a.encode_account())
+                .unwrap_or_else(|| "unknown".to_string());
+
+            let status = match entry.cemented {
+                Some(_) if entry.is_conflicting() => "conflicting",
+                Some(_) => "cemented",
+                None => "unconfirmed",
+            };
+
+            println!(
+                "root: {} previous: {} hash: {} account: {} status: {}",
+                entry.qualified_root.root,
+                entry.qualified_root.previous,
+                entry.hash,
+                account,
+                status
+            );
+        }
+
+        Ok(())
+    }
+}
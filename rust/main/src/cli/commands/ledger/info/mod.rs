@@ -2,8 +2,10 @@ use account_count::AccountCountArgs;
 use anyhow::Result;
 use block_count::BlockCountArgs;
 use blocks::Blocks;
+use cdc::CdcArgs;
 use cemented_block_count::CementedBlockCountArgs;
 use clap::{CommandFactory, Parser, Subcommand};
+use final_votes::FinalVotesArgs;
 use peers::PeersArgs;
 use representatives::RepresentativesArgs;
 use trended_online_weight::TrendedOnlineWeightArgs;
@@ -11,7 +13,9 @@ use trended_online_weight::TrendedOnlineWeightArgs;
 pub(crate) mod account_count;
 pub(crate) mod block_count;
 pub(crate) mod blocks;
+pub(crate) mod cdc;
 pub(crate) mod cemented_block_count;
+pub(crate) mod final_votes;
 pub(crate) mod peers;
 pub(crate) mod representatives;
 pub(crate) mod trended_online_weight;
@@ -24,10 +28,14 @@ pub(crate) enum InfoSubcommands {
     BlockCount(BlockCountArgs),
     /// Displays all the blocks in the ledger in text format
     Blocks(Blocks),
+    /// Displays the cemented blocks recorded in the change data capture log
+    Cdc(CdcArgs),
     /// Displays peer IPv6:port connections
     Peers(PeersArgs),
     /// Displays the number of cemented (confirmed) blocks
     CementedBlockCount(CementedBlockCountArgs),
+    /// Displays the final votes generated by this node, optionally filtered by root or account range
+    FinalVotes(FinalVotesArgs),
     /// Displays representatives and their weights
     Representatives(RepresentativesArgs),
     /// Displays trended online weight over time
@@ -46,7 +54,9 @@ impl InfoCommand {
             Some(InfoSubcommands::AccountCount(args)) => args.account_count()?,
             Some(InfoSubcommands::BlockCount(args)) => args.block_count()?,
             Some(InfoSubcommands::Blocks(args)) => args.blocks()?,
+            Some(InfoSubcommands::Cdc(args)) => args.cdc()?,
             Some(InfoSubcommands::CementedBlockCount(args)) => args.cemented_block_count()?,
+            Some(InfoSubcommands::FinalVotes(args)) => args.final_votes()?,
             Some(InfoSubcommands::Peers(args)) => args.peers()?,
             Some(InfoSubcommands::TrendedOnlineWeight(args)) => args.trended_online_weight()?,
             Some(InfoSubcommands::Representatives(args)) => args.dump_representatives()?,
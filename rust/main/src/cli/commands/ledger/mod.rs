@@ -2,11 +2,13 @@ use anyhow::Result;
 use clap::{CommandFactory, Parser, Subcommand};
 use clear::ClearCommand;
 use info::InfoCommand;
+use rollback::RollbackArgs;
 use snapshot::SnapshotArgs;
 use vacuum::VacuumArgs;
 
 pub(crate) mod clear;
 pub(crate) mod info;
+pub(crate) mod rollback;
 pub(crate) mod snapshot;
 pub(crate) mod vacuum;
 
@@ -20,6 +22,8 @@ pub(crate) enum LedgerSubcommands {
     Vacuum(VacuumArgs),
     /// Similar to vacuum but does not replace the existing database
     Snapshot(SnapshotArgs),
+    /// Rolls back blocks offline, including their dependent blocks in other accounts
+    Rollback(RollbackArgs),
 }
 
 #[derive(Parser)]
@@ -35,6 +39,7 @@ impl LedgerCommand {
             Some(LedgerSubcommands::Clear(command)) => command.run()?,
             Some(LedgerSubcommands::Vacuum(args)) => args.vacuum()?,
             Some(LedgerSubcommands::Snapshot(args)) => args.snapshot()?,
+            Some(LedgerSubcommands::Rollback(args)) => args.rollback()?,
             None => LedgerCommand::command().print_long_help()?,
         }
 
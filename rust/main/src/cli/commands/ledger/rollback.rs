@@ -0,0 +1,121 @@
+use crate::cli::get_path;
+use anyhow::{anyhow, Result};
+use clap::{ArgGroup, Parser};
+use rsnano_core::{Account, Amount, BlockHash};
+use rsnano_ledger::{Ledger, RepWeightCache};
+use rsnano_node::{config::NetworkConstants, NetworkParams};
+use rsnano_store_lmdb::LmdbStore;
+use std::sync::Arc;
+
+#[derive(Parser)]
+#[command(group = ArgGroup::new("input")
+    .args(&["data_path", "network"]))]
+pub(crate) struct RollbackArgs {
+    /// Rolls back the block with the supplied hash and all its successors
+    #[arg(long, conflicts_with = "account", required_unless_present = "account")]
+    hash: Option<String>,
+    /// Rolls back the chain of the supplied account
+    #[arg(long, requires = "to_height")]
+    account: Option<String>,
+    /// Rolls back all blocks of the account chain above the supplied height
+    #[arg(long, requires = "account")]
+    to_height: Option<u64>,
+    /// Rolls back cemented blocks too and clears the final votes for their roots
+    #[arg(long)]
+    force: bool,
+    /// Only displays the blocks that would be rolled back
+    #[arg(long)]
+    dry_run: bool,
+    /// Uses the supplied path as the data directory
+    #[arg(long, group = "input")]
+    data_path: Option<String>,
+    /// Uses the supplied network (live, test, beta or dev)
+    #[arg(long, group = "input")]
+    network: Option<String>,
+}
+
+impl RollbackArgs {
+    pub(crate) fn rollback(&self) -> Result<()> {
+        let data_path = get_path(&self.data_path, &self.network);
+        let path = data_path.join("data.ldb");
+
+        let network_params = NetworkParams::new(NetworkConstants::active_network());
+
+        let ledger = Ledger::new(
+            Arc::new(LmdbStore::open(&path).build()?),
+            network_params.ledger,
+            Amount::zero(),
+            Arc::new(RepWeightCache::new()),
+        )?;
+
+        let mut txn = ledger.rw_txn();
+
+        let target = match &self.hash {
+            Some(hash) => BlockHash::decode_hex(hash)?,
+            None => {
+                let account = Account::decode_account(self.account.as_ref().unwrap())?;
+                let to_height = self.to_height.unwrap();
+                let head = ledger
+                    .any()
+                    .account_head(&txn, &account)
+                    .ok_or_else(|| anyhow!("Account not found"))?;
+                let mut block = ledger.any().get_block(&txn, &head).unwrap();
+                if block.sideband().unwrap().height <= to_height {
+                    println!("Nothing to roll back");
+                    return Ok(());
+                }
+                while block.sideband().unwrap().height > to_height + 1 {
+                    block = ledger.any().get_block(&txn, &block.previous()).unwrap();
+                }
+                block.hash()
+            }
+        };
+
+        let result = if self.force {
+            ledger.rollback_cemented(&mut txn, &target)
+        } else {
+            ledger.rollback(&mut txn, &target)
+        };
+
+        let rolled_back = match result {
+            Ok(blocks) => blocks,
+            Err(e) => {
+                txn.abort();
+                return Err(if self.force {
+                    e
+                } else {
+                    e.context("Use --force to roll back cemented blocks")
+                });
+            }
+        };
+
+        for block in &rolled_back {
+            let sideband = block.sideband().unwrap();
+            println!(
+                "hash: {} account: {} height: {}",
+                block.hash(),
+                sideband.account.encode_account(),
+                sideband.height
+            );
+        }
+
+        if self.dry_run {
+            txn.abort();
+            println!("{} blocks would be rolled back", rolled_back.len());
+        } else {
+            txn.commit();
+            println!("Rolled back {} blocks", rolled_back.len());
+            if self.force {
+                println!("Cleared the final votes for the roots of the rolled back blocks");
+                if data_path.join("cdc").exists() {
+                    println!(
+                        "Warning: the CDC log still contains the rolled back blocks that were \
+                        cemented. Consumers of the log have to revert them"
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
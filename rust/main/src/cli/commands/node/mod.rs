@@ -74,7 +74,7 @@ impl NodeCommand {
 
         println!("Testing key derivation function");
 
-        wallets.kdf.hash_password("", &mut [0; 32]);
+        wallets.kdf.hash_password("", &[0; 32])?;
 
         println!("Testing time retrieval latency...");
 
@@ -95,6 +95,9 @@ pub(crate) struct RunDaemonArgs {
     /// Enable experimental ledger pruning
     #[arg(long)]
     enable_pruning: bool,
+    /// Maintain an index of the account chains by height and local timestamp, which speeds up account_history and enables its height and time ranges. The existing blocks are indexed in the background and the index stays enabled until it is dropped with `ledger clear account-history-index`
+    #[arg(long)]
+    enable_account_history_index: bool,
     /// Increase bootstrap processor limits to allow more blocks before hitting full state and verify/write more per database call. Also disable deletion of processed unchecked blocks.
     #[arg(long)]
     fast_bootstrap: bool,
@@ -250,6 +253,9 @@ impl RunDaemonArgs {
         if self.enable_pruning {
             node_flags.set_enable_pruning(true);
         }
+        if self.enable_account_history_index {
+            node_flags.set_enable_account_history_index(true);
+        }
         if self.fast_bootstrap {
             node_flags.set_fast_bootstrap(true);
         }
@@ -2,7 +2,7 @@ use crate::cli::get_path;
 use anyhow::{anyhow, Result};
 use clap::{ArgGroup, Parser};
 use rand::{thread_rng, Rng};
-use rsnano_core::{RawKey, WalletId};
+use rsnano_core::{Mnemonic, RawKey, WalletId};
 use rsnano_node::wallets::{Wallets, WalletsExt};
 use rsnano_store_lmdb::LmdbEnv;
 use std::sync::Arc;
@@ -12,8 +12,14 @@ use std::sync::Arc;
     .args(&["data_path", "network"]))]
 pub(crate) struct CreateWalletArgs {
     /// Optional seed of the new wallet
-    #[arg(long)]
+    #[arg(long, conflicts_with = "mnemonic")]
     seed: Option<String>,
+    /// Optional BIP39 mnemonic of the new wallet. Accounts are derived via the path 44'/165'/n'
+    #[arg(long)]
+    mnemonic: Option<String>,
+    /// Optional BIP39 passphrase of the mnemonic
+    #[arg(long, requires = "mnemonic")]
+    passphrase: Option<String>,
     /// Optional password of the new wallet
     #[arg(long)]
     password: Option<String>,
@@ -56,6 +62,15 @@ impl CreateWalletArgs {
                 .map_err(|e| anyhow!("Failed to set wallet seed: {:?}", e))?;
         }
 
+        if let Some(mnemonic) = &self.mnemonic {
+            let mnemonic = Mnemonic::parse(mnemonic)?;
+            let passphrase = self.passphrase.clone().unwrap_or_default();
+
+            wallets
+                .change_mnemonic(wallet_id, &mnemonic, &passphrase, 0)
+                .map_err(|e| anyhow!("Failed to set wallet mnemonic: {:?}", e))?;
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,78 @@
+use crate::cli::get_path;
+use anyhow::{anyhow, Result};
+use clap::{ArgGroup, Parser};
+use rsnano_core::{Argon2idParams, WalletId};
+use rsnano_node::wallets::Wallets;
+use rsnano_store_lmdb::LmdbEnv;
+use std::{fs::File, io::Write, sync::Arc};
+
+#[derive(Parser)]
+#[command(group = ArgGroup::new("input")
+    .args(&["data_path", "network"]))]
+pub(crate) struct ExportWalletArgs {
+    /// The wallet to be exported
+    #[arg(long)]
+    wallet: String,
+    /// The file to which the wallet is written. Prints to stdout if not set
+    #[arg(long)]
+    file: Option<String>,
+    /// Uses the versioned encrypted backup format instead of a plain JSON dump
+    #[arg(long)]
+    encrypted: bool,
+    /// Password of the encrypted backup
+    #[arg(long, requires = "encrypted")]
+    backup_password: Option<String>,
+    /// Argon2id memory cost in KiB
+    #[arg(long, requires = "encrypted")]
+    kdf_memory_kib: Option<u32>,
+    /// Argon2id iterations
+    #[arg(long, requires = "encrypted")]
+    kdf_iterations: Option<u32>,
+    /// Argon2id parallelism
+    #[arg(long, requires = "encrypted")]
+    kdf_parallelism: Option<u32>,
+    /// Uses the supplied path as the data directory
+    #[arg(long, group = "input")]
+    data_path: Option<String>,
+    /// Uses the supplied network (live, test, beta or dev)
+    #[arg(long, group = "input")]
+    network: Option<String>,
+}
+
+impl ExportWalletArgs {
+    pub(crate) async fn export_wallet(&self) -> Result<()> {
+        let path = get_path(&self.data_path, &self.network).join("wallets.ldb");
+        let wallet_id = WalletId::decode_hex(&self.wallet)?;
+        let env = Arc::new(LmdbEnv::new(&path)?);
+        let wallets = Arc::new(Wallets::new_null_with_env(
+            env,
+            tokio::runtime::Handle::current(),
+        ));
+
+        let contents = if self.encrypted {
+            let mut kdf = Argon2idParams::default();
+            if let Some(memory) = self.kdf_memory_kib {
+                kdf.memory_kib = memory;
+            }
+            if let Some(iterations) = self.kdf_iterations {
+                kdf.iterations = iterations;
+            }
+            if let Some(parallelism) = self.kdf_parallelism {
+                kdf.parallelism = parallelism;
+            }
+            let password = self.backup_password.clone().unwrap_or_default();
+            wallets.export_encrypted(&wallet_id, &password, &kdf)?
+        } else {
+            wallets
+                .serialize(wallet_id)
+                .map_err(|e| anyhow!("Failed to export wallet: {:?}", e))?
+        };
+
+        match &self.file {
+            Some(file) => File::create(file)?.write_all(contents.as_bytes())?,
+            None => println!("{}", contents),
+        }
+
+        Ok(())
+    }
+}
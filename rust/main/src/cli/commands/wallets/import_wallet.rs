@@ -0,0 +1,53 @@
+use crate::cli::get_path;
+use anyhow::{Context, Result};
+use clap::{ArgGroup, Parser};
+use rsnano_core::WalletId;
+use rsnano_node::wallets::{EncryptedWalletBackup, Wallets};
+use rsnano_store_lmdb::LmdbEnv;
+use std::{fs::read_to_string, sync::Arc};
+
+#[derive(Parser)]
+#[command(group = ArgGroup::new("input")
+    .args(&["data_path", "network"]))]
+pub(crate) struct ImportWalletArgs {
+    /// The path of the encrypted wallet backup
+    #[arg(long)]
+    file: String,
+    /// Password of the encrypted backup
+    #[arg(long)]
+    backup_password: Option<String>,
+    /// Optional id of the new wallet. Defaults to the id stored in the backup
+    #[arg(long)]
+    wallet: Option<String>,
+    /// Uses the supplied path as the data directory
+    #[arg(long, group = "input")]
+    data_path: Option<String>,
+    /// Uses the supplied network (live, test, beta or dev)
+    #[arg(long, group = "input")]
+    network: Option<String>,
+}
+
+impl ImportWalletArgs {
+    pub(crate) async fn import_wallet(&self) -> Result<()> {
+        let contents = read_to_string(&self.file).context("Unable to read <file> contents")?;
+        let backup = EncryptedWalletBackup::from_json(&contents)?;
+        let wallet_id = match &self.wallet {
+            Some(id) => WalletId::decode_hex(id)?,
+            None => backup.metadata.wallet,
+        };
+
+        let path = get_path(&self.data_path, &self.network).join("wallets.ldb");
+        let env = Arc::new(LmdbEnv::new(&path)?);
+        let wallets = Arc::new(Wallets::new_null_with_env(
+            env,
+            tokio::runtime::Handle::current(),
+        ));
+
+        let password = self.backup_password.clone().unwrap_or_default();
+        wallets.import_encrypted(wallet_id, &contents, &password)?;
+
+        println!("{:?}", wallet_id);
+
+        Ok(())
+    }
+}
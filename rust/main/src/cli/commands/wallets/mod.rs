@@ -7,8 +7,11 @@ use create_account::CreateAccountArgs;
 use create_wallet::CreateWalletArgs;
 use decrypt_wallet::DecryptWalletArgs;
 use destroy_wallet::DestroyWalletArgs;
+use export_mnemonic::ExportMnemonicArgs;
+use export_wallet::ExportWalletArgs;
 use get_wallet_representative::GetWalletRepresentativeArgs;
 use import_keys::ImportKeysArgs;
+use import_wallet::ImportWalletArgs;
 use list_wallets::ListWalletsArgs;
 use remove_account::RemoveAccountArgs;
 use set_wallet_representative::SetWalletRepresentativeArgs;
@@ -20,8 +23,11 @@ pub(crate) mod create_account;
 pub(crate) mod create_wallet;
 pub(crate) mod decrypt_wallet;
 pub(crate) mod destroy_wallet;
+pub(crate) mod export_mnemonic;
+pub(crate) mod export_wallet;
 pub(crate) mod get_wallet_representative;
 pub(crate) mod import_keys;
+pub(crate) mod import_wallet;
 pub(crate) mod list_wallets;
 pub(crate) mod remove_account;
 pub(crate) mod set_wallet_representative;
@@ -36,6 +42,10 @@ pub(crate) enum WalletSubcommands {
     DestroyWallet(DestroyWalletArgs),
     /// Imports keys from a file to a wallet
     ImportKeys(ImportKeysArgs),
+    /// Exports a wallet, optionally in the encrypted backup format
+    ExportWallet(ExportWalletArgs),
+    /// Creates a wallet from an encrypted backup
+    ImportWallet(ImportWalletArgs),
     /// Adds a private_key to a wallet
     AddPrivateKey(AddPrivateKeyArgs),
     /// Changes the seed of a wallet
@@ -48,6 +58,8 @@ pub(crate) enum WalletSubcommands {
     RemoveAccount(RemoveAccountArgs),
     /// Decrypts a wallet (WARNING: THIS WILL PRINT YOUR PRIVATE KEY TO STDOUT!)
     DecryptWallet(DecryptWalletArgs),
+    /// Prints the BIP39 mnemonic of a wallet (WARNING: THIS WILL PRINT YOUR MNEMONIC TO STDOUT!)
+    ExportMnemonic(ExportMnemonicArgs),
     /// List all wallets and their public keys
     ListWallets(ListWalletsArgs),
     /// Removes all send IDs from the wallets (dangerous: not intended for production use)
@@ -70,8 +82,11 @@ impl WalletsCommand {
             Some(WalletSubcommands::AddPrivateKey(args)) => args.add_key().await?,
             Some(WalletSubcommands::ChangeWalletSeed(args)) => args.change_wallet_seed().await?,
             Some(WalletSubcommands::ImportKeys(args)) => args.import_keys().await?,
+            Some(WalletSubcommands::ExportWallet(args)) => args.export_wallet().await?,
+            Some(WalletSubcommands::ImportWallet(args)) => args.import_wallet().await?,
             Some(WalletSubcommands::RemoveAccount(args)) => args.remove_account().await?,
             Some(WalletSubcommands::DecryptWallet(args)) => args.decrypt_wallet().await?,
+            Some(WalletSubcommands::ExportMnemonic(args)) => args.export_mnemonic().await?,
             Some(WalletSubcommands::GetWalletRepresentative(args)) => {
                 args.get_wallet_representative().await?
             }
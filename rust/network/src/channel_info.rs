@@ -15,12 +15,15 @@ use std::{
 
 use crate::{
     utils::{ipv4_address_or_ipv6_subnet, map_address_to_subnetwork},
-    ChannelDirection, ChannelId, ChannelMode, TrafficType,
+    ChannelDirection, ChannelId, ChannelMode, RoundTripTimes, TrafficType,
 };
 
 /// Default timeout in seconds
 const DEFAULT_TIMEOUT: u64 = 120;
 
+/// Score of a peer about which nothing is known yet. Scores range from 0 to 100
+pub const DEFAULT_PEER_SCORE: u8 = 50;
+
 pub struct ChannelInfo {
     channel_id: ChannelId,
     local_addr: SocketAddrV6,
@@ -46,6 +49,9 @@ pub struct ChannelInfo {
     closed: AtomicBool,
 
     socket_type: AtomicU8,
+
+    /// How much the peer is preferred when channels are picked at random
+    score: AtomicU8,
 }
 
 impl ChannelInfo {
@@ -70,6 +76,7 @@ impl ChannelInfo {
             timed_out: AtomicBool::new(false),
             socket_type: AtomicU8::new(ChannelMode::Undefined as u8),
             closed: AtomicBool::new(false),
+            score: AtomicU8::new(DEFAULT_PEER_SCORE),
             data: Mutex::new(ChannelInfoData {
                 node_id: None,
                 write_queue: None,
@@ -78,6 +85,7 @@ impl ChannelInfo {
                 } else {
                     None
                 },
+                round_trip_times: RoundTripTimes::default(),
             }),
         }
     }
@@ -151,6 +159,14 @@ impl ChannelInfo {
         self.protocol_version.store(version, Ordering::Relaxed);
     }
 
+    pub fn score(&self) -> u8 {
+        self.score.load(Ordering::Relaxed)
+    }
+
+    pub fn set_score(&self, score: u8) {
+        self.score.store(score.min(100), Ordering::Relaxed);
+    }
+
     pub fn last_activity(&self) -> Timestamp {
         self.last_activity.load(Ordering::Relaxed).into()
     }
@@ -218,6 +234,20 @@ impl ChannelInfo {
             .store(now.into(), Ordering::Relaxed);
     }
 
+    /// Records the time between sending a request to the peer and receiving its response
+    pub fn add_round_trip_time(&self, rtt: Duration) {
+        self.data.lock().unwrap().round_trip_times.add(rtt);
+    }
+
+    pub fn round_trip_times(&self) -> RoundTripTimes {
+        self.data.lock().unwrap().round_trip_times.clone()
+    }
+
+    /// Smoothed round trip time. None if nothing was measured yet
+    pub fn round_trip_time(&self) -> Option<Duration> {
+        self.data.lock().unwrap().round_trip_times.ewma()
+    }
+
     pub fn is_queue_full(&self, traffic_type: TrafficType) -> bool {
         let guard = self.data.lock().unwrap();
         match &guard.write_queue {
@@ -231,6 +261,7 @@ struct ChannelInfoData {
     node_id: Option<PublicKey>,
     peering_addr: Option<SocketAddrV6>,
     write_queue: Option<Box<dyn WriteQueueAdapter>>,
+    round_trip_times: RoundTripTimes,
 }
 
 pub(crate) trait WriteQueueAdapter: Send + Sync {
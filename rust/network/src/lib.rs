@@ -9,6 +9,7 @@ mod network_observer;
 mod peer_connector;
 pub mod peer_exclusion;
 mod response_server_spawner;
+mod round_trip_time;
 mod tcp_listener;
 pub mod token_bucket;
 pub mod utils;
@@ -24,6 +25,7 @@ pub use network_observer::*;
 use num_derive::FromPrimitive;
 pub use peer_connector::*;
 pub use response_server_spawner::*;
+pub use round_trip_time::*;
 use std::fmt::{Debug, Display};
 pub use tcp_listener::*;
 
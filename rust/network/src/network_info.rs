@@ -5,7 +5,7 @@ use crate::{
     utils::{is_ipv4_mapped, map_address_to_subnetwork, reserved_address},
     ChannelId, ChannelInfo, ChannelMode, TrafficType,
 };
-use rand::{seq::SliceRandom, thread_rng};
+use rand::{seq::SliceRandom, thread_rng, Rng};
 use rsnano_core::{
     utils::{ContainerInfo, ContainerInfoComponent},
     Networks, PublicKey,
@@ -135,6 +135,11 @@ impl NetworkInfo {
         self.excluded_peers.is_excluded(peer_addr, now)
     }
 
+    /// How often the peer misbehaved recently
+    pub fn misbehavior_score(&self, peer_addr: &SocketAddrV6) -> u64 {
+        self.excluded_peers.score(peer_addr)
+    }
+
     pub fn add_outbound_attempt(
         &mut self,
         peer: SocketAddrV6,
@@ -229,10 +234,10 @@ impl NetworkInfo {
             .map(|c| c.channel_id())
     }
 
+    /// Channels with a higher score are more likely to be picked
     pub fn random_realtime_channels(&self, count: usize, min_version: u8) -> Vec<Arc<ChannelInfo>> {
         let mut channels = self.list_realtime(min_version);
-        let mut rng = thread_rng();
-        channels.shuffle(&mut rng);
+        weighted_shuffle(&mut channels);
         if count > 0 {
             channels.truncate(count)
         }
@@ -267,10 +272,10 @@ impl NetworkInfo {
             || endpoint == &SocketAddrV6::new(Ipv6Addr::LOCALHOST, self.listening_port(), 0, 0)
     }
 
+    /// Channels with a higher score are more likely to be picked
     pub fn random_list_realtime(&self, count: usize, min_version: u8) -> Vec<Arc<ChannelInfo>> {
         let mut channels = self.list_realtime(min_version);
-        let mut rng = thread_rng();
-        channels.shuffle(&mut rng);
+        weighted_shuffle(&mut channels);
         if count > 0 {
             channels.truncate(count)
         }
@@ -412,7 +417,12 @@ impl NetworkInfo {
             .filter(|c| c.is_alive())
             .cloned()
             .collect();
-        channels.sort_by(|a, b| a.last_bootstrap_attempt().cmp(&b.last_bootstrap_attempt()));
+        // Peers that were never tried share the same timestamp, so prefer the better scored ones
+        channels.sort_by(|a, b| {
+            a.last_bootstrap_attempt()
+                .cmp(&b.last_bootstrap_attempt())
+                .then_with(|| b.score().cmp(&a.score()))
+        });
         channels
     }
 
@@ -689,6 +699,28 @@ impl NetworkInfo {
     }
 }
 
+/// Shuffles the channels so that channels with a higher score tend to end up at the front.
+/// Every channel keeps a chance to be picked, even if its score is zero.
+fn weighted_shuffle(channels: &mut Vec<Arc<ChannelInfo>>) {
+    weighted_shuffle_by(channels, |channel| channel.score());
+}
+
+/// Shuffles the items so that items with a higher score tend to end up at the front.
+/// Every item keeps a chance to be picked, even if its score is zero.
+pub fn weighted_shuffle_by<T>(items: &mut Vec<T>, score: impl Fn(&T) -> u8) {
+    let mut rng = thread_rng();
+    let mut keyed: Vec<_> = items
+        .drain(..)
+        .map(|item| {
+            let weight = score(&item) as f64 + 1.0;
+            let key = rng.gen::<f64>().powf(1.0 / weight);
+            (key, item)
+        })
+        .collect();
+    keyed.sort_by(|a, b| b.0.total_cmp(&a.0));
+    items.extend(keyed.into_iter().map(|(_, item)| item));
+}
+
 impl Drop for NetworkInfo {
     fn drop(&mut self) {
         self.stop();
@@ -800,10 +832,41 @@ mod tests {
         assert!(endpoints.contains(&TEST_ENDPOINT_3));
     }
 
+    #[test]
+    fn prefer_channels_with_higher_score() {
+        let mut network = NetworkInfo::new_test_instance();
+        let good = add_realtime_channel_with_peering_addr(&mut network, TEST_ENDPOINT_1);
+        let bad = add_realtime_channel_with_peering_addr(&mut network, TEST_ENDPOINT_2);
+        good.set_score(100);
+        bad.set_score(0);
+
+        let good_first = (0..100)
+            .filter(|_| network.random_list_realtime(1, 0)[0].channel_id() == good.channel_id())
+            .count();
+
+        assert!(
+            good_first > 80,
+            "good channel picked first {good_first} times"
+        );
+    }
+
+    #[test]
+    fn bootstrap_peer_prefers_higher_score() {
+        let mut network = NetworkInfo::new_test_instance();
+        let bad = add_realtime_channel_with_peering_addr(&mut network, TEST_ENDPOINT_1);
+        let good = add_realtime_channel_with_peering_addr(&mut network, TEST_ENDPOINT_2);
+        bad.set_score(10);
+        good.set_score(90);
+
+        let peer = network.bootstrap_peer(Timestamp::new_test_instance());
+
+        assert_eq!(peer, TEST_ENDPOINT_2);
+    }
+
     fn add_realtime_channel_with_peering_addr(
         network: &mut NetworkInfo,
         peering_addr: SocketAddrV6,
-    ) {
+    ) -> Arc<ChannelInfo> {
         let channel = network
             .add(
                 TEST_ENDPOINT_1,
@@ -818,6 +881,7 @@ mod tests {
             channel.channel_id(),
             PublicKey::from(peering_addr.ip().to_bits()),
         );
+        channel
     }
 
     mod purging {
@@ -51,6 +51,14 @@ impl PeerExclusion {
         }
     }
 
+    /// How often the peer misbehaved recently. Zero for unknown peers
+    pub fn score(&self, endpoint: &SocketAddrV6) -> u64 {
+        self.by_ip
+            .get(&endpoint.ip())
+            .map(|peer| peer.score)
+            .unwrap_or_default()
+    }
+
     /// Perma bans are used for prohibiting a node to connect to itself.
     pub fn perma_ban(&mut self, peer_addr: SocketAddrV6) {
         self.perma_bans.insert(peer_addr);
@@ -285,6 +293,16 @@ mod tests {
             assert!(peers.is_excluded(&endpoint1, NOW));
             assert!(peers.is_excluded(&endpoint2, NOW));
         }
+
+        #[test]
+        fn score_counts_misbehavior() {
+            let mut peers = PeerExclusion::new();
+            let endpoint = test_endpoint(1);
+            assert_eq!(peers.score(&endpoint), 0);
+            peers.peer_misbehaved(&endpoint, NOW);
+            peers.peer_misbehaved(&endpoint, NOW);
+            assert_eq!(peers.score(&endpoint), 2);
+        }
     }
 
     mod max_size {
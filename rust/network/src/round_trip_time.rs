@@ -0,0 +1,105 @@
+use std::{collections::VecDeque, time::Duration};
+
+/// Round trip time measurements of a single channel.
+/// Keeps an exponentially weighted moving average and the most recent samples
+/// for percentile calculations.
+#[derive(Clone, Default, Debug)]
+pub struct RoundTripTimes {
+    ewma: Option<Duration>,
+    samples: VecDeque<Duration>,
+}
+
+impl RoundTripTimes {
+    pub const MAX_SAMPLES: usize = 64;
+
+    /// Same smoothing factor as TCP uses for its smoothed RTT (RFC 6298)
+    const EWMA_ALPHA: f64 = 0.125;
+
+    pub fn add(&mut self, rtt: Duration) {
+        self.ewma = Some(match self.ewma {
+            Some(avg) => avg.mul_f64(1.0 - Self::EWMA_ALPHA) + rtt.mul_f64(Self::EWMA_ALPHA),
+            None => rtt,
+        });
+
+        if self.samples.len() >= Self::MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(rtt);
+    }
+
+    /// Smoothed round trip time. None if nothing was measured yet
+    pub fn ewma(&self) -> Option<Duration> {
+        self.ewma
+    }
+
+    /// Nearest-rank percentile of the recent samples. `percentile` is clamped to 0..=100
+    pub fn percentile(&self, percentile: u8) -> Option<Duration> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<_> = self.samples.iter().copied().collect();
+        sorted.sort_unstable();
+        let rank = (percentile.min(100) as usize * sorted.len()).div_ceil(100);
+        Some(sorted[rank.saturating_sub(1)])
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty() {
+        let rtt = RoundTripTimes::default();
+        assert_eq!(rtt.ewma(), None);
+        assert_eq!(rtt.percentile(50), None);
+        assert!(rtt.is_empty());
+    }
+
+    #[test]
+    fn first_sample_is_the_average() {
+        let mut rtt = RoundTripTimes::default();
+        rtt.add(Duration::from_millis(100));
+        assert_eq!(rtt.ewma(), Some(Duration::from_millis(100)));
+        assert_eq!(rtt.percentile(50), Some(Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn average_moves_slowly() {
+        let mut rtt = RoundTripTimes::default();
+        rtt.add(Duration::from_millis(100));
+        rtt.add(Duration::from_millis(900));
+        assert_eq!(rtt.ewma(), Some(Duration::from_millis(200)));
+    }
+
+    #[test]
+    fn percentiles() {
+        let mut rtt = RoundTripTimes::default();
+        for ms in (1..=10).rev() {
+            rtt.add(Duration::from_millis(ms * 10));
+        }
+        assert_eq!(rtt.percentile(0), Some(Duration::from_millis(10)));
+        assert_eq!(rtt.percentile(50), Some(Duration::from_millis(50)));
+        assert_eq!(rtt.percentile(90), Some(Duration::from_millis(90)));
+        assert_eq!(rtt.percentile(100), Some(Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn keep_only_recent_samples() {
+        let mut rtt = RoundTripTimes::default();
+        for _ in 0..RoundTripTimes::MAX_SAMPLES {
+            rtt.add(Duration::from_secs(10));
+        }
+        rtt.add(Duration::from_millis(1));
+        assert_eq!(rtt.len(), RoundTripTimes::MAX_SAMPLES);
+        assert_eq!(rtt.percentile(0), Some(Duration::from_millis(1)));
+    }
+}
@@ -0,0 +1,63 @@
+use rsnano_ledger::{Ledger, Writer};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread::JoinHandle,
+};
+use tracing::info;
+
+/// Indexes the existing blocks in the background after the account history index was enabled.
+/// The progress is stored in the ledger, so that an interrupted build is resumed on the next start
+pub struct AccountHistoryIndexer {
+    ledger: Arc<Ledger>,
+    stopped: Arc<AtomicBool>,
+    thread: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl AccountHistoryIndexer {
+    pub fn new(ledger: Arc<Ledger>) -> Self {
+        Self {
+            ledger,
+            stopped: Arc::new(AtomicBool::new(false)),
+            thread: Mutex::new(None),
+        }
+    }
+
+    pub fn start(&self) {
+        if !self.ledger.account_history_index_enabled()
+            || self.ledger.account_history_index_complete()
+        {
+            return;
+        }
+
+        debug_assert!(self.thread.lock().unwrap().is_none());
+        let ledger = Arc::clone(&self.ledger);
+        let stopped = Arc::clone(&self.stopped);
+        let join_handle = std::thread::Builder::new()
+            .name("Acc history idx".to_string())
+            .spawn(move || {
+                info!("Building account history index...");
+                while !stopped.load(Ordering::SeqCst) {
+                    let _guard = ledger.write_queue.wait(Writer::AccountHistoryIndex);
+                    if !ledger.build_account_history_index() {
+                        break;
+                    }
+                }
+                if ledger.account_history_index_complete() {
+                    info!("Account history index is complete");
+                }
+            })
+            .unwrap();
+        *self.thread.lock().unwrap() = Some(join_handle);
+    }
+
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        let join_handle = self.thread.lock().unwrap().take();
+        if let Some(join_handle) = join_handle {
+            join_handle.join().unwrap();
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use super::UncheckedMap;
+use super::{
+    local_lane::{LocalClient, LocalLane},
+    UncheckedMap,
+};
 use crate::{
     stats::{DetailType, StatType, Stats},
     transport::{FairQueue, FairQueueInfo},
@@ -6,7 +9,8 @@ use crate::{
 use rsnano_core::{
     utils::{ContainerInfo, ContainerInfoComponent},
     work::WorkThresholds,
-    BlockEnum, BlockType, Epoch, HackyUnsafeMutBlock, HashOrAccount, Networks, UncheckedInfo,
+    Account, BlockEnum, BlockType, Epoch, HackyUnsafeMutBlock, HashOrAccount, Networks,
+    UncheckedInfo,
 };
 use rsnano_ledger::{BlockStatus, Ledger, Writer};
 use rsnano_network::{ChannelId, DeadChannelCleanupStep};
@@ -49,11 +53,21 @@ impl From<BlockSource> for DetailType {
     }
 }
 
+/// Why a block which was added by a blocking call wasn't processed
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AddBlockingError {
+    /// The queue of the block source, client or account is full
+    QueueFull,
+    /// The block was dropped, because the block processor stopped
+    Stopped,
+}
+
 pub type BlockProcessorCallback = Box<dyn Fn(BlockStatus) + Send + Sync>;
 
 pub struct BlockProcessorContext {
     pub block: Arc<BlockEnum>,
     pub source: BlockSource,
+    pub client: LocalClient,
     callback: Option<BlockProcessorCallback>,
     pub arrival: Instant,
     waiter: Arc<BlockProcessorWaiter>,
@@ -68,6 +82,7 @@ impl BlockProcessorContext {
         Self {
             block,
             source,
+            client: LocalClient::Node,
             arrival: Instant::now(),
             callback,
             waiter: Arc::new(BlockProcessorWaiter::new()),
@@ -127,13 +142,23 @@ pub struct BlockProcessorConfig {
     // Maximum number of blocks to queue from network peers
     pub max_peer_queue: usize,
     //
-    // Maximum number of blocks to queue from system components (local RPC, bootstrap)
+    // Maximum number of blocks to queue from system components (bootstrap, unchecked)
     pub max_system_queue: usize,
 
+    // Maximum number of local blocks (wallets, RPC) to queue in total, per client and per account
+    pub max_local_queue: usize,
+    pub max_local_queue_per_client: usize,
+    pub max_local_queue_per_account: usize,
+
     // Higher priority gets processed more frequently
     pub priority_live: usize,
     pub priority_bootstrap: usize,
     pub priority_local: usize,
+
+    // Within the local queue, number of blocks the node itself (wallets)
+    // and each RPC client may process before it is the next client's turn
+    pub priority_local_node: usize,
+    pub priority_local_rpc: usize,
     pub batch_max_time: Duration,
     pub full_size: usize,
     pub batch_size: usize,
@@ -149,9 +174,14 @@ impl BlockProcessorConfig {
             work_thresholds,
             max_peer_queue: 128,
             max_system_queue: 16 * 1024,
+            max_local_queue: 16 * 1024,
+            max_local_queue_per_client: 4 * 1024,
+            max_local_queue_per_account: 256,
             priority_live: 1,
             priority_bootstrap: 8,
             priority_local: 16,
+            priority_local_node: 1,
+            priority_local_rpc: 1,
             batch_max_time: Duration::from_millis(500),
             full_size: Self::DEFAULT_FULL_SIZE,
             batch_size: Self::DEFAULT_BATCH_SIZE,
@@ -178,9 +208,16 @@ impl BlockProcessor {
         let config_l = config.clone();
         let max_size_query = Box::new(move |origin: &(BlockSource, ChannelId)| match origin.0 {
             BlockSource::Live | BlockSource::LiveOriginator => config_l.max_peer_queue,
+            BlockSource::Local => config_l.max_local_queue,
             _ => config_l.max_system_queue,
         });
 
+        let config_l = config.clone();
+        let local_priority_query = Box::new(move |client: &LocalClient| match client {
+            LocalClient::Node => config_l.priority_local_node,
+            LocalClient::Rpc(_) => config_l.priority_local_rpc,
+        });
+
         let config_l = config.clone();
         let priority_query = Box::new(move |origin: &(BlockSource, ChannelId)| match origin.0 {
             BlockSource::Live | BlockSource::LiveOriginator => config.priority_live,
@@ -195,6 +232,11 @@ impl BlockProcessor {
             processor_loop: Arc::new(BlockProcessorLoop {
                 mutex: Mutex::new(BlockProcessorImpl {
                     queue: FairQueue::new(max_size_query, priority_query),
+                    local_lane: LocalLane::new(
+                        config.max_local_queue_per_client,
+                        config.max_local_queue_per_account,
+                        local_priority_query,
+                    ),
                     last_log: None,
                     stopped: false,
                 }),
@@ -255,6 +297,10 @@ impl BlockProcessor {
         self.processor_loop.queue_len(source)
     }
 
+    pub fn local_client_queue_len(&self, client: &LocalClient) -> usize {
+        self.processor_loop.local_client_queue_len(client)
+    }
+
     pub fn add_block_processed_observer(
         &self,
         observer: Box<dyn Fn(BlockStatus, &BlockProcessorContext) + Send + Sync>,
@@ -288,8 +334,23 @@ impl BlockProcessor {
             .add(block, source, channel_id, Some(callback))
     }
 
-    pub fn add_blocking(&self, block: Arc<BlockEnum>, source: BlockSource) -> Option<BlockStatus> {
-        self.processor_loop.add_blocking(block, source)
+    pub fn add_blocking(
+        &self,
+        block: Arc<BlockEnum>,
+        source: BlockSource,
+    ) -> Result<BlockStatus, AddBlockingError> {
+        self.processor_loop
+            .add_blocking(block, source, LocalClient::Node)
+    }
+
+    /// Processes a local block which was submitted by the given client
+    pub fn add_local_blocking(
+        &self,
+        block: Arc<BlockEnum>,
+        client: LocalClient,
+    ) -> Result<BlockStatus, AddBlockingError> {
+        self.processor_loop
+            .add_blocking(block, BlockSource::Local, client)
     }
 
     pub fn process_active(&self, block: Arc<BlockEnum>) {
@@ -457,27 +518,37 @@ impl BlockProcessorLoop {
         )
     }
 
-    pub fn add_blocking(&self, block: Arc<BlockEnum>, source: BlockSource) -> Option<BlockStatus> {
+    pub fn add_blocking(
+        &self,
+        block: Arc<BlockEnum>,
+        source: BlockSource,
+        client: LocalClient,
+    ) -> Result<BlockStatus, AddBlockingError> {
         self.stats
             .inc(StatType::Blockprocessor, DetailType::ProcessBlocking);
         debug!(
-            "Processing block (blocking): {} (source: {:?})",
+            "Processing block (blocking): {} (source: {:?} client: {:?})",
             block.hash(),
-            source
+            source,
+            client
         );
 
         let hash = block.hash();
-        let ctx = Arc::new(BlockProcessorContext::new(block, source, None));
+        let mut ctx = BlockProcessorContext::new(block, source, None);
+        ctx.client = client;
+        let ctx = Arc::new(ctx);
         let waiter = ctx.get_waiter();
-        self.add_impl(ctx, ChannelId::LOOPBACK);
+        if !self.add_impl(ctx, ChannelId::LOOPBACK) {
+            return Err(AddBlockingError::QueueFull);
+        }
 
         match waiter.wait_result() {
-            Some(status) => Some(status),
+            Some(status) => Ok(status),
             None => {
                 self.stats
                     .inc(StatType::Blockprocessor, DetailType::ProcessBlockingTimeout);
                 error!("Block dropped when processing: {}", hash);
-                None
+                Err(AddBlockingError::Stopped)
             }
         }
     }
@@ -502,22 +573,27 @@ impl BlockProcessorLoop {
             .sum_queue_len((source, ChannelId::MIN)..=(source, ChannelId::MAX))
     }
 
+    pub fn local_client_queue_len(&self, client: &LocalClient) -> usize {
+        self.mutex.lock().unwrap().local_lane.client_len(client)
+    }
+
     fn add_impl(&self, context: Arc<BlockProcessorContext>, channel_id: ChannelId) -> bool {
-        let source = context.source;
-        let added;
-        {
+        let result = {
             let mut guard = self.mutex.lock().unwrap();
-            added = guard.queue.push((source, channel_id), context);
-        }
-        if added {
-            self.condition.notify_all();
-        } else {
-            self.stats
-                .inc(StatType::Blockprocessor, DetailType::Overfill);
-            self.stats
-                .inc(StatType::BlockprocessorOverfill, source.into());
+            guard.push(context, channel_id)
+        };
+        match result {
+            Ok(()) => {
+                self.condition.notify_all();
+                true
+            }
+            Err(detail) => {
+                self.stats
+                    .inc(StatType::Blockprocessor, DetailType::Overfill);
+                self.stats.inc(StatType::BlockprocessorOverfill, detail);
+                false
+            }
         }
-        added
     }
 
     pub fn queue_unchecked(&self, hash_or_account: &HashOrAccount) {
@@ -598,6 +674,10 @@ impl BlockProcessorLoop {
             .inc(StatType::BlockprocessorResult, result.into());
         self.stats
             .inc(StatType::BlockprocessorSource, context.source.into());
+        if context.source == BlockSource::Local {
+            self.stats
+                .inc(StatType::BlockprocessorLocal, context.client.into());
+        }
         trace!(?result, block = %block.hash(), source = ?context.source, "Block processed");
 
         match result {
@@ -722,6 +802,16 @@ impl BlockProcessorLoop {
                     sizeof_element: size_of::<Arc<BlockEnum>>(),
                 }),
                 guard.queue.collect_container_info("queue"),
+                ContainerInfoComponent::Leaf(ContainerInfo {
+                    name: "local_clients".to_owned(),
+                    count: guard.local_lane.clients_count(),
+                    sizeof_element: size_of::<LocalClient>(),
+                }),
+                ContainerInfoComponent::Leaf(ContainerInfo {
+                    name: "local_accounts".to_owned(),
+                    count: guard.local_lane.accounts_count(),
+                    sizeof_element: size_of::<Account>(),
+                }),
             ],
         )
     }
@@ -729,16 +819,46 @@ impl BlockProcessorLoop {
 
 struct BlockProcessorImpl {
     pub queue: FairQueue<(BlockSource, ChannelId), Arc<BlockProcessorContext>>,
+    /// Holds the same blocks as the local queue of the fair queue.
+    /// The fair queue decides when the local blocks get their turn,
+    /// and the local lane decides which local block is next.
+    local_lane: LocalLane<Arc<BlockProcessorContext>>,
     pub last_log: Option<Instant>,
     stopped: bool,
 }
 
 impl BlockProcessorImpl {
+    fn push(
+        &mut self,
+        context: Arc<BlockProcessorContext>,
+        channel_id: ChannelId,
+    ) -> Result<(), DetailType> {
+        let source = context.source;
+        if source != BlockSource::Local {
+            if !self.queue.push((source, channel_id), context) {
+                return Err(source.into());
+            }
+            return Ok(());
+        }
+
+        // Legacy blocks don't contain their account, so they share one queue
+        let account = context.block.account_field().unwrap_or_default();
+        self.local_lane.check(&context.client, &account)?;
+        if !self.queue.push((source, channel_id), context.clone()) {
+            return Err(source.into());
+        }
+        self.local_lane.push(context.client, account, context)?;
+        Ok(())
+    }
+
     fn next(&mut self) -> Arc<BlockProcessorContext> {
         debug_assert!(!self.queue.is_empty()); // This should be checked before calling next
         if !self.queue.is_empty() {
             let ((source, _), request) = self.queue.next().unwrap();
             assert!(source != BlockSource::Forced || request.source == BlockSource::Forced);
+            if source == BlockSource::Local {
+                return self.local_lane.pop().unwrap();
+            }
             return request;
         }
 
@@ -809,4 +929,79 @@ mod tests {
 
         assert_eq!(block_processor.total_queue_len(), 0);
     }
+
+    #[test]
+    fn local_blocks_of_different_accounts_take_turns() {
+        let block_processor = BlockProcessor::new(
+            BlockProcessorConfig::new(WorkThresholds::new_stub()),
+            Arc::new(Ledger::new_null()),
+            Arc::new(UncheckedMap::default()),
+            Arc::new(Stats::default()),
+        );
+        let a1 = test_block(1, 1);
+        let a2 = test_block(1, 2);
+        let b1 = test_block(2, 1);
+        for block in [&a1, &a2, &b1] {
+            block_processor.add(block.clone(), BlockSource::Local, ChannelId::LOOPBACK);
+        }
+
+        let mut guard = block_processor.processor_loop.mutex.lock().unwrap();
+        let order: Vec<_> = (0..3).map(|_| guard.next().block.hash()).collect();
+
+        assert_eq!(order, vec![a1.hash(), b1.hash(), a2.hash()]);
+    }
+
+    #[test]
+    fn local_account_limit() {
+        let mut config = BlockProcessorConfig::new(WorkThresholds::new_stub());
+        config.max_local_queue_per_account = 1;
+        let stats = Arc::new(Stats::default());
+        let block_processor = BlockProcessor::new(
+            config,
+            Arc::new(Ledger::new_null()),
+            Arc::new(UncheckedMap::default()),
+            stats.clone(),
+        );
+
+        assert!(block_processor.add(test_block(1, 1), BlockSource::Local, ChannelId::LOOPBACK));
+        assert!(!block_processor.add(test_block(1, 2), BlockSource::Local, ChannelId::LOOPBACK));
+
+        assert_eq!(block_processor.queue_len(BlockSource::Local), 1);
+        assert_eq!(
+            block_processor.local_client_queue_len(&LocalClient::Node),
+            1
+        );
+        assert_eq!(
+            stats.count(
+                StatType::BlockprocessorOverfill,
+                DetailType::LocalAccountFull,
+                Direction::In
+            ),
+            1
+        );
+    }
+
+    #[test]
+    fn add_blocking_reports_full_queue() {
+        let mut config = BlockProcessorConfig::new(WorkThresholds::new_stub());
+        config.max_local_queue_per_account = 1;
+        let block_processor = BlockProcessor::new(
+            config,
+            Arc::new(Ledger::new_null()),
+            Arc::new(UncheckedMap::default()),
+            Arc::new(Stats::default()),
+        );
+        block_processor.add(test_block(1, 1), BlockSource::Local, ChannelId::LOOPBACK);
+
+        let result = block_processor.add_local_blocking(test_block(1, 2), LocalClient::Node);
+
+        assert_eq!(result, Err(AddBlockingError::QueueFull));
+    }
+
+    fn test_block(key: u64, work: u64) -> Arc<BlockEnum> {
+        let mut block = BlockEnum::new_test_instance_with_key(key);
+        // The stub difficulty of a block is its work
+        block.set_work(u64::MAX - work);
+        Arc::new(block)
+    }
 }
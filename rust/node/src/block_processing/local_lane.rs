@@ -0,0 +1,276 @@
+use crate::stats::DetailType;
+use rsnano_core::Account;
+use std::{
+    collections::{HashMap, VecDeque},
+    net::IpAddr,
+};
+
+/// Who submitted a local block
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub enum LocalClient {
+    /// Blocks created by the node itself, for example by its wallets
+    #[default]
+    Node,
+    /// Blocks submitted by the RPC client with the given address
+    Rpc(IpAddr),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum LocalLaneFull {
+    Client,
+    Account,
+}
+
+impl From<LocalClient> for DetailType {
+    fn from(value: LocalClient) -> Self {
+        match value {
+            LocalClient::Node => DetailType::LocalNode,
+            LocalClient::Rpc(_) => DetailType::LocalRpc,
+        }
+    }
+}
+
+impl From<LocalLaneFull> for DetailType {
+    fn from(value: LocalLaneFull) -> Self {
+        match value {
+            LocalLaneFull::Client => DetailType::LocalClientFull,
+            LocalLaneFull::Account => DetailType::LocalAccountFull,
+        }
+    }
+}
+
+/// Queues the local blocks per client and per account, so that a burst
+/// from a single client or account doesn't delay everybody else.
+/// The clients take turns, and within a client its accounts take turns.
+/// A client may process up to its priority of blocks before it is the next client's turn.
+/// The blocks of an account are processed in the order they were added.
+pub(crate) struct LocalLane<T> {
+    clients: HashMap<LocalClient, ClientQueue<T>>,
+    client_order: VecDeque<LocalClient>,
+    max_per_client: usize,
+    max_per_account: usize,
+    priority_query: Box<dyn Fn(&LocalClient) -> usize + Send + Sync>,
+    counter: usize,
+}
+
+impl<T> LocalLane<T> {
+    pub fn new(
+        max_per_client: usize,
+        max_per_account: usize,
+        priority_query: Box<dyn Fn(&LocalClient) -> usize + Send + Sync>,
+    ) -> Self {
+        Self {
+            clients: HashMap::new(),
+            client_order: VecDeque::new(),
+            max_per_client,
+            max_per_account,
+            priority_query,
+            counter: 0,
+        }
+    }
+
+    /// Checks the limits of the client and of the account without adding anything
+    pub fn check(&self, client: &LocalClient, account: &Account) -> Result<(), LocalLaneFull> {
+        let Some(queue) = self.clients.get(client) else {
+            return Ok(());
+        };
+        if queue.len >= self.max_per_client {
+            return Err(LocalLaneFull::Client);
+        }
+        if queue.account_len(account) >= self.max_per_account {
+            return Err(LocalLaneFull::Account);
+        }
+        Ok(())
+    }
+
+    pub fn push(
+        &mut self,
+        client: LocalClient,
+        account: Account,
+        item: T,
+    ) -> Result<(), LocalLaneFull> {
+        self.check(&client, &account)?;
+        let queue = self.clients.entry(client).or_insert_with(|| {
+            self.client_order.push_back(client);
+            ClientQueue::new()
+        });
+        queue.push(account, item);
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let client = *self.client_order.front()?;
+        let queue = self.clients.get_mut(&client).unwrap();
+        let item = queue.pop();
+        self.counter += 1;
+        if queue.len == 0 {
+            self.clients.remove(&client);
+            self.client_order.pop_front();
+            self.counter = 0;
+        } else if self.counter >= (self.priority_query)(&client) {
+            self.client_order.rotate_left(1);
+            self.counter = 0;
+        }
+        Some(item)
+    }
+
+    pub fn client_len(&self, client: &LocalClient) -> usize {
+        self.clients.get(client).map(|q| q.len).unwrap_or_default()
+    }
+
+    /// Number of clients with queued blocks
+    pub fn clients_count(&self) -> usize {
+        self.clients.len()
+    }
+
+    /// Number of accounts with queued blocks
+    pub fn accounts_count(&self) -> usize {
+        self.clients.values().map(|q| q.accounts.len()).sum()
+    }
+}
+
+struct ClientQueue<T> {
+    accounts: HashMap<Account, VecDeque<T>>,
+    account_order: VecDeque<Account>,
+    len: usize,
+}
+
+impl<T> ClientQueue<T> {
+    fn new() -> Self {
+        Self {
+            accounts: HashMap::new(),
+            account_order: VecDeque::new(),
+            len: 0,
+        }
+    }
+
+    fn account_len(&self, account: &Account) -> usize {
+        self.accounts
+            .get(account)
+            .map(|q| q.len())
+            .unwrap_or_default()
+    }
+
+    fn push(&mut self, account: Account, item: T) {
+        self.accounts
+            .entry(account)
+            .or_insert_with(|| {
+                self.account_order.push_back(account);
+                VecDeque::new()
+            })
+            .push_back(item);
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> T {
+        let account = self.account_order.pop_front().unwrap();
+        let requests = self.accounts.get_mut(&account).unwrap();
+        let item = requests.pop_front().unwrap();
+        if requests.is_empty() {
+            self.accounts.remove(&account);
+        } else {
+            self.account_order.push_back(account);
+        }
+        self.len -= 1;
+        item
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn empty() {
+        let mut lane = new_lane(10, 10);
+        assert_eq!(lane.clients_count(), 0);
+        assert_eq!(lane.pop(), None);
+    }
+
+    #[test]
+    fn accounts_of_a_client_take_turns() {
+        let mut lane = new_lane(10, 10);
+        lane.push(LocalClient::Node, Account::from(1), 1).unwrap();
+        lane.push(LocalClient::Node, Account::from(1), 2).unwrap();
+        lane.push(LocalClient::Node, Account::from(1), 3).unwrap();
+        lane.push(LocalClient::Node, Account::from(2), 4).unwrap();
+
+        assert_eq!(pop_all(&mut lane), vec![1, 4, 2, 3]);
+    }
+
+    #[test]
+    fn clients_take_turns() {
+        let client = LocalClient::Rpc(Ipv4Addr::LOCALHOST.into());
+        let mut lane = new_lane(10, 10);
+        lane.push(LocalClient::Node, Account::from(1), 1).unwrap();
+        lane.push(LocalClient::Node, Account::from(2), 2).unwrap();
+        lane.push(LocalClient::Node, Account::from(3), 3).unwrap();
+        lane.push(client, Account::from(4), 4).unwrap();
+        lane.push(client, Account::from(4), 5).unwrap();
+
+        assert_eq!(lane.clients_count(), 2);
+        assert_eq!(lane.accounts_count(), 4);
+        assert_eq!(pop_all(&mut lane), vec![1, 4, 2, 5, 3]);
+        assert_eq!(lane.clients_count(), 0);
+        assert_eq!(lane.accounts_count(), 0);
+    }
+
+    #[test]
+    fn client_limit() {
+        let mut lane = new_lane(2, 10);
+        lane.push(LocalClient::Node, Account::from(1), 1).unwrap();
+        lane.push(LocalClient::Node, Account::from(2), 2).unwrap();
+
+        assert_eq!(
+            lane.push(LocalClient::Node, Account::from(3), 3),
+            Err(LocalLaneFull::Client)
+        );
+        assert_eq!(lane.client_len(&LocalClient::Node), 2);
+    }
+
+    #[test]
+    fn account_limit() {
+        let mut lane = new_lane(10, 1);
+        lane.push(LocalClient::Node, Account::from(1), 1).unwrap();
+
+        assert_eq!(
+            lane.push(LocalClient::Node, Account::from(1), 2),
+            Err(LocalLaneFull::Account)
+        );
+        assert!(lane.push(LocalClient::Node, Account::from(2), 3).is_ok());
+    }
+
+    #[test]
+    fn client_priority() {
+        let client = LocalClient::Rpc(Ipv4Addr::LOCALHOST.into());
+        let mut lane = LocalLane::new(
+            10,
+            10,
+            Box::new(|client: &LocalClient| match client {
+                LocalClient::Node => 2,
+                LocalClient::Rpc(_) => 1,
+            }),
+        );
+        lane.push(LocalClient::Node, Account::from(1), 1).unwrap();
+        lane.push(LocalClient::Node, Account::from(2), 2).unwrap();
+        lane.push(LocalClient::Node, Account::from(3), 3).unwrap();
+        lane.push(LocalClient::Node, Account::from(4), 4).unwrap();
+        lane.push(client, Account::from(5), 5).unwrap();
+        lane.push(client, Account::from(6), 6).unwrap();
+
+        assert_eq!(pop_all(&mut lane), vec![1, 2, 5, 3, 4, 6]);
+    }
+
+    fn new_lane(max_per_client: usize, max_per_account: usize) -> LocalLane<u32> {
+        LocalLane::new(
+            max_per_client,
+            max_per_account,
+            Box::new(|_: &LocalClient| 1),
+        )
+    }
+
+    fn pop_all(lane: &mut LocalLane<u32>) -> Vec<u32> {
+        std::iter::from_fn(|| lane.pop()).collect()
+    }
+}
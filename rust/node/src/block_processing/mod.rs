@@ -1,9 +1,13 @@
 mod backlog_population;
 mod block_processor;
 mod local_block_broadcaster;
+mod local_lane;
+mod persistent_unchecked;
 mod unchecked_map;
 
 pub use backlog_population::{BacklogPopulation, BacklogPopulationConfig};
 pub use block_processor::*;
 pub use local_block_broadcaster::*;
+pub use local_lane::LocalClient;
+pub use persistent_unchecked::*;
 pub use unchecked_map::*;
@@ -0,0 +1,304 @@
+use rsnano_core::{utils::seconds_since_epoch, BlockHash, UncheckedInfo, UncheckedKey};
+use rsnano_store_lmdb::{
+    EnvOptions, LmdbConfig, LmdbEnv, LmdbUncheckedStore, LmdbWriteTransaction, SyncStrategy,
+};
+use std::{path::Path, sync::Arc};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PersistentUncheckedConfig {
+    /// Stores unchecked blocks on disk instead of in memory
+    pub enabled: bool,
+    /// Approximate number of bytes the unchecked blocks may occupy on disk
+    pub max_size: u64,
+}
+
+impl Default for PersistentUncheckedConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_size: 1024 * 1024 * 1024,
+        }
+    }
+}
+
+/// Unchecked blocks which are stored in their own LMDB file, so that
+/// long running bootstraps don't lose their gap blocks on a restart.
+/// The oldest entries are evicted when the byte budget is exceeded or
+/// when they are older than the cutoff time. Expired entries are evicted
+/// on open, on insert and by the periodic cleanup of the unchecked map.
+pub struct PersistentUncheckedEntries {
+    env: Arc<LmdbEnv>,
+    store: LmdbUncheckedStore,
+    max_size: u64,
+    cutoff_s: u64,
+    size: u64,
+    count: usize,
+    next_sequence: u64,
+}
+
+impl PersistentUncheckedEntries {
+    pub fn open(
+        path: impl AsRef<Path>,
+        config: &PersistentUncheckedConfig,
+        cutoff_s: u64,
+    ) -> anyhow::Result<Self> {
+        let options = EnvOptions {
+            config: LmdbConfig {
+                // Losing the most recent unchecked blocks on a crash is harmless
+                sync: SyncStrategy::NosyncSafe,
+                // Leaves room for the B-tree overhead, so that the byte budget is reached before the map is full
+                map_size: usize::try_from(config.max_size)
+                    .unwrap_or(usize::MAX)
+                    .saturating_mul(4)
+                    .max(64 * 1024 * 1024),
+                ..Default::default()
+            },
+            use_no_mem_init: false,
+        };
+        let env = Arc::new(LmdbEnv::new_with_options(path, &options)?);
+        let store = LmdbUncheckedStore::new(env.clone())?;
+
+        let txn = env.tx_begin_read();
+        let size = store.size(&txn);
+        let count = store.count(&txn) as usize;
+        let next_sequence = store.last_sequence(&txn).map(|s| s + 1).unwrap_or_default();
+        drop(txn);
+
+        let mut entries = Self {
+            env,
+            store,
+            max_size: config.max_size,
+            cutoff_s,
+            size,
+            count,
+            next_sequence,
+        };
+        entries.cleanup();
+        Ok(entries)
+    }
+
+    pub fn insert(&mut self, key: UncheckedKey, info: UncheckedInfo) -> bool {
+        let mut txn = self.env.tx_begin_write();
+        if self.store.exists(&txn, &key) {
+            return false;
+        }
+        self.size += self.store.put(&mut txn, self.next_sequence, &key, &info) as u64;
+        self.count += 1;
+        self.next_sequence += 1;
+        self.evict(&mut txn);
+        true
+    }
+
+    /// Evicts the expired entries and the oldest entries above the byte budget
+    pub fn cleanup(&mut self) {
+        let mut txn = self.env.tx_begin_write();
+        self.evict(&mut txn);
+    }
+
+    fn evict(&mut self, txn: &mut LmdbWriteTransaction) {
+        let now = seconds_since_epoch();
+        loop {
+            let Some((_, oldest)) = self.store.iter_oldest(&*txn).next() else {
+                break;
+            };
+            if self.size <= self.max_size {
+                let expired = match self.store.get(&*txn, &oldest) {
+                    Some(info) => info.modified.saturating_add(self.cutoff_s) < now,
+                    None => true,
+                };
+                if !expired {
+                    break;
+                }
+            }
+            self.remove_impl(txn, &oldest);
+        }
+    }
+
+    pub fn remove(&mut self, key: &UncheckedKey) -> bool {
+        let mut txn = self.env.tx_begin_write();
+        self.remove_impl(&mut txn, key)
+    }
+
+    fn remove_impl(&mut self, txn: &mut LmdbWriteTransaction, key: &UncheckedKey) -> bool {
+        match self.store.del(txn, key) {
+            Some(freed) => {
+                self.size = self.size.saturating_sub(freed as u64);
+                self.count -= 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn exists(&self, key: &UncheckedKey) -> bool {
+        self.store.exists(&self.env.tx_begin_read(), key)
+    }
+
+    pub fn clear(&mut self) {
+        let mut txn = self.env.tx_begin_write();
+        self.store.clear(&mut txn);
+        self.size = 0;
+        self.count = 0;
+    }
+
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// The number of bytes which are occupied by the entries
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn for_each(
+        &self,
+        action: &mut dyn FnMut(&UncheckedKey, &UncheckedInfo),
+        predicate: &dyn Fn() -> bool,
+    ) {
+        let txn = self.env.tx_begin_read();
+        for (_, key) in self.store.iter_oldest(&txn) {
+            if !predicate() {
+                break;
+            }
+            if let Some(info) = self.store.get(&txn, &key) {
+                action(&key, &info);
+            }
+        }
+    }
+
+    pub fn for_each_with_dependency(
+        &self,
+        dependency: &BlockHash,
+        action: &mut dyn FnMut(&UncheckedKey, &UncheckedInfo),
+        predicate: &dyn Fn() -> bool,
+    ) {
+        let txn = self.env.tx_begin_read();
+        for (key, info) in self.store.iter_dependency(&txn, dependency) {
+            if !predicate() {
+                break;
+            }
+            action(&key, &info);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsnano_core::BlockEnum;
+    use rsnano_store_lmdb::TestDbFile;
+
+    #[test]
+    fn entries_survive_reopening() {
+        let file = TestDbFile::random();
+        let key = UncheckedKey::new(BlockHash::from(1), BlockHash::from(2));
+        {
+            let mut entries = open(&file, 1024 * 1024);
+            assert!(entries.insert(key.clone(), test_info(1)));
+            assert!(!entries.insert(key.clone(), test_info(1)));
+        }
+
+        let entries = open(&file, 1024 * 1024);
+
+        assert_eq!(entries.len(), 1);
+        assert!(entries.exists(&key));
+        assert!(entries.size() > 0);
+        let mut found = Vec::new();
+        entries.for_each_with_dependency(
+            &BlockHash::from(1),
+            &mut |key, _| found.push(key.clone()),
+            &|| true,
+        );
+        assert_eq!(found, vec![key]);
+    }
+
+    #[test]
+    fn evict_oldest_when_budget_exceeded() {
+        let file = TestDbFile::random();
+        let key1 = UncheckedKey::new(BlockHash::from(1), BlockHash::from(2));
+        let key2 = UncheckedKey::new(BlockHash::from(3), BlockHash::from(4));
+        let mut entries = open(&file, 1024 * 1024);
+        entries.insert(key1.clone(), test_info(1));
+        entries.max_size = entries.size();
+
+        entries.insert(key2.clone(), test_info(2));
+
+        assert_eq!(entries.len(), 1);
+        assert!(!entries.exists(&key1));
+        assert!(entries.exists(&key2));
+    }
+
+    #[test]
+    fn evict_expired_entries() {
+        let file = TestDbFile::random();
+        let key1 = UncheckedKey::new(BlockHash::from(1), BlockHash::from(2));
+        let key2 = UncheckedKey::new(BlockHash::from(3), BlockHash::from(4));
+        let mut entries = open(&file, 1024 * 1024);
+        let mut old_info = test_info(1);
+        old_info.modified = seconds_since_epoch() - 2 * 60 * 60;
+        entries.insert(key1.clone(), old_info);
+
+        entries.insert(key2.clone(), test_info(2));
+
+        assert!(!entries.exists(&key1));
+        assert!(entries.exists(&key2));
+    }
+
+    #[test]
+    fn evict_expired_entries_on_open() {
+        let file = TestDbFile::random();
+        let key = UncheckedKey::new(BlockHash::from(1), BlockHash::from(2));
+        {
+            let mut entries = open_with_cutoff(&file, 1024 * 1024, 4 * 60 * 60);
+            let mut old_info = test_info(1);
+            old_info.modified = seconds_since_epoch() - 2 * 60 * 60;
+            entries.insert(key.clone(), old_info);
+            assert!(entries.exists(&key));
+        }
+
+        let entries = open(&file, 1024 * 1024);
+
+        assert!(!entries.exists(&key));
+        assert!(entries.is_empty());
+    }
+
+    #[test]
+    fn cleanup_evicts_expired_entries() {
+        let file = TestDbFile::random();
+        let key = UncheckedKey::new(BlockHash::from(1), BlockHash::from(2));
+        let mut entries = open(&file, 1024 * 1024);
+        let mut old_info = test_info(1);
+        old_info.modified = seconds_since_epoch() - 30 * 60;
+        entries.insert(key.clone(), old_info);
+        entries.cutoff_s = 10 * 60;
+
+        entries.cleanup();
+
+        assert!(!entries.exists(&key));
+        assert_eq!(entries.size(), 0);
+    }
+
+    fn open(file: &TestDbFile, max_size: u64) -> PersistentUncheckedEntries {
+        open_with_cutoff(file, max_size, 60 * 60)
+    }
+
+    fn open_with_cutoff(
+        file: &TestDbFile,
+        max_size: u64,
+        cutoff_s: u64,
+    ) -> PersistentUncheckedEntries {
+        let config = PersistentUncheckedConfig {
+            enabled: true,
+            max_size,
+        };
+        PersistentUncheckedEntries::open(&file.path, &config, cutoff_s).unwrap()
+    }
+
+    fn test_info(key: u64) -> UncheckedInfo {
+        UncheckedInfo::new(Arc::new(BlockEnum::new_test_instance_with_key(key)))
+    }
+}
@@ -1,3 +1,4 @@
+use super::PersistentUncheckedEntries;
 use crate::stats::{DetailType, StatType, Stats};
 use rsnano_core::{
     utils::{ContainerInfo, ContainerInfoComponent},
@@ -10,8 +11,12 @@ use std::{
     ops::DerefMut,
     sync::{Arc, Condvar, Mutex},
     thread::JoinHandle,
+    time::{Duration, Instant},
 };
 
+/// How often expired entries are evicted from the persistent store
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+
 pub struct UncheckedMap {
     join_handle: Mutex<Option<JoinHandle<()>>>,
     thread: Arc<UncheckedMapThread>,
@@ -23,7 +28,36 @@ pub struct UncheckedMap {
 
 impl UncheckedMap {
     pub fn new(max_unchecked_blocks: usize, stats: Arc<Stats>, disable_delete: bool) -> Self {
-        let mutable = Arc::new(Mutex::new(ThreadMutableData::new()));
+        Self::with_entries(
+            UncheckedEntries::Memory(EntriesContainer::new()),
+            max_unchecked_blocks,
+            stats,
+            disable_delete,
+        )
+    }
+
+    /// The entries are limited by the byte budget of the persistent store
+    /// instead of the number of blocks
+    pub fn new_persistent(
+        entries: PersistentUncheckedEntries,
+        stats: Arc<Stats>,
+        disable_delete: bool,
+    ) -> Self {
+        Self::with_entries(
+            UncheckedEntries::Persistent(entries),
+            usize::MAX,
+            stats,
+            disable_delete,
+        )
+    }
+
+    fn with_entries(
+        entries: UncheckedEntries,
+        max_unchecked_blocks: usize,
+        stats: Arc<Stats>,
+        disable_delete: bool,
+    ) -> Self {
+        let mutable = Arc::new(Mutex::new(ThreadMutableData::new(entries)));
         let condition = Arc::new(Condvar::new());
 
         let thread = Arc::new(UncheckedMapThread {
@@ -74,10 +108,9 @@ impl UncheckedMap {
     pub fn put(&self, dependency: HashOrAccount, info: UncheckedInfo) {
         let mut lock = self.mutable.lock().unwrap();
         let key = UncheckedKey::new(dependency.into(), info.block.as_ref().unwrap().hash());
-        let inserted = lock.entries_container.insert(Entry::new(key, info));
-        if lock.entries_container.len() > self.max_unchecked_blocks {
-            lock.entries_container.pop_front();
-        }
+        let inserted = lock
+            .entries_container
+            .insert(Entry::new(key, info), self.max_unchecked_blocks);
         if inserted {
             self.stats.inc(StatType::Unchecked, DetailType::Put);
         }
@@ -197,17 +230,17 @@ struct ThreadMutableData {
     stopped: bool,
     buffer: VecDeque<HashOrAccount>,
     writing_back_buffer: bool,
-    entries_container: EntriesContainer,
+    entries_container: UncheckedEntries,
     satisfied_callback: Option<Box<dyn Fn(&UncheckedInfo) + Send>>,
 }
 
 impl ThreadMutableData {
-    fn new() -> Self {
+    fn new(entries_container: UncheckedEntries) -> Self {
         Self {
             stopped: false,
             buffer: VecDeque::new(),
             writing_back_buffer: false,
-            entries_container: EntriesContainer::new(),
+            entries_container,
             satisfied_callback: None,
         }
     }
@@ -223,8 +256,14 @@ pub struct UncheckedMapThread {
 
 impl UncheckedMapThread {
     fn run(&self) {
+        let mut last_cleanup = Instant::now();
         let mut lock = self.mutable.lock().unwrap();
         while !lock.stopped {
+            if last_cleanup.elapsed() >= CLEANUP_INTERVAL {
+                lock.entries_container.cleanup();
+                last_cleanup = Instant::now();
+            }
+
             if !lock.buffer.is_empty() {
                 let mut back_buffer_lock = self.back_buffer.lock().unwrap();
                 std::mem::swap(&mut lock.buffer, back_buffer_lock.deref_mut());
@@ -237,10 +276,11 @@ impl UncheckedMapThread {
             } else {
                 lock = self
                     .condition
-                    .wait_while(lock, |other_lock| {
+                    .wait_timeout_while(lock, CLEANUP_INTERVAL, |other_lock| {
                         !other_lock.stopped && other_lock.buffer.is_empty()
                     })
-                    .unwrap();
+                    .unwrap()
+                    .0;
             }
         }
     }
@@ -273,6 +313,99 @@ impl UncheckedMapThread {
     }
 }
 
+enum UncheckedEntries {
+    Memory(EntriesContainer),
+    Persistent(PersistentUncheckedEntries),
+}
+
+impl UncheckedEntries {
+    fn insert(&mut self, entry: Entry, max_entries: usize) -> bool {
+        match self {
+            Self::Memory(container) => {
+                let inserted = container.insert(entry);
+                if container.len() > max_entries {
+                    container.pop_front();
+                }
+                inserted
+            }
+            Self::Persistent(entries) => entries.insert(entry.key, entry.info),
+        }
+    }
+
+    fn remove(&mut self, key: &UncheckedKey) {
+        match self {
+            Self::Memory(container) => {
+                container.remove(key);
+            }
+            Self::Persistent(entries) => {
+                entries.remove(key);
+            }
+        }
+    }
+
+    fn exists(&self, key: &UncheckedKey) -> bool {
+        match self {
+            Self::Memory(container) => container.exists(key),
+            Self::Persistent(entries) => entries.exists(key),
+        }
+    }
+
+    fn clear(&mut self) {
+        match self {
+            Self::Memory(container) => container.clear(),
+            Self::Persistent(entries) => entries.clear(),
+        }
+    }
+
+    /// The memory container is bounded by its entry count and needs no cleanup
+    fn cleanup(&mut self) {
+        if let Self::Persistent(entries) = self {
+            entries.cleanup();
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Self::Memory(container) => container.len(),
+            Self::Persistent(entries) => entries.len(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Self::Memory(container) => container.is_empty(),
+            Self::Persistent(entries) => entries.is_empty(),
+        }
+    }
+
+    fn for_each(
+        &self,
+        mut action: Box<dyn FnMut(&UncheckedKey, &UncheckedInfo)>,
+        predicate: Box<dyn Fn() -> bool>,
+    ) {
+        match self {
+            Self::Memory(container) => container.for_each(action, predicate),
+            Self::Persistent(entries) => entries.for_each(&mut action, &predicate),
+        }
+    }
+
+    fn for_each_with_dependency(
+        &self,
+        dependency: &HashOrAccount,
+        action: &mut dyn FnMut(&UncheckedKey, &UncheckedInfo),
+        predicate: &dyn Fn() -> bool,
+    ) {
+        match self {
+            Self::Memory(container) => {
+                container.for_each_with_dependency(dependency, action, predicate)
+            }
+            Self::Persistent(entries) => {
+                entries.for_each_with_dependency(&dependency.into(), action, predicate)
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Entry {
     key: UncheckedKey,
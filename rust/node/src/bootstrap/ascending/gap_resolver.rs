@@ -0,0 +1,143 @@
+use rsnano_core::BlockHash;
+use rsnano_nullable_clock::Timestamp;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    mem::size_of,
+    time::Duration,
+};
+
+#[derive(PartialEq, Eq, Debug)]
+pub(crate) enum GapPushResult {
+    Queued,
+    /// The hash is already queued or was requested recently
+    Duplicate,
+    Overfill,
+}
+
+/// Missing dependencies of live blocks, which are requested directly from peers
+/// instead of waiting for the next bootstrap cycle to bring them.
+/// A hash is requested at most once per cooldown period.
+pub(crate) struct GapResolver {
+    queue: VecDeque<BlockHash>,
+    queued: HashSet<BlockHash>,
+    requested: HashMap<BlockHash, Timestamp>,
+    requested_order: VecDeque<(BlockHash, Timestamp)>,
+    max_queue: usize,
+    cooldown: Duration,
+}
+
+impl GapResolver {
+    pub const ELEMENT_SIZE: usize = size_of::<BlockHash>() * 2 + size_of::<Timestamp>();
+
+    pub fn new(max_queue: usize, cooldown: Duration) -> Self {
+        Self {
+            queue: VecDeque::new(),
+            queued: HashSet::new(),
+            requested: HashMap::new(),
+            requested_order: VecDeque::new(),
+            max_queue,
+            cooldown,
+        }
+    }
+
+    pub fn push(&mut self, hash: BlockHash) -> GapPushResult {
+        if self.queued.contains(&hash) || self.requested.contains_key(&hash) {
+            return GapPushResult::Duplicate;
+        }
+        if self.queue.len() >= self.max_queue {
+            return GapPushResult::Overfill;
+        }
+        self.queue.push_back(hash);
+        self.queued.insert(hash);
+        GapPushResult::Queued
+    }
+
+    /// Takes the oldest queued hash and remembers it as requested
+    pub fn pop(&mut self, now: Timestamp) -> Option<BlockHash> {
+        let hash = self.queue.pop_front()?;
+        self.queued.remove(&hash);
+        self.requested.insert(hash, now);
+        self.requested_order.push_back((hash, now));
+        Some(hash)
+    }
+
+    /// Allows requesting hashes again whose cooldown is over
+    pub fn cleanup(&mut self, now: Timestamp) {
+        let cutoff = now - self.cooldown;
+        while let Some((hash, timestamp)) = self.requested_order.front() {
+            if *timestamp >= cutoff {
+                break;
+            }
+            if self.requested.get(hash) == Some(timestamp) {
+                self.requested.remove(hash);
+            }
+            self.requested_order.pop_front();
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn requested_len(&self) -> usize {
+        self.requested.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty() {
+        let mut resolver = GapResolver::new(10, Duration::from_secs(5));
+        assert_eq!(resolver.len(), 0);
+        assert_eq!(resolver.pop(Timestamp::new_test_instance()), None);
+    }
+
+    #[test]
+    fn pop_in_insertion_order() {
+        let mut resolver = GapResolver::new(10, Duration::from_secs(5));
+        let now = Timestamp::new_test_instance();
+        resolver.push(BlockHash::from(1));
+        resolver.push(BlockHash::from(2));
+
+        assert_eq!(resolver.pop(now), Some(BlockHash::from(1)));
+        assert_eq!(resolver.pop(now), Some(BlockHash::from(2)));
+        assert_eq!(resolver.pop(now), None);
+        assert_eq!(resolver.requested_len(), 2);
+    }
+
+    #[test]
+    fn reject_duplicates() {
+        let mut resolver = GapResolver::new(10, Duration::from_secs(5));
+        let now = Timestamp::new_test_instance();
+
+        assert_eq!(resolver.push(BlockHash::from(1)), GapPushResult::Queued);
+        assert_eq!(resolver.push(BlockHash::from(1)), GapPushResult::Duplicate);
+        resolver.pop(now);
+        assert_eq!(resolver.push(BlockHash::from(1)), GapPushResult::Duplicate);
+    }
+
+    #[test]
+    fn overfill() {
+        let mut resolver = GapResolver::new(1, Duration::from_secs(5));
+        resolver.push(BlockHash::from(1));
+        assert_eq!(resolver.push(BlockHash::from(2)), GapPushResult::Overfill);
+    }
+
+    #[test]
+    fn request_again_after_cooldown() {
+        let mut resolver = GapResolver::new(10, Duration::from_secs(5));
+        let now = Timestamp::new_test_instance();
+        resolver.push(BlockHash::from(1));
+        resolver.pop(now);
+
+        resolver.cleanup(now + Duration::from_secs(4));
+        assert_eq!(resolver.push(BlockHash::from(1)), GapPushResult::Duplicate);
+
+        resolver.cleanup(now + Duration::from_secs(6));
+        assert_eq!(resolver.requested_len(), 0);
+        assert_eq!(resolver.push(BlockHash::from(1)), GapPushResult::Queued);
+    }
+}
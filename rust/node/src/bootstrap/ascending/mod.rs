@@ -1,4 +1,5 @@
 mod account_sets;
+mod gap_resolver;
 mod iterator;
 mod ordered_blocking;
 mod ordered_priorities;
@@ -9,6 +10,7 @@ mod throttle;
 
 use self::{
     account_sets::*,
+    gap_resolver::{GapPushResult, GapResolver},
     iterator::BufferedIterator,
     ordered_tags::{AsyncTag, OrderedTags},
     peer_scoring::PeerScoring,
@@ -64,6 +66,7 @@ pub struct BootstrapAscending {
     /// Requests for accounts from database have much lower hitrate and could introduce strain on the network
     /// A separate (lower) limiter ensures that we always reserve resources for querying accounts from priority queue
     database_limiter: BandwidthLimiter,
+    gap_limiter: BandwidthLimiter,
     clock: Arc<SteadyClock>,
 }
 
@@ -72,6 +75,7 @@ struct Threads {
     priorities: JoinHandle<()>,
     database: Option<JoinHandle<()>>,
     dependencies: Option<JoinHandle<()>>,
+    gaps: Option<JoinHandle<()>>,
 }
 
 impl BootstrapAscending {
@@ -93,6 +97,7 @@ impl BootstrapAscending {
                 scoring: PeerScoring::new(config.clone()),
                 iterator: BufferedIterator::new(Arc::clone(&ledger)),
                 tags: OrderedTags::default(),
+                gaps: GapResolver::new(config.max_gap_queue, config.gap_cooldown),
                 throttle: Throttle::new(compute_throttle_size(
                     ledger.account_count(),
                     config.throttle_coefficient,
@@ -103,6 +108,7 @@ impl BootstrapAscending {
             })),
             condition: Arc::new(Condvar::new()),
             database_limiter: BandwidthLimiter::new(1.0, config.database_rate_limit),
+            gap_limiter: BandwidthLimiter::new(1.0, config.gap_rate_limit),
             config,
             stats,
             ledger,
@@ -124,6 +130,9 @@ impl BootstrapAscending {
             if let Some(dependencies) = threads.dependencies {
                 dependencies.join().unwrap();
             }
+            if let Some(gaps) = threads.gaps {
+                gaps.join().unwrap();
+            }
         }
     }
 
@@ -254,6 +263,15 @@ impl BootstrapAscending {
         result
     }
 
+    fn wait_gap(&self) -> BlockHash {
+        let mut result = BlockHash::zero();
+        self.wait(|i| {
+            result = i.next_gap(&self.gap_limiter, &self.stats, self.clock.now());
+            !result.is_zero()
+        });
+        result
+    }
+
     fn wait_blocking(&self) -> BlockHash {
         let mut result = BlockHash::zero();
         self.wait(|i| {
@@ -416,6 +434,73 @@ impl BootstrapAscending {
         self.send(channel_id, &request);
     }
 
+    fn run_one_gap(&self) {
+        self.wait_tags();
+        let hash = self.wait_gap();
+        if hash.is_zero() {
+            return;
+        }
+
+        // The dependency may have arrived in the meantime
+        if self
+            .ledger
+            .any()
+            .block_exists_or_pruned(&self.ledger.read_txn(), &hash)
+        {
+            self.stats
+                .inc(StatType::BootstrapAscendingGap, DetailType::Old);
+            return;
+        }
+
+        let channels = self
+            .mutex
+            .lock()
+            .unwrap()
+            .scoring
+            .channels(self.config.gap_fanout);
+
+        if channels.is_empty() {
+            self.stats
+                .inc(StatType::BootstrapAscendingGap, DetailType::NoChannels);
+            return;
+        }
+
+        let now = self.clock.now();
+        for channel in channels {
+            let id = thread_rng().next_u64();
+            let request = self.create_gap_request(id, hash, now);
+            self.stats
+                .inc(StatType::BootstrapAscendingGap, DetailType::Request);
+            self.send(channel.channel_id(), &request);
+        }
+    }
+
+    fn create_gap_request(&self, id: u64, hash: BlockHash, now: Timestamp) -> Message {
+        let tag = AsyncTag {
+            query_type: QueryType::BlocksByHash,
+            source: QuerySource::Gap,
+            start: hash.into(),
+            account: Account::zero(),
+            hash,
+            count: 1,
+            id,
+            timestamp: now,
+        };
+
+        self.create_asc_pull_request(&tag)
+    }
+
+    fn run_gaps(&self) {
+        let mut guard = self.mutex.lock().unwrap();
+        while !guard.stopped {
+            drop(guard);
+            self.stats
+                .inc(StatType::BootstrapAscendingGap, DetailType::Loop);
+            self.run_one_gap();
+            guard = self.mutex.lock().unwrap();
+        }
+    }
+
     fn run_dependencies(&self) {
         let mut guard = self.mutex.lock().unwrap();
         while !guard.stopped {
@@ -487,10 +572,16 @@ impl BootstrapAscending {
         );
 
         guard.scoring.received_message(channel_id);
+        if let Some(channel) = guard.network_info.read().unwrap().get(channel_id) {
+            channel.add_round_trip_time(tag.timestamp.elapsed(self.clock.now()));
+        }
         drop(guard);
 
         // Process the response payload
         match &message.pull_type {
+            AscPullAckType::Blocks(blocks) if tag.source == QuerySource::Gap => {
+                self.process_gap(blocks, &tag)
+            }
             AscPullAckType::Blocks(blocks) => self.process_blocks(blocks, &tag),
             AscPullAckType::AccountInfo(info) => self.process_accounts(info, &tag),
             AscPullAckType::Frontiers(_) => {
@@ -601,6 +692,32 @@ impl BootstrapAscending {
         }
     }
 
+    /// Only the requested dependency is of interest, its successors are left to the bootstrapper
+    fn process_gap(&self, response: &BlocksAckPayload, tag: &AsyncTag) {
+        self.stats
+            .inc(StatType::BootstrapAscendingGap, DetailType::Reply);
+
+        match response.blocks().front() {
+            Some(block) if block.hash() == tag.hash => {
+                self.stats
+                    .inc(StatType::BootstrapAscendingGap, DetailType::Process);
+                self.block_processor.add(
+                    Arc::new(block.clone()),
+                    BlockSource::Bootstrap,
+                    ChannelId::LOOPBACK,
+                );
+            }
+            Some(_) => {
+                self.stats
+                    .inc(StatType::BootstrapAscendingGap, DetailType::Invalid);
+            }
+            None => {
+                self.stats
+                    .inc(StatType::BootstrapAscendingGap, DetailType::Empty);
+            }
+        }
+    }
+
     fn process_accounts(&self, response: &AccountInfoAckPayload, tag: &AsyncTag) {
         if response.account.is_zero() {
             self.stats.inc(
@@ -757,6 +874,18 @@ impl BootstrapAscendingExt for Arc<BootstrapAscending> {
             None
         };
 
+        let gaps = if self.config.enable_gap_resolver {
+            let self_l = Arc::clone(self);
+            Some(
+                std::thread::Builder::new()
+                    .name("Bootstrap gaps".to_string())
+                    .spawn(Box::new(move || self_l.run_gaps()))
+                    .unwrap(),
+            )
+        } else {
+            None
+        };
+
         let self_l = Arc::clone(self);
         let timeout = std::thread::Builder::new()
             .name("Bootstrap asc".to_string())
@@ -768,6 +897,7 @@ impl BootstrapAscendingExt for Arc<BootstrapAscending> {
             priorities,
             database,
             dependencies,
+            gaps,
         });
     }
 }
@@ -778,6 +908,7 @@ struct BootstrapAscendingLogic {
     scoring: PeerScoring,
     iterator: BufferedIterator,
     tags: OrderedTags,
+    gaps: GapResolver,
     throttle: Throttle,
     sync_dependencies_interval: Instant,
     config: BootstrapAscendingConfig,
@@ -856,6 +987,9 @@ impl BootstrapAscendingLogic {
                 }
             }
             BlockStatus::GapSource => {
+                if source == BlockSource::Live {
+                    self.resolve_gap(stats, block.source_or_link());
+                }
                 if source == BlockSource::Bootstrap {
                     assert!(!account.is_zero());
                     let source = block.source_or_link();
@@ -874,6 +1008,9 @@ impl BootstrapAscendingLogic {
                 }
             }
             BlockStatus::GapPrevious => {
+                if source == BlockSource::Live {
+                    self.resolve_gap(stats, block.previous());
+                }
                 // Prevent live traffic from evicting accounts from the priority list
                 if source == BlockSource::Live
                     && !self.accounts.priority_half_full()
@@ -901,6 +1038,33 @@ impl BootstrapAscendingLogic {
         }
     }
 
+    /// Queues the missing dependency of a live block, so that it gets requested from peers right away
+    fn resolve_gap(&mut self, stats: &Stats, dependency: BlockHash) {
+        if !self.config.enable_gap_resolver {
+            return;
+        }
+        let detail = match self.gaps.push(dependency) {
+            GapPushResult::Queued => DetailType::Insert,
+            GapPushResult::Duplicate => DetailType::Duplicate,
+            GapPushResult::Overfill => DetailType::Overfill,
+        };
+        stats.inc(StatType::BootstrapAscendingGap, detail);
+    }
+
+    fn next_gap(&mut self, limiter: &BandwidthLimiter, stats: &Stats, now: Timestamp) -> BlockHash {
+        // Keep the gaps queued until there are peers to ask
+        if self.gaps.len() == 0 || self.scoring.len() == 0 || !limiter.should_pass(1) {
+            return BlockHash::zero();
+        }
+
+        let Some(hash) = self.gaps.pop(now) else {
+            return BlockHash::zero();
+        };
+
+        stats.inc(StatType::BootstrapAscendingNext, DetailType::NextGap);
+        hash
+    }
+
     fn count_tags_by_hash(&self, hash: &BlockHash, source: QuerySource) -> usize {
         self.tags
             .iter_hash(hash)
@@ -976,6 +1140,7 @@ impl BootstrapAscendingLogic {
             account_count,
             self.config.throttle_coefficient,
         ));
+        self.gaps.cleanup(now);
 
         let cutoff = now - self.config.request_timeout;
         let should_timeout = |tag: &AsyncTag| tag.timestamp < cutoff;
@@ -1015,6 +1180,16 @@ impl BootstrapAscendingLogic {
                     count: self.tags.len(),
                     sizeof_element: OrderedTags::ELEMENT_SIZE,
                 }),
+                ContainerInfoComponent::Leaf(ContainerInfo {
+                    name: "gaps".to_string(),
+                    count: self.gaps.len(),
+                    sizeof_element: GapResolver::ELEMENT_SIZE,
+                }),
+                ContainerInfoComponent::Leaf(ContainerInfo {
+                    name: "gaps_requested".to_string(),
+                    count: self.gaps.requested_len(),
+                    sizeof_element: GapResolver::ELEMENT_SIZE,
+                }),
                 ContainerInfoComponent::Leaf(ContainerInfo {
                     name: "throttle".to_string(),
                     count: self.throttle.len(),
@@ -1109,6 +1284,15 @@ pub struct BootstrapAscendingConfig {
     pub min_protocol_version: u8,
     pub max_requests: usize,
     pub account_sets: AccountSetsConfig,
+    /// Requests the missing dependencies of live blocks from peers right away
+    pub enable_gap_resolver: bool,
+    /// Number of peers which are asked for a missing dependency
+    pub gap_fanout: usize,
+    /// Maximum number of missing dependencies to request per second
+    pub gap_rate_limit: usize,
+    pub max_gap_queue: usize,
+    /// Minimum time before the same dependency is requested again
+    pub gap_cooldown: Duration,
 }
 
 impl Default for BootstrapAscendingConfig {
@@ -1128,6 +1312,11 @@ impl Default for BootstrapAscendingConfig {
             block_processor_theshold: 1000,
             min_protocol_version: 0x14, // TODO don't hard code
             max_requests: 1024,
+            enable_gap_resolver: true,
+            gap_fanout: 2,
+            gap_rate_limit: 64,
+            max_gap_queue: 1024,
+            gap_cooldown: Duration::from_secs(10),
         }
     }
 }
@@ -34,6 +34,8 @@ pub(crate) enum QuerySource {
     Priority,
     Database,
     Blocking,
+    /// Missing dependency of a live block
+    Gap,
 }
 
 #[derive(Clone)]
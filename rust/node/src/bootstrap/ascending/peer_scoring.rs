@@ -1,5 +1,5 @@
 use super::BootstrapAscendingConfig;
-use rsnano_network::{ChannelId, ChannelInfo, TrafficType};
+use rsnano_network::{weighted_shuffle_by, ChannelId, ChannelInfo, TrafficType};
 use std::{
     collections::{BTreeMap, HashMap},
     sync::{Arc, Weak},
@@ -40,19 +40,45 @@ impl PeerScoring {
         }
     }
 
+    /// Up to `count` different channels with the fewest outstanding requests
+    pub fn channels(&mut self, count: usize) -> Vec<Arc<ChannelInfo>> {
+        let channels: Vec<_> = self.available_channels().take(count).collect();
+        for channel in &channels {
+            self.scoring.modify(channel.channel_id(), |i| {
+                i.outstanding += 1;
+                i.request_count_total += 1;
+            });
+        }
+        channels
+    }
+
     fn get_next_channel(&self) -> Option<Arc<ChannelInfo>> {
-        self.scoring.iter_by_outstanding().find_map(|score| {
-            if let Some(channel) = score.channel.upgrade() {
-                if !channel.is_queue_full(TrafficType::Generic)
-                    && score.outstanding < self.config.channel_limit
-                {
-                    return Some(channel);
-                }
-            }
-            None
+        self.available_channels().next()
+    }
+
+    /// Available channels in ascending order of outstanding requests. Among channels
+    /// with the same number of outstanding requests, well scored channels tend to come first
+    fn available_channels(&self) -> impl Iterator<Item = Arc<ChannelInfo>> + '_ {
+        self.scoring.iter_by_outstanding().flat_map(move |scores| {
+            let mut channels: Vec<_> = scores
+                .filter_map(|score| self.available_channel(score))
+                .collect();
+            weighted_shuffle_by(&mut channels, |channel| channel.score());
+            channels
         })
     }
 
+    fn available_channel(&self, score: &PeerScore) -> Option<Arc<ChannelInfo>> {
+        if let Some(channel) = score.channel.upgrade() {
+            if !channel.is_queue_full(TrafficType::Generic)
+                && score.outstanding < self.config.channel_limit
+            {
+                return Some(channel);
+            }
+        }
+        None
+    }
+
     pub fn len(&self) -> usize {
         self.scoring.len()
     }
@@ -197,10 +223,10 @@ impl Scoring {
         }
     }
 
-    fn iter_by_outstanding(&self) -> impl Iterator<Item = &PeerScore> {
+    /// Groups of peers with the same number of outstanding requests, fewest first
+    fn iter_by_outstanding(&self) -> impl Iterator<Item = impl Iterator<Item = &PeerScore>> {
         self.by_outstanding
             .values()
-            .flatten()
-            .map(|id| self.by_channel.get(id).unwrap())
+            .map(move |ids| ids.iter().map(move |id| self.by_channel.get(id).unwrap()))
     }
 }
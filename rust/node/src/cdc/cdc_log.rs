@@ -0,0 +1,723 @@
+use anyhow::Context;
+use blake2::{
+    digest::{Update, VariableOutput},
+    Blake2bVar,
+};
+use rsnano_core::{
+    utils::{BufferReader, BufferWriter, Deserialize, MemoryStream, Serialize, Stream, StreamExt},
+    Amount, BlockEnum, BlockHash, BlockSubType,
+};
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufReader, ErrorKind, Read, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tracing::{info, warn};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CdcConfig {
+    pub enabled: bool,
+    /// A new segment file is started when the current one reaches this size
+    pub max_segment_size: usize,
+    /// The oldest segments are deleted when there are more segments than this. 0 keeps all segments
+    pub max_segments: usize,
+}
+
+impl Default for CdcConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_segment_size: 64 * 1024 * 1024,
+            max_segments: 0,
+        }
+    }
+}
+
+/// The election which confirmed a cemented block. Blocks which are
+/// cemented as dependencies of another block have no election of their own.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CdcElectionInfo {
+    pub tally: Amount,
+    pub final_tally: Amount,
+    pub confirmation_request_count: u32,
+    pub voter_count: u32,
+    pub duration: Duration,
+}
+
+/// A cemented block as it is recorded in the CDC log
+#[derive(Clone, Debug, PartialEq)]
+pub struct CdcEntry {
+    pub sequence: u64,
+    /// The block including its sideband
+    pub block: BlockEnum,
+    pub amount: Amount,
+    pub subtype: BlockSubType,
+    /// The block whose confirmation cemented this block
+    pub confirmation_root: BlockHash,
+    pub election: Option<CdcElectionInfo>,
+}
+
+impl CdcEntry {
+    fn serialize(&self) -> Vec<u8> {
+        let mut stream = MemoryStream::new();
+        stream.write_u64_be_safe(self.sequence);
+        self.confirmation_root.serialize(&mut stream);
+        stream.write_bytes_safe(&self.amount.to_be_bytes());
+        stream.write_u8_safe(subtype_to_u8(self.subtype));
+        match &self.election {
+            Some(election) => {
+                stream.write_u8_safe(1);
+                stream.write_bytes_safe(&election.tally.to_be_bytes());
+                stream.write_bytes_safe(&election.final_tally.to_be_bytes());
+                stream.write_u32_be_safe(election.confirmation_request_count);
+                stream.write_u32_be_safe(election.voter_count);
+                stream.write_u64_be_safe(election.duration.as_millis() as u64);
+            }
+            None => stream.write_u8_safe(0),
+        }
+        stream.write_bytes_safe(&self.block.serialize_with_sideband());
+        stream.to_vec()
+    }
+
+    fn deserialize(bytes: &[u8]) -> anyhow::Result<Self> {
+        let mut stream = BufferReader::new(bytes);
+        let sequence = stream.read_u64_be()?;
+        let confirmation_root = BlockHash::deserialize(&mut stream)?;
+        let amount = read_amount(&mut stream)?;
+        let subtype = subtype_from_u8(stream.read_u8()?)?;
+        let election = match stream.read_u8()? {
+            0 => None,
+            _ => Some(CdcElectionInfo {
+                tally: read_amount(&mut stream)?,
+                final_tally: read_amount(&mut stream)?,
+                confirmation_request_count: stream.read_u32_be()?,
+                voter_count: stream.read_u32_be()?,
+                duration: Duration::from_millis(stream.read_u64_be()?),
+            }),
+        };
+        let block = BlockEnum::deserialize_with_sideband(stream.remaining())?;
+        Ok(Self {
+            sequence,
+            block,
+            amount,
+            subtype,
+            confirmation_root,
+            election,
+        })
+    }
+}
+
+fn read_amount(stream: &mut dyn Stream) -> anyhow::Result<Amount> {
+    let mut bytes = [0; 16];
+    stream.read_bytes(&mut bytes, 16)?;
+    Ok(Amount::from_be_bytes(bytes))
+}
+
+fn subtype_to_u8(subtype: BlockSubType) -> u8 {
+    match subtype {
+        BlockSubType::Send => 0,
+        BlockSubType::Receive => 1,
+        BlockSubType::Open => 2,
+        BlockSubType::Change => 3,
+        BlockSubType::Epoch => 4,
+    }
+}
+
+fn subtype_from_u8(value: u8) -> anyhow::Result<BlockSubType> {
+    Ok(match value {
+        0 => BlockSubType::Send,
+        1 => BlockSubType::Receive,
+        2 => BlockSubType::Open,
+        3 => BlockSubType::Change,
+        4 => BlockSubType::Epoch,
+        _ => bail!("invalid block subtype {}", value),
+    })
+}
+
+/// Each record is framed as: payload length (u32 LE) ++ checksum (8 bytes) ++ payload
+const RECORD_HEADER_SIZE: usize = 12;
+/// Upper bound for the payload length, so that a corrupt length can't trigger a huge allocation
+const MAX_PAYLOAD_SIZE: usize = 64 * 1024;
+
+fn checksum(payload: &[u8]) -> [u8; 8] {
+    let mut hasher = Blake2bVar::new(8).unwrap();
+    hasher.update(payload);
+    let mut result = [0; 8];
+    hasher.finalize_variable(&mut result).unwrap();
+    result
+}
+
+const SEGMENT_EXTENSION: &str = "cdc";
+
+/// Segment files are named after the sequence number of their first entry
+fn segment_path(dir: &Path, first_sequence: u64) -> PathBuf {
+    dir.join(format!("{:020}.{}", first_sequence, SEGMENT_EXTENSION))
+}
+
+/// The first sequence numbers of all segments in ascending order
+fn list_segments(dir: &Path) -> anyhow::Result<Vec<u64>> {
+    let mut segments = Vec::new();
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(segments),
+        Err(e) => return Err(e).with_context(|| format!("Could not read CDC dir {:?}", dir)),
+    };
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some(SEGMENT_EXTENSION) {
+            continue;
+        }
+        if let Some(first_sequence) = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .and_then(|s| s.parse().ok())
+        {
+            segments.push(first_sequence);
+        }
+    }
+    segments.sort_unstable();
+    Ok(segments)
+}
+
+/// Reads the records of a segment up to the first incomplete or corrupt one.
+/// Returns the entries together with the file offset at which each of them starts,
+/// and the offset after the last valid record.
+fn read_segment(path: &Path) -> anyhow::Result<(Vec<(u64, CdcEntry)>, u64)> {
+    let mut entries = Vec::new();
+    let valid_len = visit_segment(path, |offset, entry| {
+        entries.push((offset, entry));
+        true
+    })?;
+    Ok((entries, valid_len))
+}
+
+/// Passes the records of a segment one by one to `visit`, together with the file offset
+/// at which they start, until `visit` returns false or an incomplete or corrupt record is found.
+/// Returns the offset after the last visited record.
+fn visit_segment(path: &Path, mut visit: impl FnMut(u64, CdcEntry) -> bool) -> anyhow::Result<u64> {
+    let file =
+        File::open(path).with_context(|| format!("Could not open CDC segment {:?}", path))?;
+    let mut reader = BufReader::new(file);
+    let mut offset = 0;
+    loop {
+        let mut header = [0; RECORD_HEADER_SIZE];
+        if reader.read_exact(&mut header).is_err() {
+            break;
+        }
+        let len = u32::from_le_bytes(header[..4].try_into().unwrap()) as usize;
+        if len > MAX_PAYLOAD_SIZE {
+            break;
+        }
+        let mut payload = vec![0; len];
+        if reader.read_exact(&mut payload).is_err() || checksum(&payload) != header[4..] {
+            break;
+        }
+        let Ok(entry) = CdcEntry::deserialize(&payload) else {
+            break;
+        };
+        let entry_offset = offset;
+        offset += (RECORD_HEADER_SIZE + len) as u64;
+        if !visit(entry_offset, entry) {
+            break;
+        }
+    }
+    Ok(offset)
+}
+
+/// The result of reading from the CDC log
+#[derive(Clone, Debug, PartialEq)]
+pub struct CdcEntries {
+    pub entries: Vec<CdcEntry>,
+    /// The sequence number of the oldest entry which wasn't deleted yet.
+    /// If it is greater than the requested sequence number, the entries
+    /// in between were deleted before they could be read
+    pub first_available: u64,
+}
+
+/// Reads at most `count` committed entries, starting with the entry with the sequence
+/// number `from`. If that entry was already deleted, reading starts with the
+/// oldest entry available. The segments are read record by record, so that
+/// only the returned entries are kept in memory
+pub fn read_cdc_entries(dir: &Path, from: u64, count: usize) -> anyhow::Result<CdcEntries> {
+    read_entries(dir, from, count, read_committed_sequence(dir))
+}
+
+/// Reads the entries with a sequence number below `committed`
+pub(super) fn read_entries(
+    dir: &Path,
+    from: u64,
+    count: usize,
+    committed: u64,
+) -> anyhow::Result<CdcEntries> {
+    let segments = list_segments(dir)?;
+    let first_available = segments.first().copied().unwrap_or_default();
+    let first_segment = segments
+        .iter()
+        .rposition(|first| *first <= from)
+        .unwrap_or(0);
+
+    let mut entries = Vec::new();
+    for first_sequence in &segments[first_segment..] {
+        if entries.len() >= count || *first_sequence >= committed {
+            break;
+        }
+        visit_segment(&segment_path(dir, *first_sequence), |_, entry| {
+            if entry.sequence >= committed {
+                return false;
+            }
+            if entry.sequence >= from {
+                entries.push(entry);
+            }
+            entries.len() < count
+        })?;
+    }
+    Ok(CdcEntries {
+        entries,
+        first_available,
+    })
+}
+
+const COMMITTED_FILE: &str = "committed";
+
+/// Reads the persisted committed sequence number. Entries at or above it may
+/// belong to a cementing transaction which was never committed.
+/// Returns 0 if the file is missing or invalid
+fn read_committed_sequence(dir: &Path) -> u64 {
+    std::fs::read(dir.join(COMMITTED_FILE))
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u64::from_le_bytes)
+        .unwrap_or_default()
+}
+
+fn write_committed_sequence(dir: &Path, committed: u64) -> anyhow::Result<()> {
+    let tmp_path = dir.join(format!("{}.tmp", COMMITTED_FILE));
+    std::fs::write(&tmp_path, committed.to_le_bytes())?;
+    std::fs::rename(&tmp_path, dir.join(COMMITTED_FILE))?;
+    Ok(())
+}
+
+/// Append-only log of cemented blocks, split into segment files.
+/// Every record carries a checksum, so that a record which was only
+/// partially written before a crash is detected and discarded on startup.
+/// Appended entries are only readable after they were committed, see [`CdcLog::commit`].
+pub struct CdcLog {
+    dir: PathBuf,
+    config: CdcConfig,
+    segments: Vec<u64>,
+    file: Option<File>,
+    segment_size: usize,
+    next_sequence: u64,
+    committed: u64,
+    /// Set when an append failed. The uncommitted entries are removed before the next append
+    discard_uncommitted: bool,
+}
+
+impl CdcLog {
+    pub fn open(dir: impl Into<PathBuf>, config: CdcConfig) -> anyhow::Result<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Could not create CDC dir {:?}", dir))?;
+        let mut segments = list_segments(&dir)?;
+        if segments.is_empty() {
+            segments.push(0);
+        }
+
+        let first_sequence = *segments.last().unwrap();
+        let path = segment_path(&dir, first_sequence);
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Could not open CDC segment {:?}", path))?;
+        let (entries, valid_len) = read_segment(&path)?;
+        if file.metadata()?.len() > valid_len {
+            warn!(
+                "Discarding incomplete record at the end of CDC segment {:?}",
+                path
+            );
+            file.set_len(valid_len)?;
+        }
+
+        let next_sequence = entries
+            .last()
+            .map(|(_, e)| e.sequence + 1)
+            .unwrap_or(first_sequence);
+        let committed = read_committed_sequence(&dir).min(next_sequence);
+        info!("CDC log opened, next sequence number: {}", next_sequence);
+
+        Ok(Self {
+            dir,
+            config,
+            segments,
+            file: Some(file),
+            segment_size: valid_len as usize,
+            next_sequence,
+            committed,
+            discard_uncommitted: false,
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The sequence number which the next appended entry will get
+    pub fn next_sequence(&self) -> u64 {
+        self.next_sequence
+    }
+
+    /// Entries with a lower sequence number belong to committed cementing transactions
+    pub fn committed_sequence(&self) -> u64 {
+        self.committed
+    }
+
+    /// Appends the entries, assigns their sequence numbers and flushes them to disk.
+    /// They stay invisible to readers until [`CdcLog::commit`] is called
+    pub fn append(&mut self, entries: &mut [CdcEntry]) -> anyhow::Result<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        if self.discard_uncommitted {
+            self.truncate_from(self.committed)?;
+            self.discard_uncommitted = false;
+        }
+        let result = self.append_impl(entries);
+        if result.is_err() {
+            self.discard_uncommitted = true;
+        }
+        result
+    }
+
+    fn append_impl(&mut self, entries: &mut [CdcEntry]) -> anyhow::Result<()> {
+        let mut buffer = Vec::new();
+        for entry in entries.iter_mut() {
+            let size = self.segment_size + buffer.len();
+            if size > 0 && size >= self.config.max_segment_size {
+                self.write_and_sync(&buffer)?;
+                buffer.clear();
+                self.rotate()?;
+            }
+            entry.sequence = self.next_sequence;
+            self.next_sequence += 1;
+            let payload = entry.serialize();
+            buffer.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+            buffer.extend_from_slice(&checksum(&payload));
+            buffer.extend_from_slice(&payload);
+        }
+        self.write_and_sync(&buffer)
+    }
+
+    fn write_and_sync(&mut self, bytes: &[u8]) -> anyhow::Result<()> {
+        if bytes.is_empty() {
+            return Ok(());
+        }
+        let file = self.file.as_mut().unwrap();
+        file.write_all(bytes)?;
+        file.sync_data()?;
+        self.segment_size += bytes.len();
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> anyhow::Result<()> {
+        let path = segment_path(&self.dir, self.next_sequence);
+        self.file = Some(
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .with_context(|| format!("Could not create CDC segment {:?}", path))?,
+        );
+        self.segments.push(self.next_sequence);
+        self.segment_size = 0;
+
+        if self.config.max_segments > 0 {
+            while self.segments.len() > self.config.max_segments {
+                let oldest = self.segments.remove(0);
+                std::fs::remove_file(segment_path(&self.dir, oldest))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Makes all appended entries readable. Must be called after the cementing
+    /// transaction of the entries was committed. The in memory state is updated
+    /// even if persisting the committed sequence number fails
+    pub fn commit(&mut self) -> anyhow::Result<()> {
+        if self.committed == self.next_sequence {
+            return Ok(());
+        }
+        self.committed = self.next_sequence;
+        write_committed_sequence(&self.dir, self.committed)
+    }
+
+    pub fn read(&self, from: u64, count: usize) -> anyhow::Result<CdcEntries> {
+        read_entries(&self.dir, from, count, self.committed)
+    }
+
+    /// Removes the uncommitted entries at the end of the log whose blocks were not cemented
+    /// and commits the remaining ones. Entries are written before the cementing transaction
+    /// is committed, so a crash can leave such entries behind.
+    /// Returns the number of removed entries.
+    pub fn truncate_uncemented(
+        &mut self,
+        is_cemented: impl Fn(&BlockHash) -> bool,
+    ) -> anyhow::Result<u64> {
+        // Walk backwards until the first entry with a cemented block is found
+        let mut truncate_at = None;
+        'segments: for first_sequence in self.segments.iter().rev() {
+            let (entries, _) = read_segment(&segment_path(&self.dir, *first_sequence))?;
+            for (_, entry) in entries.iter().rev() {
+                if entry.sequence < self.committed || is_cemented(&entry.block.hash()) {
+                    break 'segments;
+                }
+                truncate_at = Some(entry.sequence);
+            }
+        }
+
+        let removed = match truncate_at {
+            Some(sequence) => {
+                let removed = self.next_sequence - sequence;
+                self.truncate_from(sequence)?;
+                removed
+            }
+            None => 0,
+        };
+        self.commit()?;
+        Ok(removed)
+    }
+
+    /// Removes all entries with a sequence number of `sequence` or higher
+    fn truncate_from(&mut self, sequence: u64) -> anyhow::Result<()> {
+        let index = self
+            .segments
+            .iter()
+            .rposition(|first| *first <= sequence)
+            .unwrap_or(0);
+        let path = segment_path(&self.dir, self.segments[index]);
+        let (entries, valid_len) = read_segment(&path)?;
+        let offset = entries
+            .iter()
+            .find(|(_, entry)| entry.sequence >= sequence)
+            .map(|(offset, _)| *offset)
+            .unwrap_or(valid_len);
+
+        for first_sequence in self.segments.drain(index + 1..) {
+            std::fs::remove_file(segment_path(&self.dir, first_sequence))?;
+        }
+        let file = OpenOptions::new().read(true).write(true).open(&path)?;
+        file.set_len(offset)?;
+        file.sync_all()?;
+        drop(file);
+        self.file = Some(OpenOptions::new().append(true).open(&path)?);
+        self.segment_size = offset as usize;
+        self.next_sequence = sequence.max(self.segments[index]);
+        self.committed = self.committed.min(self.next_sequence);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsnano_core::BlockBuilder;
+
+    struct TestDir(PathBuf);
+
+    impl TestDir {
+        fn new() -> Self {
+            Self(std::env::temp_dir().join(format!("cdc-{}", rand::random::<u64>())))
+        }
+    }
+
+    impl Drop for TestDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn test_entry(i: u64) -> CdcEntry {
+        let block = BlockBuilder::state()
+            .previous(BlockHash::from(i))
+            .with_sideband()
+            .build();
+        CdcEntry {
+            sequence: 0,
+            block,
+            amount: Amount::raw(i as u128),
+            subtype: BlockSubType::Send,
+            confirmation_root: BlockHash::from(i),
+            election: if i % 2 == 0 {
+                Some(CdcElectionInfo {
+                    tally: Amount::raw(100),
+                    final_tally: Amount::raw(90),
+                    confirmation_request_count: 3,
+                    voter_count: 5,
+                    duration: Duration::from_millis(1234),
+                })
+            } else {
+                None
+            },
+        }
+    }
+
+    fn append_uncommitted(log: &mut CdcLog, range: std::ops::Range<u64>) -> Vec<CdcEntry> {
+        let mut entries: Vec<_> = range.map(test_entry).collect();
+        log.append(&mut entries).unwrap();
+        entries
+    }
+
+    fn append(log: &mut CdcLog, range: std::ops::Range<u64>) -> Vec<CdcEntry> {
+        let entries = append_uncommitted(log, range);
+        log.commit().unwrap();
+        entries
+    }
+
+    #[test]
+    fn serialize_entry() {
+        let mut entry = test_entry(2);
+        entry.sequence = 42;
+        assert_eq!(CdcEntry::deserialize(&entry.serialize()).unwrap(), entry);
+    }
+
+    #[test]
+    fn append_assigns_sequence_numbers() {
+        let dir = TestDir::new();
+        let mut log = CdcLog::open(&dir.0, CdcConfig::default()).unwrap();
+
+        let entries = append(&mut log, 0..3);
+
+        assert_eq!(
+            entries.iter().map(|e| e.sequence).collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+        assert_eq!(log.next_sequence(), 3);
+        assert_eq!(log.read(1, 10).unwrap().entries, entries[1..].to_vec());
+        assert_eq!(log.read(0, 2).unwrap().entries, entries[..2].to_vec());
+    }
+
+    #[test]
+    fn uncommitted_entries_are_not_readable() {
+        let dir = TestDir::new();
+        let mut log = CdcLog::open(&dir.0, CdcConfig::default()).unwrap();
+        let committed = append(&mut log, 0..2);
+
+        append_uncommitted(&mut log, 2..4);
+
+        assert_eq!(log.committed_sequence(), 2);
+        assert_eq!(log.read(0, 10).unwrap().entries, committed);
+        assert_eq!(read_cdc_entries(&dir.0, 0, 10).unwrap().entries, committed);
+    }
+
+    #[test]
+    fn commit_is_persisted() {
+        let dir = TestDir::new();
+        let entries = {
+            let mut log = CdcLog::open(&dir.0, CdcConfig::default()).unwrap();
+            append(&mut log, 0..2)
+        };
+
+        let log = CdcLog::open(&dir.0, CdcConfig::default()).unwrap();
+
+        assert_eq!(log.committed_sequence(), 2);
+        assert_eq!(read_cdc_entries(&dir.0, 0, 10).unwrap().entries, entries);
+    }
+
+    #[test]
+    fn continues_after_reopen() {
+        let dir = TestDir::new();
+        let entries = {
+            let mut log = CdcLog::open(&dir.0, CdcConfig::default()).unwrap();
+            append(&mut log, 0..2)
+        };
+
+        let mut log = CdcLog::open(&dir.0, CdcConfig::default()).unwrap();
+        let more = append(&mut log, 2..3);
+
+        assert_eq!(more[0].sequence, 2);
+        assert_eq!(log.read(0, 10).unwrap().entries, [entries, more].concat());
+    }
+
+    #[test]
+    fn discards_incomplete_record() {
+        let dir = TestDir::new();
+        {
+            let mut log = CdcLog::open(&dir.0, CdcConfig::default()).unwrap();
+            append(&mut log, 0..2);
+        }
+        let path = segment_path(&dir.0, 0);
+        let len = std::fs::metadata(&path).unwrap().len();
+        OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_len(len - 10)
+            .unwrap();
+
+        let mut log = CdcLog::open(&dir.0, CdcConfig::default()).unwrap();
+
+        assert_eq!(log.next_sequence(), 1);
+        let entries = append(&mut log, 2..3);
+        assert_eq!(entries[0].sequence, 1);
+        assert_eq!(log.read(0, 10).unwrap().entries.len(), 2);
+    }
+
+    #[test]
+    fn rotates_and_deletes_old_segments() {
+        let dir = TestDir::new();
+        let config = CdcConfig {
+            enabled: true,
+            max_segment_size: 1,
+            max_segments: 2,
+        };
+        let mut log = CdcLog::open(&dir.0, config).unwrap();
+
+        append(&mut log, 0..4);
+
+        assert_eq!(list_segments(&dir.0).unwrap(), vec![2, 3]);
+        let read = log.read(0, 10).unwrap();
+        assert_eq!(read.first_available, 2);
+        assert_eq!(
+            read.entries.iter().map(|e| e.sequence).collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+    }
+
+    #[test]
+    fn truncate_uncemented_entries() {
+        let dir = TestDir::new();
+        let config = CdcConfig {
+            enabled: true,
+            max_segment_size: 1,
+            max_segments: 0,
+        };
+        let mut log = CdcLog::open(&dir.0, config).unwrap();
+        let entries = append_uncommitted(&mut log, 0..4);
+        let cemented: Vec<_> = entries[..2].iter().map(|e| e.block.hash()).collect();
+
+        let removed = log
+            .truncate_uncemented(|hash| cemented.contains(hash))
+            .unwrap();
+
+        assert_eq!(removed, 2);
+        assert_eq!(log.next_sequence(), 2);
+        assert_eq!(log.committed_sequence(), 2);
+        assert_eq!(log.read(0, 10).unwrap().entries, entries[..2].to_vec());
+        let more = append(&mut log, 4..5);
+        assert_eq!(more[0].sequence, 2);
+    }
+
+    #[test]
+    fn truncate_uncemented_keeps_committed_entries() {
+        let dir = TestDir::new();
+        let mut log = CdcLog::open(&dir.0, CdcConfig::default()).unwrap();
+        let committed = append(&mut log, 0..2);
+        append_uncommitted(&mut log, 2..4);
+
+        let removed = log.truncate_uncemented(|_| false).unwrap();
+
+        assert_eq!(removed, 2);
+        assert_eq!(log.read(0, 10).unwrap().entries, committed);
+    }
+}
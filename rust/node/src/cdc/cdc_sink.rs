@@ -0,0 +1,139 @@
+use super::{read_entries, CdcElectionInfo, CdcEntries, CdcEntry, CdcLog};
+use crate::consensus::VoteApplier;
+use rsnano_core::{BlockEnum, BlockHash, BlockSubType};
+use rsnano_ledger::Ledger;
+use rsnano_store_lmdb::Transaction;
+use std::{
+    collections::VecDeque,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+use tracing::{info, warn};
+
+/// Records the blocks cemented by the confirming set in the CDC log.
+/// Blocks are appended before the cementing transaction is committed, but they are only
+/// served to readers after the confirming set reported the commit. Entries of
+/// blocks whose cementing never got committed are removed again when the sink is created,
+/// so that every cemented block is recorded exactly once.
+pub struct CdcSink {
+    dir: PathBuf,
+    log: Mutex<CdcLog>,
+    committed: AtomicU64,
+    ledger: Arc<Ledger>,
+    vote_applier: Arc<VoteApplier>,
+}
+
+impl CdcSink {
+    pub fn new(
+        mut log: CdcLog,
+        ledger: Arc<Ledger>,
+        vote_applier: Arc<VoteApplier>,
+    ) -> anyhow::Result<Self> {
+        let removed = {
+            let tx = ledger.read_txn();
+            log.truncate_uncemented(|hash| ledger.confirmed().block_exists_or_pruned(&tx, hash))?
+        };
+        if removed > 0 {
+            info!("Removed {} uncemented entries from the CDC log", removed);
+        }
+        Ok(Self {
+            dir: log.dir().to_owned(),
+            committed: AtomicU64::new(log.committed_sequence()),
+            log: Mutex::new(log),
+            ledger,
+            vote_applier,
+        })
+    }
+
+    /// Appends the blocks which were cemented because `confirmation_root` got confirmed.
+    /// The cementing transaction must not be committed if this fails
+    pub(crate) fn cemented(
+        &self,
+        tx: &dyn Transaction,
+        blocks: &VecDeque<BlockEnum>,
+        confirmation_root: BlockHash,
+    ) -> anyhow::Result<()> {
+        let mut entries: Vec<_> = blocks
+            .iter()
+            .map(|block| self.create_entry(tx, block, confirmation_root))
+            .collect();
+
+        self.log.lock().unwrap().append(&mut entries)
+    }
+
+    /// Makes the entries appended so far readable. Must be called after the cementing
+    /// transaction was committed
+    pub(crate) fn committed(&self) {
+        let mut log = self.log.lock().unwrap();
+        if let Err(e) = log.commit() {
+            // The committed sequence number is restored from the ledger on the next start
+            warn!("Could not persist committed CDC sequence number: {:?}", e);
+        }
+        self.committed
+            .store(log.committed_sequence(), Ordering::SeqCst);
+    }
+
+    fn create_entry(
+        &self,
+        tx: &dyn Transaction,
+        block: &BlockEnum,
+        confirmation_root: BlockHash,
+    ) -> CdcEntry {
+        let hash = block.hash();
+        CdcEntry {
+            sequence: 0,
+            block: block.clone(),
+            amount: self
+                .ledger
+                .any()
+                .block_amount(tx, &hash)
+                .unwrap_or_default(),
+            subtype: block_subtype(block),
+            confirmation_root,
+            election: self.election_info(&hash),
+        }
+    }
+
+    fn election_info(&self, hash: &BlockHash) -> Option<CdcElectionInfo> {
+        let election = self.vote_applier.election_winner_details(hash)?;
+        let guard = election.mutex.lock().unwrap();
+        Some(CdcElectionInfo {
+            tally: guard.status.tally,
+            final_tally: guard.status.final_tally,
+            confirmation_request_count: guard.status.confirmation_request_count,
+            voter_count: guard.status.voter_count,
+            duration: guard.status.election_duration,
+        })
+    }
+
+    /// Reads directly from the segment files, so that readers don't block the confirming set.
+    /// Entries of cementing transactions which weren't committed yet are never returned
+    pub fn read(&self, from: u64, count: usize) -> anyhow::Result<CdcEntries> {
+        let committed = self.committed.load(Ordering::SeqCst);
+        read_entries(&self.dir, from, count, committed)
+    }
+
+    /// The sequence number the next committed entry will get
+    pub fn next_sequence(&self) -> u64 {
+        self.committed.load(Ordering::SeqCst)
+    }
+}
+
+fn block_subtype(block: &BlockEnum) -> BlockSubType {
+    if block.is_send() {
+        BlockSubType::Send
+    } else if block.is_epoch() {
+        BlockSubType::Epoch
+    } else if block.is_receive() {
+        if block.is_open() {
+            BlockSubType::Open
+        } else {
+            BlockSubType::Receive
+        }
+    } else {
+        BlockSubType::Change
+    }
+}
@@ -0,0 +1,5 @@
+mod cdc_log;
+mod cdc_sink;
+
+pub use cdc_log::*;
+pub use cdc_sink::*;
@@ -1,5 +1,6 @@
 use super::{BatchCementedCallback, BlockCallback};
 use crate::{
+    cdc::CdcSink,
     stats::{DetailType, StatType, Stats},
     utils::{ThreadPool, ThreadPoolImpl},
 };
@@ -18,6 +19,11 @@ use std::{
     thread::JoinHandle,
     time::Duration,
 };
+use tracing::error;
+
+/// Bounds of the delay before cementing is retried after the CDC log could not be written
+const MIN_RETRY_DELAY: Duration = Duration::from_millis(100);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct ConfirmingSetConfig {
@@ -55,6 +61,7 @@ impl ConfirmingSet {
                 stats,
                 config,
                 observers: Arc::new(Mutex::new(Observers::default())),
+                cdc_sink: Mutex::new(None),
                 notification_workers: ThreadPoolImpl::create(1, "Conf notif"),
             }),
         }
@@ -78,6 +85,11 @@ impl ConfirmingSet {
             .push(callback);
     }
 
+    /// Records all cemented blocks in the given CDC sink
+    pub fn set_cdc_sink(&self, sink: Arc<CdcSink>) {
+        *self.thread.cdc_sink.lock().unwrap() = Some(sink);
+    }
+
     /// Adds a block to the set of blocks to be confirmed
     pub fn add(&self, hash: BlockHash) {
         self.thread.add(hash);
@@ -155,6 +167,7 @@ struct ConfirmingSetThread {
     config: ConfirmingSetConfig,
     notification_workers: ThreadPoolImpl,
     observers: Arc<Mutex<Observers>>,
+    cdc_sink: Mutex<Option<Arc<CdcSink>>>,
 }
 
 impl ConfirmingSetThread {
@@ -191,12 +204,27 @@ impl ConfirmingSetThread {
 
     fn run(&self) {
         let mut guard = self.mutex.lock().unwrap();
+        let mut retry_delay = Duration::ZERO;
         while !self.stopped.load(Ordering::SeqCst) {
             if !guard.set.is_empty() {
                 let batch = guard.next_batch(256);
                 drop(guard);
-                self.run_batch(batch);
+                let result = self.run_batch(batch);
                 guard = self.mutex.lock().unwrap();
+                match result {
+                    Ok(()) => retry_delay = Duration::ZERO,
+                    Err(retry) => {
+                        guard.set.extend(retry);
+                        retry_delay = (retry_delay * 2).clamp(MIN_RETRY_DELAY, MAX_RETRY_DELAY);
+                        guard = self
+                            .condition
+                            .wait_timeout_while(guard, retry_delay, |_| {
+                                !self.stopped.load(Ordering::SeqCst)
+                            })
+                            .unwrap()
+                            .0;
+                    }
+                }
             } else {
                 guard = self
                     .condition
@@ -258,7 +286,7 @@ impl ConfirmingSetThread {
             self.stats
                 .inc(StatType::ConfirmingSet, DetailType::NotifyIntermediate);
             drop(write_guard);
-            tx.commit();
+            self.commit(&mut tx);
 
             self.notify(cemented, already_cemented);
 
@@ -268,21 +296,50 @@ impl ConfirmingSetThread {
         (write_guard, tx)
     }
 
-    fn run_batch(&self, batch: VecDeque<BlockHash>) {
+    fn refresh_if_needed(
+        &self,
+        write_guard: WriteGuard,
+        mut tx: LmdbWriteTransaction,
+    ) -> (WriteGuard, LmdbWriteTransaction) {
+        if tx.elapsed() > Duration::from_millis(500) {
+            self.commit(&mut tx);
+            drop(write_guard);
+
+            let write_guard = self.ledger.write_queue.wait(Writer::ConfirmationHeight);
+            tx.renew();
+            (write_guard, tx)
+        } else {
+            (write_guard, tx)
+        }
+    }
+
+    /// Commits the transaction and makes the CDC entries of its cemented blocks readable
+    fn commit(&self, tx: &mut LmdbWriteTransaction) {
+        tx.commit();
+        if let Some(sink) = self.cdc_sink.lock().unwrap().as_ref() {
+            sink.committed();
+        }
+    }
+
+    /// Returns the hashes which have to be cemented again, because recording
+    /// their blocks in the CDC log failed
+    fn run_batch(&self, batch: VecDeque<BlockHash>) -> Result<(), Vec<BlockHash>> {
         let mut cemented = VecDeque::new();
         let mut already_cemented = VecDeque::new();
+        let mut cdc_failed = false;
 
         {
             let mut write_guard = self.ledger.write_queue.wait(Writer::ConfirmationHeight);
             let mut tx = self.ledger.rw_txn();
 
-            for hash in batch {
+            'batch: for &hash in &batch {
                 loop {
-                    (write_guard, tx) = self.ledger.refresh_if_needed(write_guard, tx);
+                    (write_guard, tx) = self.refresh_if_needed(write_guard, tx);
 
                     // Cementing deep dependency chains might take a long time, allow for graceful shutdown, ignore notifications
                     if self.stopped.load(Ordering::Relaxed) {
-                        return;
+                        self.commit(&mut tx);
+                        return Ok(());
                     }
 
                     // Issue notifications here, so that `cemented` set is not too large before we add more blocks
@@ -297,6 +354,19 @@ impl ConfirmingSetThread {
                         .confirm_max(&mut tx, hash, self.config.max_blocks);
                     let added_len = added.len();
                     if !added.is_empty() {
+                        // Recorded before the transaction is committed, see CdcSink
+                        if let Some(sink) = self.cdc_sink.lock().unwrap().as_ref() {
+                            if let Err(e) = sink.cemented(&tx, &added, hash) {
+                                // Cemented blocks must not be missing in the CDC log
+                                error!(
+                                    "Could not append to CDC log, cementing will be retried: {:?}",
+                                    e
+                                );
+                                tx.abort();
+                                cdc_failed = true;
+                                break 'batch;
+                            }
+                        }
                         // Confirming this block may implicitly confirm more
                         self.stats.add(
                             StatType::ConfirmingSet,
@@ -322,9 +392,32 @@ impl ConfirmingSetThread {
                 self.stats
                     .inc(StatType::ConfirmingSet, DetailType::CementedHash);
             }
+
+            if !cdc_failed {
+                self.commit(&mut tx);
+            }
         }
 
+        let retry: Vec<_> = if cdc_failed {
+            // Only the blocks of the transactions committed before the failure stay cemented
+            let tx = self.ledger.read_txn();
+            let confirmed = self.ledger.confirmed();
+            cemented.retain(|(block, _)| confirmed.block_exists(&tx, &block.hash()));
+            batch
+                .into_iter()
+                .filter(|hash| !confirmed.block_exists(&tx, hash))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
         self.notify(&mut cemented, &mut already_cemented);
+
+        if retry.is_empty() {
+            Ok(())
+        } else {
+            Err(retry)
+        }
     }
 }
 
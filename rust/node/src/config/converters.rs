@@ -12,9 +12,14 @@ impl From<&GlobalConfig> for BlockProcessorConfig {
         Self {
             max_peer_queue: config.max_peer_queue,
             priority_local: config.priority_local,
+            priority_local_node: config.priority_local_node,
+            priority_local_rpc: config.priority_local_rpc,
             priority_bootstrap: config.priority_bootstrap,
             priority_live: config.priority_live,
             max_system_queue: config.max_system_queue,
+            max_local_queue: config.max_local_queue,
+            max_local_queue_per_client: config.max_local_queue_per_client,
+            max_local_queue_per_account: config.max_local_queue_per_account,
             batch_max_time: Duration::from_millis(
                 value.node_config.block_processor_batch_max_time_ms as u64,
             ),
@@ -1,11 +1,15 @@
 use super::{DiagnosticsConfig, Networks};
 use crate::{
-    block_processing::{BlockProcessorConfig, LocalBlockBroadcasterConfig},
+    block_processing::{
+        BlockProcessorConfig, LocalBlockBroadcasterConfig, PersistentUncheckedConfig,
+    },
     bootstrap::{BootstrapAscendingConfig, BootstrapInitiatorConfig, BootstrapServerConfig},
+    cdc::CdcConfig,
     cementation::ConfirmingSetConfig,
     consensus::{
-        ActiveElectionsConfig, HintedSchedulerConfig, OptimisticSchedulerConfig,
-        PriorityBucketConfig, RequestAggregatorConfig, VoteCacheConfig, VoteProcessorConfig,
+        ActiveElectionsConfig, ElectionTraceConfig, HintedSchedulerConfig,
+        OptimisticSchedulerConfig, PriorityBucketConfig, RequestAggregatorConfig, VoteCacheConfig,
+        VoteProcessorConfig,
     },
     stats::StatsConfig,
     transport::{MessageProcessorConfig, TcpConfig},
@@ -16,7 +20,7 @@ use once_cell::sync::Lazy;
 use rand::{thread_rng, Rng};
 use rsnano_core::{
     utils::{get_env_or_default_string, is_sanitizer_build},
-    Account, Amount, PublicKey, GXRB_RATIO, XRB_RATIO,
+    Account, Amount, Argon2idParams, PublicKey, GXRB_RATIO, XRB_RATIO,
 };
 use rsnano_store_lmdb::LmdbConfig;
 use std::{cmp::max, fmt, net::Ipv6Addr, str::FromStr, time::Duration};
@@ -113,6 +117,11 @@ pub struct NodeConfig {
     pub local_block_broadcaster: LocalBlockBroadcasterConfig,
     pub confirming_set: ConfirmingSetConfig,
     pub monitor: MonitorConfig,
+    pub wallet_backup: WalletBackupConfig,
+    pub external_signer: ExternalSignerConfig,
+    pub election_trace: ElectionTraceConfig,
+    pub cdc: CdcConfig,
+    pub persistent_unchecked: PersistentUncheckedConfig,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -373,6 +382,15 @@ impl NodeConfig {
             ),
             confirming_set: Default::default(),
             monitor: Default::default(),
+            wallet_backup: if network_params.network.is_dev_network() {
+                WalletBackupConfig::new_dev()
+            } else {
+                Default::default()
+            },
+            external_signer: Default::default(),
+            election_trace: Default::default(),
+            cdc: Default::default(),
+            persistent_unchecked: Default::default(),
         }
     }
 
@@ -400,3 +418,43 @@ impl Default for MonitorConfig {
         }
     }
 }
+
+/// Settings of the periodic wallet backup
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct WalletBackupConfig {
+    /// Password of the encrypted backup files. The private keys in the backup are
+    /// additionally encrypted with the wallet password. If it is empty, the backups
+    /// are written in the unencrypted format
+    pub password: String,
+    pub kdf: Argon2idParams,
+}
+
+impl WalletBackupConfig {
+    pub fn new_dev() -> Self {
+        Self {
+            password: String::new(),
+            kdf: Argon2idParams::new_dev(),
+        }
+    }
+}
+
+/// Signing daemon which holds the keys of watch only wallet accounts
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExternalSignerConfig {
+    /// `unix:/path/to/socket` or `http://host:port/path`. Empty disables the external signer
+    pub address: String,
+    pub timeout: Duration,
+    /// Votes are only useful while the election is running, so vote signatures
+    /// are given up much earlier than block signatures
+    pub vote_timeout: Duration,
+}
+
+impl Default for ExternalSignerConfig {
+    fn default() -> Self {
+        Self {
+            address: String::new(),
+            timeout: Duration::from_secs(5),
+            vote_timeout: Duration::from_millis(250),
+        }
+    }
+}
@@ -27,6 +27,7 @@ pub struct NodeFlags {
     pub force_use_write_queue: bool,            // For testing only
     pub disable_search_pending: bool,           // For testing only
     pub enable_pruning: bool,
+    pub enable_account_history_index: bool,
     pub fast_bootstrap: bool,
     pub read_only: bool,
     pub disable_connection_cleanup: bool,
@@ -66,6 +67,7 @@ impl NodeFlags {
             force_use_write_queue: false,
             disable_search_pending: false,
             enable_pruning: false,
+            enable_account_history_index: false,
             fast_bootstrap: false,
             read_only: false,
             disable_connection_cleanup: false,
@@ -159,6 +161,10 @@ impl NodeFlags {
         self.enable_pruning = value;
     }
 
+    pub fn set_enable_account_history_index(&mut self, value: bool) {
+        self.enable_account_history_index = value;
+    }
+
     pub fn set_fast_bootstrap(&mut self, value: bool) {
         self.fast_bootstrap = value;
     }
@@ -5,9 +5,14 @@ use serde::{Deserialize, Serialize};
 pub struct BlockProcessorToml {
     pub max_peer_queue: Option<usize>,
     pub max_system_queue: Option<usize>,
+    pub max_local_queue: Option<usize>,
+    pub max_local_queue_per_client: Option<usize>,
+    pub max_local_queue_per_account: Option<usize>,
     pub priority_bootstrap: Option<usize>,
     pub priority_live: Option<usize>,
     pub priority_local: Option<usize>,
+    pub priority_local_node: Option<usize>,
+    pub priority_local_rpc: Option<usize>,
 }
 
 impl From<&BlockProcessorConfig> for BlockProcessorToml {
@@ -15,9 +20,14 @@ impl From<&BlockProcessorConfig> for BlockProcessorToml {
         Self {
             max_peer_queue: Some(config.max_peer_queue),
             max_system_queue: Some(config.max_system_queue),
+            max_local_queue: Some(config.max_local_queue),
+            max_local_queue_per_client: Some(config.max_local_queue_per_client),
+            max_local_queue_per_account: Some(config.max_local_queue_per_account),
             priority_live: Some(config.priority_live),
             priority_bootstrap: Some(config.priority_bootstrap),
             priority_local: Some(config.priority_local),
+            priority_local_node: Some(config.priority_local_node),
+            priority_local_rpc: Some(config.priority_local_rpc),
         }
     }
 }
@@ -30,12 +40,27 @@ impl BlockProcessorConfig {
         if let Some(max_system_queue) = toml.max_system_queue {
             self.max_system_queue = max_system_queue;
         }
+        if let Some(max_local_queue) = toml.max_local_queue {
+            self.max_local_queue = max_local_queue;
+        }
+        if let Some(max_local_queue_per_client) = toml.max_local_queue_per_client {
+            self.max_local_queue_per_client = max_local_queue_per_client;
+        }
+        if let Some(max_local_queue_per_account) = toml.max_local_queue_per_account {
+            self.max_local_queue_per_account = max_local_queue_per_account;
+        }
         if let Some(priority_live) = toml.priority_live {
             self.priority_live = priority_live;
         }
         if let Some(priority_local) = toml.priority_local {
             self.priority_local = priority_local;
         }
+        if let Some(priority_local_node) = toml.priority_local_node {
+            self.priority_local_node = priority_local_node;
+        }
+        if let Some(priority_local_rpc) = toml.priority_local_rpc {
+            self.priority_local_rpc = priority_local_rpc;
+        }
         if let Some(priority_bootstrap) = toml.priority_bootstrap {
             self.priority_bootstrap = priority_bootstrap;
         }
@@ -17,6 +17,11 @@ pub struct BootstrapAscendingToml {
     pub request_timeout: Option<u64>,
     pub max_requests: Option<usize>,
     pub account_sets: Option<AccountSetsToml>,
+    pub enable_gap_resolver: Option<bool>,
+    pub gap_fanout: Option<usize>,
+    pub gap_rate_limit: Option<usize>,
+    pub max_gap_queue: Option<usize>,
+    pub gap_cooldown: Option<u64>,
 }
 
 impl From<&BootstrapAscendingConfig> for BootstrapAscendingToml {
@@ -35,6 +40,11 @@ impl From<&BootstrapAscendingConfig> for BootstrapAscendingToml {
             account_sets: Some((&config.account_sets).into()),
             block_processor_threshold: Some(config.block_processor_theshold),
             max_requests: Some(config.max_requests),
+            enable_gap_resolver: Some(config.enable_gap_resolver),
+            gap_fanout: Some(config.gap_fanout),
+            gap_rate_limit: Some(config.gap_rate_limit),
+            max_gap_queue: Some(config.max_gap_queue),
+            gap_cooldown: Some(config.gap_cooldown.as_millis() as u64),
         }
     }
 }
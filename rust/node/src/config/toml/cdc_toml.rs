@@ -0,0 +1,33 @@
+use crate::cdc::CdcConfig;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+pub struct CdcToml {
+    pub enabled: Option<bool>,
+    pub max_segment_size: Option<usize>,
+    pub max_segments: Option<usize>,
+}
+
+impl CdcConfig {
+    pub fn merge_toml(&mut self, toml: &CdcToml) {
+        if let Some(enabled) = toml.enabled {
+            self.enabled = enabled;
+        }
+        if let Some(max_segment_size) = toml.max_segment_size {
+            self.max_segment_size = max_segment_size;
+        }
+        if let Some(max_segments) = toml.max_segments {
+            self.max_segments = max_segments;
+        }
+    }
+}
+
+impl From<&CdcConfig> for CdcToml {
+    fn from(config: &CdcConfig) -> Self {
+        Self {
+            enabled: Some(config.enabled),
+            max_segment_size: Some(config.max_segment_size),
+            max_segments: Some(config.max_segments),
+        }
+    }
+}
@@ -120,9 +120,14 @@ mod tests {
         [node.block_processor]
         max_peer_queue = 999
         max_system_queue = 999
+        max_local_queue = 999
+        max_local_queue_per_client = 999
+        max_local_queue_per_account = 999
         priority_live = 999
         priority_bootstrap = 999
         priority_local = 999
+        priority_local_node = 999
+        priority_local_rpc = 999
 
         [node.active_elections]
         size = 999
@@ -147,6 +152,15 @@ mod tests {
         max_elections = 999
         reserved_elections = 999
 
+        [[node.priority_bucket.buckets]]
+        minimum_balance = "0"
+
+        [[node.priority_bucket.buckets]]
+        minimum_balance = "999"
+        max_blocks = 999
+        max_elections = 999
+        reserved_elections = 999
+
         [node.rep_crawler]
         query_timeout = 999
 
@@ -154,6 +168,32 @@ mod tests {
         enable = false
         interval = 999
 
+        [node.wallet_backup]
+        password = "secret"
+        kdf_memory_kib = 999
+        kdf_iterations = 999
+        kdf_parallelism = 999
+
+        [node.external_signer]
+        address = "unix:/tmp/signer.sock"
+        timeout = 999
+        vote_timeout = 999
+
+        [node.election_trace]
+        enabled = true
+        max_events = 999
+        history_size = 999
+        dump_path = "/tmp/election_traces"
+
+        [node.cdc]
+        enabled = true
+        max_segment_size = 999
+        max_segments = 999
+
+        [node.persistent_unchecked]
+        enabled = true
+        max_size = 999
+
         [node.ipc.local]
         allow_unsafe = true
         enable = true
@@ -213,6 +253,7 @@ mod tests {
         age_cutoff = 999
         max_size = 999
         max_voters = 999
+        persist = false
 
         [node.vote_processor]
         max_pr_queue = 999
@@ -233,6 +274,11 @@ mod tests {
         throttle_wait = 999
         request_timeout = 999
         max_requests = 999
+        enable_gap_resolver = false
+        gap_fanout = 999
+        gap_rate_limit = 999
+        max_gap_queue = 999
+        gap_cooldown = 999
 
         [node.bootstrap_ascending.account_sets]
         blocking_max = 999
@@ -467,6 +513,21 @@ mod tests {
             deserialized.node.block_processor.max_system_queue,
             default_cfg.node.block_processor.max_system_queue
         );
+        assert_ne!(
+            deserialized.node.block_processor.max_local_queue,
+            default_cfg.node.block_processor.max_local_queue
+        );
+        assert_ne!(
+            deserialized.node.block_processor.max_local_queue_per_client,
+            default_cfg.node.block_processor.max_local_queue_per_client
+        );
+        assert_ne!(
+            deserialized
+                .node
+                .block_processor
+                .max_local_queue_per_account,
+            default_cfg.node.block_processor.max_local_queue_per_account
+        );
         assert_ne!(
             deserialized.node.block_processor.priority_live,
             default_cfg.node.block_processor.priority_live
@@ -479,6 +540,14 @@ mod tests {
             deserialized.node.block_processor.priority_local,
             default_cfg.node.block_processor.priority_local
         );
+        assert_ne!(
+            deserialized.node.block_processor.priority_local_node,
+            default_cfg.node.block_processor.priority_local_node
+        );
+        assert_ne!(
+            deserialized.node.block_processor.priority_local_rpc,
+            default_cfg.node.block_processor.priority_local_rpc
+        );
 
         // Active Elections section
         assert_ne!(
@@ -577,6 +646,10 @@ mod tests {
             deserialized.node.priority_bucket.reserved_elections,
             default_cfg.node.priority_bucket.reserved_elections
         );
+        assert_ne!(
+            deserialized.node.priority_bucket.buckets,
+            default_cfg.node.priority_bucket.buckets
+        );
 
         // Rep Crawler section
         assert_ne!(
@@ -594,6 +667,33 @@ mod tests {
             default_cfg.node.monitor.interval
         );
 
+        // Wallet backup section
+        assert_ne!(
+            deserialized.node.wallet_backup,
+            default_cfg.node.wallet_backup
+        );
+
+        // External signer section
+        assert_ne!(
+            deserialized.node.external_signer,
+            default_cfg.node.external_signer
+        );
+
+        // Election trace section
+        assert_ne!(
+            deserialized.node.election_trace,
+            default_cfg.node.election_trace
+        );
+
+        // CDC section
+        assert_ne!(deserialized.node.cdc, default_cfg.node.cdc);
+
+        // Persistent unchecked section
+        assert_ne!(
+            deserialized.node.persistent_unchecked,
+            default_cfg.node.persistent_unchecked
+        );
+
         // IPC Local section
         assert_ne!(
             deserialized
@@ -821,6 +921,10 @@ mod tests {
             deserialized.node.vote_cache.max_voters,
             default_cfg.node.vote_cache.max_voters
         );
+        assert_ne!(
+            deserialized.node.vote_cache.persist,
+            default_cfg.node.vote_cache.persist
+        );
 
         // Vote Processor section
         assert_ne!(
@@ -879,6 +983,26 @@ mod tests {
             deserialized.node.bootstrap_ascending.request_timeout,
             default_cfg.node.bootstrap_ascending.request_timeout
         );
+        assert_ne!(
+            deserialized.node.bootstrap_ascending.enable_gap_resolver,
+            default_cfg.node.bootstrap_ascending.enable_gap_resolver
+        );
+        assert_ne!(
+            deserialized.node.bootstrap_ascending.gap_fanout,
+            default_cfg.node.bootstrap_ascending.gap_fanout
+        );
+        assert_ne!(
+            deserialized.node.bootstrap_ascending.gap_rate_limit,
+            default_cfg.node.bootstrap_ascending.gap_rate_limit
+        );
+        assert_ne!(
+            deserialized.node.bootstrap_ascending.max_gap_queue,
+            default_cfg.node.bootstrap_ascending.max_gap_queue
+        );
+        assert_ne!(
+            deserialized.node.bootstrap_ascending.gap_cooldown,
+            default_cfg.node.bootstrap_ascending.gap_cooldown
+        );
 
         // Bootstrap Ascending Account Sets section
         assert_ne!(
@@ -0,0 +1,49 @@
+use crate::consensus::ElectionTraceConfig;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Deserialize, Serialize)]
+pub struct ElectionTraceToml {
+    pub enabled: Option<bool>,
+    pub max_events: Option<usize>,
+    pub history_size: Option<usize>,
+    pub dump_path: Option<String>,
+}
+
+impl ElectionTraceConfig {
+    pub fn merge_toml(&mut self, toml: &ElectionTraceToml) {
+        if let Some(enabled) = toml.enabled {
+            self.enabled = enabled;
+        }
+        if let Some(max_events) = toml.max_events {
+            self.max_events = max_events;
+        }
+        if let Some(history_size) = toml.history_size {
+            self.history_size = history_size;
+        }
+        if let Some(dump_path) = &toml.dump_path {
+            self.dump_path = if dump_path.is_empty() {
+                None
+            } else {
+                Some(PathBuf::from(dump_path))
+            };
+        }
+    }
+}
+
+impl From<&ElectionTraceConfig> for ElectionTraceToml {
+    fn from(config: &ElectionTraceConfig) -> Self {
+        Self {
+            enabled: Some(config.enabled),
+            max_events: Some(config.max_events),
+            history_size: Some(config.history_size),
+            dump_path: Some(
+                config
+                    .dump_path
+                    .as_ref()
+                    .map(|p| p.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+            ),
+        }
+    }
+}
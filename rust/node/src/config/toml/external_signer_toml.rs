@@ -0,0 +1,34 @@
+use crate::config::ExternalSignerConfig;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Deserialize, Serialize)]
+pub struct ExternalSignerToml {
+    pub address: Option<String>,
+    pub timeout: Option<u64>,
+    pub vote_timeout: Option<u64>,
+}
+
+impl ExternalSignerConfig {
+    pub fn merge_toml(&mut self, toml: &ExternalSignerToml) {
+        if let Some(address) = &toml.address {
+            self.address = address.clone();
+        }
+        if let Some(timeout) = toml.timeout {
+            self.timeout = Duration::from_millis(timeout);
+        }
+        if let Some(vote_timeout) = toml.vote_timeout {
+            self.vote_timeout = Duration::from_millis(vote_timeout);
+        }
+    }
+}
+
+impl From<&ExternalSignerConfig> for ExternalSignerToml {
+    fn from(config: &ExternalSignerConfig) -> Self {
+        Self {
+            address: Some(config.address.clone()),
+            timeout: Some(config.timeout.as_millis() as u64),
+            vote_timeout: Some(config.vote_timeout.as_millis() as u64),
+        }
+    }
+}
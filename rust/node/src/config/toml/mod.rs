@@ -2,9 +2,12 @@ mod active_elections_toml;
 mod block_processor_toml;
 mod bootstrap_ascending_toml;
 mod bootstrap_server_toml;
+mod cdc_toml;
 mod daemon_toml;
 mod diagnostics_toml;
+mod election_trace_toml;
 mod experimental_toml;
+mod external_signer_toml;
 mod hinted_scheduler_toml;
 mod httpcallback_toml;
 mod ipc_toml;
@@ -16,21 +19,26 @@ mod node_toml;
 mod opencl_toml;
 mod optimistic_scheduler_toml;
 mod parse_peers;
+mod persistent_unchecked_toml;
 mod priority_bucket_toml;
 mod rep_crawler_toml;
 mod request_aggregator_toml;
 mod stats_toml;
 mod vote_cache_toml;
 mod vote_processor_toml;
+mod wallet_backup_toml;
 mod websocket_toml;
 
 pub use active_elections_toml::*;
 pub use block_processor_toml::*;
 pub use bootstrap_ascending_toml::*;
 pub use bootstrap_server_toml::*;
+pub use cdc_toml::*;
 pub use daemon_toml::*;
 pub use diagnostics_toml::*;
+pub use election_trace_toml::*;
 pub use experimental_toml::*;
+pub use external_signer_toml::*;
 pub use hinted_scheduler_toml::*;
 pub use httpcallback_toml::*;
 pub use ipc_toml::*;
@@ -41,10 +49,12 @@ pub use node_rpc_toml::*;
 pub use node_toml::*;
 pub use opencl_toml::*;
 pub use optimistic_scheduler_toml::*;
+pub use persistent_unchecked_toml::*;
 pub use priority_bucket_toml::*;
 pub use rep_crawler_toml::*;
 pub use request_aggregator_toml::*;
 pub use stats_toml::*;
 pub use vote_cache_toml::*;
 pub use vote_processor_toml::*;
+pub use wallet_backup_toml::*;
 pub use websocket_toml::*;
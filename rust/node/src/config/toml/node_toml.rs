@@ -1,9 +1,10 @@
 use super::{
     parse_peers::parse_peers, ActiveElectionsToml, BlockProcessorToml, BootstrapAscendingToml,
-    BootstrapServerToml, DiagnosticsToml, ExperimentalToml, HintedSchedulerToml, HttpcallbackToml,
-    IpcToml, LmdbToml, MessageProcessorToml, MonitorToml, OptimisticSchedulerToml,
+    BootstrapServerToml, CdcToml, DiagnosticsToml, ElectionTraceToml, ExperimentalToml,
+    ExternalSignerToml, HintedSchedulerToml, HttpcallbackToml, IpcToml, LmdbToml,
+    MessageProcessorToml, MonitorToml, OptimisticSchedulerToml, PersistentUncheckedToml,
     PriorityBucketToml, RepCrawlerToml, RequestAggregatorToml, StatsToml, VoteCacheToml,
-    VoteProcessorToml, WebsocketToml,
+    VoteProcessorToml, WalletBackupToml, WebsocketToml,
 };
 use crate::config::{FrontiersConfirmationMode, NodeConfig, Peer};
 use rsnano_core::{Account, Amount};
@@ -79,6 +80,11 @@ pub struct NodeToml {
     pub statistics: Option<StatsToml>,
     pub vote_cache: Option<VoteCacheToml>,
     pub vote_processor: Option<VoteProcessorToml>,
+    pub wallet_backup: Option<WalletBackupToml>,
+    pub external_signer: Option<ExternalSignerToml>,
+    pub election_trace: Option<ElectionTraceToml>,
+    pub cdc: Option<CdcToml>,
+    pub persistent_unchecked: Option<PersistentUncheckedToml>,
     pub websocket: Option<WebsocketToml>,
 }
 
@@ -299,6 +305,21 @@ impl NodeConfig {
             if let Some(max) = ascending_toml.max_requests {
                 config.max_requests = max;
             }
+            if let Some(enable) = ascending_toml.enable_gap_resolver {
+                config.enable_gap_resolver = enable;
+            }
+            if let Some(fanout) = ascending_toml.gap_fanout {
+                config.gap_fanout = fanout;
+            }
+            if let Some(rate_limit) = ascending_toml.gap_rate_limit {
+                config.gap_rate_limit = rate_limit;
+            }
+            if let Some(max) = ascending_toml.max_gap_queue {
+                config.max_gap_queue = max;
+            }
+            if let Some(cooldown) = ascending_toml.gap_cooldown {
+                config.gap_cooldown = Duration::from_millis(cooldown);
+            }
         }
         if let Some(bootstrap_server_toml) = &toml.bootstrap_server {
             self.bootstrap_server = bootstrap_server_toml.into();
@@ -345,6 +366,22 @@ impl NodeConfig {
         if let Some(monitor_toml) = &toml.monitor {
             self.monitor = monitor_toml.into();
         }
+        if let Some(wallet_backup_toml) = &toml.wallet_backup {
+            self.wallet_backup.merge_toml(wallet_backup_toml);
+        }
+        if let Some(external_signer_toml) = &toml.external_signer {
+            self.external_signer.merge_toml(external_signer_toml);
+        }
+        if let Some(election_trace_toml) = &toml.election_trace {
+            self.election_trace.merge_toml(election_trace_toml);
+        }
+        if let Some(cdc_toml) = &toml.cdc {
+            self.cdc.merge_toml(cdc_toml);
+        }
+        if let Some(persistent_unchecked_toml) = &toml.persistent_unchecked {
+            self.persistent_unchecked
+                .merge_toml(persistent_unchecked_toml);
+        }
         if let Some(rep_crawler_weight_minimum) = &toml.rep_crawler_weight_minimum {
             self.rep_crawler_weight_minimum = Amount::decode_dec(&rep_crawler_weight_minimum)
                 .expect("Invalid rep crawler weight minimum");
@@ -456,6 +493,11 @@ impl From<&NodeConfig> for NodeToml {
             request_aggregator: Some((&config.request_aggregator).into()),
             message_processor: Some((&config.message_processor).into()),
             monitor: Some((&config.monitor).into()),
+            wallet_backup: Some((&config.wallet_backup).into()),
+            external_signer: Some((&config.external_signer).into()),
+            election_trace: Some((&config.election_trace).into()),
+            cdc: Some((&config.cdc).into()),
+            persistent_unchecked: Some((&config.persistent_unchecked).into()),
             httpcallback: Some(config.into()),
             rep_crawler: Some(config.into()),
             experimental: Some(config.into()),
@@ -491,6 +533,11 @@ mod tests {
             max_requests: Some(107),
             database_warmup_ratio: Some(108),
             account_sets: Some(sets_toml),
+            enable_gap_resolver: Some(false),
+            gap_fanout: Some(109),
+            gap_rate_limit: Some(110),
+            max_gap_queue: Some(111),
+            gap_cooldown: Some(112),
         };
 
         let toml = NodeToml {
@@ -514,6 +561,11 @@ mod tests {
         assert_eq!(ascending.request_timeout, Duration::from_millis(106));
         assert_eq!(ascending.max_requests, 107);
         assert_eq!(ascending.database_warmup_ratio, 108);
+        assert_eq!(ascending.enable_gap_resolver, false);
+        assert_eq!(ascending.gap_fanout, 109);
+        assert_eq!(ascending.gap_rate_limit, 110);
+        assert_eq!(ascending.max_gap_queue, 111);
+        assert_eq!(ascending.gap_cooldown, Duration::from_millis(112));
 
         let sets = &cfg.bootstrap_ascending.account_sets;
         assert_eq!(sets.blocking_max, 200);
@@ -539,6 +591,11 @@ mod tests {
         assert_eq!(ascending_toml.throttle_wait, Some(100));
         assert_eq!(ascending_toml.request_timeout, Some(3000));
         assert_eq!(ascending_toml.max_requests, Some(1024));
+        assert_eq!(ascending_toml.enable_gap_resolver, Some(true));
+        assert_eq!(ascending_toml.gap_fanout, Some(2));
+        assert_eq!(ascending_toml.gap_rate_limit, Some(64));
+        assert_eq!(ascending_toml.max_gap_queue, Some(1024));
+        assert_eq!(ascending_toml.gap_cooldown, Some(10000));
 
         let sets_toml = ascending_toml.account_sets.as_ref().unwrap();
         assert_eq!(sets_toml.consideration_count, Some(4));
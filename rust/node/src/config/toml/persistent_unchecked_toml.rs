@@ -0,0 +1,28 @@
+use crate::block_processing::PersistentUncheckedConfig;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+pub struct PersistentUncheckedToml {
+    pub enabled: Option<bool>,
+    pub max_size: Option<u64>,
+}
+
+impl PersistentUncheckedConfig {
+    pub fn merge_toml(&mut self, toml: &PersistentUncheckedToml) {
+        if let Some(enabled) = toml.enabled {
+            self.enabled = enabled;
+        }
+        if let Some(max_size) = toml.max_size {
+            self.max_size = max_size;
+        }
+    }
+}
+
+impl From<&PersistentUncheckedConfig> for PersistentUncheckedToml {
+    fn from(config: &PersistentUncheckedConfig) -> Self {
+        Self {
+            enabled: Some(config.enabled),
+            max_size: Some(config.max_size),
+        }
+    }
+}
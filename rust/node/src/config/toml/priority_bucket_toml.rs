@@ -1,4 +1,5 @@
-use crate::consensus::PriorityBucketConfig;
+use crate::consensus::{PriorityBucketConfig, PriorityBucketLayout};
+use rsnano_core::Amount;
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize)]
@@ -6,6 +7,15 @@ pub struct PriorityBucketToml {
     pub max_blocks: Option<usize>,
     pub max_elections: Option<usize>,
     pub reserved_elections: Option<usize>,
+    pub buckets: Option<Vec<PriorityBucketLayoutToml>>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct PriorityBucketLayoutToml {
+    pub minimum_balance: String,
+    pub max_blocks: Option<usize>,
+    pub max_elections: Option<usize>,
+    pub reserved_elections: Option<usize>,
 }
 
 impl Default for PriorityBucketToml {
@@ -28,6 +38,9 @@ impl From<&PriorityBucketToml> for PriorityBucketConfig {
         if let Some(reserved_elections) = toml.reserved_elections {
             config.reserved_elections = reserved_elections;
         }
+        if let Some(buckets) = &toml.buckets {
+            config.buckets = buckets.iter().map(|b| b.into()).collect();
+        }
         config
     }
 }
@@ -38,6 +51,34 @@ impl From<&PriorityBucketConfig> for PriorityBucketToml {
             max_blocks: Some(config.max_blocks),
             reserved_elections: Some(config.reserved_elections),
             max_elections: Some(config.max_elections),
+            buckets: if config.buckets.is_empty() {
+                None
+            } else {
+                Some(config.buckets.iter().map(|b| b.into()).collect())
+            },
+        }
+    }
+}
+
+impl From<&PriorityBucketLayoutToml> for PriorityBucketLayout {
+    fn from(toml: &PriorityBucketLayoutToml) -> Self {
+        Self {
+            minimum_balance: Amount::decode_dec(&toml.minimum_balance)
+                .expect("Invalid bucket minimum balance"),
+            max_blocks: toml.max_blocks,
+            reserved_elections: toml.reserved_elections,
+            max_elections: toml.max_elections,
+        }
+    }
+}
+
+impl From<&PriorityBucketLayout> for PriorityBucketLayoutToml {
+    fn from(layout: &PriorityBucketLayout) -> Self {
+        Self {
+            minimum_balance: layout.minimum_balance.to_string_dec(),
+            max_blocks: layout.max_blocks,
+            reserved_elections: layout.reserved_elections,
+            max_elections: layout.max_elections,
         }
     }
 }
@@ -0,0 +1,38 @@
+use crate::config::WalletBackupConfig;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+pub struct WalletBackupToml {
+    pub password: Option<String>,
+    pub kdf_memory_kib: Option<u32>,
+    pub kdf_iterations: Option<u32>,
+    pub kdf_parallelism: Option<u32>,
+}
+
+impl WalletBackupConfig {
+    pub fn merge_toml(&mut self, toml: &WalletBackupToml) {
+        if let Some(password) = &toml.password {
+            self.password = password.clone();
+        }
+        if let Some(memory) = toml.kdf_memory_kib {
+            self.kdf.memory_kib = memory;
+        }
+        if let Some(iterations) = toml.kdf_iterations {
+            self.kdf.iterations = iterations;
+        }
+        if let Some(parallelism) = toml.kdf_parallelism {
+            self.kdf.parallelism = parallelism;
+        }
+    }
+}
+
+impl From<&WalletBackupConfig> for WalletBackupToml {
+    fn from(config: &WalletBackupConfig) -> Self {
+        Self {
+            password: Some(config.password.clone()),
+            kdf_memory_kib: Some(config.kdf.memory_kib),
+            kdf_iterations: Some(config.kdf.iterations),
+            kdf_parallelism: Some(config.kdf.parallelism),
+        }
+    }
+}
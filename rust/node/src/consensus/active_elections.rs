@@ -1,8 +1,8 @@
 use super::{
     confirmation_solicitor::ConfirmationSolicitor, election_schedulers::ElectionSchedulers,
     Election, ElectionBehavior, ElectionData, ElectionState, ElectionStatus, ElectionStatusType,
-    RecentlyConfirmedCache, VoteApplier, VoteCache, VoteCacheProcessor, VoteGenerators, VoteRouter,
-    NEXT_ELECTION_ID,
+    ElectionTraceEvent, ElectionTraceHistory, ElectionTraceSnapshot, RecentlyConfirmedCache,
+    VoteApplier, VoteCache, VoteCacheProcessor, VoteGenerators, VoteRouter, NEXT_ELECTION_ID,
 };
 use crate::{
     block_processing::BlockProcessor,
@@ -23,7 +23,7 @@ use rsnano_core::{
 };
 use rsnano_ledger::{BlockStatus, Ledger};
 use rsnano_messages::{Message, Publish};
-use rsnano_network::{DropPolicy, NetworkInfo};
+use rsnano_network::{weighted_shuffle_by, DropPolicy, NetworkInfo};
 use rsnano_nullable_clock::SteadyClock;
 use rsnano_store_lmdb::{LmdbReadTransaction, Transaction};
 use std::{
@@ -35,7 +35,7 @@ use std::{
     thread::JoinHandle,
     time::{Duration, Instant},
 };
-use tracing::{debug, trace};
+use tracing::{debug, trace, warn};
 
 const ELECTION_MAX_BLOCKS: usize = 10;
 
@@ -87,6 +87,8 @@ pub struct ActiveElections {
     pub recently_confirmed: Arc<RecentlyConfirmedCache>,
     /// Helper container for storing recently cemented elections (a block from election might be confirmed but not yet cemented by confirmation height processor)
     recently_cemented: Arc<Mutex<BoundedVecDeque<ElectionStatus>>>,
+    /// Traces of finished elections. Only filled if election tracing is enabled
+    trace_history: Mutex<ElectionTraceHistory>,
     block_processor: Arc<BlockProcessor>,
     vote_generators: Arc<VoteGenerators>,
     publish_filter: Arc<NetworkFilter>,
@@ -149,6 +151,9 @@ impl ActiveElections {
             recently_cemented: Arc::new(Mutex::new(BoundedVecDeque::new(
                 node_config.active_elections.confirmation_history_size,
             ))),
+            trace_history: Mutex::new(ElectionTraceHistory::new(
+                node_config.election_trace.history_size,
+            )),
             config: node_config.active_elections.clone(),
             node_config,
             block_processor,
@@ -535,6 +540,7 @@ impl ActiveElections {
                 election_guard
                     .last_blocks
                     .insert(block.hash(), Arc::clone(block));
+                election_guard.record(ElectionTraceEvent::BlockAdded { hash: block.hash() });
             }
         }
         /*
@@ -635,11 +641,13 @@ impl ActiveElections {
         let election_winner: BlockHash;
         let election_state;
         let blocks;
+        let trace;
         {
             let election_guard = election.mutex.lock().unwrap();
             blocks = election_guard.last_blocks.clone();
             election_winner = election_guard.status.winner.as_ref().unwrap().hash();
             election_state = election_guard.state;
+            trace = election_guard.trace_snapshot(election);
         }
 
         self.vote_router.disconnect_election(election);
@@ -680,6 +688,10 @@ impl ActiveElections {
 
         drop(guard);
 
+        if let Some(trace) = trace {
+            self.finish_trace(trace, election_state);
+        }
+
         // Track election duration
         self.stats.sample(
             Sample::ActiveElectionDuration,
@@ -710,6 +722,28 @@ impl ActiveElections {
         }
     }
 
+    fn finish_trace(&self, trace: ElectionTraceSnapshot, state: ElectionState) {
+        if state == ElectionState::ExpiredUnconfirmed {
+            if let Some(dir) = &self.node_config.election_trace.dump_path {
+                match trace.dump(dir) {
+                    Ok(path) => debug!("Election trace written to {:?}", path),
+                    Err(e) => warn!("Could not write election trace: {:?}", e),
+                }
+            }
+        }
+        self.trace_history.lock().unwrap().insert(trace);
+    }
+
+    /// Timeline of an active or a recently finished election, if election tracing is enabled
+    pub fn election_trace(&self, root: &QualifiedRoot) -> Option<ElectionTraceSnapshot> {
+        if let Some(election) = self.election(root) {
+            if let Some(trace) = election.trace_snapshot() {
+                return Some(trace);
+            }
+        }
+        self.trace_history.lock().unwrap().get(root).cloned()
+    }
+
     pub fn confirmed(&self, election: &Election) -> bool {
         election.mutex.lock().unwrap().is_confirmed()
     }
@@ -806,7 +840,17 @@ impl ActiveElections {
         let publisher = self.message_publisher.lock().unwrap().clone();
         let mut solicitor =
             ConfirmationSolicitor::new(&self.network_params, &self.network_info, publisher);
-        let peered_prs = self.online_reps.lock().unwrap().peered_principal_reps();
+        let mut peered_prs = self.online_reps.lock().unwrap().peered_principal_reps();
+        {
+            // Well scored peers are asked first, because the solicitor stops after a limit
+            let network_info = self.network_info.read().unwrap();
+            weighted_shuffle_by(&mut peered_prs, |rep| {
+                network_info
+                    .get(rep.channel_id)
+                    .map(|channel| channel.score())
+                    .unwrap_or_default()
+            });
+        }
         solicitor.prepare(&peered_prs);
 
         /*
@@ -883,7 +927,7 @@ impl ActiveElections {
             ElectionState::Active => {
                 self.broadcast_vote(election, &mut guard);
                 self.broadcast_block(solicitor, election, &mut guard);
-                self.send_confirm_req(solicitor, election, &guard);
+                self.send_confirm_req(solicitor, election, &mut guard);
             }
             ElectionState::Confirmed => {
                 result = true; // Return true to indicate this election should be cleaned up
@@ -921,14 +965,16 @@ impl ActiveElections {
         &self,
         solicitor: &mut ConfirmationSolicitor,
         election: &Election,
-        election_guard: &MutexGuard<ElectionData>,
+        election_guard: &mut MutexGuard<ElectionData>,
     ) {
         if self.confirm_req_time(election) < election.last_req_elapsed() {
             if !solicitor.add(election, election_guard) {
                 election.set_last_req();
-                election
+                let count = election
                     .confirmation_request_count
-                    .fetch_add(1, Ordering::SeqCst);
+                    .fetch_add(1, Ordering::SeqCst)
+                    + 1;
+                election_guard.record(ElectionTraceEvent::ConfirmReq { count });
             }
         }
     }
@@ -981,6 +1027,11 @@ impl ActiveElections {
                         sizeof_element: size_of::<ElectionStatus>(),
                     })],
                 ),
+                ContainerInfoComponent::Leaf(ContainerInfo {
+                    name: "trace_history".to_string(),
+                    count: self.trace_history.lock().unwrap().len(),
+                    sizeof_element: size_of::<ElectionTraceSnapshot>(),
+                }),
             ],
         )
     }
@@ -1319,6 +1370,9 @@ impl ActiveElectionsExt for Arc<ActiveElections> {
                     Box::new(|_| {}),
                     observer_rep_cb,
                 ));
+                if self.node_config.election_trace.enabled {
+                    election.enable_trace(self.node_config.election_trace.max_events);
+                }
                 guard.roots.insert(Entry {
                     root,
                     election: election.clone(),
@@ -3,7 +3,7 @@ use crate::{
     consensus::ActiveElectionsExt,
     stats::{DetailType, StatType, Stats},
 };
-use rsnano_core::{Amount, BlockEnum, QualifiedRoot};
+use rsnano_core::{utils::seconds_since_epoch, Amount, BlockEnum, QualifiedRoot};
 use std::{
     cmp::Ordering,
     collections::{BTreeMap, BTreeSet, HashMap},
@@ -20,6 +20,32 @@ pub struct PriorityBucketConfig {
 
     /// Maximum number of slots per bucket available for election activation if the active election count is below the configured limit. (node.active_elections.size)
     pub max_elections: usize,
+
+    /// Custom bucket boundaries with optional per-bucket limits. The default layout is used if empty.
+    pub buckets: Vec<PriorityBucketLayout>,
+}
+
+impl PriorityBucketConfig {
+    /// The configured buckets ordered by their minimum balance, or the default layout
+    pub fn layout(&self) -> Vec<PriorityBucketLayout> {
+        if self.buckets.is_empty() {
+            return default_layout();
+        }
+        let mut layout = self.buckets.clone();
+        layout.sort_by_key(|b| b.minimum_balance);
+        layout.dedup_by_key(|b| b.minimum_balance);
+        layout
+    }
+
+    /// The limits of a single bucket, with the global values as fallback
+    pub fn bucket_config(&self, layout: &PriorityBucketLayout) -> PriorityBucketConfig {
+        PriorityBucketConfig {
+            max_blocks: layout.max_blocks.unwrap_or(self.max_blocks),
+            reserved_elections: layout.reserved_elections.unwrap_or(self.reserved_elections),
+            max_elections: layout.max_elections.unwrap_or(self.max_elections),
+            buckets: Vec::new(),
+        }
+    }
 }
 
 impl Default for PriorityBucketConfig {
@@ -28,10 +54,73 @@ impl Default for PriorityBucketConfig {
             max_blocks: 1024 * 8,
             reserved_elections: 100,
             max_elections: 150,
+            buckets: Vec::new(),
+        }
+    }
+}
+
+/// A bucket boundary. The first bucket also takes the blocks below its minimum balance.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PriorityBucketLayout {
+    pub minimum_balance: Amount,
+    pub max_blocks: Option<usize>,
+    pub reserved_elections: Option<usize>,
+    pub max_elections: Option<usize>,
+}
+
+impl PriorityBucketLayout {
+    pub fn new(minimum_balance: Amount) -> Self {
+        Self {
+            minimum_balance,
+            max_blocks: None,
+            reserved_elections: None,
+            max_elections: None,
         }
     }
 }
 
+/// The bucket layout of the live network
+fn default_layout() -> Vec<PriorityBucketLayout> {
+    let mut layout = Vec::new();
+    let mut build_region = |begin: u128, end: u128, count: usize| {
+        let width = (end - begin) / (count as u128);
+        for i in 0..count {
+            let minimum_balance = begin + (i as u128 * width);
+            layout.push(PriorityBucketLayout::new(minimum_balance.into()))
+        }
+    };
+
+    build_region(0, 1 << 79, 1);
+    build_region(1 << 79, 1 << 88, 1);
+    build_region(1 << 88, 1 << 92, 2);
+    build_region(1 << 92, 1 << 96, 4);
+    build_region(1 << 96, 1 << 100, 8);
+    build_region(1 << 100, 1 << 104, 16);
+    build_region(1 << 104, 1 << 108, 16);
+    build_region(1 << 108, 1 << 112, 8);
+    build_region(1 << 112, 1 << 116, 4);
+    build_region(1 << 116, 1 << 120, 2);
+    build_region(1 << 120, 1 << 127, 1);
+
+    layout
+}
+
+/// A snapshot of the state of a bucket
+#[derive(Clone, Debug, PartialEq)]
+pub struct BucketInfo {
+    pub minimum_balance: Amount,
+    pub max_blocks: usize,
+    pub reserved_elections: usize,
+    pub max_elections: usize,
+    /// Number of queued blocks
+    pub blocks: usize,
+    /// Time when the longest waiting block was queued, in seconds since epoch
+    pub oldest_block_time: Option<u64>,
+    pub elections: usize,
+    /// Number of elections which were cancelled to make room for blocks with higher priority
+    pub cancellations: u64,
+}
+
 type Priority = u64;
 
 /// A struct which holds an ordered set of blocks to be scheduled, ordered by their block arrival time
@@ -59,6 +148,7 @@ impl Bucket {
             data: Mutex::new(BucketData {
                 queue: BTreeSet::new(),
                 elections: OrderedElections::default(),
+                cancellations: 0,
             }),
         }
     }
@@ -111,7 +201,7 @@ impl Bucket {
     }
 
     pub fn update(&self) {
-        let guard = self.data.lock().unwrap();
+        let mut guard = self.data.lock().unwrap();
         if self.election_overfill(&guard) {
             guard.cancel_lowest_election();
             drop(guard);
@@ -123,7 +213,11 @@ impl Bucket {
     pub fn push(&self, time: u64, block: Arc<BlockEnum>) -> bool {
         let hash = block.hash();
         let mut guard = self.data.lock().unwrap();
-        let inserted = guard.queue.insert(BlockEntry { time, block });
+        let inserted = guard.queue.insert(BlockEntry {
+            time,
+            block,
+            queued_at: seconds_since_epoch(),
+        });
         if guard.queue.len() > self.config.max_blocks {
             if let Some(removed) = guard.queue.pop_last() {
                 inserted && !(removed.time == time && removed.block.hash() == hash)
@@ -147,6 +241,21 @@ impl Bucket {
         let guard = self.data.lock().unwrap();
         guard.queue.iter().map(|i| i.block.clone()).collect()
     }
+
+    pub fn info(&self) -> BucketInfo {
+        let guard = self.data.lock().unwrap();
+        BucketInfo {
+            minimum_balance: self.minimum_balance,
+            max_blocks: self.config.max_blocks,
+            reserved_elections: self.config.reserved_elections,
+            max_elections: self.config.max_elections,
+            blocks: guard.queue.len(),
+            // The queue is ordered by priority, so the longest waiting block can be anywhere
+            oldest_block_time: guard.queue.iter().map(|i| i.queued_at).min(),
+            elections: guard.elections.len(),
+            cancellations: guard.cancellations,
+        }
+    }
 }
 
 pub(crate) trait BucketExt {
@@ -203,19 +312,24 @@ impl BucketExt for Arc<Bucket> {
 struct BucketData {
     queue: BTreeSet<BlockEntry>,
     elections: OrderedElections,
+    cancellations: u64,
 }
 
 impl BucketData {
-    fn cancel_lowest_election(&self) {
+    fn cancel_lowest_election(&mut self) {
         if let Some(entry) = self.elections.entry_with_lowest_priority() {
             entry.election.cancel();
+            self.cancellations += 1;
         }
     }
 }
 
 struct BlockEntry {
+    /// The priority of the block, which is the time the account was last modified
     time: u64,
     block: Arc<BlockEnum>,
+    /// Seconds since epoch when the block was queued
+    queued_at: u64,
 }
 
 impl Ord for BlockEntry {
@@ -0,0 +1,136 @@
+use super::ElectionStatus;
+use rsnano_core::{
+    utils::{ContainerInfo, ContainerInfoComponent},
+    BlockHash,
+};
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+use tokio::sync::oneshot;
+
+/// How the wait for the confirmation of a block ended
+#[derive(Clone, Debug)]
+pub enum ConfirmationOutcome {
+    Confirmed(ElectionStatus),
+    /// The block was replaced by a fork which won the election
+    Fork,
+    /// The block was rolled back together with one of its dependencies
+    RolledBack,
+}
+
+pub type ConfirmationReceiver = oneshot::Receiver<ConfirmationOutcome>;
+
+/// Lets callers wait until a block is cemented or rolled back,
+/// without having to poll the ledger
+#[derive(Default)]
+pub struct ConfirmationWaiters {
+    waiters: Mutex<HashMap<BlockHash, Vec<oneshot::Sender<ConfirmationOutcome>>>>,
+}
+
+impl ConfirmationWaiters {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The returned receiver resolves as soon as the block is cemented or rolled back.
+    /// Register the waiter before the block gets processed, otherwise the outcome may be missed.
+    pub fn register(&self, hash: BlockHash) -> ConfirmationReceiver {
+        let (tx, rx) = oneshot::channel();
+        self.waiters
+            .lock()
+            .unwrap()
+            .entry(hash)
+            .or_default()
+            .push(tx);
+        rx
+    }
+
+    /// Returns None if the timeout expired before the outcome was known.
+    /// The waiter stays registered until its receiver is dropped and it gets cancelled
+    pub async fn wait(
+        &self,
+        receiver: &mut ConfirmationReceiver,
+        timeout: Duration,
+    ) -> Option<ConfirmationOutcome> {
+        match tokio::time::timeout(timeout, receiver).await {
+            Ok(Ok(outcome)) => Some(outcome),
+            _ => None,
+        }
+    }
+
+    /// Removes the waiters of the block whose receivers were dropped
+    pub fn cancel(&self, hash: &BlockHash) {
+        let mut waiters = self.waiters.lock().unwrap();
+        if let Some(senders) = waiters.get_mut(hash) {
+            senders.retain(|s| !s.is_closed());
+            if senders.is_empty() {
+                waiters.remove(hash);
+            }
+        }
+    }
+
+    pub fn notify(&self, hash: &BlockHash, outcome: ConfirmationOutcome) {
+        let senders = self.waiters.lock().unwrap().remove(hash);
+        for sender in senders.into_iter().flatten() {
+            let _ = sender.send(outcome.clone());
+        }
+    }
+
+    /// Number of blocks which are waited for
+    pub fn len(&self) -> usize {
+        self.waiters.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn collect_container_info(&self, name: impl Into<String>) -> ContainerInfoComponent {
+        ContainerInfoComponent::Composite(
+            name.into(),
+            vec![ContainerInfoComponent::Leaf(ContainerInfo {
+                name: "waiters".to_string(),
+                count: self.len(),
+                sizeof_element: std::mem::size_of::<BlockHash>()
+                    + std::mem::size_of::<oneshot::Sender<ConfirmationOutcome>>(),
+            })],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notify_all_waiters_of_block() {
+        let waiters = ConfirmationWaiters::new();
+        let mut rx1 = waiters.register(BlockHash::from(1));
+        let mut rx2 = waiters.register(BlockHash::from(1));
+        let mut rx3 = waiters.register(BlockHash::from(2));
+
+        waiters.notify(&BlockHash::from(1), ConfirmationOutcome::RolledBack);
+
+        assert!(matches!(
+            rx1.try_recv(),
+            Ok(ConfirmationOutcome::RolledBack)
+        ));
+        assert!(matches!(
+            rx2.try_recv(),
+            Ok(ConfirmationOutcome::RolledBack)
+        ));
+        assert!(rx3.try_recv().is_err());
+        assert_eq!(waiters.len(), 1);
+    }
+
+    #[test]
+    fn cancel_removes_dropped_waiters() {
+        let waiters = ConfirmationWaiters::new();
+        let rx1 = waiters.register(BlockHash::from(1));
+        let _rx2 = waiters.register(BlockHash::from(2));
+
+        drop(rx1);
+        waiters.cancel(&BlockHash::from(1));
+        waiters.cancel(&BlockHash::from(2));
+
+        assert_eq!(waiters.len(), 1);
+    }
+}
@@ -1,4 +1,4 @@
-use super::ElectionStatus;
+use super::{ElectionStatus, ElectionTrace, ElectionTraceEvent, ElectionTraceSnapshot};
 use crate::{
     stats::{DetailType, StatType},
     utils::HardenedConstants,
@@ -7,6 +7,7 @@ use rsnano_core::{Amount, BlockEnum, BlockHash, PublicKey, QualifiedRoot, Root};
 use std::{
     collections::HashMap,
     fmt::Debug,
+    str::FromStr,
     sync::{
         atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering},
         Arc, Mutex, RwLock,
@@ -67,6 +68,7 @@ impl Election {
             final_weight: Amount::zero(),
             last_vote: None,
             last_block_hash: BlockHash::zero(),
+            trace: None,
         };
 
         Self {
@@ -150,6 +152,21 @@ impl Election {
         self.mutex.lock().unwrap().last_votes.len()
     }
 
+    /// Starts recording the timeline of this election
+    pub fn enable_trace(&self, max_events: usize) {
+        let mut guard = self.mutex.lock().unwrap();
+        let mut trace = ElectionTrace::new(max_events);
+        for hash in guard.last_blocks.keys() {
+            trace.record(ElectionTraceEvent::BlockAdded { hash: *hash });
+        }
+        guard.trace = Some(trace);
+    }
+
+    pub fn trace_snapshot(&self) -> Option<ElectionTraceSnapshot> {
+        let guard = self.mutex.lock().unwrap();
+        guard.trace_snapshot(self)
+    }
+
     pub fn winner_hash(&self) -> Option<BlockHash> {
         self.mutex
             .lock()
@@ -183,6 +200,8 @@ pub struct ElectionData {
     /** The last time vote for this election was generated */
     pub last_vote: Option<Instant>,
     pub last_block_hash: BlockHash,
+    /// Only present if election tracing is enabled
+    pub trace: Option<ElectionTrace>,
 }
 
 impl ElectionData {
@@ -211,6 +230,7 @@ impl ElectionData {
             if self.state == expected {
                 self.state = desired;
                 self.state_start = Instant::now();
+                self.record(ElectionTraceEvent::state_change(expected, desired));
                 return Ok(());
             }
         }
@@ -218,6 +238,23 @@ impl ElectionData {
         Err(())
     }
 
+    pub fn record(&mut self, event: ElectionTraceEvent) {
+        if let Some(trace) = &mut self.trace {
+            trace.record(event);
+        }
+    }
+
+    pub fn trace_snapshot(&self, election: &Election) -> Option<ElectionTraceSnapshot> {
+        self.trace.as_ref().map(|trace| {
+            trace.snapshot(
+                election.qualified_root.clone(),
+                election.behavior,
+                self.state,
+                self.status.winner.as_ref().map(|w| w.hash()),
+            )
+        })
+    }
+
     fn valid_change(expected: ElectionState, desired: ElectionState) -> bool {
         match expected {
             ElectionState::Passive => matches!(
@@ -338,6 +375,20 @@ pub enum ElectionBehavior {
     Optimistic,
 }
 
+impl FromStr for ElectionBehavior {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "manual" => Ok(ElectionBehavior::Manual),
+            "priority" => Ok(ElectionBehavior::Priority),
+            "hinted" => Ok(ElectionBehavior::Hinted),
+            "optimistic" => Ok(ElectionBehavior::Optimistic),
+            _ => Err("Invalid election behavior"),
+        }
+    }
+}
+
 impl From<ElectionBehavior> for DetailType {
     fn from(value: ElectionBehavior) -> Self {
         match value {
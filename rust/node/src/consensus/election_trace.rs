@@ -0,0 +1,314 @@
+use super::{ElectionBehavior, ElectionState};
+use crate::stats::DetailType;
+use rsnano_core::{Account, Amount, BlockHash, QualifiedRoot, VoteSource};
+use rsnano_network::ChannelId;
+use serde::{Serialize, Serializer};
+use std::{
+    collections::{HashMap, VecDeque},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ElectionTraceConfig {
+    /// Record the timeline of every election
+    pub enabled: bool,
+    /// Maximum number of events per election. The oldest events are dropped first
+    pub max_events: usize,
+    /// Number of traces of finished elections which can still be queried
+    pub history_size: usize,
+    /// Directory into which the traces of expired elections are written as JSON
+    pub dump_path: Option<PathBuf>,
+}
+
+impl Default for ElectionTraceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_events: 1024,
+            history_size: 256,
+            dump_path: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ElectionTraceEvent {
+    StateChange {
+        from: &'static str,
+        to: &'static str,
+    },
+    BlockAdded {
+        hash: BlockHash,
+    },
+    Vote {
+        representative: Account,
+        weight: Amount,
+        hash: BlockHash,
+        #[serde(rename = "final")]
+        is_final: bool,
+        timestamp: u64,
+        source: &'static str,
+        #[serde(serialize_with = "serialize_channel")]
+        channel: Option<ChannelId>,
+    },
+    Tally {
+        tally: Vec<(BlockHash, Amount)>,
+        final_tally: Amount,
+    },
+    ConfirmReq {
+        count: u32,
+    },
+}
+
+fn serialize_channel<S: Serializer>(
+    channel: &Option<ChannelId>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    channel.map(|c| c.as_usize()).serialize(serializer)
+}
+
+impl ElectionTraceEvent {
+    pub fn state_change(from: ElectionState, to: ElectionState) -> Self {
+        Self::StateChange {
+            from: DetailType::from(from).as_str(),
+            to: DetailType::from(to).as_str(),
+        }
+    }
+
+    pub fn vote(
+        representative: Account,
+        weight: Amount,
+        hash: BlockHash,
+        timestamp: u64,
+        source: VoteSource,
+        channel: Option<ChannelId>,
+    ) -> Self {
+        Self::Vote {
+            representative,
+            weight,
+            hash,
+            is_final: timestamp == u64::MAX,
+            timestamp,
+            source: DetailType::from(source).as_str(),
+            channel,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ElectionTraceEntry {
+    /// Milliseconds since the start of the trace
+    pub time: u64,
+    #[serde(flatten)]
+    pub event: ElectionTraceEvent,
+}
+
+/// Bounded timeline of everything that happened in a single election
+pub struct ElectionTrace {
+    start: Instant,
+    max_events: usize,
+    dropped: usize,
+    events: VecDeque<ElectionTraceEntry>,
+}
+
+impl ElectionTrace {
+    pub fn new(max_events: usize) -> Self {
+        Self {
+            start: Instant::now(),
+            max_events,
+            dropped: 0,
+            events: VecDeque::new(),
+        }
+    }
+
+    pub fn record(&mut self, event: ElectionTraceEvent) {
+        self.record_at(self.start.elapsed(), event);
+    }
+
+    fn record_at(&mut self, elapsed: Duration, event: ElectionTraceEvent) {
+        if self.max_events == 0 {
+            self.dropped += 1;
+            return;
+        }
+        if self.events.len() >= self.max_events {
+            self.events.pop_front();
+            self.dropped += 1;
+        }
+        self.events.push_back(ElectionTraceEntry {
+            time: elapsed.as_millis() as u64,
+            event,
+        });
+    }
+
+    pub fn snapshot(
+        &self,
+        root: QualifiedRoot,
+        behavior: ElectionBehavior,
+        state: ElectionState,
+        winner: Option<BlockHash>,
+    ) -> ElectionTraceSnapshot {
+        ElectionTraceSnapshot {
+            root,
+            behavior: DetailType::from(behavior).as_str(),
+            state: DetailType::from(state).as_str(),
+            winner,
+            duration: self.start.elapsed().as_millis() as u64,
+            dropped_events: self.dropped,
+            events: self.events.iter().cloned().collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ElectionTraceSnapshot {
+    pub root: QualifiedRoot,
+    pub behavior: &'static str,
+    pub state: &'static str,
+    pub winner: Option<BlockHash>,
+    /// Milliseconds since the start of the trace
+    pub duration: u64,
+    pub dropped_events: usize,
+    pub events: Vec<ElectionTraceEntry>,
+}
+
+impl ElectionTraceSnapshot {
+    /// Writes the trace as JSON into `dir` and returns the path of the created file
+    pub fn dump(&self, dir: &Path) -> anyhow::Result<PathBuf> {
+        std::fs::create_dir_all(dir)?;
+        let file_name = format!("election_{}_{}.json", self.root.root, self.root.previous);
+        let path = dir.join(file_name);
+        std::fs::write(&path, serde_json::to_vec_pretty(self)?)?;
+        Ok(path)
+    }
+}
+
+/// Traces of finished elections, oldest are dropped first
+pub struct ElectionTraceHistory {
+    max_size: usize,
+    traces: HashMap<QualifiedRoot, ElectionTraceSnapshot>,
+    sequence: VecDeque<QualifiedRoot>,
+}
+
+impl ElectionTraceHistory {
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            max_size,
+            traces: HashMap::new(),
+            sequence: VecDeque::new(),
+        }
+    }
+
+    pub fn insert(&mut self, trace: ElectionTraceSnapshot) {
+        if self.max_size == 0 {
+            return;
+        }
+        let root = trace.root.clone();
+        if self.traces.insert(root.clone(), trace).is_some() {
+            self.sequence.retain(|r| *r != root);
+        }
+        self.sequence.push_back(root);
+        while self.sequence.len() > self.max_size {
+            if let Some(oldest) = self.sequence.pop_front() {
+                self.traces.remove(&oldest);
+            }
+        }
+    }
+
+    pub fn get(&self, root: &QualifiedRoot) -> Option<&ElectionTraceSnapshot> {
+        self.traces.get(root)
+    }
+
+    pub fn len(&self) -> usize {
+        self.traces.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.traces.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drop_oldest_events() {
+        let mut trace = ElectionTrace::new(2);
+        trace.record(ElectionTraceEvent::ConfirmReq { count: 1 });
+        trace.record(ElectionTraceEvent::ConfirmReq { count: 2 });
+        trace.record(ElectionTraceEvent::ConfirmReq { count: 3 });
+
+        let snapshot = trace.snapshot(
+            QualifiedRoot::default(),
+            ElectionBehavior::Priority,
+            ElectionState::Active,
+            None,
+        );
+        assert_eq!(snapshot.dropped_events, 1);
+        assert_eq!(
+            snapshot
+                .events
+                .iter()
+                .map(|e| e.event.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                ElectionTraceEvent::ConfirmReq { count: 2 },
+                ElectionTraceEvent::ConfirmReq { count: 3 }
+            ]
+        );
+    }
+
+    #[test]
+    fn serialize_events() {
+        let mut trace = ElectionTrace::new(10);
+        trace.record_at(
+            Duration::from_millis(5),
+            ElectionTraceEvent::vote(
+                Account::from(1),
+                Amount::raw(100),
+                BlockHash::from(2),
+                u64::MAX,
+                VoteSource::Live,
+                Some(ChannelId::from(7)),
+            ),
+        );
+        trace.record_at(
+            Duration::from_millis(6),
+            ElectionTraceEvent::state_change(ElectionState::Active, ElectionState::Confirmed),
+        );
+        let json = serde_json::to_value(&trace.events).unwrap();
+        assert_eq!(json[0]["type"], "vote");
+        assert_eq!(json[0]["time"], 5);
+        assert_eq!(json[0]["final"], true);
+        assert_eq!(json[0]["weight"], "100");
+        assert_eq!(json[0]["source"], "live");
+        assert_eq!(json[0]["channel"], 7);
+        assert_eq!(json[1]["type"], "state_change");
+        assert_eq!(json[1]["from"], "active");
+        assert_eq!(json[1]["to"], "confirmed");
+    }
+
+    #[test]
+    fn history_is_bounded() {
+        let mut history = ElectionTraceHistory::new(2);
+        for i in 1..=3 {
+            let root = QualifiedRoot::new(i.into(), BlockHash::zero());
+            let trace = ElectionTrace::new(10).snapshot(
+                root,
+                ElectionBehavior::Priority,
+                ElectionState::ExpiredConfirmed,
+                None,
+            );
+            history.insert(trace);
+        }
+        assert_eq!(history.len(), 2);
+        assert!(history
+            .get(&QualifiedRoot::new(1.into(), BlockHash::zero()))
+            .is_none());
+        assert!(history
+            .get(&QualifiedRoot::new(3.into(), BlockHash::zero()))
+            .is_some());
+    }
+}
@@ -47,10 +47,19 @@ impl ManualScheduler {
     }
 
     pub fn push(&self, block: Arc<BlockEnum>, previous_balance: Option<Amount>) {
+        self.push_with_behavior(block, previous_balance, ElectionBehavior::Manual);
+    }
+
+    /// Starts an election which is treated like one that was started by the given scheduler,
+    /// for example to get the shorter lifetime of hinted elections
+    pub fn push_with_behavior(
+        &self,
+        block: Arc<BlockEnum>,
+        previous_balance: Option<Amount>,
+        behavior: ElectionBehavior,
+    ) {
         let mut guard = self.mutex.lock().unwrap();
-        guard
-            .queue
-            .push_back((block, previous_balance, ElectionBehavior::Manual));
+        guard.queue.push_back((block, previous_balance, behavior));
         self.notify();
     }
 
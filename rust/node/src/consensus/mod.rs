@@ -2,9 +2,11 @@ mod active_elections;
 mod bootstrap_weights;
 mod bucket;
 mod confirmation_solicitor;
+mod confirmation_waiters;
 mod election;
 pub(crate) mod election_schedulers;
 mod election_status;
+mod election_trace;
 mod hinted_scheduler;
 mod manual_scheduler;
 mod optimistic_scheduler;
@@ -20,13 +22,16 @@ mod vote_generation;
 mod vote_processor;
 mod vote_processor_queue;
 mod vote_router;
+mod vote_snapshot;
 
 pub use active_elections::*;
 pub(crate) use bootstrap_weights::*;
 pub use bucket::*;
 pub use confirmation_solicitor::ConfirmationSolicitor;
+pub use confirmation_waiters::*;
 pub use election::*;
 pub use election_status::{ElectionStatus, ElectionStatusType};
+pub use election_trace::*;
 pub use hinted_scheduler::*;
 pub use manual_scheduler::*;
 pub use optimistic_scheduler::*;
@@ -42,3 +47,4 @@ pub use vote_generation::*;
 pub use vote_processor::*;
 pub use vote_processor_queue::*;
 pub use vote_router::*;
+pub use vote_snapshot::*;
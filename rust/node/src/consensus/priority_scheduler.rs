@@ -1,4 +1,4 @@
-use super::{ActiveElections, Bucket, BucketExt, PriorityBucketConfig};
+use super::{ActiveElections, Bucket, BucketExt, BucketInfo, PriorityBucketConfig};
 use crate::stats::{DetailType, StatType, Stats};
 use rsnano_core::{
     utils::{ContainerInfo, ContainerInfoComponent},
@@ -29,33 +29,18 @@ fn create_buckets(
     active: Arc<ActiveElections>,
     stats: Arc<Stats>,
 ) -> Vec<Arc<Bucket>> {
-    let mut buckets = Vec::new();
-    let mut build_region = |begin: u128, end: u128, count: usize| {
-        let width = (end - begin) / (count as u128);
-        for i in 0..count {
-            let minimum_balance = begin + (i as u128 * width);
-            buckets.push(Arc::new(Bucket::new(
-                minimum_balance.into(),
-                config.clone(),
+    config
+        .layout()
+        .iter()
+        .map(|layout| {
+            Arc::new(Bucket::new(
+                layout.minimum_balance,
+                config.bucket_config(layout),
                 active.clone(),
                 stats.clone(),
-            )))
-        }
-    };
-
-    build_region(0, 1 << 79, 1);
-    build_region(1 << 79, 1 << 88, 1);
-    build_region(1 << 88, 1 << 92, 2);
-    build_region(1 << 92, 1 << 96, 4);
-    build_region(1 << 96, 1 << 100, 8);
-    build_region(1 << 100, 1 << 104, 16);
-    build_region(1 << 104, 1 << 108, 16);
-    build_region(1 << 108, 1 << 112, 8);
-    build_region(1 << 112, 1 << 116, 4);
-    build_region(1 << 116, 1 << 120, 2);
-    build_region(1 << 120, 1 << 127, 1);
-
-    buckets
+            ))
+        })
+        .collect()
 }
 
 impl PriorityScheduler {
@@ -188,6 +173,11 @@ impl PriorityScheduler {
         self.len() == 0
     }
 
+    /// The state of all buckets, ordered by their minimum balance
+    pub fn bucket_infos(&self) -> Vec<BucketInfo> {
+        self.buckets.iter().map(|b| b.info()).collect()
+    }
+
     fn predicate(&self) -> bool {
         self.buckets.iter().any(|b| b.available())
     }
@@ -2,7 +2,7 @@ use crate::{
     block_processing::BlockProcessor,
     cementation::ConfirmingSet,
     config::NodeConfig,
-    consensus::{ElectionState, VoteInfo},
+    consensus::{ElectionState, ElectionTraceEvent, VoteInfo},
     representatives::OnlineReps,
     stats::{DetailType, StatType, Stats},
     utils::ThreadPool,
@@ -19,6 +19,7 @@ use rsnano_core::{
     Amount, BlockEnum, BlockHash, PublicKey, VoteCode, VoteSource,
 };
 use rsnano_ledger::Ledger;
+use rsnano_network::ChannelId;
 use std::{
     collections::{BTreeMap, HashMap},
     mem::size_of,
@@ -131,6 +132,14 @@ impl VoteApplier {
                 guard.final_weight = *final_weight;
             }
         }
+        if guard.trace.is_some() {
+            let tally = result
+                .iter()
+                .map(|(weight, block)| (block.hash(), weight.amount()))
+                .collect();
+            let final_tally = guard.final_weight;
+            guard.record(ElectionTraceEvent::Tally { tally, final_tally });
+        }
         result
     }
 
@@ -166,6 +175,14 @@ impl VoteApplier {
             .insert(hash, election);
     }
 
+    pub fn election_winner_details(&self, hash: &BlockHash) -> Option<Arc<Election>> {
+        self.election_winner_details
+            .lock()
+            .unwrap()
+            .get(hash)
+            .cloned()
+    }
+
     pub fn election_winner_details_len(&self) -> usize {
         self.election_winner_details.lock().unwrap().len()
     }
@@ -210,6 +227,7 @@ pub trait VoteApplierExt {
         timestamp: u64,
         block_hash: &BlockHash,
         vote_source: VoteSource,
+        channel_id: Option<ChannelId>,
     ) -> VoteCode;
     fn confirm_if_quorum(&self, election_lock: MutexGuard<ElectionData>, election: &Arc<Election>);
     fn confirm_once(&self, election_lock: MutexGuard<ElectionData>, election: &Arc<Election>);
@@ -224,6 +242,7 @@ impl VoteApplierExt for Arc<VoteApplier> {
         timestamp: u64,
         block_hash: &BlockHash,
         vote_source: VoteSource,
+        channel_id: Option<ChannelId>,
     ) -> VoteCode {
         let weight = self.ledger.weight(rep);
         if !self.network_params.network.is_dev_network()
@@ -258,6 +277,14 @@ impl VoteApplierExt for Arc<VoteApplier> {
         guard
             .last_votes
             .insert(*rep, VoteInfo::new(timestamp, *block_hash));
+        guard.record(ElectionTraceEvent::vote(
+            (*rep).into(),
+            weight,
+            *block_hash,
+            timestamp,
+            vote_source,
+            channel_id,
+        ));
 
         if vote_source != VoteSource::Cache {
             (election.live_vote_action)(*rep);
@@ -325,6 +352,12 @@ impl VoteApplierExt for Arc<VoteApplier> {
         let old_state = election_lock.state;
         let just_confirmed = old_state != ElectionState::Confirmed;
         election_lock.state = ElectionState::Confirmed;
+        if just_confirmed {
+            election_lock.record(ElectionTraceEvent::state_change(
+                old_state,
+                ElectionState::Confirmed,
+            ));
+        }
         if just_confirmed && !winners_guard.contains_key(&status.winner.as_ref().unwrap().hash()) {
             winners_guard.insert(status.winner.as_ref().unwrap().hash(), Arc::clone(election));
             drop(winners_guard);
@@ -22,6 +22,8 @@ pub struct VoteCacheConfig {
     pub max_size: usize,
     pub max_voters: usize,
     pub age_cutoff: Duration,
+    /// Write recent entries to disk on shutdown and load them again on startup
+    pub persist: bool,
 }
 
 impl Default for VoteCacheConfig {
@@ -30,6 +32,7 @@ impl Default for VoteCacheConfig {
             max_size: 1024 * 64,
             max_voters: 64,
             age_cutoff: Duration::from_secs(15 * 60),
+            persist: true,
         }
     }
 }
@@ -130,6 +133,30 @@ impl VoteCache {
         self.cache.clear()
     }
 
+    pub fn config(&self) -> &VoteCacheConfig {
+        &self.config
+    }
+
+    /// Returns all entries that are younger than `age_cutoff`, oldest first.
+    /// Each entry contains the time since its last vote and its votes
+    pub fn snapshot(&self) -> Vec<(BlockHash, Duration, Vec<Arc<Vote>>)> {
+        self.cache
+            .iter_sequential()
+            .map(|entry| (entry.hash, entry.last_vote.elapsed(), entry.votes()))
+            .filter(|(_, age, _)| *age < self.config.age_cutoff)
+            .collect()
+    }
+
+    /// Re-inserts an entry that was saved with `snapshot`
+    pub fn restore(&mut self, hash: &BlockHash, votes: &[(Arc<Vote>, Amount)], age: Duration) {
+        for (vote, rep_weight) in votes {
+            self.insert_impl(vote, hash, *rep_weight);
+        }
+        let last_vote = Instant::now().checked_sub(age).unwrap_or_else(Instant::now);
+        self.cache
+            .modify_by_hash(hash, |entry| entry.last_vote = last_vote);
+    }
+
     /// Returns blocks with highest observed tally, greater than `min_tally`
     /// The blocks are sorted in descending order by final tally, then by tally
     /// @param min_tally minimum tally threshold, entries below with their voting weight
@@ -418,6 +445,13 @@ impl CacheEntryCollection {
         self.by_hash.values()
     }
 
+    /// Iterates from the oldest to the newest entry
+    pub fn iter_sequential(&self) -> impl Iterator<Item = &CacheEntry> {
+        self.sequential
+            .values()
+            .map(|hash| self.by_hash.get(hash).unwrap())
+    }
+
     pub fn iter_by_tally_desc(&self) -> impl Iterator<Item = &CacheEntry> {
         self.by_tally
             .values()
@@ -550,6 +584,7 @@ mod tests {
             max_size: 3,
             max_voters: 80,
             age_cutoff: Duration::from_secs(5 * 60),
+            persist: false,
         }
     }
 
@@ -928,6 +963,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn snapshot_and_restore() {
+        let mut cache = create_vote_cache();
+        let hash1 = BlockHash::from(1);
+        let hash2 = BlockHash::from(2);
+        add_test_vote(&mut cache, &hash1, Amount::raw(7));
+        add_test_vote(&mut cache, &hash2, Amount::raw(9));
+
+        let snapshot = cache.snapshot();
+        assert_eq!(snapshot.len(), 2);
+        assert_eq!(snapshot[0].0, hash1);
+        assert_eq!(snapshot[1].0, hash2);
+
+        let mut restored = create_vote_cache();
+        for (hash, _, votes) in &snapshot {
+            let votes: Vec<_> = votes.iter().map(|v| (v.clone(), Amount::raw(5))).collect();
+            restored.restore(hash, &votes, Duration::ZERO);
+        }
+        assert_eq!(restored.size(), 2);
+        assert_eq!(restored.find(&hash1), cache.find(&hash1));
+        assert_eq!(restored.top(0)[0].tally, Amount::raw(5));
+    }
+
+    #[test]
+    fn snapshot_skips_old_entries() {
+        let mut cache = create_vote_cache();
+        add_test_vote(&mut cache, &BlockHash::from(1), Amount::raw(7));
+        MockClock::advance(test_config().age_cutoff);
+        assert!(cache.snapshot().is_empty());
+    }
+
     fn add_test_vote(cache: &mut VoteCache, hash: &BlockHash, rep_weight: Amount) {
         let vote = create_vote(&KeyPair::new(), &hash, 0);
         cache.insert(&vote, rep_weight, &HashMap::new());
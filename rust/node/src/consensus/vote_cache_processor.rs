@@ -161,7 +161,7 @@ impl VoteCacheLoop {
             let cached = self.vote_cache.lock().unwrap().find(&hash);
             for cached_vote in cached {
                 self.vote_router
-                    .vote_filter(&cached_vote, VoteSource::Cache, &hash);
+                    .vote_filter(&cached_vote, VoteSource::Cache, None, &hash);
             }
         }
     }
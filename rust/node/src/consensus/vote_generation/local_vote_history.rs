@@ -120,6 +120,16 @@ impl LocalVoteHistory {
         result
     }
 
+    /// All votes in the history, oldest first
+    pub fn all(&self) -> Vec<(Root, BlockHash, Arc<Vote>)> {
+        let data_lk = self.data.lock().unwrap();
+        data_lk
+            .history
+            .values()
+            .map(|entry| (entry.root, entry.hash, entry.vote.clone()))
+            .collect()
+    }
+
     pub fn exists(&self, root: &Root) -> bool {
         let data_lk = self.data.lock().unwrap();
         data_lk.history_by_root.contains_key(root)
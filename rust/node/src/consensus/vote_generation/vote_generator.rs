@@ -267,8 +267,7 @@ impl SharedState {
         F: Fn(Arc<Vote>),
     {
         debug_assert_eq!(hashes.len(), roots.len());
-        let mut votes = Vec::new();
-        self.wallets.foreach_representative(|keys| {
+        let votes = self.wallets.sign_representative_votes(|pub_key| {
             let timestamp = if self.is_final {
                 Vote::TIMESTAMP_MAX
             } else {
@@ -279,15 +278,10 @@ impl SharedState {
             } else {
                 0x9 /*8192ms*/
             };
-            votes.push(Arc::new(Vote::new(
-                keys,
-                timestamp,
-                duration,
-                hashes.clone(),
-            )));
+            Vote::new_unsigned(*pub_key, timestamp, duration, hashes.clone())
         });
 
-        for vote in votes {
+        for vote in votes.into_iter().map(Arc::new) {
             {
                 let mut spacing = self.spacing.lock().unwrap();
                 for i in 0..hashes.len() {
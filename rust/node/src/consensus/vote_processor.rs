@@ -116,7 +116,7 @@ impl VoteProcessor {
     ) -> VoteCode {
         let mut result = VoteCode::Invalid;
         if vote.validate().is_ok() {
-            let vote_results = self.vote_router.vote(vote, source);
+            let vote_results = self.vote_router.vote(vote, source, Some(channel_id));
 
             // Aggregate results for individual hashes
             let mut replay = false;
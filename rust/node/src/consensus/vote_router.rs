@@ -4,6 +4,7 @@ use rsnano_core::{
     utils::{ContainerInfo, ContainerInfoComponent},
     BlockHash, Vote, VoteCode, VoteSource,
 };
+use rsnano_network::ChannelId;
 use std::{
     collections::HashMap,
     mem::size_of,
@@ -108,6 +109,7 @@ impl VoteRouter {
         &self,
         vote: &Arc<Vote>,
         source: VoteSource,
+        channel_id: Option<ChannelId>,
         filter: &BlockHash,
     ) -> HashMap<BlockHash, VoteCode> {
         debug_assert!(vote.validate().is_ok());
@@ -149,6 +151,7 @@ impl VoteRouter {
                 vote.timestamp(),
                 &block_hash,
                 source,
+                channel_id,
             );
             results.insert(block_hash, vote_result);
         }
@@ -160,8 +163,13 @@ impl VoteRouter {
 
     /// Route vote to associated elections
     /// Distinguishes replay votes, cannot be determined if the block is not in any election
-    pub fn vote(&self, vote: &Arc<Vote>, source: VoteSource) -> HashMap<BlockHash, VoteCode> {
-        self.vote_filter(vote, source, &BlockHash::zero())
+    pub fn vote(
+        &self,
+        vote: &Arc<Vote>,
+        source: VoteSource,
+        channel_id: Option<ChannelId>,
+    ) -> HashMap<BlockHash, VoteCode> {
+        self.vote_filter(vote, source, channel_id, &BlockHash::zero())
     }
 
     pub fn active(&self, hash: &BlockHash) -> bool {
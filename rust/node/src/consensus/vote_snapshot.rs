@@ -0,0 +1,487 @@
+use super::{LocalVoteHistory, VoteCache};
+use anyhow::Context;
+use rsnano_core::{
+    utils::{BufferWriter, Deserialize, FullHash, MemoryStream, Serialize, Stream, StreamExt},
+    BlockHash, Root, Vote,
+};
+use rsnano_ledger::Ledger;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tracing::{debug, info, warn};
+
+/// Recent entries of the vote cache and of our local vote history.
+/// It is written to disk when the node stops and loaded again on startup,
+/// so that a restarting node does not have to solicit all votes again.
+#[derive(Default, Debug, PartialEq)]
+pub struct VoteSnapshot {
+    pub saved_at: u64,
+    pub cache_entries: Vec<CachedVotes>,
+    pub local_votes: Vec<LocalVote>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CachedVotes {
+    pub hash: BlockHash,
+    /// Time since the last vote for this hash when the snapshot was taken
+    pub age: Duration,
+    pub votes: Vec<Arc<Vote>>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct LocalVote {
+    pub root: Root,
+    pub hash: BlockHash,
+    pub vote: Arc<Vote>,
+}
+
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct RestoreResult {
+    pub cache_entries: usize,
+    pub local_votes: usize,
+    pub stale: usize,
+    pub invalid: usize,
+}
+
+impl VoteSnapshot {
+    const MAGIC: &'static [u8; 8] = b"RSNVOTES";
+    const VERSION: u8 = 1;
+    const FILE_NAME: &'static str = "vote_snapshot.dat";
+
+    pub fn file_path(data_path: &Path) -> PathBuf {
+        data_path.join(Self::FILE_NAME)
+    }
+
+    pub fn capture(vote_cache: &VoteCache, history: &LocalVoteHistory, now: SystemTime) -> Self {
+        let cache_entries = vote_cache
+            .snapshot()
+            .into_iter()
+            .map(|(hash, age, votes)| CachedVotes { hash, age, votes })
+            .collect();
+
+        let local_votes = history
+            .all()
+            .into_iter()
+            .map(|(root, hash, vote)| LocalVote { root, hash, vote })
+            .collect();
+
+        Self {
+            saved_at: seconds_since_epoch(now),
+            cache_entries,
+            local_votes,
+        }
+    }
+
+    /// Puts all entries that are younger than `age_cutoff` and carry a valid
+    /// signature back into the vote cache and the local vote history
+    pub fn restore(
+        &self,
+        vote_cache: &Mutex<VoteCache>,
+        history: &LocalVoteHistory,
+        ledger: &Ledger,
+        age_cutoff: Duration,
+        now: SystemTime,
+    ) -> RestoreResult {
+        let mut result = RestoreResult::default();
+        let downtime = Duration::from_secs(seconds_since_epoch(now).saturating_sub(self.saved_at));
+        let mut validated: HashMap<BlockHash, bool> = HashMap::new();
+        let mut is_valid = |vote: &Vote| {
+            *validated
+                .entry(vote.full_hash())
+                .or_insert_with(|| vote.validate().is_ok())
+        };
+
+        {
+            let mut cache = vote_cache.lock().unwrap();
+            for entry in &self.cache_entries {
+                let age = entry.age + downtime;
+                if age >= age_cutoff {
+                    result.stale += 1;
+                    continue;
+                }
+
+                let mut votes = Vec::with_capacity(entry.votes.len());
+                for vote in &entry.votes {
+                    if is_valid(vote) {
+                        votes.push((vote.clone(), ledger.weight(&vote.voting_account)));
+                    } else {
+                        result.invalid += 1;
+                    }
+                }
+
+                if !votes.is_empty() {
+                    cache.restore(&entry.hash, &votes, age);
+                    result.cache_entries += 1;
+                }
+            }
+        }
+
+        let now_ms = seconds_since_epoch(now) * 1000;
+        for local in &self.local_votes {
+            // Final votes don't carry a creation time, so only the downtime counts
+            let vote_age = if local.vote.is_final() {
+                downtime
+            } else {
+                Duration::from_millis(now_ms.saturating_sub(local.vote.timestamp()))
+            };
+            if vote_age >= age_cutoff {
+                result.stale += 1;
+                continue;
+            }
+            if !is_valid(&local.vote) {
+                result.invalid += 1;
+                continue;
+            }
+            history.add(&local.root, &local.hash, &local.vote);
+            result.local_votes += 1;
+        }
+
+        result
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        std::fs::write(path, self.serialize())
+            .with_context(|| format!("Could not write vote snapshot {:?}", path))
+    }
+
+    /// Loads the snapshot and removes the file, so that it is used only once
+    pub fn load(path: &Path) -> anyhow::Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let bytes = std::fs::read(path)
+            .with_context(|| format!("Could not read vote snapshot {:?}", path))?;
+        std::fs::remove_file(path)
+            .with_context(|| format!("Could not remove vote snapshot {:?}", path))?;
+        Self::deserialize(&bytes).map(Some)
+    }
+
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut stream = MemoryStream::new();
+        stream.write_bytes_safe(Self::MAGIC);
+        stream.write_u8_safe(Self::VERSION);
+        stream.write_bytes_safe(&self.saved_at.to_le_bytes());
+
+        // Votes usually cover several hashes, so each of them is written only once
+        let mut votes: Vec<Arc<Vote>> = Vec::new();
+        let mut vote_indices: HashMap<BlockHash, u32> = HashMap::new();
+        let mut index_of = |vote: &Arc<Vote>| -> u32 {
+            *vote_indices.entry(vote.full_hash()).or_insert_with(|| {
+                votes.push(vote.clone());
+                (votes.len() - 1) as u32
+            })
+        };
+
+        let cache_entries: Vec<_> = self
+            .cache_entries
+            .iter()
+            .map(|entry| {
+                let indices: Vec<u32> = entry.votes.iter().map(&mut index_of).collect();
+                (entry, indices)
+            })
+            .collect();
+        let local_votes: Vec<_> = self
+            .local_votes
+            .iter()
+            .map(|local| (local, index_of(&local.vote)))
+            .collect();
+
+        stream.write_bytes_safe(&(votes.len() as u32).to_le_bytes());
+        for vote in votes {
+            stream.write_u8_safe(vote.hashes.len() as u8);
+            vote.serialize(&mut stream);
+        }
+
+        stream.write_bytes_safe(&(cache_entries.len() as u32).to_le_bytes());
+        for (entry, indices) in cache_entries {
+            entry.hash.serialize(&mut stream);
+            stream.write_bytes_safe(&(entry.age.as_millis() as u64).to_le_bytes());
+            stream.write_bytes_safe(&(indices.len() as u16).to_le_bytes());
+            for index in indices {
+                stream.write_bytes_safe(&index.to_le_bytes());
+            }
+        }
+
+        stream.write_bytes_safe(&(local_votes.len() as u32).to_le_bytes());
+        for (local, index) in local_votes {
+            local.root.serialize(&mut stream);
+            local.hash.serialize(&mut stream);
+            stream.write_bytes_safe(&index.to_le_bytes());
+        }
+
+        stream.to_vec()
+    }
+
+    pub fn deserialize(bytes: &[u8]) -> anyhow::Result<Self> {
+        let mut stream = MemoryStream::new();
+        stream.write_bytes(bytes)?;
+
+        let mut magic = [0; 8];
+        stream.read_bytes(&mut magic, 8)?;
+        if &magic != Self::MAGIC {
+            bail!("not a vote snapshot");
+        }
+        let version = stream.read_u8()?;
+        if version != Self::VERSION {
+            bail!("unsupported vote snapshot version {}", version);
+        }
+        let saved_at = stream.read_u64_le()?;
+
+        let vote_count = read_u32_le(&mut stream)?;
+        let mut votes = Vec::new();
+        for _ in 0..vote_count {
+            let hash_count = stream.read_u8()? as usize;
+            let len = Vote::serialized_size(hash_count);
+            let mut buffer = vec![0; len];
+            stream.read_bytes(&mut buffer, len)?;
+            let mut vote_stream = MemoryStream::new();
+            vote_stream.write_bytes(&buffer)?;
+            let mut vote = Vote::null();
+            vote.deserialize(&mut vote_stream)?;
+            votes.push(Arc::new(vote));
+        }
+        let vote_at = |index: u32| {
+            votes
+                .get(index as usize)
+                .cloned()
+                .ok_or_else(|| anyhow!("invalid vote index {}", index))
+        };
+
+        let entry_count = read_u32_le(&mut stream)?;
+        let mut cache_entries = Vec::new();
+        for _ in 0..entry_count {
+            let hash = BlockHash::deserialize(&mut stream)?;
+            let age = Duration::from_millis(stream.read_u64_le()?);
+            let mut buffer = [0; 2];
+            stream.read_bytes(&mut buffer, 2)?;
+            let voter_count = u16::from_le_bytes(buffer);
+            let mut entry_votes = Vec::with_capacity(voter_count as usize);
+            for _ in 0..voter_count {
+                entry_votes.push(vote_at(read_u32_le(&mut stream)?)?);
+            }
+            cache_entries.push(CachedVotes {
+                hash,
+                age,
+                votes: entry_votes,
+            });
+        }
+
+        let local_count = read_u32_le(&mut stream)?;
+        let mut local_votes = Vec::new();
+        for _ in 0..local_count {
+            let root = Root::deserialize(&mut stream)?;
+            let hash = BlockHash::deserialize(&mut stream)?;
+            let vote = vote_at(read_u32_le(&mut stream)?)?;
+            local_votes.push(LocalVote { root, hash, vote });
+        }
+
+        Ok(Self {
+            saved_at,
+            cache_entries,
+            local_votes,
+        })
+    }
+}
+
+/// Writes the vote cache and the local vote history to the data path
+pub fn save_vote_snapshot(
+    data_path: &Path,
+    vote_cache: &Mutex<VoteCache>,
+    history: &LocalVoteHistory,
+) {
+    let snapshot = VoteSnapshot::capture(&vote_cache.lock().unwrap(), history, SystemTime::now());
+    let path = VoteSnapshot::file_path(data_path);
+    match snapshot.save(&path) {
+        Ok(()) => info!(
+            "Saved {} vote cache entries and {} local votes to {:?}",
+            snapshot.cache_entries.len(),
+            snapshot.local_votes.len(),
+            path
+        ),
+        Err(e) => warn!("Could not save vote snapshot: {:?}", e),
+    }
+}
+
+/// Loads the vote snapshot from the data path if there is one
+pub fn load_vote_snapshot(
+    data_path: &Path,
+    vote_cache: &Mutex<VoteCache>,
+    history: &LocalVoteHistory,
+    ledger: &Ledger,
+) {
+    let path = VoteSnapshot::file_path(data_path);
+    let snapshot = match VoteSnapshot::load(&path) {
+        Ok(Some(snapshot)) => snapshot,
+        Ok(None) => {
+            debug!("No vote snapshot found at {:?}", path);
+            return;
+        }
+        Err(e) => {
+            warn!("Could not load vote snapshot: {:?}", e);
+            return;
+        }
+    };
+
+    let age_cutoff = vote_cache.lock().unwrap().config().age_cutoff;
+    let result = snapshot.restore(vote_cache, history, ledger, age_cutoff, SystemTime::now());
+    info!(
+        "Restored {} vote cache entries and {} local votes ({} stale, {} invalid)",
+        result.cache_entries, result.local_votes, result.stale, result.invalid
+    );
+}
+
+fn read_u32_le(stream: &mut impl Stream) -> anyhow::Result<u32> {
+    let mut buffer = [0; 4];
+    stream.read_bytes(&mut buffer, 4)?;
+    Ok(u32::from_le_bytes(buffer))
+}
+
+fn seconds_since_epoch(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{consensus::VoteCacheConfig, stats::Stats};
+    use rsnano_core::KeyPair;
+
+    #[test]
+    fn serialize_empty() {
+        let snapshot = VoteSnapshot::default();
+        let bytes = snapshot.serialize();
+        assert_eq!(VoteSnapshot::deserialize(&bytes).unwrap(), snapshot);
+    }
+
+    #[test]
+    fn serialize_round_trip() {
+        let key = KeyPair::new();
+        let shared_vote = Arc::new(Vote::new(
+            &key,
+            123,
+            0,
+            vec![BlockHash::from(1), BlockHash::from(2)],
+        ));
+        let final_vote = Arc::new(Vote::new_final(&key, vec![BlockHash::from(3)]));
+        let snapshot = VoteSnapshot {
+            saved_at: 1_000,
+            cache_entries: vec![
+                CachedVotes {
+                    hash: BlockHash::from(1),
+                    age: Duration::from_millis(1500),
+                    votes: vec![shared_vote.clone()],
+                },
+                CachedVotes {
+                    hash: BlockHash::from(2),
+                    age: Duration::from_millis(500),
+                    votes: vec![shared_vote.clone(), final_vote.clone()],
+                },
+            ],
+            local_votes: vec![LocalVote {
+                root: Root::from(7),
+                hash: BlockHash::from(3),
+                vote: final_vote,
+            }],
+        };
+
+        let bytes = snapshot.serialize();
+
+        assert_eq!(VoteSnapshot::deserialize(&bytes).unwrap(), snapshot);
+    }
+
+    #[test]
+    fn reject_garbage() {
+        assert!(VoteSnapshot::deserialize(b"not a snapshot").is_err());
+    }
+
+    #[test]
+    fn restore_discards_stale_and_invalid_votes() {
+        let ledger = Ledger::new_null();
+        let cache = Mutex::new(VoteCache::new(
+            VoteCacheConfig::default(),
+            Arc::new(Stats::default()),
+        ));
+        let history = LocalVoteHistory::new(256);
+        let now = UNIX_EPOCH + Duration::from_secs(100_000);
+        let key = KeyPair::new();
+        let valid = Arc::new(Vote::new(&key, 0, 0, vec![BlockHash::from(1)]));
+        let mut tampered = Vote::new(&key, 0, 0, vec![BlockHash::from(2)]);
+        tampered.hashes[0] = BlockHash::from(3);
+
+        let snapshot = VoteSnapshot {
+            saved_at: 100_000 - 60,
+            cache_entries: vec![
+                CachedVotes {
+                    hash: BlockHash::from(1),
+                    age: Duration::from_secs(10),
+                    votes: vec![valid.clone()],
+                },
+                CachedVotes {
+                    hash: BlockHash::from(3),
+                    age: Duration::from_secs(10),
+                    votes: vec![Arc::new(tampered)],
+                },
+                CachedVotes {
+                    hash: BlockHash::from(4),
+                    age: Duration::from_secs(3600),
+                    votes: vec![valid],
+                },
+            ],
+            local_votes: Vec::new(),
+        };
+
+        let result = snapshot.restore(&cache, &history, &ledger, Duration::from_secs(15 * 60), now);
+
+        assert_eq!(
+            result,
+            RestoreResult {
+                cache_entries: 1,
+                local_votes: 0,
+                stale: 1,
+                invalid: 1,
+            }
+        );
+        let cache = cache.lock().unwrap();
+        assert_eq!(cache.size(), 1);
+        assert_eq!(cache.find(&BlockHash::from(1)).len(), 1);
+    }
+
+    #[test]
+    fn restore_local_final_votes() {
+        let ledger = Ledger::new_null();
+        let cache = Mutex::new(VoteCache::new(
+            VoteCacheConfig::default(),
+            Arc::new(Stats::default()),
+        ));
+        let history = LocalVoteHistory::new(256);
+        let key = KeyPair::new();
+        let root = Root::from(7);
+        let hash = BlockHash::from(3);
+        let vote = Arc::new(Vote::new_final(&key, vec![hash]));
+        let snapshot = VoteSnapshot {
+            saved_at: 1000,
+            cache_entries: Vec::new(),
+            local_votes: vec![LocalVote {
+                root,
+                hash,
+                vote: vote.clone(),
+            }],
+        };
+
+        let result = snapshot.restore(
+            &cache,
+            &history,
+            &ledger,
+            Duration::from_secs(60),
+            UNIX_EPOCH + Duration::from_secs(1010),
+        );
+
+        assert_eq!(result.local_votes, 1);
+        assert_eq!(history.votes(&root, &hash, true), vec![vote]);
+    }
+}
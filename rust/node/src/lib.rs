@@ -7,8 +7,10 @@ extern crate num_derive;
 extern crate anyhow;
 extern crate core;
 
+mod account_history_indexer;
 pub mod block_processing;
 pub mod bootstrap;
+pub mod cdc;
 pub mod cementation;
 pub mod config;
 pub mod consensus;
@@ -1,17 +1,20 @@
 use crate::{
+    account_history_indexer::AccountHistoryIndexer,
     block_processing::{
         BacklogPopulation, BlockProcessor, BlockProcessorCleanup, BlockSource,
-        LocalBlockBroadcaster, LocalBlockBroadcasterExt, UncheckedMap,
+        LocalBlockBroadcaster, LocalBlockBroadcasterExt, PersistentUncheckedEntries, UncheckedMap,
     },
     bootstrap::{
         BootstrapAscending, BootstrapAscendingExt, BootstrapInitiator, BootstrapInitiatorExt,
         BootstrapServer, BootstrapServerCleanup, OngoingBootstrap, OngoingBootstrapExt,
     },
+    cdc::{CdcLog, CdcSink},
     cementation::ConfirmingSet,
     config::{GlobalConfig, NodeConfig, NodeFlags},
     consensus::{
-        election_schedulers::ElectionSchedulers, get_bootstrap_weights, log_bootstrap_weights,
-        ActiveElections, ActiveElectionsExt, ElectionStatusType, LocalVoteHistory,
+        election_schedulers::ElectionSchedulers, get_bootstrap_weights, load_vote_snapshot,
+        log_bootstrap_weights, save_vote_snapshot, ActiveElections, ActiveElectionsExt,
+        ConfirmationOutcome, ConfirmationWaiters, ElectionStatusType, LocalVoteHistory,
         ProcessLiveDispatcher, ProcessLiveDispatcherExt, RecentlyConfirmedCache, RepTiers,
         RequestAggregator, RequestAggregatorCleanup, VoteApplier, VoteBroadcaster, VoteCache,
         VoteCacheProcessor, VoteGenerators, VoteProcessor, VoteProcessorExt, VoteProcessorQueue,
@@ -20,7 +23,9 @@ use crate::{
     monitor::Monitor,
     node_id_key_file::NodeIdKeyFile,
     pruning::{LedgerPruning, LedgerPruningExt},
-    representatives::{OnlineReps, OnlineRepsCleanup, RepCrawler, RepCrawlerExt},
+    representatives::{
+        OnlineReps, OnlineRepsCleanup, RepCrawler, RepCrawlerExt, RepRegistry, RepRegistryFlusher,
+    },
     stats::{
         adapters::{LedgerStats, NetworkStats},
         DetailType, Direction, StatType, Stats,
@@ -28,18 +33,19 @@ use crate::{
     transport::{
         InboundMessageQueue, InboundMessageQueueCleanup, KeepaliveFactory, LatestKeepalives,
         LatestKeepalivesCleanup, MessageProcessor, MessagePublisher, NanoResponseServerSpawner,
-        NetworkFilter, NetworkThreads, PeerCacheConnector, PeerCacheUpdater,
+        NetworkFilter, NetworkThreads, PeerCacheConnector, PeerCacheUpdater, PeerScorer,
         RealtimeMessageHandler, SynCookies,
     },
     utils::{
         LongRunningTransactionLogger, ThreadPool, ThreadPoolImpl, TimerThread, TxnTrackingConfig,
     },
-    wallets::{Wallets, WalletsExt},
+    wallets::{RepHealthChecker, RepHealthMonitor, Wallets, WalletsExt},
     websocket::{create_websocket_server, WebsocketListenerExt},
     work::DistributedWorkFactory,
     NetworkParams, NodeCallbacks, OnlineWeightSampler, TelementryConfig, TelementryExt, Telemetry,
     BUILD_INFO, VERSION_STRING,
 };
+use anyhow::Context;
 use rsnano_core::{
     utils::{as_nano_json, system_time_as_nanoseconds, ContainerInfoComponent, SerdePropertyTree},
     work::{WorkPool, WorkPoolImpl},
@@ -97,15 +103,21 @@ pub struct Node {
     pub bootstrap_server: Arc<BootstrapServer>,
     online_weight_sampler: Arc<OnlineWeightSampler>,
     pub online_reps: Arc<Mutex<OnlineReps>>,
+    pub rep_registry: Arc<RepRegistry>,
+    rep_registry_flusher: TimerThread<RepRegistryFlusher>,
     pub rep_tiers: Arc<RepTiers>,
     pub vote_processor_queue: Arc<VoteProcessorQueue>,
     pub history: Arc<LocalVoteHistory>,
     pub confirming_set: Arc<ConfirmingSet>,
+    pub cdc: Option<Arc<CdcSink>>,
     pub vote_cache: Arc<Mutex<VoteCache>>,
     pub block_processor: Arc<BlockProcessor>,
     pub wallets: Arc<Wallets>,
+    pub rep_health_monitor: Arc<RepHealthMonitor>,
+    rep_health_checker: TimerThread<RepHealthChecker>,
     pub vote_generators: Arc<VoteGenerators>,
     pub active: Arc<ActiveElections>,
+    pub confirmation_waiters: Arc<ConfirmationWaiters>,
     pub vote_router: Arc<VoteRouter>,
     pub vote_processor: Arc<VoteProcessor>,
     vote_cache_processor: Arc<VoteCacheProcessor>,
@@ -122,10 +134,12 @@ pub struct Node {
     message_processor: Mutex<MessageProcessor>,
     network_threads: Arc<Mutex<NetworkThreads>>,
     ledger_pruning: Arc<LedgerPruning>,
+    account_history_indexer: AccountHistoryIndexer,
     pub peer_connector: Arc<PeerConnector>,
     ongoing_bootstrap: Arc<OngoingBootstrap>,
     peer_cache_updater: TimerThread<PeerCacheUpdater>,
     peer_cache_connector: TimerThread<PeerCacheConnector>,
+    peer_scorer: TimerThread<PeerScorer>,
     pub inbound_message_queue: Arc<InboundMessageQueue>,
     monitor: TimerThread<Monitor>,
     stopped: AtomicBool,
@@ -143,6 +157,7 @@ pub(crate) struct NodeArgs {
     pub flags: NodeFlags,
     pub work: Arc<WorkPoolImpl>,
     pub callbacks: NodeCallbacks,
+    pub steady_clock: Option<Arc<SteadyClock>>,
 }
 
 impl NodeArgs {
@@ -157,6 +172,7 @@ impl NodeArgs {
             flags: Default::default(),
             callbacks: Default::default(),
             work: Arc::new(WorkPoolImpl::new_null(123)),
+            steady_clock: None,
         }
     }
 }
@@ -167,14 +183,18 @@ impl Node {
             callbacks,
             ..NodeArgs::create_test_instance()
         };
-        Self::new(args, true, NodeIdKeyFile::new_null())
+        Self::new(args, true, NodeIdKeyFile::new_null()).unwrap()
     }
 
-    pub(crate) fn new_with_args(args: NodeArgs) -> Self {
+    pub(crate) fn new_with_args(args: NodeArgs) -> anyhow::Result<Self> {
         Self::new(args, false, NodeIdKeyFile::default())
     }
 
-    fn new(args: NodeArgs, is_nulled: bool, mut node_id_key_file: NodeIdKeyFile) -> Self {
+    fn new(
+        args: NodeArgs,
+        is_nulled: bool,
+        mut node_id_key_file: NodeIdKeyFile,
+    ) -> anyhow::Result<Self> {
         let network_params = args.network_params;
         let config = args.config;
         let flags = args.flags;
@@ -182,7 +202,7 @@ impl Node {
         let work = args.work;
         // Time relative to the start of the node. This makes time exlicit and enables us to
         // write time relevant unit tests with ease.
-        let steady_clock = Arc::new(SteadyClock::default());
+        let steady_clock = args.steady_clock.unwrap_or_default();
 
         let network_label = network_params.network.get_current_network_as_string();
         let global_config = GlobalConfig {
@@ -310,11 +330,26 @@ impl Node {
             enable_ongoing_broadcasts: !flags.disable_providing_telemetry_metrics,
         };
 
-        let unchecked = Arc::new(UncheckedMap::new(
-            config.max_unchecked_blocks as usize,
-            stats.clone(),
-            flags.disable_block_processor_unchecked_deletion,
-        ));
+        let unchecked = if config.persistent_unchecked.enabled && !is_nulled {
+            let entries = PersistentUncheckedEntries::open(
+                application_path.join("unchecked.ldb"),
+                &config.persistent_unchecked,
+                config.unchecked_cutoff_time_s as u64,
+            )
+            .context("Could not open unchecked store")?;
+            UncheckedMap::new_persistent(
+                entries,
+                stats.clone(),
+                flags.disable_block_processor_unchecked_deletion,
+            )
+        } else {
+            UncheckedMap::new(
+                config.max_unchecked_blocks as usize,
+                stats.clone(),
+                flags.disable_block_processor_unchecked_deletion,
+            )
+        };
+        let unchecked = Arc::new(unchecked);
 
         let online_weight_sampler = Arc::new(OnlineWeightSampler::new(
             ledger.clone(),
@@ -331,6 +366,11 @@ impl Node {
         ));
         dead_channel_cleanup.add_step(OnlineRepsCleanup::new(online_reps.clone()));
 
+        let rep_registry = Arc::new(RepRegistry::new(
+            ledger.clone(),
+            config.representative_vote_weight_minimum,
+        ));
+
         let mut message_publisher = MessagePublisher::new(
             online_reps.clone(),
             network.clone(),
@@ -355,6 +395,13 @@ impl Node {
             steady_clock.clone(),
         ));
 
+        let rep_registry_w = Arc::downgrade(&rep_registry);
+        telemetry.on_telemetry_processed(Box::new(move |data, _| {
+            if let Some(registry) = rep_registry_w.upgrade() {
+                registry.telemetry_received(data, SystemTime::now());
+            }
+        }));
+
         let bootstrap_server = Arc::new(BootstrapServer::new(
             config.bootstrap_server.clone(),
             stats.clone(),
@@ -437,7 +484,7 @@ impl Node {
             online_reps.clone(),
             confirming_set.clone(),
             message_publisher.clone(),
-        );
+        )?;
         if !is_nulled {
             wallets.initialize().expect("Could not create wallet");
         }
@@ -765,9 +812,17 @@ impl Node {
                 }));
         }
 
+        let confirmation_waiters = Arc::new(ConfirmationWaiters::new());
+        let waiters = confirmation_waiters.clone();
+        active_elections.add_election_end_callback(Box::new(move |status, _, _, _, _, _| {
+            let hash = status.winner.as_ref().unwrap().hash();
+            waiters.notify(&hash, ConfirmationOutcome::Confirmed(status.clone()));
+        }));
+
         let block_processor_w = Arc::downgrade(&block_processor);
         let history_w = Arc::downgrade(&history);
         let active_w = Arc::downgrade(&active_elections);
+        let waiters = confirmation_waiters.clone();
         block_processor.set_blocks_rolled_back_callback(Box::new(
             move |rolled_back, initial_block| {
                 // Deleting from votes cache, stop active transaction
@@ -782,6 +837,12 @@ impl Node {
                 };
                 for i in rolled_back {
                     block_processor.notify_block_rolled_back(&i);
+                    let outcome = if i.hash() == initial_block.hash() {
+                        ConfirmationOutcome::Fork
+                    } else {
+                        ConfirmationOutcome::RolledBack
+                    };
+                    waiters.notify(&i.hash(), outcome);
 
                     history.erase(&i.root());
                     // Stop all rolled back active transactions except initial
@@ -865,6 +926,11 @@ impl Node {
 
         let rep_crawler_w = Arc::downgrade(&rep_crawler);
         let reps_w = Arc::downgrade(&online_reps);
+        let rep_registry_w = Arc::downgrade(&rep_registry);
+        let network_info_w = Arc::downgrade(&network_info);
+        let rep_weights_w = Arc::downgrade(&rep_weights);
+        let vote_router_w = Arc::downgrade(&vote_router);
+        let rep_weight_minimum = config.representative_vote_weight_minimum;
         let clock = steady_clock.clone();
         vote_processor.add_vote_processed_callback(Box::new(
             move |vote, channel_id, source, code| {
@@ -880,6 +946,35 @@ impl Node {
                     return;
                 }
 
+                // Only votes which were applied to an active election are recorded,
+                // so that replays and votes for unknown blocks don't count
+                if let (Some(registry), Some(network_info), Some(weights), Some(router)) = (
+                    rep_registry_w.upgrade(),
+                    network_info_w.upgrade(),
+                    rep_weights_w.upgrade(),
+                    vote_router_w.upgrade(),
+                ) {
+                    let weight = weights.weight(&vote.voting_account);
+                    let is_rep = !weight.is_zero() && weight >= rep_weight_minimum;
+                    if code == VoteCode::Vote && is_rep {
+                        let channel = network_info.read().unwrap().get(channel_id).cloned();
+                        // The vote timestamp is set by the remote node, so the latency
+                        // is measured from the start of the local election instead
+                        let latency = vote
+                            .hashes
+                            .iter()
+                            .filter_map(|hash| router.election(hash))
+                            .map(|election| election.election_start.elapsed())
+                            .min();
+                        registry.vote_observed(
+                            vote.voting_account,
+                            channel.as_deref(),
+                            latency,
+                            SystemTime::now(),
+                        );
+                    }
+                }
+
                 let active_in_rep_crawler = rep_crawler.process(vote.clone(), channel_id);
                 if active_in_rep_crawler {
                     // Representative is defined as online if replying to live votes or rep_crawler queries
@@ -916,6 +1011,27 @@ impl Node {
             }
         }
 
+        // The index stays enabled once it was enabled, until it is dropped with the CLI
+        if flags.enable_account_history_index {
+            ledger.enable_account_history_index();
+        }
+        if ledger.account_history_index_enabled() {
+            info!("Account history index is enabled");
+        }
+
+        let cdc = if config.cdc.enabled {
+            let log = CdcLog::open(application_path.join("cdc"), config.cdc.clone())
+                .context("Could not open CDC log")?;
+            let sink = Arc::new(
+                CdcSink::new(log, ledger.clone(), vote_applier.clone())
+                    .context("Could not recover CDC log")?,
+            );
+            confirming_set.set_cdc_sink(sink.clone());
+            Some(sink)
+        } else {
+            None
+        };
+
         if ledger.pruning_enabled() {
             if config.enable_voting && !flags.inactive_node {
                 let msg = "Incompatibility detected between config node.enable_voting and existing pruned blocks";
@@ -1060,6 +1176,8 @@ impl Node {
             workers.clone(),
         ));
 
+        let account_history_indexer = AccountHistoryIndexer::new(ledger.clone());
+
         let monitor = TimerThread::new(
             "Monitor",
             Monitor::new(
@@ -1070,10 +1188,34 @@ impl Node {
             ),
         );
 
-        Self {
+        let rep_health_monitor = Arc::new(RepHealthMonitor::new(
+            wallets.clone(),
+            ledger.clone(),
+            online_reps.clone(),
+            rep_tiers.clone(),
+            rep_registry.clone(),
+            stats.clone(),
+            websocket.clone(),
+        ));
+
+        Ok(Self {
             is_nulled,
             steady_clock,
+            rep_health_checker: TimerThread::new(
+                "Rep health",
+                RepHealthChecker::new(rep_health_monitor.clone()),
+            ),
+            rep_health_monitor,
             peer_cache_updater: TimerThread::new("Peer history", peer_cache_updater),
+            peer_scorer: TimerThread::new(
+                "Peer scoring",
+                PeerScorer::new(network_info.clone(), telemetry.clone(), stats.clone()),
+            ),
+            rep_registry_flusher: TimerThread::new(
+                "Rep registry",
+                RepRegistryFlusher::new(rep_registry.clone()),
+            ),
+            rep_registry,
             peer_cache_connector: TimerThread::new_run_immedately(
                 "Net reachout",
                 peer_cache_connector,
@@ -1108,11 +1250,13 @@ impl Node {
             vote_processor_queue,
             history,
             confirming_set,
+            cdc,
             vote_cache,
             block_processor,
             wallets,
             vote_generators,
             active: active_elections,
+            confirmation_waiters,
             vote_processor,
             vote_cache_processor,
             websocket,
@@ -1126,6 +1270,7 @@ impl Node {
             local_block_broadcaster,
             _process_live_dispatcher: process_live_dispatcher, // needs to stay alive
             ledger_pruning,
+            account_history_indexer,
             network_threads,
             message_processor,
             inbound_message_queue,
@@ -1134,7 +1279,7 @@ impl Node {
             publish_filter,
             stopped: AtomicBool::new(false),
             start_stop_listener: OutputListenerMt::new(),
-        }
+        })
     }
 
     pub fn collect_container_info(&self, name: impl Into<String>) -> ContainerInfoComponent {
@@ -1178,11 +1323,16 @@ impl Node {
                 self.vote_cache_processor
                     .collect_container_info("vote_cache_processor"),
                 self.rep_crawler.collect_container_info("rep_crawler"),
+                self.rep_registry.collect_container_info("rep_registry"),
+                self.rep_health_monitor
+                    .collect_container_info("rep_health_monitor"),
                 self.block_processor
                     .collect_container_info("block_processor"),
                 online_reps,
                 self.history.collect_container_info("history"),
                 self.confirming_set.collect_container_info("confirming_set"),
+                self.confirmation_waiters
+                    .collect_container_info("confirmation_waiters"),
                 self.request_aggregator
                     .collect_container_info("request_aggregator"),
                 self.election_schedulers
@@ -1236,6 +1386,7 @@ impl Node {
     pub fn process_local(&self, block: BlockEnum) -> Option<BlockStatus> {
         self.block_processor
             .add_blocking(Arc::new(block), BlockSource::Local)
+            .ok()
     }
 
     pub fn process(&self, mut block: BlockEnum) -> Result<(), BlockStatus> {
@@ -1376,6 +1527,14 @@ impl NodeExt for Arc<Node> {
         }
 
         self.long_inactivity_cleanup();
+        if self.config.vote_cache.persist {
+            load_vote_snapshot(
+                &self.data_path,
+                &self.vote_cache,
+                &self.history,
+                &self.ledger,
+            );
+        }
         self.network_threads.lock().unwrap().start();
         self.message_processor.lock().unwrap().start();
 
@@ -1387,6 +1546,8 @@ impl NodeExt for Arc<Node> {
             self.ledger_pruning.start();
         }
 
+        self.account_history_indexer.start();
+
         if !self.flags.disable_rep_crawler {
             self.rep_crawler.start();
         }
@@ -1453,6 +1614,10 @@ impl NodeExt for Arc<Node> {
             Duration::from_secs(15)
         };
         self.peer_cache_updater.start(peer_cache_update_interval);
+        self.peer_scorer.start(peer_cache_update_interval);
+        self.rep_registry_flusher.start(peer_cache_update_interval);
+        self.rep_health_checker
+            .start(peer_cache_update_interval * 4);
 
         if !self.network_params.network.merge_period.is_zero() {
             self.peer_cache_connector
@@ -1484,8 +1649,13 @@ impl NodeExt for Arc<Node> {
         self.vote_router.stop();
         self.peer_connector.stop();
         self.ledger_pruning.stop();
+        self.account_history_indexer.stop();
         self.peer_cache_connector.stop();
         self.peer_cache_updater.stop();
+        self.peer_scorer.stop();
+        self.rep_registry_flusher.stop();
+        self.rep_registry.flush();
+        self.rep_health_checker.stop();
         // Cancels ongoing work generation tasks, which may be blocking other threads
         // No tasks may wait for work generation in I/O threads, or termination signal capturing will be unable to call node::stop()
         self.distributed_work.stop();
@@ -1503,6 +1673,9 @@ impl NodeExt for Arc<Node> {
         self.election_schedulers.stop();
         self.active.stop();
         self.vote_generators.stop();
+        if self.config.vote_cache.persist {
+            save_vote_snapshot(&self.data_path, &self.vote_cache, &self.history);
+        }
         self.confirming_set.stop();
         self.telemetry.stop();
         if let Some(ws_listener) = &self.websocket {
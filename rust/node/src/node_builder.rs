@@ -12,6 +12,7 @@ use rsnano_core::{
 };
 use rsnano_messages::Message;
 use rsnano_network::ChannelId;
+use rsnano_nullable_clock::SteadyClock;
 use std::{path::PathBuf, sync::Arc, time::Duration};
 
 #[derive(Default)]
@@ -102,6 +103,7 @@ pub struct NodeBuilder {
     flags: Option<NodeFlags>,
     work: Option<Arc<WorkPoolImpl>>,
     callbacks: Option<NodeCallbacks>,
+    steady_clock: Option<Arc<SteadyClock>>,
 }
 
 impl NodeBuilder {
@@ -115,6 +117,7 @@ impl NodeBuilder {
             flags: None,
             work: None,
             callbacks: None,
+            steady_clock: None,
         }
     }
 
@@ -153,6 +156,12 @@ impl NodeBuilder {
         self
     }
 
+    /// Lets the node use the given clock instead of the system clock
+    pub fn steady_clock(mut self, clock: Arc<SteadyClock>) -> Self {
+        self.steady_clock = Some(clock);
+        self
+    }
+
     pub fn finish(self) -> anyhow::Result<Node> {
         let runtime = self
             .runtime
@@ -203,8 +212,9 @@ impl NodeBuilder {
             flags,
             work,
             callbacks,
+            steady_clock: self.steady_clock,
         };
 
-        Ok(Node::new_with_args(args))
+        Node::new_with_args(args)
     }
 }
@@ -1,7 +1,9 @@
 mod online_reps;
 mod online_weight_sampler;
 mod rep_crawler;
+mod rep_registry;
 
 pub use online_reps::*;
 pub use online_weight_sampler::OnlineWeightSampler;
 pub use rep_crawler::*;
+pub use rep_registry::*;
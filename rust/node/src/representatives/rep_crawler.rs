@@ -128,6 +128,8 @@ impl RepCrawler {
                 self.stats
                     .inc_dir(StatType::RepCrawler, DetailType::Response, Direction::In);
 
+                // Not recorded as round trip time of the channel, because it includes
+                // the vote generation delay of the peer
                 self.stats.sample(
                     Sample::RepResponseTime,
                     query.time.elapsed().as_millis() as i64,
@@ -164,7 +166,7 @@ impl RepCrawler {
         let mut guard = self.rep_crawler_impl.lock().unwrap();
 
         for channel in target_channels {
-            guard.track_rep_request(hash_root, channel.channel_id(), self.steady_clock.now());
+            guard.track_rep_request(hash_root, &channel, self.steady_clock.now());
             debug!(
                 "Sending query for block: {} to: {}",
                 hash_root.0,
@@ -200,10 +202,12 @@ impl RepCrawler {
     pub fn force_query(&self, hash: BlockHash, channel_id: ChannelId) {
         assert!(self.network_params.network.is_dev_network());
         let mut guard = self.rep_crawler_impl.lock().unwrap();
+        let timeout = guard.query_timeout;
         guard.queries.insert(QueryEntry {
             hash,
             channel_id,
             time: Instant::now(),
+            timeout,
             replies: 0,
         })
     }
@@ -485,6 +489,12 @@ struct RepCrawlerImpl {
 }
 
 impl RepCrawlerImpl {
+    /// A query is never aborted earlier than this
+    const MIN_QUERY_TIMEOUT: Duration = Duration::from_secs(1);
+    /// How many times the usual round trip time we wait for a vote.
+    /// This leaves room for the vote generation delay of the peer
+    const RTT_TIMEOUT_FACTOR: u32 = 8;
+
     fn query_predicate(&self, query_interval: Duration) -> bool {
         match &self.last_query {
             Some(last) => last.elapsed() >= query_interval,
@@ -545,16 +555,30 @@ impl RepCrawlerImpl {
         random_peers
     }
 
+    /// Peers with a known round trip time get a timeout derived from it.
+    /// The configured query timeout is the upper bound and the fallback for unknown peers
+    fn query_timeout_for(&self, channel: &ChannelInfo) -> Duration {
+        match channel.round_trip_times().percentile(90) {
+            Some(rtt) => (rtt * Self::RTT_TIMEOUT_FACTOR).clamp(
+                Self::MIN_QUERY_TIMEOUT.min(self.query_timeout),
+                self.query_timeout,
+            ),
+            None => self.query_timeout,
+        }
+    }
+
     fn track_rep_request(
         &mut self,
         hash_root: (BlockHash, Root),
-        channel_id: ChannelId,
+        channel: &ChannelInfo,
         now: Timestamp,
     ) {
+        let channel_id = channel.channel_id();
         self.queries.insert(QueryEntry {
             hash: hash_root.0,
             channel_id,
             time: Instant::now(),
+            timeout: self.query_timeout_for(channel),
             replies: 0,
         });
         // Find and update the timestamp on all reps available on the endpoint (a single host may have multiple reps)
@@ -567,7 +591,7 @@ impl RepCrawlerImpl {
     fn cleanup(&mut self) {
         // Evict queries that haven't been responded to in a while
         self.queries.retain(|query| {
-            if query.time.elapsed() < self.query_timeout {
+            if query.time.elapsed() < query.timeout {
                 return true; // Retain
             }
 
@@ -602,6 +626,8 @@ struct QueryEntry {
     hash: BlockHash,
     channel_id: ChannelId,
     time: Instant,
+    /// the query is evicted after this time
+    timeout: Duration,
     /// number of replies to the query
     replies: usize,
 }
@@ -721,3 +747,55 @@ impl RepCrawlerExt for Arc<RepCrawler> {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rep_crawler_impl(query_timeout: Duration) -> RepCrawlerImpl {
+        RepCrawlerImpl {
+            queries: OrderedQueries::new(),
+            online_reps: Arc::new(Mutex::new(OnlineReps::default())),
+            stats: Arc::new(Stats::default()),
+            query_timeout,
+            stopped: false,
+            last_query: None,
+            responses: BoundedVecDeque::new(1),
+            is_dev_network: true,
+        }
+    }
+
+    #[test]
+    fn query_timeout_without_round_trip_time() {
+        let crawler = rep_crawler_impl(Duration::from_secs(60));
+        let channel = ChannelInfo::new_test_instance();
+        assert_eq!(crawler.query_timeout_for(&channel), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn query_timeout_derived_from_round_trip_time() {
+        let crawler = rep_crawler_impl(Duration::from_secs(60));
+        let channel = ChannelInfo::new_test_instance();
+        channel.add_round_trip_time(Duration::from_millis(500));
+        assert_eq!(
+            crawler.query_timeout_for(&channel),
+            Duration::from_millis(500) * RepCrawlerImpl::RTT_TIMEOUT_FACTOR
+        );
+    }
+
+    #[test]
+    fn query_timeout_is_clamped() {
+        let crawler = rep_crawler_impl(Duration::from_secs(60));
+
+        let fast = ChannelInfo::new_test_instance();
+        fast.add_round_trip_time(Duration::from_millis(1));
+        assert_eq!(
+            crawler.query_timeout_for(&fast),
+            RepCrawlerImpl::MIN_QUERY_TIMEOUT
+        );
+
+        let slow = ChannelInfo::new_test_instance();
+        slow.add_round_trip_time(Duration::from_secs(30));
+        assert_eq!(crawler.query_timeout_for(&slow), Duration::from_secs(60));
+    }
+}
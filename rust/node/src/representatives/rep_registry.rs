@@ -0,0 +1,315 @@
+use crate::utils::{CancellationToken, Runnable};
+use rsnano_core::{
+    utils::{ContainerInfo, ContainerInfoComponent},
+    Amount, PublicKey, RepDetails, RepTelemetry,
+};
+use rsnano_ledger::Ledger;
+use rsnano_messages::TelemetryData;
+use rsnano_network::ChannelInfo;
+use std::{
+    collections::{HashMap, HashSet},
+    mem::size_of,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tracing::debug;
+
+/// Remembers what was observed about representatives, so that the
+/// history survives restarts. Changes are written to the store periodically
+pub struct RepRegistry {
+    ledger: Arc<Ledger>,
+    minimum_weight: Amount,
+    data: Mutex<RepRegistryData>,
+}
+
+#[derive(Default)]
+struct RepRegistryData {
+    reps: HashMap<PublicKey, RepDetails>,
+    dirty: HashSet<PublicKey>,
+}
+
+impl RepRegistry {
+    /// Representatives which didn't vote for this long are forgotten
+    pub const RETENTION: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+    /// Only representatives with at least `minimum_weight` are kept
+    pub fn new(ledger: Arc<Ledger>, minimum_weight: Amount) -> Self {
+        let reps: HashMap<_, _> = {
+            let tx = ledger.read_txn();
+            ledger.store.rep_details.iter(&tx).collect()
+        };
+        debug!("Loaded {} representatives from the registry", reps.len());
+        Self {
+            ledger,
+            minimum_weight,
+            data: Mutex::new(RepRegistryData {
+                reps,
+                dirty: HashSet::new(),
+            }),
+        }
+    }
+
+    /// `latency` is the time between the start of the local election and the arrival of the vote
+    pub fn vote_observed(
+        &self,
+        rep: PublicKey,
+        channel: Option<&ChannelInfo>,
+        latency: Option<Duration>,
+        now: SystemTime,
+    ) {
+        let latency = latency.map(|l| l.as_millis() as u64);
+        let node_id = channel.and_then(|c| c.node_id());
+        let endpoint = channel.map(|c| c.peering_addr().unwrap_or(c.peer_addr()));
+        let now = millis_since_epoch(now) / 1000;
+
+        let mut guard = self.data.lock().unwrap();
+        guard
+            .reps
+            .entry(rep)
+            .or_insert_with(|| RepDetails::new(now))
+            .observe_vote(now, node_id, endpoint, latency);
+        guard.dirty.insert(rep);
+    }
+
+    /// Attaches the telemetry to all representatives which currently vote through the sending node
+    pub fn telemetry_received(&self, data: &TelemetryData, now: SystemTime) {
+        let telemetry = RepTelemetry {
+            received: millis_since_epoch(now) / 1000,
+            node_id: data.node_id,
+            protocol_version: data.protocol_version,
+            major_version: data.major_version,
+            minor_version: data.minor_version,
+            patch_version: data.patch_version,
+            pre_release_version: data.pre_release_version,
+            maker: data.maker,
+            block_count: data.block_count,
+            cemented_count: data.cemented_count,
+            peer_count: data.peer_count,
+            uptime: data.uptime,
+        };
+
+        let mut guard = self.data.lock().unwrap();
+        let RepRegistryData { reps, dirty } = &mut *guard;
+        for (rep, details) in reps.iter_mut() {
+            if details.current_node_id() == Some(&data.node_id) {
+                details.telemetry = Some(telemetry.clone());
+                dirty.insert(*rep);
+            }
+        }
+    }
+
+    pub fn get(&self, rep: &PublicKey) -> Option<RepDetails> {
+        self.data.lock().unwrap().reps.get(rep).cloned()
+    }
+
+    pub fn all(&self) -> Vec<(PublicKey, RepDetails)> {
+        self.data
+            .lock()
+            .unwrap()
+            .reps
+            .iter()
+            .map(|(rep, details)| (*rep, details.clone()))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.lock().unwrap().reps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Forgets representatives which fell below the minimum weight or
+    /// which weren't seen within the retention period
+    pub fn evict(&self, now: SystemTime) {
+        let cutoff = millis_since_epoch(now - Self::RETENTION) / 1000;
+        let evicted: Vec<_> = {
+            let mut guard = self.data.lock().unwrap();
+            let evicted: Vec<_> = guard
+                .reps
+                .iter()
+                .filter(|(rep, details)| {
+                    details.last_seen < cutoff || self.ledger.weight(rep) < self.minimum_weight
+                })
+                .map(|(rep, _)| *rep)
+                .collect();
+            for rep in &evicted {
+                guard.reps.remove(rep);
+                guard.dirty.remove(rep);
+            }
+            evicted
+        };
+        if evicted.is_empty() {
+            return;
+        }
+
+        let mut tx = self.ledger.rw_txn();
+        for rep in &evicted {
+            self.ledger.store.rep_details.del(&mut tx, rep);
+        }
+        debug!(
+            "Evicted {} representatives from the registry",
+            evicted.len()
+        );
+    }
+
+    /// Writes all changed entries to the store
+    pub fn flush(&self) {
+        let changed: Vec<_> = {
+            let mut guard = self.data.lock().unwrap();
+            let dirty = std::mem::take(&mut guard.dirty);
+            dirty
+                .into_iter()
+                .filter_map(|rep| guard.reps.get(&rep).map(|d| (rep, d.clone())))
+                .collect()
+        };
+        if changed.is_empty() {
+            return;
+        }
+
+        let mut tx = self.ledger.rw_txn();
+        for (rep, details) in &changed {
+            self.ledger.store.rep_details.put(&mut tx, rep, details);
+        }
+        debug!("Saved {} representatives to the registry", changed.len());
+    }
+
+    pub fn collect_container_info(&self, name: impl Into<String>) -> ContainerInfoComponent {
+        let guard = self.data.lock().unwrap();
+        ContainerInfoComponent::Composite(
+            name.into(),
+            vec![
+                ContainerInfoComponent::Leaf(ContainerInfo {
+                    name: "reps".to_string(),
+                    count: guard.reps.len(),
+                    sizeof_element: size_of::<(PublicKey, RepDetails)>(),
+                }),
+                ContainerInfoComponent::Leaf(ContainerInfo {
+                    name: "dirty".to_string(),
+                    count: guard.dirty.len(),
+                    sizeof_element: size_of::<PublicKey>(),
+                }),
+            ],
+        )
+    }
+}
+
+fn millis_since_epoch(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// Periodically persists the representative registry
+pub struct RepRegistryFlusher(Arc<RepRegistry>);
+
+impl RepRegistryFlusher {
+    pub fn new(registry: Arc<RepRegistry>) -> Self {
+        Self(registry)
+    }
+}
+
+impl Runnable for RepRegistryFlusher {
+    fn run(&mut self, _cancel_token: &CancellationToken) {
+        self.0.evict(SystemTime::now());
+        self.0.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsnano_core::utils::TEST_ENDPOINT_2;
+    use std::time::Duration;
+
+    #[test]
+    fn observe_vote() {
+        let registry = RepRegistry::new(Arc::new(Ledger::new_null()), Amount::zero());
+        let channel = ChannelInfo::new_test_instance();
+        channel.set_node_id(PublicKey::from(42));
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+
+        registry.vote_observed(
+            PublicKey::from(1),
+            Some(&channel),
+            Some(Duration::from_millis(300)),
+            now,
+        );
+        registry.vote_observed(PublicKey::from(1), None, None, now);
+
+        let details = registry.get(&PublicKey::from(1)).unwrap();
+        assert_eq!(details.vote_count, 2);
+        assert_eq!(details.node_ids, vec![PublicKey::from(42)]);
+        assert_eq!(details.endpoints, vec![TEST_ENDPOINT_2]);
+        assert_eq!(details.latency_samples(), 1);
+        assert_eq!(details.latency_percentile(50), Some(500));
+        assert_eq!(details.first_seen, 1_700_000_000);
+    }
+
+    #[test]
+    fn attach_telemetry_by_node_id() {
+        let registry = RepRegistry::new(Arc::new(Ledger::new_null()), Amount::zero());
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        registry.vote_observed(PublicKey::from(1), None, None, now);
+
+        let mut data = TelemetryData::new_test_instance();
+        registry.telemetry_received(&data, now);
+        assert!(registry
+            .get(&PublicKey::from(1))
+            .unwrap()
+            .telemetry
+            .is_none());
+
+        let channel = ChannelInfo::new_test_instance();
+        channel.set_node_id(data.node_id);
+        registry.vote_observed(PublicKey::from(1), Some(&channel), None, now);
+        data.block_count = 1234;
+        registry.telemetry_received(&data, now);
+        let telemetry = registry
+            .get(&PublicKey::from(1))
+            .unwrap()
+            .telemetry
+            .unwrap();
+        assert_eq!(telemetry.block_count, 1234);
+        assert_eq!(telemetry.received, 1_700_000_000);
+    }
+
+    #[test]
+    fn flush_writes_changed_entries() {
+        let ledger = Arc::new(Ledger::new_null());
+        let put_tracker = ledger.store.rep_details.track_puts();
+        let registry = RepRegistry::new(ledger, Amount::zero());
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        registry.vote_observed(PublicKey::from(1), None, None, now);
+
+        registry.flush();
+        registry.flush();
+
+        assert_eq!(put_tracker.output().len(), 1);
+    }
+
+    #[test]
+    fn evict_reps_not_seen_within_retention() {
+        let registry = RepRegistry::new(Arc::new(Ledger::new_null()), Amount::zero());
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        registry.vote_observed(PublicKey::from(1), None, None, now);
+        registry.vote_observed(PublicKey::from(2), None, None, now + RepRegistry::RETENTION);
+
+        registry.evict(now + RepRegistry::RETENTION + Duration::from_secs(1));
+
+        assert!(registry.get(&PublicKey::from(1)).is_none());
+        assert!(registry.get(&PublicKey::from(2)).is_some());
+    }
+
+    #[test]
+    fn evict_reps_below_minimum_weight() {
+        let registry = RepRegistry::new(Arc::new(Ledger::new_null()), Amount::raw(1));
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        registry.vote_observed(PublicKey::from(1), None, None, now);
+
+        registry.evict(now);
+
+        assert!(registry.is_empty());
+    }
+}
@@ -85,6 +85,10 @@ pub enum StatType {
     MessageProcessor,
     MessageProcessorOverfill,
     MessageProcessorType,
+    RepHealth,
+    PeerScoring,
+    BootstrapAscendingGap,
+    BlockprocessorLocal,
 }
 
 impl StatType {
@@ -509,6 +513,23 @@ pub enum DetailType {
     BlocksByHash,
     BlocksByAccount,
     AccountInfoByHash,
+
+    // rep health
+    Unhealthy,
+    NoFallback,
+    WalletLocked,
+    RateLimited,
+    Redelegated,
+
+    // block processor local lane
+    LocalClientFull,
+    LocalAccountFull,
+    LocalNode,
+    LocalRpc,
+
+    // bootstrap ascending gap resolver
+    NoChannels,
+    NextGap,
 }
 
 impl DetailType {
@@ -83,6 +83,7 @@ impl Telemetry {
                 telemetries: Default::default(),
                 last_broadcast: None,
                 last_request: None,
+                pending_requests: HashMap::new(),
             }),
             telemetry_processed_callbacks: Mutex::new(Vec::new()),
             node_id,
@@ -151,17 +152,29 @@ impl Telemetry {
 
         let mut guard = self.mutex.lock().unwrap();
         let peer_addr = channel.peer_addr();
+        let now = Instant::now();
+        let response_latency = guard
+            .pending_requests
+            .remove(&peer_addr)
+            .map(|requested| now.duration_since(requested));
+        if let Some(rtt) = response_latency {
+            channel.add_round_trip_time(rtt);
+        }
 
         if let Some(entry) = guard.telemetries.get_mut(&peer_addr) {
             self.stats.inc(StatType::Telemetry, DetailType::Update);
             entry.data = data.clone();
-            entry.last_updated = Instant::now();
+            entry.last_updated = now;
+            if response_latency.is_some() {
+                entry.response_latency = response_latency;
+            }
         } else {
             self.stats.inc(StatType::Telemetry, DetailType::Insert);
             guard.telemetries.push_back(Entry {
                 endpoint: peer_addr,
                 data: data.clone(),
-                last_updated: Instant::now(),
+                last_updated: now,
+                response_latency,
             });
 
             if guard.telemetries.len() > Self::MAX_SIZE {
@@ -257,9 +270,23 @@ impl Telemetry {
     }
 
     fn run_requests(&self) {
-        let channel_ids = self.network_info.read().unwrap().random_list_realtime_ids();
-        for channel_id in channel_ids {
-            self.request(channel_id);
+        let channels = self
+            .network_info
+            .read()
+            .unwrap()
+            .random_list_realtime(usize::MAX, 0);
+
+        // Remember when we asked, so that the response latency can be measured
+        {
+            let now = Instant::now();
+            let mut guard = self.mutex.lock().unwrap();
+            for channel in &channels {
+                guard.pending_requests.insert(channel.peer_addr(), now);
+            }
+        }
+
+        for channel in channels {
+            self.request(channel.channel_id());
         }
     }
 
@@ -293,6 +320,10 @@ impl Telemetry {
     }
 
     fn cleanup(&self, data: &mut TelemetryImpl) {
+        let cutoff = self.cache_cutoff();
+        data.pending_requests
+            .retain(|_, requested| requested.elapsed() <= cutoff);
+
         data.telemetries.retain(|entry| {
             // Remove if telemetry data is stale
             if self.has_timed_out(entry) {
@@ -306,8 +337,12 @@ impl Telemetry {
     }
 
     fn has_timed_out(&self, entry: &Entry) -> bool {
-        entry.last_updated.elapsed()
-            > Duration::from_millis(self.network_params.network.telemetry_cache_cutoff_ms as u64)
+        entry.last_updated.elapsed() > self.cache_cutoff()
+    }
+
+    /// Telemetry data older than this is discarded
+    pub fn cache_cutoff(&self) -> Duration {
+        Duration::from_millis(self.network_params.network.telemetry_cache_cutoff_ms as u64)
     }
 
     /// Returns telemetry for selected endpoint
@@ -321,6 +356,27 @@ impl Telemetry {
         None
     }
 
+    /// Returns the telemetry of all peers together with how fresh it is
+    /// and how fast the peers responded to our last request
+    pub fn get_all_peer_telemetries(&self) -> HashMap<SocketAddrV6, PeerTelemetry> {
+        let guard = self.mutex.lock().unwrap();
+        guard
+            .telemetries
+            .iter()
+            .filter(|entry| !self.has_timed_out(entry))
+            .map(|entry| {
+                (
+                    entry.endpoint,
+                    PeerTelemetry {
+                        data: entry.data.clone(),
+                        age: entry.last_updated.elapsed(),
+                        response_latency: entry.response_latency,
+                    },
+                )
+            })
+            .collect()
+    }
+
     pub fn get_all_telemetries(&self) -> HashMap<SocketAddrV6, TelemetryData> {
         let guard = self.mutex.lock().unwrap();
         let mut result = HashMap::new();
@@ -401,12 +457,21 @@ pub trait TelementryExt {
     fn start(&self);
 }
 
+pub struct PeerTelemetry {
+    pub data: TelemetryData,
+    /// Time since the telemetry was received
+    pub age: Duration,
+    /// Time it took the peer to answer our telemetry request
+    pub response_latency: Option<Duration>,
+}
+
 struct TelemetryImpl {
     stopped: bool,
     triggered: bool,
     telemetries: OrderedTelemetries,
     last_request: Option<Instant>,
     last_broadcast: Option<Instant>,
+    pending_requests: HashMap<SocketAddrV6, Instant>,
 }
 
 impl TelementryExt for Arc<Telemetry> {
@@ -428,6 +493,7 @@ struct Entry {
     endpoint: SocketAddrV6,
     data: TelemetryData,
     last_updated: Instant,
+    response_latency: Option<Duration>,
 }
 
 #[derive(Default)]
@@ -10,6 +10,7 @@ mod network_filter;
 mod network_threads;
 mod peer_cache_connector;
 mod peer_cache_updater;
+mod peer_scorer;
 mod realtime_message_handler;
 mod response_server;
 mod response_server_spawner;
@@ -28,6 +29,7 @@ pub use network_filter::NetworkFilter;
 pub(crate) use network_threads::*;
 pub use peer_cache_connector::*;
 pub use peer_cache_updater::*;
+pub use peer_scorer::*;
 pub use realtime_message_handler::RealtimeMessageHandler;
 pub use response_server::*;
 pub use response_server_spawner::*;
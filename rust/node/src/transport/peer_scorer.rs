@@ -0,0 +1,212 @@
+use crate::{
+    stats::{DetailType, StatType, Stats},
+    utils::{CancellationToken, Runnable},
+    Telemetry,
+};
+use rsnano_network::{NetworkInfo, DEFAULT_PEER_SCORE};
+use std::{
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+/// Everything we know about a peer that is relevant for its score
+#[derive(Clone, Debug, Default)]
+pub struct PeerScoreInput {
+    /// Time since the last telemetry of the peer was received
+    pub telemetry_age: Option<Duration>,
+    /// How many blocks the peer has cemented less than the network median
+    pub cemented_lag: Option<u64>,
+    /// Round trip time of the channel, or the time it took the peer to answer
+    /// our last telemetry request if no round trip time was measured yet
+    pub response_latency: Option<Duration>,
+    /// How often the peer misbehaved recently
+    pub misbehavior: u64,
+}
+
+impl PeerScoreInput {
+    /// A peer that is this far behind the network median gets no points for being in sync
+    pub const MAX_CEMENTED_LAG: u64 = 10_000;
+    /// Responses faster than this get full points
+    pub const GOOD_LATENCY: Duration = Duration::from_millis(100);
+    /// Responses slower than this get no points
+    pub const BAD_LATENCY: Duration = Duration::from_secs(5);
+
+    /// Returns a score between 0 and 100. Unknown properties count as average,
+    /// so a well behaved peer we know nothing about gets the default score.
+    /// Every recorded misbehavior divides the score.
+    pub fn score(&self, telemetry_cutoff: Duration) -> u8 {
+        const UNKNOWN: f64 = DEFAULT_PEER_SCORE as f64 / 100.0;
+
+        let freshness = self
+            .telemetry_age
+            .map(|age| 1.0 - ratio(age.as_secs_f64(), telemetry_cutoff.as_secs_f64()))
+            .unwrap_or(UNKNOWN);
+
+        let sync = self
+            .cemented_lag
+            .map(|lag| 1.0 - ratio(lag as f64, Self::MAX_CEMENTED_LAG as f64))
+            .unwrap_or(UNKNOWN);
+
+        let responsiveness = self
+            .response_latency
+            .map(|latency| {
+                let slowness = latency.saturating_sub(Self::GOOD_LATENCY);
+                let range = Self::BAD_LATENCY - Self::GOOD_LATENCY;
+                1.0 - ratio(slowness.as_secs_f64(), range.as_secs_f64())
+            })
+            .unwrap_or(UNKNOWN);
+
+        let quality = (freshness + sync + responsiveness) / 3.0;
+        let score = 100.0 * quality / (1 + self.misbehavior) as f64;
+        score.round() as u8
+    }
+}
+
+fn ratio(value: f64, max: f64) -> f64 {
+    if max <= 0.0 {
+        1.0
+    } else {
+        (value / max).clamp(0.0, 1.0)
+    }
+}
+
+/// Periodically rates all realtime channels by their telemetry and their past behavior.
+/// Channels with a higher score are preferred when channels are picked at random,
+/// for example when flooding blocks or when selecting a bootstrap peer.
+pub struct PeerScorer {
+    network_info: Arc<RwLock<NetworkInfo>>,
+    telemetry: Arc<Telemetry>,
+    stats: Arc<Stats>,
+}
+
+impl PeerScorer {
+    pub fn new(
+        network_info: Arc<RwLock<NetworkInfo>>,
+        telemetry: Arc<Telemetry>,
+        stats: Arc<Stats>,
+    ) -> Self {
+        Self {
+            network_info,
+            telemetry,
+            stats,
+        }
+    }
+
+    pub fn update_scores(&self) {
+        let telemetries = self.telemetry.get_all_peer_telemetries();
+        let median = median_cemented_count(telemetries.values().map(|t| t.data.cemented_count));
+        let telemetry_cutoff = self.telemetry.cache_cutoff();
+
+        let network = self.network_info.read().unwrap();
+        for channel in network.list_realtime(0) {
+            let peer_addr = channel.peer_addr();
+            let telemetry = telemetries.get(&peer_addr);
+            let input = PeerScoreInput {
+                telemetry_age: telemetry.map(|t| t.age),
+                cemented_lag: telemetry
+                    .zip(median)
+                    .map(|(t, median)| median.saturating_sub(t.data.cemented_count)),
+                response_latency: channel
+                    .round_trip_time()
+                    .or_else(|| telemetry.and_then(|t| t.response_latency)),
+                misbehavior: network.misbehavior_score(&peer_addr),
+            };
+            channel.set_score(input.score(telemetry_cutoff));
+            self.stats.inc(StatType::PeerScoring, DetailType::Updated);
+        }
+    }
+}
+
+impl Runnable for PeerScorer {
+    fn run(&mut self, _cancel_token: &CancellationToken) {
+        self.stats.inc(StatType::PeerScoring, DetailType::Loop);
+        self.update_scores();
+    }
+}
+
+fn median_cemented_count(counts: impl Iterator<Item = u64>) -> Option<u64> {
+    let mut counts: Vec<_> = counts.collect();
+    if counts.is_empty() {
+        return None;
+    }
+    counts.sort_unstable();
+    Some(counts[counts.len() / 2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CUTOFF: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn unknown_peer_gets_default_score() {
+        let input = PeerScoreInput::default();
+        assert_eq!(input.score(CUTOFF), DEFAULT_PEER_SCORE);
+    }
+
+    #[test]
+    fn perfect_peer() {
+        let input = PeerScoreInput {
+            telemetry_age: Some(Duration::ZERO),
+            cemented_lag: Some(0),
+            response_latency: Some(Duration::from_millis(50)),
+            misbehavior: 0,
+        };
+        assert_eq!(input.score(CUTOFF), 100);
+    }
+
+    #[test]
+    fn lagging_peer_scores_lower() {
+        let in_sync = PeerScoreInput {
+            cemented_lag: Some(0),
+            ..Default::default()
+        };
+        let lagging = PeerScoreInput {
+            cemented_lag: Some(PeerScoreInput::MAX_CEMENTED_LAG),
+            ..Default::default()
+        };
+        assert!(lagging.score(CUTOFF) < in_sync.score(CUTOFF));
+    }
+
+    #[test]
+    fn slow_peer_scores_lower() {
+        let fast = PeerScoreInput {
+            response_latency: Some(Duration::from_millis(100)),
+            ..Default::default()
+        };
+        let slow = PeerScoreInput {
+            response_latency: Some(Duration::from_secs(10)),
+            ..Default::default()
+        };
+        assert!(slow.score(CUTOFF) < fast.score(CUTOFF));
+    }
+
+    #[test]
+    fn stale_telemetry_scores_lower() {
+        let fresh = PeerScoreInput {
+            telemetry_age: Some(Duration::from_secs(1)),
+            ..Default::default()
+        };
+        let stale = PeerScoreInput {
+            telemetry_age: Some(CUTOFF),
+            ..Default::default()
+        };
+        assert!(stale.score(CUTOFF) < fresh.score(CUTOFF));
+    }
+
+    #[test]
+    fn misbehavior_divides_score() {
+        let input = PeerScoreInput {
+            misbehavior: 1,
+            ..Default::default()
+        };
+        assert_eq!(input.score(CUTOFF), DEFAULT_PEER_SCORE / 2);
+    }
+
+    #[test]
+    fn median() {
+        assert_eq!(median_cemented_count([].into_iter()), None);
+        assert_eq!(median_cemented_count([5, 1, 3].into_iter()), Some(3));
+    }
+}
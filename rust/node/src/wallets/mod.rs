@@ -1,9 +1,23 @@
+mod receive_policy;
+mod rep_health;
+mod rep_health_monitor;
+mod signer;
+mod signer_thread;
 mod wallet;
 mod wallet_action_thread;
+mod wallet_backup;
 mod wallet_representatives;
 mod wallets;
 
+pub use rsnano_core::WalletsError;
+
+pub use receive_policy::*;
+pub use rep_health::*;
+pub use rep_health_monitor::*;
+pub use signer::*;
+pub use signer_thread::*;
 pub use wallet::*;
 pub use wallet_action_thread::*;
+pub use wallet_backup::*;
 pub use wallet_representatives::*;
 pub use wallets::*;
@@ -0,0 +1,220 @@
+use rsnano_core::{Account, Amount, WalletId};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+/// Decides which receivable blocks get received automatically
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReceivePolicy {
+    /// Sends below this amount are not received automatically.
+    /// The node wide `receive_minimum` applies in any case
+    pub minimum: Option<Amount>,
+    /// If not empty, only sends from these accounts are received automatically
+    pub allow: Vec<Account>,
+    /// Sends from these accounts are never received automatically
+    pub deny: Vec<Account>,
+    pub disabled: bool,
+    /// Maximum number of automatic receives per minute
+    pub max_per_minute: Option<u32>,
+}
+
+impl ReceivePolicy {
+    /// Checks everything except the receive budget
+    pub fn allows(&self, source: &Account, amount: Amount) -> bool {
+        if self.disabled || self.deny.contains(source) {
+            return false;
+        }
+        if !self.allow.is_empty() && !self.allow.contains(source) {
+            return false;
+        }
+        match self.minimum {
+            Some(minimum) => amount >= minimum,
+            None => true,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceivePolicyScope {
+    Account,
+    Wallet,
+    Default,
+}
+
+/// All receive policies of a wallet. An account policy replaces the wallet policy
+/// for that account
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalletReceivePolicies {
+    pub wallet: Option<ReceivePolicy>,
+    pub accounts: HashMap<Account, ReceivePolicy>,
+}
+
+impl WalletReceivePolicies {
+    pub fn is_empty(&self) -> bool {
+        self.wallet.is_none() && self.accounts.is_empty()
+    }
+
+    pub fn set(&mut self, account: Option<Account>, policy: Option<ReceivePolicy>) {
+        match (account, policy) {
+            (None, policy) => self.wallet = policy,
+            (Some(account), Some(policy)) => {
+                self.accounts.insert(account, policy);
+            }
+            (Some(account), None) => {
+                self.accounts.remove(&account);
+            }
+        }
+    }
+
+    /// The policy which applies to `account` and where it is defined
+    pub fn effective(&self, account: &Account) -> (ReceivePolicy, ReceivePolicyScope) {
+        if let Some(policy) = self.accounts.get(account) {
+            (policy.clone(), ReceivePolicyScope::Account)
+        } else if let Some(policy) = &self.wallet {
+            (policy.clone(), ReceivePolicyScope::Wallet)
+        } else {
+            (ReceivePolicy::default(), ReceivePolicyScope::Default)
+        }
+    }
+}
+
+/// Sliding window counters for the receives-per-minute budgets. The budget of a
+/// wallet policy is shared by all accounts which use it
+#[derive(Default)]
+pub struct ReceiveBudgets {
+    receives: HashMap<(WalletId, Account), VecDeque<Instant>>,
+}
+
+const BUDGET_WINDOW: Duration = Duration::from_secs(60);
+
+impl ReceiveBudgets {
+    pub fn try_consume(
+        &mut self,
+        wallet_id: WalletId,
+        account: &Account,
+        scope: ReceivePolicyScope,
+        max_per_minute: Option<u32>,
+        now: Instant,
+    ) -> bool {
+        let Some(max) = max_per_minute else {
+            return true;
+        };
+        let key = match scope {
+            ReceivePolicyScope::Account => (wallet_id, *account),
+            _ => (wallet_id, Account::zero()),
+        };
+        let receives = self.receives.entry(key).or_default();
+        while let Some(oldest) = receives.front() {
+            if now.duration_since(*oldest) >= BUDGET_WINDOW {
+                receives.pop_front();
+            } else {
+                break;
+            }
+        }
+        if receives.len() >= max as usize {
+            return false;
+        }
+        receives.push_back(now);
+        true
+    }
+
+    pub fn clear_wallet(&mut self, wallet_id: &WalletId) {
+        self.receives.retain(|(id, _), _| id != wallet_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_policy_allows_everything() {
+        assert!(ReceivePolicy::default().allows(&Account::from(1), Amount::raw(1)));
+    }
+
+    #[test]
+    fn filters() {
+        let policy = ReceivePolicy {
+            minimum: Some(Amount::raw(100)),
+            allow: vec![Account::from(1), Account::from(2)],
+            deny: vec![Account::from(2)],
+            ..Default::default()
+        };
+        assert!(policy.allows(&Account::from(1), Amount::raw(100)));
+        assert!(!policy.allows(&Account::from(1), Amount::raw(99)));
+        assert!(!policy.allows(&Account::from(2), Amount::raw(100)));
+        assert!(!policy.allows(&Account::from(3), Amount::raw(100)));
+
+        let disabled = ReceivePolicy {
+            disabled: true,
+            ..Default::default()
+        };
+        assert!(!disabled.allows(&Account::from(1), Amount::raw(100)));
+    }
+
+    #[test]
+    fn account_policy_replaces_wallet_policy() {
+        let mut policies = WalletReceivePolicies::default();
+        let wallet_policy = ReceivePolicy {
+            disabled: true,
+            ..Default::default()
+        };
+        let account_policy = ReceivePolicy {
+            minimum: Some(Amount::raw(5)),
+            ..Default::default()
+        };
+        policies.set(None, Some(wallet_policy.clone()));
+        policies.set(Some(Account::from(1)), Some(account_policy.clone()));
+
+        assert_eq!(
+            policies.effective(&Account::from(1)),
+            (account_policy, ReceivePolicyScope::Account)
+        );
+        assert_eq!(
+            policies.effective(&Account::from(2)),
+            (wallet_policy, ReceivePolicyScope::Wallet)
+        );
+
+        policies.set(None, None);
+        policies.set(Some(Account::from(1)), None);
+        assert!(policies.is_empty());
+        assert_eq!(
+            policies.effective(&Account::from(1)).1,
+            ReceivePolicyScope::Default
+        );
+    }
+
+    #[test]
+    fn budget_per_minute() {
+        let mut budgets = ReceiveBudgets::default();
+        let wallet = WalletId::from(1);
+        let account = Account::from(1);
+        let now = Instant::now();
+        let scope = ReceivePolicyScope::Account;
+        assert!(budgets.try_consume(wallet, &account, scope, Some(2), now));
+        assert!(budgets.try_consume(wallet, &account, scope, Some(2), now));
+        assert!(!budgets.try_consume(wallet, &account, scope, Some(2), now));
+        assert!(budgets.try_consume(wallet, &Account::from(2), scope, Some(2), now));
+        assert!(budgets.try_consume(
+            wallet,
+            &account,
+            scope,
+            Some(2),
+            now + Duration::from_secs(60)
+        ));
+        assert!(budgets.try_consume(wallet, &account, scope, None, now));
+    }
+
+    #[test]
+    fn wallet_budget_is_shared() {
+        let mut budgets = ReceiveBudgets::default();
+        let wallet = WalletId::from(1);
+        let now = Instant::now();
+        let scope = ReceivePolicyScope::Wallet;
+        assert!(budgets.try_consume(wallet, &Account::from(1), scope, Some(1), now));
+        assert!(!budgets.try_consume(wallet, &Account::from(2), scope, Some(1), now));
+    }
+}
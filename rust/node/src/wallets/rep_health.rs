@@ -0,0 +1,273 @@
+use crate::consensus::RepTier;
+use rsnano_core::{Account, Amount, PublicKey, WalletId};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+/// Opt-in policy which replaces the representative of a wallet when it becomes unhealthy
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepHealthPolicy {
+    /// Candidates for the new representative. The first healthy one is used
+    pub fallback_representatives: Vec<Account>,
+    /// Seconds a representative has to be unhealthy before it gets replaced
+    pub grace_period: u64,
+    /// Representatives with less voting weight are unhealthy
+    pub minimum_weight: Option<Amount>,
+    /// Representatives below this tier (1-3) are unhealthy
+    pub minimum_tier: Option<u8>,
+    /// Representatives which voted in less than this percentage of the last 24 hours are unhealthy
+    pub minimum_uptime: Option<u8>,
+    /// Maximum number of change blocks per hour for the whole wallet
+    pub max_changes_per_hour: Option<u32>,
+}
+
+impl Default for RepHealthPolicy {
+    fn default() -> Self {
+        Self {
+            fallback_representatives: Vec::new(),
+            grace_period: 60 * 60,
+            minimum_weight: None,
+            minimum_tier: None,
+            minimum_uptime: None,
+            max_changes_per_hour: None,
+        }
+    }
+}
+
+impl RepHealthPolicy {
+    pub fn grace_period(&self) -> Duration {
+        Duration::from_secs(self.grace_period)
+    }
+
+    /// Returns the first problem of the representative or `None` if it is healthy
+    pub fn check(&self, status: &RepStatus) -> Option<RepHealthIssue> {
+        if !status.online {
+            return Some(RepHealthIssue::Offline);
+        }
+        if let Some(minimum) = self.minimum_weight {
+            if status.weight < minimum {
+                return Some(RepHealthIssue::LowWeight);
+            }
+        }
+        if let Some(minimum) = self.minimum_tier {
+            if (status.tier as u8) < minimum {
+                return Some(RepHealthIssue::LowTier);
+            }
+        }
+        if let Some(minimum) = self.minimum_uptime {
+            if status.uptime.unwrap_or_default() < minimum as f64 {
+                return Some(RepHealthIssue::LowUptime);
+            }
+        }
+        None
+    }
+}
+
+/// What is currently known about a representative
+#[derive(Clone, Debug, PartialEq)]
+pub struct RepStatus {
+    pub online: bool,
+    pub weight: Amount,
+    pub tier: RepTier,
+    /// Percentage of the last 24 hours in which the representative voted
+    pub uptime: Option<f64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RepHealthIssue {
+    Offline,
+    LowWeight,
+    LowTier,
+    LowUptime,
+}
+
+impl RepHealthIssue {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RepHealthIssue::Offline => "offline",
+            RepHealthIssue::LowWeight => "low_weight",
+            RepHealthIssue::LowTier => "low_tier",
+            RepHealthIssue::LowUptime => "low_uptime",
+        }
+    }
+}
+
+/// Remembers since when the representatives of each wallet are unhealthy
+#[derive(Default)]
+pub struct UnhealthyReps {
+    since: HashMap<(WalletId, PublicKey), Instant>,
+}
+
+impl UnhealthyReps {
+    /// Returns for how long the representative has been unhealthy
+    pub fn update(
+        &mut self,
+        wallet_id: WalletId,
+        rep: PublicKey,
+        issue: Option<RepHealthIssue>,
+        now: Instant,
+    ) -> Option<Duration> {
+        match issue {
+            Some(_) => {
+                let since = self.since.entry((wallet_id, rep)).or_insert(now);
+                Some(now.duration_since(*since))
+            }
+            None => {
+                self.since.remove(&(wallet_id, rep));
+                None
+            }
+        }
+    }
+
+    /// Forgets all representatives of the wallet which are not in `reps`
+    pub fn retain(&mut self, wallet_id: WalletId, reps: &[PublicKey]) {
+        self.since
+            .retain(|(id, rep), _| *id != wallet_id || reps.contains(rep));
+    }
+
+    pub fn len(&self) -> usize {
+        self.since.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.since.is_empty()
+    }
+}
+
+/// Sliding window counters for the changes-per-hour limit of each wallet
+#[derive(Default)]
+pub struct RepChangeBudgets {
+    changes: HashMap<WalletId, VecDeque<Instant>>,
+}
+
+const CHANGE_WINDOW: Duration = Duration::from_secs(60 * 60);
+
+impl RepChangeBudgets {
+    pub fn try_consume(
+        &mut self,
+        wallet_id: WalletId,
+        max_per_hour: Option<u32>,
+        now: Instant,
+    ) -> bool {
+        let Some(max) = max_per_hour else {
+            return true;
+        };
+        let changes = self.changes.entry(wallet_id).or_default();
+        while let Some(oldest) = changes.front() {
+            if now.duration_since(*oldest) >= CHANGE_WINDOW {
+                changes.pop_front();
+            } else {
+                break;
+            }
+        }
+        if changes.len() >= max as usize {
+            return false;
+        }
+        changes.push_back(now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn healthy() -> RepStatus {
+        RepStatus {
+            online: true,
+            weight: Amount::raw(1000),
+            tier: RepTier::Tier2,
+            uptime: Some(100.0),
+        }
+    }
+
+    #[test]
+    fn default_policy_only_requires_online() {
+        let policy = RepHealthPolicy::default();
+        let status = RepStatus {
+            weight: Amount::zero(),
+            tier: RepTier::None,
+            uptime: None,
+            ..healthy()
+        };
+        assert_eq!(policy.check(&status), None);
+        assert_eq!(
+            policy.check(&RepStatus {
+                online: false,
+                ..healthy()
+            }),
+            Some(RepHealthIssue::Offline)
+        );
+    }
+
+    #[test]
+    fn thresholds() {
+        let policy = RepHealthPolicy {
+            minimum_weight: Some(Amount::raw(1000)),
+            minimum_tier: Some(2),
+            minimum_uptime: Some(90),
+            ..Default::default()
+        };
+        assert_eq!(policy.check(&healthy()), None);
+        assert_eq!(
+            policy.check(&RepStatus {
+                weight: Amount::raw(999),
+                ..healthy()
+            }),
+            Some(RepHealthIssue::LowWeight)
+        );
+        assert_eq!(
+            policy.check(&RepStatus {
+                tier: RepTier::Tier1,
+                ..healthy()
+            }),
+            Some(RepHealthIssue::LowTier)
+        );
+        assert_eq!(
+            policy.check(&RepStatus {
+                uptime: None,
+                ..healthy()
+            }),
+            Some(RepHealthIssue::LowUptime)
+        );
+    }
+
+    #[test]
+    fn track_unhealthy_duration() {
+        let mut unhealthy = UnhealthyReps::default();
+        let wallet = WalletId::from(1);
+        let rep = PublicKey::from(2);
+        let now = Instant::now();
+        let issue = Some(RepHealthIssue::Offline);
+
+        assert_eq!(
+            unhealthy.update(wallet, rep, issue, now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(
+            unhealthy.update(wallet, rep, issue, now + Duration::from_secs(5)),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(unhealthy.update(wallet, rep, None, now), None);
+        assert!(unhealthy.is_empty());
+
+        unhealthy.update(wallet, rep, issue, now);
+        unhealthy.retain(wallet, &[]);
+        assert!(unhealthy.is_empty());
+    }
+
+    #[test]
+    fn changes_per_hour() {
+        let mut budgets = RepChangeBudgets::default();
+        let wallet = WalletId::from(1);
+        let now = Instant::now();
+        assert!(budgets.try_consume(wallet, Some(1), now));
+        assert!(!budgets.try_consume(wallet, Some(1), now));
+        assert!(budgets.try_consume(WalletId::from(2), Some(1), now));
+        assert!(budgets.try_consume(wallet, Some(1), now + CHANGE_WINDOW));
+        assert!(budgets.try_consume(wallet, None, now));
+    }
+}
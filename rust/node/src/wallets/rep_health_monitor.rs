@@ -0,0 +1,317 @@
+use super::{
+    RepChangeBudgets, RepHealthIssue, RepHealthPolicy, RepStatus, UnhealthyReps, Wallets,
+    WalletsExt,
+};
+use crate::{
+    consensus::RepTiers,
+    representatives::{OnlineReps, RepRegistry},
+    stats::{DetailType, StatType, Stats},
+    utils::{CancellationToken, Runnable},
+    websocket::{representative_changed, Topic, WebsocketListener},
+};
+use rsnano_core::{
+    utils::{ContainerInfo, ContainerInfoComponent},
+    Account, PublicKey, WalletId,
+};
+use rsnano_ledger::Ledger;
+use std::{
+    collections::HashSet,
+    mem::size_of,
+    sync::{Arc, Mutex},
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+use tracing::{info, warn};
+
+/// Replaces the representatives of wallets with a `RepHealthPolicy` once they
+/// are unhealthy for longer than the policy's grace period
+pub struct RepHealthMonitor {
+    wallets: Arc<Wallets>,
+    ledger: Arc<Ledger>,
+    online_reps: Arc<Mutex<OnlineReps>>,
+    rep_tiers: Arc<RepTiers>,
+    rep_registry: Arc<RepRegistry>,
+    stats: Arc<Stats>,
+    websocket: Option<Arc<WebsocketListener>>,
+    data: Mutex<RepHealthData>,
+    /// Accounts whose change block wasn't processed yet. They are skipped,
+    /// because the ledger still shows their old representative
+    in_flight: Arc<Mutex<HashSet<Account>>>,
+}
+
+#[derive(Default)]
+struct RepHealthData {
+    unhealthy: UnhealthyReps,
+    budgets: RepChangeBudgets,
+}
+
+impl RepHealthMonitor {
+    pub fn new(
+        wallets: Arc<Wallets>,
+        ledger: Arc<Ledger>,
+        online_reps: Arc<Mutex<OnlineReps>>,
+        rep_tiers: Arc<RepTiers>,
+        rep_registry: Arc<RepRegistry>,
+        stats: Arc<Stats>,
+        websocket: Option<Arc<WebsocketListener>>,
+    ) -> Self {
+        Self {
+            wallets,
+            ledger,
+            online_reps,
+            rep_tiers,
+            rep_registry,
+            stats,
+            websocket,
+            data: Mutex::new(RepHealthData::default()),
+            in_flight: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+
+    /// Checks the representatives of all wallets which have a policy
+    pub fn check_all(&self, now: Instant) {
+        for (wallet_id, policy) in self.wallets.rep_health_policies() {
+            self.check_wallet(wallet_id, &policy, now);
+        }
+    }
+
+    pub fn rep_status(&self, rep: &PublicKey) -> RepStatus {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        RepStatus {
+            online: self
+                .online_reps
+                .lock()
+                .unwrap()
+                .online_reps()
+                .any(|r| r == rep),
+            weight: self.ledger.weight(rep),
+            tier: self.rep_tiers.tier(rep),
+            uptime: self.rep_registry.get(rep).map(|d| d.uptime(24, now)),
+        }
+    }
+
+    fn check_wallet(&self, wallet_id: WalletId, policy: &RepHealthPolicy, now: Instant) {
+        let Ok(accounts) = self.wallets.get_accounts_of_wallet(&wallet_id) else {
+            return;
+        };
+        let Ok(wallet_rep) = self.wallets.get_representative(wallet_id) else {
+            return;
+        };
+        let delegations: Vec<(Account, PublicKey)> = {
+            let tx = self.ledger.read_txn();
+            accounts
+                .iter()
+                .filter_map(|account| {
+                    self.ledger
+                        .account_info(&tx, account)
+                        .map(|info| (*account, info.representative))
+                })
+                .collect()
+        };
+
+        let mut reps: Vec<PublicKey> = delegations.iter().map(|(_, rep)| *rep).collect();
+        reps.push(wallet_rep);
+        reps.sort();
+        reps.dedup();
+        self.data.lock().unwrap().unhealthy.retain(wallet_id, &reps);
+
+        for rep in reps {
+            let issue = policy.check(&self.rep_status(&rep));
+            let unhealthy_for = self
+                .data
+                .lock()
+                .unwrap()
+                .unhealthy
+                .update(wallet_id, rep, issue, now);
+            let (Some(issue), Some(unhealthy_for)) = (issue, unhealthy_for) else {
+                continue;
+            };
+            if unhealthy_for < policy.grace_period() {
+                continue;
+            }
+            self.stats.inc(StatType::RepHealth, DetailType::Unhealthy);
+
+            let Some(fallback) = self.healthy_fallback(policy, &rep) else {
+                self.stats.inc(StatType::RepHealth, DetailType::NoFallback);
+                warn!(
+                    "Representative {} of wallet {} is unhealthy ({}), but no fallback representative is healthy",
+                    Account::from(rep).encode_account(),
+                    wallet_id,
+                    issue.as_str()
+                );
+                continue;
+            };
+
+            let redelegation = Redelegation {
+                wallet_id,
+                old_rep: rep,
+                new_rep: fallback,
+                issue,
+                is_wallet_rep: rep == wallet_rep,
+                accounts: delegations
+                    .iter()
+                    .filter(|(_, r)| *r == rep)
+                    .map(|(account, _)| *account)
+                    .collect(),
+            };
+            self.redelegate(redelegation, policy, now);
+        }
+    }
+
+    fn healthy_fallback(&self, policy: &RepHealthPolicy, current: &PublicKey) -> Option<PublicKey> {
+        policy
+            .fallback_representatives
+            .iter()
+            .map(|account| PublicKey::from(*account))
+            .filter(|rep| rep != current)
+            .find(|rep| policy.check(&self.rep_status(rep)).is_none())
+    }
+
+    fn redelegate(&self, redelegation: Redelegation, policy: &RepHealthPolicy, now: Instant) {
+        let Redelegation {
+            wallet_id,
+            old_rep,
+            new_rep,
+            issue,
+            is_wallet_rep,
+            accounts,
+        } = redelegation;
+        if is_wallet_rep
+            && self
+                .wallets
+                .set_representative(wallet_id, new_rep, false)
+                .is_err()
+        {
+            return;
+        }
+
+        if !accounts.is_empty() && !self.wallets.valid_password(&wallet_id).unwrap_or(false) {
+            self.stats
+                .inc(StatType::RepHealth, DetailType::WalletLocked);
+            warn!(
+                "Cannot change the representative of wallet {} because it is locked",
+                wallet_id
+            );
+            return;
+        }
+
+        let accounts: Vec<_> = {
+            let in_flight = self.in_flight.lock().unwrap();
+            accounts
+                .into_iter()
+                .filter(|account| !in_flight.contains(account))
+                .collect()
+        };
+        if accounts.is_empty() && !is_wallet_rep {
+            return;
+        }
+
+        let mut changed = Vec::new();
+        {
+            let mut guard = self.data.lock().unwrap();
+            for account in &accounts {
+                if !guard
+                    .budgets
+                    .try_consume(wallet_id, policy.max_changes_per_hour, now)
+                {
+                    self.stats.inc(StatType::RepHealth, DetailType::RateLimited);
+                    break;
+                }
+                changed.push(*account);
+            }
+            if changed.len() == accounts.len() {
+                guard.unhealthy.update(wallet_id, old_rep, None, now);
+            }
+        }
+
+        for account in &changed {
+            self.in_flight.lock().unwrap().insert(*account);
+            let in_flight = Arc::clone(&self.in_flight);
+            let account = *account;
+            let result = self.wallets.change_async(
+                wallet_id,
+                account,
+                new_rep,
+                Box::new(move |_| {
+                    in_flight.lock().unwrap().remove(&account);
+                }),
+                0,
+                true,
+            );
+            if result.is_err() {
+                self.in_flight.lock().unwrap().remove(&account);
+            }
+        }
+
+        self.stats.add(
+            StatType::RepHealth,
+            DetailType::Redelegated,
+            changed.len() as u64,
+        );
+        info!(
+            "Replacing representative {} of wallet {} ({}) with {} for {} accounts",
+            Account::from(old_rep).encode_account(),
+            wallet_id,
+            issue.as_str(),
+            Account::from(new_rep).encode_account(),
+            changed.len()
+        );
+
+        if let Some(websocket) = &self.websocket {
+            if websocket.any_subscriber(Topic::RepresentativeChange) {
+                websocket.broadcast(&representative_changed(
+                    &wallet_id,
+                    &old_rep,
+                    &new_rep,
+                    issue.as_str(),
+                    &changed,
+                ));
+            }
+        }
+    }
+
+    pub fn collect_container_info(&self, name: impl Into<String>) -> ContainerInfoComponent {
+        let guard = self.data.lock().unwrap();
+        ContainerInfoComponent::Composite(
+            name.into(),
+            vec![
+                ContainerInfoComponent::Leaf(ContainerInfo {
+                    name: "unhealthy".to_string(),
+                    count: guard.unhealthy.len(),
+                    sizeof_element: size_of::<(WalletId, PublicKey, Instant)>(),
+                }),
+                ContainerInfoComponent::Leaf(ContainerInfo {
+                    name: "in_flight".to_string(),
+                    count: self.in_flight.lock().unwrap().len(),
+                    sizeof_element: size_of::<Account>(),
+                }),
+            ],
+        )
+    }
+}
+
+struct Redelegation {
+    wallet_id: WalletId,
+    old_rep: PublicKey,
+    new_rep: PublicKey,
+    issue: RepHealthIssue,
+    is_wallet_rep: bool,
+    accounts: Vec<Account>,
+}
+
+/// Periodically checks the representatives of the wallets
+pub struct RepHealthChecker(Arc<RepHealthMonitor>);
+
+impl RepHealthChecker {
+    pub fn new(monitor: Arc<RepHealthMonitor>) -> Self {
+        Self(monitor)
+    }
+}
+
+impl Runnable for RepHealthChecker {
+    fn run(&mut self, _cancel_token: &CancellationToken) {
+        self.0.check_all(Instant::now());
+    }
+}
@@ -0,0 +1,469 @@
+use crate::config::ExternalSignerConfig;
+use anyhow::{anyhow, bail, Context};
+use rsnano_core::{
+    sign_message, validate_message, Account, Amount, BlockEnum, BlockHash, JsonBlock, KeyPair,
+    Link, PublicKey, Signature, StateBlock, Vote,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    os::unix::net::UnixStream,
+    path::PathBuf,
+    str::FromStr,
+    time::Duration,
+};
+
+/// What has to be signed. The full block or vote is sent instead of its hash,
+/// so that a signing daemon can check what it signs against its own policy
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SignContent {
+    Block {
+        block: JsonBlock,
+    },
+    Vote {
+        /// The timestamp including the duration bits, as it is used in the vote hash
+        timestamp: u64,
+        hashes: Vec<BlockHash>,
+    },
+}
+
+/// Asks for the signature of a block or vote with the key of `account`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignRequest {
+    pub account: Account,
+    #[serde(flatten)]
+    pub content: SignContent,
+}
+
+impl SignRequest {
+    pub fn block(block: &BlockEnum) -> Self {
+        Self {
+            account: block.account_field().unwrap_or_default(),
+            content: SignContent::Block {
+                block: block.into(),
+            },
+        }
+    }
+
+    pub fn vote(vote: &Vote) -> Self {
+        Self {
+            account: vote.voting_account.into(),
+            content: SignContent::Vote {
+                timestamp: vote.timestamp,
+                hashes: vote.hashes.clone(),
+            },
+        }
+    }
+
+    /// The hash which gets signed
+    pub fn hash(&self) -> BlockHash {
+        match &self.content {
+            SignContent::Block { block } => BlockEnum::from(block.clone()).hash(),
+            SignContent::Vote { timestamp, hashes } => Vote {
+                timestamp: *timestamp,
+                voting_account: self.account.into(),
+                signature: Signature::new(),
+                hashes: hashes.clone(),
+            }
+            .hash(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct SignResponse {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<Signature>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl SignResponse {
+    pub fn signature(signature: Signature) -> Self {
+        Self {
+            signature: Some(signature),
+            error: None,
+        }
+    }
+
+    pub fn error(error: impl Into<String>) -> Self {
+        Self {
+            signature: None,
+            error: Some(error.into()),
+        }
+    }
+}
+
+/// Creates the signatures of wallet blocks and votes
+pub trait Signer: Send + Sync {
+    fn sign(&self, request: &SignRequest) -> anyhow::Result<Signature>;
+}
+
+/// Signs with a private key that was loaded from the wallet store
+pub struct LocalSigner {
+    keys: KeyPair,
+}
+
+impl LocalSigner {
+    pub fn new(keys: KeyPair) -> Self {
+        Self { keys }
+    }
+}
+
+impl Signer for LocalSigner {
+    fn sign(&self, request: &SignRequest) -> anyhow::Result<Signature> {
+        if PublicKey::from(request.account) != self.keys.public_key() {
+            bail!("signer has no key for account {}", request.account);
+        }
+        Ok(sign_message(
+            &self.keys.private_key(),
+            request.hash().as_bytes(),
+        ))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SignerEndpoint {
+    /// Newline delimited JSON over a Unix domain socket
+    Unix(PathBuf),
+    /// JSON POST request
+    Http { host: String, path: String },
+}
+
+impl FromStr for SignerEndpoint {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(path) = s.strip_prefix("unix:") {
+            if path.is_empty() {
+                bail!("missing socket path");
+            }
+            Ok(Self::Unix(PathBuf::from(path)))
+        } else if let Some(rest) = s.strip_prefix("http://") {
+            let (host, path) = match rest.find('/') {
+                Some(i) => (&rest[..i], &rest[i..]),
+                None => (rest, "/"),
+            };
+            if host.is_empty() {
+                bail!("missing host");
+            }
+            Ok(Self::Http {
+                host: host.to_owned(),
+                path: path.to_owned(),
+            })
+        } else {
+            bail!("unsupported signer address: {}", s)
+        }
+    }
+}
+
+/// Delegates signing to a separate signing daemon. Each request is a JSON encoded
+/// `SignRequest` and the daemon answers with a JSON encoded `SignResponse`.
+/// The daemon has to compute the hash of the block or vote itself
+pub struct ExternalSigner {
+    endpoint: SignerEndpoint,
+    timeout: Duration,
+}
+
+impl ExternalSigner {
+    pub fn new(endpoint: SignerEndpoint, timeout: Duration) -> Self {
+        Self { endpoint, timeout }
+    }
+
+    /// Returns `None` if no signer address is configured
+    pub fn from_config(config: &ExternalSignerConfig) -> anyhow::Result<Option<Self>> {
+        if config.address.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(Self::new(config.address.parse()?, config.timeout)))
+    }
+
+    fn call(&self, request: &SignRequest) -> anyhow::Result<SignResponse> {
+        let body = serde_json::to_string(request)?;
+        let response = match &self.endpoint {
+            SignerEndpoint::Unix(path) => self.call_unix(path, &body)?,
+            SignerEndpoint::Http { host, path } => self.call_http(host, path, &body)?,
+        };
+        serde_json::from_str(&response).context("invalid signer response")
+    }
+
+    fn call_unix(&self, path: &PathBuf, body: &str) -> anyhow::Result<String> {
+        let mut stream = UnixStream::connect(path)
+            .with_context(|| format!("could not connect to signer at {:?}", path))?;
+        stream.set_read_timeout(Some(self.timeout))?;
+        stream.set_write_timeout(Some(self.timeout))?;
+        stream.write_all(body.as_bytes())?;
+        stream.write_all(b"\n")?;
+
+        let mut response = String::new();
+        BufReader::new(stream).read_line(&mut response)?;
+        Ok(response)
+    }
+
+    fn call_http(&self, host: &str, path: &str, body: &str) -> anyhow::Result<String> {
+        let address = host
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| anyhow!("could not resolve signer host {}", host))?;
+        let mut stream = TcpStream::connect_timeout(&address, self.timeout)
+            .with_context(|| format!("could not connect to signer at {}", host))?;
+        stream.set_read_timeout(Some(self.timeout))?;
+        stream.set_write_timeout(Some(self.timeout))?;
+        write!(
+            stream,
+            "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            path,
+            host,
+            body.len(),
+            body
+        )?;
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+        let (head, content) = response
+            .split_once("\r\n\r\n")
+            .ok_or_else(|| anyhow!("invalid HTTP response from signer"))?;
+        let status = head.lines().next().unwrap_or_default();
+        if status.split_whitespace().nth(1) != Some("200") {
+            bail!("signer returned {}", status);
+        }
+        Ok(content.to_owned())
+    }
+}
+
+impl Signer for ExternalSigner {
+    fn sign(&self, request: &SignRequest) -> anyhow::Result<Signature> {
+        let response = self.call(request)?;
+        if let Some(error) = response.error {
+            bail!("signer refused request: {}", error);
+        }
+        let signature = response
+            .signature
+            .ok_or_else(|| anyhow!("signer response contains no signature"))?;
+        validate_message(
+            &request.account.into(),
+            request.hash().as_bytes(),
+            &signature,
+        )
+        .context("signer returned an invalid signature")?;
+        Ok(signature)
+    }
+}
+
+/// Creates a state block and lets `signer` sign it
+pub fn sign_state_block(
+    signer: &dyn Signer,
+    account: Account,
+    previous: BlockHash,
+    representative: PublicKey,
+    balance: Amount,
+    link: Link,
+    work: u64,
+) -> anyhow::Result<BlockEnum> {
+    let mut block = BlockEnum::State(StateBlock::with_signature(
+        account,
+        previous,
+        representative,
+        balance,
+        link,
+        Signature::new(),
+        work,
+    ));
+    let signature = signer.sign(&SignRequest::block(&block))?;
+    block.set_block_signature(&signature);
+    Ok(block)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsnano_core::validate_block_signature;
+    use std::{net::TcpListener, os::unix::net::UnixListener, thread};
+
+    #[test]
+    fn parse_endpoint() {
+        assert_eq!(
+            "unix:/tmp/signer.sock".parse::<SignerEndpoint>().unwrap(),
+            SignerEndpoint::Unix("/tmp/signer.sock".into())
+        );
+        assert_eq!(
+            "http://127.0.0.1:7080/sign"
+                .parse::<SignerEndpoint>()
+                .unwrap(),
+            SignerEndpoint::Http {
+                host: "127.0.0.1:7080".to_owned(),
+                path: "/sign".to_owned()
+            }
+        );
+        assert_eq!(
+            "http://localhost:7080".parse::<SignerEndpoint>().unwrap(),
+            SignerEndpoint::Http {
+                host: "localhost:7080".to_owned(),
+                path: "/".to_owned()
+            }
+        );
+        assert!("tcp://localhost".parse::<SignerEndpoint>().is_err());
+    }
+
+    #[test]
+    fn local_signer_signs_state_block() {
+        let keys = KeyPair::new();
+        let signer = LocalSigner::new(keys.clone());
+        let block = sign_state_block(
+            &signer,
+            keys.account(),
+            BlockHash::from(1),
+            PublicKey::from(2),
+            Amount::raw(3),
+            Link::from(4),
+            5,
+        )
+        .unwrap();
+        let BlockEnum::State(state) = &block else {
+            panic!("not a state block")
+        };
+        validate_block_signature(state).unwrap();
+    }
+
+    fn vote_request(account: Account) -> SignRequest {
+        SignRequest {
+            account,
+            content: SignContent::Vote {
+                timestamp: 1,
+                hashes: vec![BlockHash::from(2)],
+            },
+        }
+    }
+
+    #[test]
+    fn local_signer_rejects_foreign_account() {
+        let signer = LocalSigner::new(KeyPair::new());
+        let request = SignRequest::block(&BlockEnum::new_test_instance());
+        assert!(signer.sign(&request).is_err());
+    }
+
+    #[test]
+    fn request_contains_the_block() {
+        let block = BlockEnum::new_test_instance();
+
+        let json = serde_json::to_value(SignRequest::block(&block)).unwrap();
+
+        assert_eq!(json["kind"], "block");
+        assert_eq!(json["block"]["type"], "state");
+        let request: SignRequest = serde_json::from_value(json).unwrap();
+        assert_eq!(request.hash(), block.hash());
+    }
+
+    #[test]
+    fn request_contains_the_vote() {
+        let vote = Vote::new_test_instance();
+
+        let json = serde_json::to_value(SignRequest::vote(&vote)).unwrap();
+
+        assert_eq!(json["kind"], "vote");
+        assert_eq!(json["hashes"][0], vote.hashes[0].to_string());
+        let request: SignRequest = serde_json::from_value(json).unwrap();
+        assert_eq!(request.hash(), vote.hash());
+    }
+
+    #[test]
+    fn external_signer_over_unix_socket() {
+        let path = std::env::temp_dir().join(format!("signer-{}.sock", rand::random::<u64>()));
+        let listener = UnixListener::bind(&path).unwrap();
+        let keys = KeyPair::new();
+        let daemon_keys = keys.clone();
+        let daemon = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let request: SignRequest = serde_json::from_str(&line).unwrap();
+            let signature = LocalSigner::new(daemon_keys).sign(&request).unwrap();
+            let mut stream = stream;
+            serde_json::to_writer(&mut stream, &SignResponse::signature(signature)).unwrap();
+            stream.write_all(b"\n").unwrap();
+            request
+        });
+
+        let signer =
+            ExternalSigner::new(SignerEndpoint::Unix(path.clone()), Duration::from_secs(5));
+        let vote = Vote::new_unsigned(keys.public_key(), 1, 2, vec![BlockHash::from(3)]);
+        let mut signed = vote.clone();
+        signed.signature = signer.sign(&SignRequest::vote(&vote)).unwrap();
+
+        signed.validate().unwrap();
+        assert!(matches!(
+            daemon.join().unwrap().content,
+            SignContent::Vote { .. }
+        ));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn external_signer_over_http() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let daemon = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut content_length = 0;
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                if header == "\r\n" {
+                    break;
+                }
+                if let Some(len) = header.strip_prefix("Content-Length: ") {
+                    content_length = len.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            let _request: SignRequest = serde_json::from_slice(&body).unwrap();
+            let response = serde_json::to_string(&SignResponse::error("account locked")).unwrap();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}",
+                response.len(),
+                response
+            )
+            .unwrap();
+            request_line
+        });
+
+        let signer = ExternalSigner::new(
+            format!("http://{}/sign", address).parse().unwrap(),
+            Duration::from_secs(5),
+        );
+        let error = signer.sign(&vote_request(Account::from(1))).unwrap_err();
+
+        assert!(error.to_string().contains("account locked"));
+        assert!(daemon.join().unwrap().starts_with("POST /sign HTTP/1.1"));
+    }
+
+    #[test]
+    fn external_signer_rejects_invalid_signature() {
+        let path = std::env::temp_dir().join(format!("signer-{}.sock", rand::random::<u64>()));
+        let listener = UnixListener::bind(&path).unwrap();
+        let daemon = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut line = String::new();
+            BufReader::new(stream.try_clone().unwrap())
+                .read_line(&mut line)
+                .unwrap();
+            serde_json::to_writer(&mut stream, &SignResponse::signature(Signature::new())).unwrap();
+            stream.write_all(b"\n").unwrap();
+        });
+
+        let signer =
+            ExternalSigner::new(SignerEndpoint::Unix(path.clone()), Duration::from_secs(5));
+        let request = vote_request(KeyPair::new().account());
+        assert!(signer.sign(&request).is_err());
+        daemon.join().unwrap();
+        std::fs::remove_file(path).unwrap();
+    }
+}
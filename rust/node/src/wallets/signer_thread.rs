@@ -0,0 +1,214 @@
+use super::{SignRequest, Signer};
+use rsnano_core::Signature;
+use std::{
+    sync::{
+        mpsc::{self, Receiver, SyncSender, TrySendError},
+        Arc, Mutex,
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+use tracing::warn;
+
+struct SignJob {
+    index: usize,
+    request: SignRequest,
+    deadline: Instant,
+    reply: mpsc::Sender<(usize, anyhow::Result<Signature>)>,
+}
+
+/// Sends the requests of a slow signer from a dedicated thread, so that callers
+/// can stop waiting after a deadline. Jobs which are still queued when their
+/// deadline has passed are dropped without being sent to the signer
+pub struct SignerThread {
+    signer: Arc<dyn Signer>,
+    sender: Mutex<Option<SyncSender<SignJob>>>,
+    join_handle: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl SignerThread {
+    pub const MAX_QUEUED: usize = 1024;
+
+    pub fn new(signer: Arc<dyn Signer>) -> Self {
+        Self {
+            signer,
+            sender: Mutex::new(None),
+            join_handle: Mutex::new(None),
+        }
+    }
+
+    pub fn start(&self) {
+        let (sender, receiver) = mpsc::sync_channel(Self::MAX_QUEUED);
+        let signer = Arc::clone(&self.signer);
+        let mut guard = self.join_handle.lock().unwrap();
+        assert!(guard.is_none(), "signer thread already running");
+        *guard = Some(
+            std::thread::Builder::new()
+                .name("Signer".to_string())
+                .spawn(move || Self::run(signer.as_ref(), receiver))
+                .unwrap(),
+        );
+        *self.sender.lock().unwrap() = Some(sender);
+    }
+
+    pub fn stop(&self) {
+        self.sender.lock().unwrap().take();
+        let join_handle = self.join_handle.lock().unwrap().take();
+        if let Some(join_handle) = join_handle {
+            join_handle.join().unwrap();
+        }
+    }
+
+    /// Queues all requests at once and waits at most `timeout` for their signatures.
+    /// The result has the same order as `requests` and is `None` for requests which
+    /// failed, were dropped or didn't complete in time
+    pub fn sign_batch(
+        &self,
+        requests: &[SignRequest],
+        timeout: Duration,
+    ) -> Vec<Option<Signature>> {
+        let mut result = vec![None; requests.len()];
+        let deadline = Instant::now() + timeout;
+        let (reply, replies) = mpsc::channel();
+        let mut pending = 0;
+        {
+            let guard = self.sender.lock().unwrap();
+            let Some(sender) = guard.as_ref() else {
+                return result;
+            };
+            for (index, request) in requests.iter().enumerate() {
+                let job = SignJob {
+                    index,
+                    request: request.clone(),
+                    deadline,
+                    reply: reply.clone(),
+                };
+                match sender.try_send(job) {
+                    Ok(()) => pending += 1,
+                    Err(TrySendError::Full(_)) => {
+                        warn!(
+                            "Signer queue is full, dropping request of {}",
+                            request.account
+                        )
+                    }
+                    Err(TrySendError::Disconnected(_)) => break,
+                }
+            }
+        }
+
+        while pending > 0 {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match replies.recv_timeout(remaining) {
+                Ok((index, Ok(signature))) => {
+                    result[index] = Some(signature);
+                    pending -= 1;
+                }
+                Ok((index, Err(e))) => {
+                    warn!(
+                        "Could not sign request of {}: {:?}",
+                        requests[index].account, e
+                    );
+                    pending -= 1;
+                }
+                Err(_) => {
+                    warn!("Signer didn't answer {} requests in time", pending);
+                    break;
+                }
+            }
+        }
+        result
+    }
+
+    fn run(signer: &dyn Signer, receiver: Receiver<SignJob>) {
+        while let Ok(job) = receiver.recv() {
+            if Instant::now() >= job.deadline {
+                continue;
+            }
+            let _ = job.reply.send((job.index, signer.sign(&job.request)));
+        }
+    }
+}
+
+impl Drop for SignerThread {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wallets::{LocalSigner, SignContent};
+    use rsnano_core::{BlockHash, KeyPair};
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    /// Answers the first request right away and blocks all further
+    /// requests until the sender of `release` is dropped
+    struct StuckSigner {
+        answered: AtomicBool,
+        release: Mutex<Receiver<()>>,
+    }
+
+    impl Signer for StuckSigner {
+        fn sign(&self, _request: &SignRequest) -> anyhow::Result<Signature> {
+            if self.answered.swap(true, Ordering::SeqCst) {
+                let _ = self.release.lock().unwrap().recv();
+            }
+            Ok(Signature::new())
+        }
+    }
+
+    fn request(keys: &KeyPair) -> SignRequest {
+        SignRequest {
+            account: keys.account(),
+            content: SignContent::Vote {
+                timestamp: 1,
+                hashes: vec![BlockHash::from(1)],
+            },
+        }
+    }
+
+    #[test]
+    fn sign_batch() {
+        let keys = KeyPair::new();
+        let other = KeyPair::new();
+        let thread = SignerThread::new(Arc::new(LocalSigner::new(keys.clone())));
+        thread.start();
+
+        let result = thread.sign_batch(&[request(&keys), request(&other)], Duration::from_secs(5));
+
+        assert!(result[0].is_some());
+        assert!(result[1].is_none());
+    }
+
+    #[test]
+    fn not_started() {
+        let keys = KeyPair::new();
+        let thread = SignerThread::new(Arc::new(LocalSigner::new(keys.clone())));
+        assert_eq!(
+            thread.sign_batch(&[request(&keys)], Duration::from_secs(5)),
+            vec![None]
+        );
+    }
+
+    #[test]
+    fn give_up_after_deadline() {
+        let keys = KeyPair::new();
+        let (release, released) = mpsc::channel();
+        let thread = SignerThread::new(Arc::new(StuckSigner {
+            answered: AtomicBool::new(false),
+            release: Mutex::new(released),
+        }));
+        thread.start();
+
+        let result = thread.sign_batch(
+            &[request(&keys), request(&keys), request(&keys)],
+            Duration::from_millis(500),
+        );
+        drop(release);
+
+        assert!(result[0].is_some());
+        assert!(result[1].is_none());
+        assert!(result[2].is_none());
+    }
+}
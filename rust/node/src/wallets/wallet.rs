@@ -1,17 +1,18 @@
 use anyhow::Context;
 use rsnano_core::{
-    work::WorkThresholds, KeyDerivationFunction, KeyPair, PublicKey, Root, WorkVersion,
+    work::WorkThresholds, KeyDerivationFunction, KeyPair, PublicKey, Root, WalletId, WorkVersion,
 };
 use rsnano_ledger::Ledger;
-use rsnano_store_lmdb::{LmdbWalletStore, LmdbWriteTransaction, Transaction};
+use rsnano_store_lmdb::{LmdbWalletStore, LmdbWriteTransaction, Transaction, WalletValue};
 use std::{
     collections::HashSet,
-    path::Path,
+    path::PathBuf,
     sync::{Arc, Mutex},
 };
 use tracing::warn;
 
 pub struct Wallet {
+    pub id: WalletId,
     pub representatives: Mutex<HashSet<PublicKey>>,
     pub store: Arc<LmdbWalletStore>,
     ledger: Arc<Ledger>,
@@ -26,12 +27,13 @@ impl Wallet {
         fanout: usize,
         kdf: KeyDerivationFunction,
         representative: PublicKey,
-        wallet_path: &Path,
+        id: WalletId,
     ) -> anyhow::Result<Self> {
-        let store = LmdbWalletStore::new(fanout, kdf, txn, &representative, &wallet_path)
+        let store = LmdbWalletStore::new(fanout, kdf, txn, &representative, &Self::path(&id))
             .context("could not create wallet store")?;
 
         Ok(Self {
+            id,
             representatives: Mutex::new(HashSet::new()),
             store: Arc::new(store),
             ledger,
@@ -45,13 +47,14 @@ impl Wallet {
         txn: &mut LmdbWriteTransaction,
         fanout: usize,
         kdf: KeyDerivationFunction,
-        wallet_path: &Path,
+        id: WalletId,
         json: &str,
     ) -> anyhow::Result<Self> {
-        let store = LmdbWalletStore::new_from_json(fanout, kdf, txn, &wallet_path, json)
+        let store = LmdbWalletStore::new_from_json(fanout, kdf, txn, &Self::path(&id), json)
             .context("could not create wallet store")?;
 
         Ok(Self {
+            id,
             representatives: Mutex::new(HashSet::new()),
             store: Arc::new(store),
             ledger,
@@ -59,6 +62,31 @@ impl Wallet {
         })
     }
 
+    pub fn new_from_entries(
+        ledger: Arc<Ledger>,
+        work_thresholds: WorkThresholds,
+        txn: &mut LmdbWriteTransaction,
+        fanout: usize,
+        kdf: KeyDerivationFunction,
+        id: WalletId,
+        entries: &[(PublicKey, WalletValue)],
+    ) -> anyhow::Result<Self> {
+        let store = LmdbWalletStore::new_from_entries(fanout, kdf, txn, &Self::path(&id), entries)
+            .context("could not create wallet store")?;
+
+        Ok(Self {
+            id,
+            representatives: Mutex::new(HashSet::new()),
+            store: Arc::new(store),
+            ledger,
+            work_thresholds,
+        })
+    }
+
+    fn path(id: &WalletId) -> PathBuf {
+        PathBuf::from(id.encode_hex())
+    }
+
     pub fn work_update(
         &self,
         txn: &mut LmdbWriteTransaction,
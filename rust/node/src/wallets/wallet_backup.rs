@@ -0,0 +1,438 @@
+use anyhow::{anyhow, bail};
+use chacha20poly1305::{
+    aead::{Aead, Payload},
+    ChaCha20Poly1305, Key, KeyInit, Nonce,
+};
+use rand::{thread_rng, Rng};
+use rsnano_core::{Argon2idParams, KeyDerivationFunction, PublicKey, RawKey, WalletId};
+use rsnano_store_lmdb::{KeyType, LmdbWalletStore, WalletValue};
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Version of the encrypted wallet backup format
+pub const WALLET_BACKUP_VERSION: u32 = 1;
+
+const CIPHER: &str = "chacha20poly1305";
+const KDF_ALGORITHM: &str = "argon2id";
+
+/// Plain text information about the backup. It is authenticated, but not encrypted
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalletBackupMetadata {
+    pub wallet: WalletId,
+    /// Unix timestamp in seconds
+    pub created: u64,
+    pub account_count: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalletBackupKdf {
+    pub algorithm: String,
+    #[serde(flatten)]
+    pub params: Argon2idParams,
+    pub salt: RawKey,
+}
+
+/// The file format of an encrypted wallet backup
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptedWalletBackup {
+    pub version: u32,
+    pub metadata: WalletBackupMetadata,
+    pub kdf: WalletBackupKdf,
+    pub cipher: String,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+impl EncryptedWalletBackup {
+    pub fn encrypt(
+        content: &WalletBackupContent,
+        wallet: WalletId,
+        password: &str,
+        params: &Argon2idParams,
+    ) -> anyhow::Result<Self> {
+        if password.is_empty() {
+            bail!("the wallet backup password must not be empty");
+        }
+        let metadata = WalletBackupMetadata {
+            wallet,
+            created: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            account_count: content.account_count(),
+        };
+        let kdf = WalletBackupKdf {
+            algorithm: KDF_ALGORITHM.to_string(),
+            params: params.clone(),
+            salt: RawKey::random(),
+        };
+        let nonce: [u8; 12] = thread_rng().gen();
+        let plaintext = serde_json::to_vec(content)?;
+        let aad = serde_json::to_vec(&metadata)?;
+        let ciphertext = Self::cipher(&kdf, password)?
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &plaintext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| anyhow!("could not encrypt wallet backup"))?;
+
+        Ok(Self {
+            version: WALLET_BACKUP_VERSION,
+            metadata,
+            kdf,
+            cipher: CIPHER.to_string(),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+
+    pub fn decrypt(&self, password: &str) -> anyhow::Result<WalletBackupContent> {
+        if self.version != WALLET_BACKUP_VERSION {
+            bail!("unsupported wallet backup version {}", self.version);
+        }
+        if self.kdf.algorithm != KDF_ALGORITHM || self.cipher != CIPHER {
+            bail!("unsupported wallet backup encryption");
+        }
+        let nonce = hex::decode(&self.nonce)?;
+        if nonce.len() != 12 {
+            bail!("invalid nonce");
+        }
+        let ciphertext = hex::decode(&self.ciphertext)?;
+        let aad = serde_json::to_vec(&self.metadata)?;
+        let plaintext = Self::cipher(&self.kdf, password)?
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| anyhow!("invalid password or corrupted wallet backup"))?;
+        Ok(serde_json::from_slice(&plaintext)?)
+    }
+
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn from_json(json: &str) -> anyhow::Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    fn cipher(kdf: &WalletBackupKdf, password: &str) -> anyhow::Result<ChaCha20Poly1305> {
+        let key = KeyDerivationFunction::argon2id(&kdf.params)?
+            .hash_password(password, kdf.salt.as_bytes())?;
+        Ok(ChaCha20Poly1305::new(Key::from_slice(key.as_bytes())))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupAdhocKey {
+    pub account: PublicKey,
+    pub key: RawKey,
+    pub work: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupDeterministicKey {
+    pub account: PublicKey,
+    pub index: u32,
+    /// True if the key is derived via BIP44 instead of from the seed
+    pub bip44: bool,
+    pub work: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupWatchAccount {
+    pub account: PublicKey,
+    pub work: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupMnemonic {
+    pub entropy: RawKey,
+    pub length: u64,
+    pub bip44_key: RawKey,
+    pub bip44_chain_code: RawKey,
+}
+
+/// The encrypted part of a wallet backup.
+/// Private keys, the seed and the mnemonic are stored in the form they have in the
+/// wallet store, so they stay encrypted with the wallet password.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalletBackupContent {
+    pub store_version: u32,
+    pub salt: RawKey,
+    pub wallet_key: RawKey,
+    pub check: RawKey,
+    pub representative: PublicKey,
+    pub seed: RawKey,
+    pub deterministic_index: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mnemonic: Option<BackupMnemonic>,
+    pub adhoc_keys: Vec<BackupAdhocKey>,
+    pub deterministic_keys: Vec<BackupDeterministicKey>,
+    pub watch_accounts: Vec<BackupWatchAccount>,
+}
+
+impl WalletBackupContent {
+    pub fn from_entries(entries: &[(PublicKey, WalletValue)]) -> anyhow::Result<Self> {
+        let special = |key: PublicKey| {
+            entries
+                .iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, v)| v)
+                .ok_or_else(|| anyhow!("wallet entry {} is missing", key))
+        };
+
+        let mnemonic_entry = special(LmdbWalletStore::mnemonic_special()).ok();
+        let mnemonic = match mnemonic_entry {
+            Some(entry) if entry.work != 0 => Some(BackupMnemonic {
+                entropy: entry.key,
+                length: entry.work,
+                bip44_key: special(LmdbWalletStore::bip44_key_special())?.key,
+                bip44_chain_code: special(LmdbWalletStore::bip44_chain_code_special())?.key,
+            }),
+            _ => None,
+        };
+
+        let mut result = Self {
+            store_version: special(LmdbWalletStore::version_special())?
+                .key
+                .number()
+                .low_u32(),
+            salt: special(LmdbWalletStore::salt_special())?.key,
+            wallet_key: special(LmdbWalletStore::wallet_key_special())?.key,
+            check: special(LmdbWalletStore::check_special())?.key,
+            representative: PublicKey::from_bytes(
+                *special(LmdbWalletStore::representative_special())?
+                    .key
+                    .as_bytes(),
+            ),
+            seed: special(LmdbWalletStore::seed_special())?.key,
+            deterministic_index: special(LmdbWalletStore::deterministic_index_special())?
+                .key
+                .number()
+                .low_u32(),
+            mnemonic,
+            adhoc_keys: Vec::new(),
+            deterministic_keys: Vec::new(),
+            watch_accounts: Vec::new(),
+        };
+
+        let special_count = LmdbWalletStore::special_count().number();
+        for (account, value) in entries {
+            if account.number() < special_count {
+                continue;
+            }
+            if value.key.is_zero() {
+                result.watch_accounts.push(BackupWatchAccount {
+                    account: *account,
+                    work: value.work,
+                });
+                continue;
+            }
+            match LmdbWalletStore::key_type(value) {
+                KeyType::Adhoc => result.adhoc_keys.push(BackupAdhocKey {
+                    account: *account,
+                    key: value.key,
+                    work: value.work,
+                }),
+                key_type @ (KeyType::Deterministic | KeyType::Bip44) => {
+                    result.deterministic_keys.push(BackupDeterministicKey {
+                        account: *account,
+                        index: value.key.number().low_u32(),
+                        bip44: matches!(key_type, KeyType::Bip44),
+                        work: value.work,
+                    })
+                }
+                _ => bail!("unknown key type of account {}", account),
+            }
+        }
+
+        Ok(result)
+    }
+
+    pub fn to_entries(&self) -> Vec<(PublicKey, WalletValue)> {
+        let mut entries = vec![
+            (
+                LmdbWalletStore::version_special(),
+                WalletValue::new(RawKey::from(self.store_version as u64), 0),
+            ),
+            (
+                LmdbWalletStore::salt_special(),
+                WalletValue::new(self.salt, 0),
+            ),
+            (
+                LmdbWalletStore::wallet_key_special(),
+                WalletValue::new(self.wallet_key, 0),
+            ),
+            (
+                LmdbWalletStore::check_special(),
+                WalletValue::new(self.check, 0),
+            ),
+            (
+                LmdbWalletStore::representative_special(),
+                WalletValue::new(RawKey::from_bytes(*self.representative.as_bytes()), 0),
+            ),
+            (
+                LmdbWalletStore::seed_special(),
+                WalletValue::new(self.seed, 0),
+            ),
+            (
+                LmdbWalletStore::deterministic_index_special(),
+                WalletValue::new(RawKey::from(self.deterministic_index as u64), 0),
+            ),
+        ];
+
+        if let Some(mnemonic) = &self.mnemonic {
+            entries.push((
+                LmdbWalletStore::mnemonic_special(),
+                WalletValue::new(mnemonic.entropy, mnemonic.length),
+            ));
+            entries.push((
+                LmdbWalletStore::bip44_key_special(),
+                WalletValue::new(mnemonic.bip44_key, 0),
+            ));
+            entries.push((
+                LmdbWalletStore::bip44_chain_code_special(),
+                WalletValue::new(mnemonic.bip44_chain_code, 0),
+            ));
+        }
+
+        for key in &self.adhoc_keys {
+            entries.push((key.account, WalletValue::new(key.key, key.work)));
+        }
+
+        for key in &self.deterministic_keys {
+            let key_type = if key.bip44 { 2u64 } else { 1u64 };
+            let marker = RawKey::from((key_type << 32) | key.index as u64);
+            entries.push((key.account, WalletValue::new(marker, key.work)));
+        }
+
+        for watch in &self.watch_accounts {
+            entries.push((watch.account, WalletValue::new(RawKey::zero(), watch.work)));
+        }
+
+        entries
+    }
+
+    pub fn account_count(&self) -> usize {
+        self.adhoc_keys.len() + self.deterministic_keys.len() + self.watch_accounts.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn content() -> WalletBackupContent {
+        WalletBackupContent {
+            store_version: 4,
+            salt: RawKey::from(1),
+            wallet_key: RawKey::from(2),
+            check: RawKey::from(3),
+            representative: PublicKey::from(4),
+            seed: RawKey::from(5),
+            deterministic_index: 2,
+            mnemonic: None,
+            adhoc_keys: vec![BackupAdhocKey {
+                account: PublicKey::from(100),
+                key: RawKey::decode_hex(
+                    "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+                )
+                .unwrap(),
+                work: 42,
+            }],
+            deterministic_keys: vec![BackupDeterministicKey {
+                account: PublicKey::from(101),
+                index: 1,
+                bip44: false,
+                work: 0,
+            }],
+            watch_accounts: vec![BackupWatchAccount {
+                account: PublicKey::from(102),
+                work: 0,
+            }],
+        }
+    }
+
+    #[test]
+    fn entries_roundtrip() {
+        let content = content();
+        let restored = WalletBackupContent::from_entries(&content.to_entries()).unwrap();
+        assert_eq!(restored, content);
+    }
+
+    #[test]
+    fn encrypt_and_decrypt() {
+        let backup = EncryptedWalletBackup::encrypt(
+            &content(),
+            WalletId::from_bytes([7; 32]),
+            "password",
+            &Argon2idParams::new_dev(),
+        )
+        .unwrap();
+        assert_eq!(backup.version, WALLET_BACKUP_VERSION);
+        assert_eq!(backup.metadata.account_count, 3);
+
+        let json = backup.to_json().unwrap();
+        let decrypted = EncryptedWalletBackup::from_json(&json)
+            .unwrap()
+            .decrypt("password")
+            .unwrap();
+        assert_eq!(decrypted, content());
+    }
+
+    #[test]
+    fn wrong_password() {
+        let backup = EncryptedWalletBackup::encrypt(
+            &content(),
+            WalletId::from_bytes([7; 32]),
+            "password",
+            &Argon2idParams::new_dev(),
+        )
+        .unwrap();
+        assert!(backup.decrypt("wrong").is_err());
+    }
+
+    #[test]
+    fn metadata_is_authenticated() {
+        let mut backup = EncryptedWalletBackup::encrypt(
+            &content(),
+            WalletId::from_bytes([7; 32]),
+            "password",
+            &Argon2idParams::new_dev(),
+        )
+        .unwrap();
+        backup.metadata.account_count = 1;
+        assert!(backup.decrypt("password").is_err());
+    }
+
+    #[test]
+    fn empty_password() {
+        let result = EncryptedWalletBackup::encrypt(
+            &content(),
+            WalletId::from_bytes([7; 32]),
+            "",
+            &Argon2idParams::new_dev(),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn reject_excessive_kdf_params() {
+        let mut backup = EncryptedWalletBackup::encrypt(
+            &content(),
+            WalletId::from_bytes([7; 32]),
+            "password",
+            &Argon2idParams::new_dev(),
+        )
+        .unwrap();
+        backup.kdf.params.memory_kib = u32::MAX;
+        assert!(backup.decrypt("password").is_err());
+    }
+}
@@ -1,8 +1,12 @@
-use super::{Wallet, WalletActionThread, WalletRepresentatives};
+use super::{
+    sign_state_block, EncryptedWalletBackup, ExternalSigner, LocalSigner, ReceiveBudgets,
+    ReceivePolicy, ReceivePolicyScope, RepHealthPolicy, SignRequest, Signer, SignerThread, Wallet,
+    WalletActionThread, WalletBackupContent, WalletReceivePolicies, WalletRepresentatives,
+};
 use crate::{
     block_processing::{BlockProcessor, BlockSource},
     cementation::{ConfirmingSet, ConfirmingSetConfig},
-    config::{NetworkConstants, NodeConfig},
+    config::{ExternalSignerConfig, NetworkConstants, NodeConfig},
     representatives::OnlineReps,
     stats::Stats,
     transport::MessagePublisher,
@@ -10,13 +14,14 @@ use crate::{
     work::DistributedWorkFactory,
     NetworkParams,
 };
+use anyhow::{bail, Context};
 use rand::{thread_rng, Rng};
 use rsnano_core::{
     utils::{get_env_or_default_string, ContainerInfo, ContainerInfoComponent},
     work::{WorkPoolImpl, WorkThresholds},
-    Account, Amount, BlockDetails, BlockEnum, BlockHash, Epoch, HackyUnsafeMutBlock,
-    KeyDerivationFunction, KeyPair, Link, NoValue, PendingKey, PublicKey, RawKey, Root, StateBlock,
-    WalletId, WorkVersion,
+    Account, Amount, Argon2idParams, BlockDetails, BlockEnum, BlockHash, Epoch,
+    HackyUnsafeMutBlock, KeyDerivationFunction, KeyPair, Link, Mnemonic, NoValue, PendingKey,
+    PublicKey, RawKey, Root, Vote, WalletId, WalletsError, WorkVersion,
 };
 use rsnano_ledger::{BlockStatus, Ledger, RepWeightCache};
 use rsnano_messages::{Message, Publish};
@@ -26,51 +31,26 @@ use rsnano_store_lmdb::{
     create_backup_file, BinaryDbIterator, KeyType, LmdbEnv, LmdbIteratorImpl, LmdbWalletStore,
     LmdbWriteTransaction, Transaction,
 };
-use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet},
-    fmt,
-    fs::Permissions,
+    fs::{OpenOptions, Permissions},
+    io::Write,
     mem::size_of,
     ops::Deref,
-    os::unix::fs::PermissionsExt,
+    os::unix::fs::{OpenOptionsExt, PermissionsExt},
     path::{Path, PathBuf},
     sync::{Arc, Condvar, Mutex},
     time::{Duration, Instant},
 };
 use tracing::{info, warn};
 
-#[derive(FromPrimitive, Debug, Serialize, Deserialize)]
-pub enum WalletsError {
-    None,
-    Generic,
-    WalletNotFound,
-    WalletLocked,
-    AccountNotFound,
-    InvalidPassword,
-    BadPublicKey,
-}
-
-impl fmt::Display for WalletsError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let error_message = match self {
-            WalletsError::None => "No error",
-            WalletsError::Generic => "Unknown error",
-            WalletsError::WalletNotFound => "Wallet not found",
-            WalletsError::WalletLocked => "Wallet is locked",
-            WalletsError::AccountNotFound => "Account not found",
-            WalletsError::InvalidPassword => "Invalid password",
-            WalletsError::BadPublicKey => "Bad public key",
-        };
-        write!(f, "{}", error_message)
-    }
-}
-
 pub type WalletsIterator<'txn> = BinaryDbIterator<'txn, [u8; 64], NoValue>;
 
 pub struct Wallets {
     db: Option<LmdbDatabase>,
     send_action_ids_handle: Option<LmdbDatabase>,
+    receive_policies_handle: Option<LmdbDatabase>,
+    rep_health_policies_handle: Option<LmdbDatabase>,
     env: Arc<LmdbEnv>,
     pub mutex: Mutex<HashMap<WalletId, Arc<Wallet>>>,
     node_config: NodeConfig,
@@ -89,6 +69,9 @@ pub struct Wallets {
     start_election: Mutex<Option<Box<dyn Fn(Arc<BlockEnum>) + Send + Sync>>>,
     confirming_set: Arc<ConfirmingSet>,
     message_publisher: Mutex<MessagePublisher>,
+    external_signer: Option<Arc<dyn Signer>>,
+    vote_signer: Option<SignerThread>,
+    receive_budgets: Mutex<ReceiveBudgets>,
 }
 
 impl Wallets {
@@ -118,6 +101,7 @@ impl Wallets {
             )),
             MessagePublisher::new_null(tokio_handle.clone()),
         )
+        .unwrap()
     }
 
     pub fn new(
@@ -133,11 +117,21 @@ impl Wallets {
         online_reps: Arc<Mutex<OnlineReps>>,
         confirming_set: Arc<ConfirmingSet>,
         message_publisher: MessagePublisher,
-    ) -> Self {
+    ) -> anyhow::Result<Self> {
         let kdf = KeyDerivationFunction::new(kdf_work);
-        Self {
+        let external_signer = ExternalSigner::from_config(&node_config.external_signer)
+            .context("Invalid external signer address")?
+            .map(|s| Arc::new(s) as Arc<dyn Signer>);
+        let vote_signer = ExternalSigner::from_config(&ExternalSignerConfig {
+            timeout: node_config.external_signer.vote_timeout,
+            ..node_config.external_signer.clone()
+        })?
+        .map(|s| SignerThread::new(Arc::new(s)));
+        Ok(Self {
             db: None,
             send_action_ids_handle: None,
+            receive_policies_handle: None,
+            rep_health_policies_handle: None,
             mutex: Mutex::new(HashMap::new()),
             env,
             node_config: node_config.clone(),
@@ -159,15 +153,24 @@ impl Wallets {
             start_election: Mutex::new(None),
             confirming_set,
             message_publisher: Mutex::new(message_publisher),
-        }
+            external_signer,
+            vote_signer,
+            receive_budgets: Mutex::new(ReceiveBudgets::default()),
+        })
     }
 
     pub fn start(&self) {
         self.wallet_actions.start();
+        if let Some(vote_signer) = &self.vote_signer {
+            vote_signer.start();
+        }
     }
 
     pub fn stop(&self) {
         self.wallet_actions.stop();
+        if let Some(vote_signer) = &self.vote_signer {
+            vote_signer.stop();
+        }
     }
 
     pub fn set_start_election_callback(&self, callback: Box<dyn Fn(Arc<BlockEnum>) + Send + Sync>) {
@@ -181,13 +184,20 @@ impl Wallets {
             txn.rw_txn_mut()
                 .create_db(Some("send_action_ids"), DatabaseFlags::empty())?
         });
+        self.receive_policies_handle = Some(unsafe {
+            txn.rw_txn_mut()
+                .create_db(Some("receive_policies"), DatabaseFlags::empty())?
+        });
+        self.rep_health_policies_handle = Some(unsafe {
+            txn.rw_txn_mut()
+                .create_db(Some("rep_health_policies"), DatabaseFlags::empty())?
+        });
         {
             let mut guard = self.mutex.lock().unwrap();
             let wallet_ids = self.get_wallet_ids(&txn);
             for id in wallet_ids {
                 assert!(!guard.contains_key(&id));
                 let representative = self.node_config.random_representative();
-                let text = PathBuf::from(id.encode_hex());
                 let wallet = Wallet::new(
                     self.ledger.clone(),
                     self.work_thresholds.clone(),
@@ -195,7 +205,7 @@ impl Wallets {
                     self.node_config.password_fanout as usize,
                     self.kdf.clone(),
                     representative,
-                    &text,
+                    id,
                 )?;
 
                 guard.insert(id, Arc::new(wallet));
@@ -215,6 +225,10 @@ impl Wallets {
             if backup_required {
                 create_backup_file(&self.env)?;
             }
+
+            for wallet in guard.values() {
+                wallet.store.upgrade(&mut txn)?;
+            }
         }
         Ok(())
     }
@@ -283,6 +297,187 @@ impl Wallets {
         Ok(())
     }
 
+    fn get_receive_policies(
+        &self,
+        txn: &dyn Transaction,
+        wallet_id: &WalletId,
+    ) -> WalletReceivePolicies {
+        match txn.get(self.receive_policies_handle.unwrap(), wallet_id.as_bytes()) {
+            Ok(bytes) => serde_json::from_slice(bytes).unwrap_or_else(|e| {
+                warn!("Invalid receive policies of wallet {}: {:?}", wallet_id, e);
+                Default::default()
+            }),
+            Err(_) => Default::default(),
+        }
+    }
+
+    fn put_receive_policies(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        wallet_id: &WalletId,
+        policies: &WalletReceivePolicies,
+    ) {
+        if policies.is_empty() {
+            let _ = txn.delete(
+                self.receive_policies_handle.unwrap(),
+                wallet_id.as_bytes(),
+                None,
+            );
+        } else {
+            txn.put(
+                self.receive_policies_handle.unwrap(),
+                wallet_id.as_bytes(),
+                &serde_json::to_vec(policies).unwrap(),
+                WriteFlags::empty(),
+            )
+            .unwrap();
+        }
+    }
+
+    /// The receive policy of `account` or the wallet policy if `account` is `None`
+    pub fn receive_policy(
+        &self,
+        wallet_id: &WalletId,
+        account: Option<Account>,
+    ) -> Result<(ReceivePolicy, ReceivePolicyScope), WalletsError> {
+        let guard = self.mutex.lock().unwrap();
+        let wallet = Self::get_wallet(&guard, wallet_id)?;
+        let tx = self.env.tx_begin_read();
+        let policies = self.get_receive_policies(&tx, wallet_id);
+        match account {
+            Some(account) => {
+                if !wallet.store.exists(&tx, &account.into()) {
+                    return Err(WalletsError::AccountNotFound);
+                }
+                Ok(policies.effective(&account))
+            }
+            None => Ok(match policies.wallet {
+                Some(policy) => (policy, ReceivePolicyScope::Wallet),
+                None => (ReceivePolicy::default(), ReceivePolicyScope::Default),
+            }),
+        }
+    }
+
+    /// Sets or removes (`policy` is `None`) the receive policy of `account` or of the
+    /// whole wallet if `account` is `None`
+    pub fn set_receive_policy(
+        &self,
+        wallet_id: &WalletId,
+        account: Option<Account>,
+        policy: Option<ReceivePolicy>,
+    ) -> Result<(), WalletsError> {
+        let guard = self.mutex.lock().unwrap();
+        let wallet = Self::get_wallet(&guard, wallet_id)?;
+        let mut tx = self.env.tx_begin_write();
+        if let Some(account) = &account {
+            if !wallet.store.exists(&tx, &(*account).into()) {
+                return Err(WalletsError::AccountNotFound);
+            }
+        }
+        let mut policies = self.get_receive_policies(&tx, wallet_id);
+        policies.set(account, policy);
+        self.put_receive_policies(&mut tx, wallet_id, &policies);
+        Ok(())
+    }
+
+    fn get_rep_health_policy(
+        &self,
+        txn: &dyn Transaction,
+        wallet_id: &WalletId,
+    ) -> Option<RepHealthPolicy> {
+        match txn.get(
+            self.rep_health_policies_handle.unwrap(),
+            wallet_id.as_bytes(),
+        ) {
+            Ok(bytes) => serde_json::from_slice(bytes)
+                .inspect_err(|e| {
+                    warn!(
+                        "Invalid representative health policy of wallet {}: {:?}",
+                        wallet_id, e
+                    )
+                })
+                .ok(),
+            Err(_) => None,
+        }
+    }
+
+    /// The representative health policy of the wallet. Wallets without policy are not monitored
+    pub fn rep_health_policy(
+        &self,
+        wallet_id: &WalletId,
+    ) -> Result<Option<RepHealthPolicy>, WalletsError> {
+        let guard = self.mutex.lock().unwrap();
+        Self::get_wallet(&guard, wallet_id)?;
+        let tx = self.env.tx_begin_read();
+        Ok(self.get_rep_health_policy(&tx, wallet_id))
+    }
+
+    /// Sets or removes (`policy` is `None`) the representative health policy of the wallet
+    pub fn set_rep_health_policy(
+        &self,
+        wallet_id: &WalletId,
+        policy: Option<RepHealthPolicy>,
+    ) -> Result<(), WalletsError> {
+        let guard = self.mutex.lock().unwrap();
+        Self::get_wallet(&guard, wallet_id)?;
+        let mut tx = self.env.tx_begin_write();
+        let handle = self.rep_health_policies_handle.unwrap();
+        match policy {
+            Some(policy) => tx
+                .put(
+                    handle,
+                    wallet_id.as_bytes(),
+                    &serde_json::to_vec(&policy).unwrap(),
+                    WriteFlags::empty(),
+                )
+                .unwrap(),
+            None => {
+                let _ = tx.delete(handle, wallet_id.as_bytes(), None);
+            }
+        }
+        Ok(())
+    }
+
+    /// All wallets which have a representative health policy
+    pub fn rep_health_policies(&self) -> Vec<(WalletId, RepHealthPolicy)> {
+        let guard = self.mutex.lock().unwrap();
+        let tx = self.env.tx_begin_read();
+        guard
+            .keys()
+            .filter_map(|id| self.get_rep_health_policy(&tx, id).map(|p| (*id, p)))
+            .collect()
+    }
+
+    /// Checks the receive policy of `account` before a receivable block gets received
+    /// automatically. Consumes one receive of the policy's budget if allowed
+    fn auto_receive_allowed(
+        &self,
+        wallet: &Wallet,
+        policies: &WalletReceivePolicies,
+        account: &Account,
+        source: &Account,
+        amount: Amount,
+    ) -> bool {
+        let (policy, scope) = policies.effective(account);
+        if !policy.allows(source, amount) {
+            return false;
+        }
+        let allowed = self.receive_budgets.lock().unwrap().try_consume(
+            wallet.id,
+            account,
+            scope,
+            policy.max_per_minute,
+            Instant::now(),
+        );
+        if !allowed {
+            info!(
+                "Receive budget of account {} exhausted",
+                account.encode_account()
+            );
+        }
+        allowed
+    }
+
     pub fn clear_send_ids(&self) {
         let mut tx = self.env.tx_begin_write();
         tx.clear_db(self.send_action_ids_handle.unwrap()).unwrap();
@@ -292,44 +487,169 @@ impl Wallets {
     where
         F: FnMut(&KeyPair),
     {
-        if self.node_config.enable_voting {
-            let mut action_accounts_l: Vec<KeyPair> = Vec::new();
-            {
-                let transaction_l = self.env.tx_begin_read();
-                let ledger_txn = self.ledger.read_txn();
-                let lock = self.mutex.lock().unwrap();
-                for (wallet_id, wallet) in lock.iter() {
-                    let representatives_l = wallet.representatives.lock().unwrap().clone();
-                    for account in representatives_l {
-                        if wallet.store.exists(&transaction_l, &account.into()) {
-                            if !self.ledger.weight_exact(&ledger_txn, account).is_zero() {
-                                if wallet.store.valid_password(&transaction_l) {
-                                    let prv = wallet
-                                        .store
-                                        .fetch(&transaction_l, &account.into())
-                                        .expect("could not fetch account from wallet");
-
-                                    action_accounts_l.push(prv.into());
-                                } else {
-                                    let mut last_log_guard = self.last_log.lock().unwrap();
-                                    let should_log = match last_log_guard.as_ref() {
-                                        Some(i) => i.elapsed() >= Duration::from_secs(60),
-                                        None => true,
-                                    };
-                                    if should_log {
-                                        *last_log_guard = Some(Instant::now());
-                                        warn!("Representative locked inside wallet {}", wallet_id);
-                                    }
-                                }
+        for (_, keys) in self.representative_keys() {
+            if let Some(keys) = keys {
+                action(&keys);
+            }
+        }
+    }
+
+    /// Creates a vote for every voting representative with `create_vote` and signs it.
+    /// Votes of watch only representatives are sent to the external signer as one batch,
+    /// which is given up after the `vote_timeout`, so a slow signer can't stall voting
+    pub fn sign_representative_votes<F>(&self, mut create_vote: F) -> Vec<Vote>
+    where
+        F: FnMut(&PublicKey) -> Vote,
+    {
+        let mut signed = Vec::new();
+        let mut external = Vec::new();
+        for (pub_key, keys) in self.representative_keys() {
+            let mut vote = create_vote(&pub_key);
+            match keys {
+                Some(keys) => match LocalSigner::new(keys).sign(&SignRequest::vote(&vote)) {
+                    Ok(signature) => {
+                        vote.signature = signature;
+                        signed.push(vote);
+                    }
+                    Err(e) => warn!("Could not sign vote of representative {}: {:?}", pub_key, e),
+                },
+                None => external.push(vote),
+            }
+        }
+
+        if let Some(vote_signer) = &self.vote_signer {
+            if !external.is_empty() {
+                let requests: Vec<_> = external.iter().map(SignRequest::vote).collect();
+                let signatures = vote_signer
+                    .sign_batch(&requests, self.node_config.external_signer.vote_timeout);
+                for (mut vote, signature) in external.into_iter().zip(signatures) {
+                    if let Some(signature) = signature {
+                        vote.signature = signature;
+                        signed.push(vote);
+                    }
+                }
+            }
+        }
+        signed
+    }
+
+    /// Voting representatives of all wallets with their private key. The key is `None`
+    /// for representatives which are only watched by the wallet
+    fn representative_keys(&self) -> Vec<(PublicKey, Option<KeyPair>)> {
+        let mut result = Vec::new();
+        if !self.node_config.enable_voting {
+            return result;
+        }
+        let transaction_l = self.env.tx_begin_read();
+        let ledger_txn = self.ledger.read_txn();
+        let lock = self.mutex.lock().unwrap();
+        for (wallet_id, wallet) in lock.iter() {
+            let representatives_l = wallet.representatives.lock().unwrap().clone();
+            for account in representatives_l {
+                if wallet.store.exists(&transaction_l, &account.into()) {
+                    if !self.ledger.weight_exact(&ledger_txn, account).is_zero() {
+                        if wallet.store.valid_password(&transaction_l) {
+                            let keys = wallet
+                                .store
+                                .fetch(&transaction_l, &account.into())
+                                .ok()
+                                .map(KeyPair::from);
+                            result.push((account, keys));
+                        } else {
+                            let mut last_log_guard = self.last_log.lock().unwrap();
+                            let should_log = match last_log_guard.as_ref() {
+                                Some(i) => i.elapsed() >= Duration::from_secs(60),
+                                None => true,
+                            };
+                            if should_log {
+                                *last_log_guard = Some(Instant::now());
+                                warn!("Representative locked inside wallet {}", wallet_id);
                             }
                         }
                     }
                 }
             }
-            for keys in action_accounts_l {
-                action(&keys);
+        }
+        result
+    }
+
+    /// The signer for blocks of `pub_key`: its private key from the wallet store or the
+    /// external signer if the wallet only watches the account
+    fn signer(
+        &self,
+        wallet: &Wallet,
+        tx: &dyn Transaction,
+        pub_key: &PublicKey,
+    ) -> Option<Arc<dyn Signer>> {
+        if !wallet.store.valid_password(tx) {
+            return None;
+        }
+        match wallet.store.fetch(tx, pub_key) {
+            Ok(prv) => Some(Arc::new(LocalSigner::new(prv.into()))),
+            Err(_) => self.external_signer.clone(),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn unsigned_block(
+        &self,
+        wallet: &Wallet,
+        tx: &dyn Transaction,
+        account: Account,
+        previous: BlockHash,
+        representative: PublicKey,
+        balance: Amount,
+        link: Link,
+        work: u64,
+    ) -> Option<UnsignedBlock> {
+        let signer = self.signer(wallet, tx, &account.into())?;
+        Some(UnsignedBlock {
+            signer,
+            account,
+            previous,
+            representative,
+            balance,
+            link,
+            work,
+        })
+    }
+
+    /// Signs the block without holding any transaction, because an external signer
+    /// can block until its timeout. The block is dropped if the account chain was
+    /// extended in the meantime
+    fn sign_unsigned(&self, unsigned: &UnsignedBlock) -> Option<BlockEnum> {
+        let block = match sign_state_block(
+            unsigned.signer.as_ref(),
+            unsigned.account,
+            unsigned.previous,
+            unsigned.representative,
+            unsigned.balance,
+            unsigned.link,
+            unsigned.work,
+        ) {
+            Ok(block) => block,
+            Err(e) => {
+                warn!(
+                    "Could not sign block of account {}: {:?}",
+                    unsigned.account, e
+                );
+                return None;
             }
+        };
+
+        let head = self
+            .ledger
+            .any()
+            .account_head(&self.ledger.read_txn(), &unsigned.account)
+            .unwrap_or_default();
+        if head != unsigned.previous {
+            warn!(
+                "Account {} changed while its block was signed",
+                unsigned.account
+            );
+            return None;
         }
+        Some(block)
     }
 
     pub fn work_cache_blocking2(
@@ -480,7 +800,6 @@ impl Wallets {
         for id in wallet_ids {
             // New wallet
             if !guard.contains_key(&id) {
-                let text = PathBuf::from(id.encode_hex());
                 let representative = self.node_config.random_representative();
                 if let Ok(wallet) = Wallet::new(
                     Arc::clone(&self.ledger),
@@ -489,7 +808,7 @@ impl Wallets {
                     self.node_config.password_fanout as usize,
                     self.kdf.clone(),
                     representative,
-                    &text,
+                    id,
                 ) {
                     guard.insert(id, Arc::new(wallet));
                 }
@@ -516,6 +835,13 @@ impl Wallets {
         let _action_guard = self.wallet_actions.lock_safe();
         let wallet = guard.remove(id).unwrap();
         wallet.store.destroy(&mut tx);
+        self.put_receive_policies(&mut tx, id, &WalletReceivePolicies::default());
+        let _ = tx.delete(
+            self.rep_health_policies_handle.unwrap(),
+            id.as_bytes(),
+            None,
+        );
+        self.receive_budgets.lock().unwrap().clear_wallet(id);
     }
 
     pub fn remove_key(
@@ -572,19 +898,103 @@ impl Wallets {
             .map_err(|_| WalletsError::AccountNotFound)
     }
 
+    /// Writes a backup file for each wallet. If the `wallet_backup` config has a password,
+    /// the backups are encrypted with it, otherwise the unencrypted format is written
     pub fn backup(&self, path: &Path) -> anyhow::Result<()> {
+        std::fs::create_dir_all(path)?;
+        std::fs::set_permissions(path, Permissions::from_mode(0o700))?;
+        let config = &self.node_config.wallet_backup;
+        if config.password.is_empty() {
+            return self.backup_unencrypted(path);
+        }
+
+        // Deriving the key is slow on purpose, so the wallets are encrypted
+        // without holding the lock
+        let contents = {
+            let guard = self.mutex.lock().unwrap();
+            let tx = self.env.tx_begin_read();
+            guard
+                .iter()
+                .map(|(id, wallet)| {
+                    let content = WalletBackupContent::from_entries(&wallet.store.entries(&tx))?;
+                    Ok((*id, content))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?
+        };
+
+        for (id, content) in contents {
+            let backup =
+                EncryptedWalletBackup::encrypt(&content, id, &config.password, &config.kdf)?;
+            // The file is created with restricted permissions right away and
+            // then moved over the previous backup
+            let backup_path = path.join(format!("{}.wallet", id));
+            let tmp_path = backup_path.with_extension("wallet.tmp");
+            let _ = std::fs::remove_file(&tmp_path);
+            let mut file = OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .mode(0o600)
+                .open(&tmp_path)?;
+            file.write_all(backup.to_json()?.as_bytes())?;
+            file.sync_all()?;
+            std::fs::rename(&tmp_path, &backup_path)?;
+        }
+        Ok(())
+    }
+
+    fn backup_unencrypted(&self, path: &Path) -> anyhow::Result<()> {
         let guard = self.mutex.lock().unwrap();
         let tx = self.env.tx_begin_read();
         for (id, wallet) in guard.iter() {
-            std::fs::create_dir_all(path)?;
-            std::fs::set_permissions(path, Permissions::from_mode(0o700))?;
-            let mut backup_path = PathBuf::from(path);
-            backup_path.push(format!("{}.json", id));
+            let backup_path = path.join(format!("{}.json", id));
             wallet.store.write_backup(&tx, &backup_path)?;
         }
         Ok(())
     }
 
+    /// Exports a wallet in the encrypted backup format.
+    /// The wallet doesn't need to be unlocked, because the private keys stay
+    /// encrypted with the wallet password
+    pub fn export_encrypted(
+        &self,
+        wallet_id: &WalletId,
+        password: &str,
+        kdf: &Argon2idParams,
+    ) -> anyhow::Result<String> {
+        let guard = self.mutex.lock().unwrap();
+        let wallet = Self::get_wallet(&guard, wallet_id)?;
+        let tx = self.env.tx_begin_read();
+        let content = WalletBackupContent::from_entries(&wallet.store.entries(&tx))?;
+        EncryptedWalletBackup::encrypt(&content, *wallet_id, password, kdf)?.to_json()
+    }
+
+    /// Creates a new wallet from an encrypted backup. The wallet can then be
+    /// unlocked with the password it had when the backup was created
+    pub fn import_encrypted(
+        &self,
+        wallet_id: WalletId,
+        backup: &str,
+        password: &str,
+    ) -> anyhow::Result<()> {
+        let content = EncryptedWalletBackup::from_json(backup)?.decrypt(password)?;
+        let mut guard = self.mutex.lock().unwrap();
+        if guard.contains_key(&wallet_id) {
+            bail!("wallet {} already exists", wallet_id);
+        }
+        let mut tx = self.env.tx_begin_write();
+        let wallet = Wallet::new_from_entries(
+            Arc::clone(&self.ledger),
+            self.work_thresholds.clone(),
+            &mut tx,
+            self.node_config.password_fanout as usize,
+            self.kdf.clone(),
+            wallet_id,
+            &content.to_entries(),
+        )?;
+        guard.insert(wallet_id, Arc::new(wallet));
+        Ok(())
+    }
+
     pub fn deterministic_index_get(&self, wallet_id: &WalletId) -> Result<u32, WalletsError> {
         let guard = self.mutex.lock().unwrap();
         let wallet = Self::get_wallet(&guard, wallet_id)?;
@@ -600,7 +1010,7 @@ impl Wallets {
         account: Account,
         amount: Amount,
         mut work: u64,
-    ) -> (Option<BlockEnum>, bool, bool, BlockDetails) {
+    ) -> (Option<UnsignedBlock>, BlockDetails) {
         let block_tx = self.ledger.read_txn();
         let mut details = BlockDetails::new(Epoch::Epoch0, true, false, false);
         let mut block = None;
@@ -612,102 +1022,85 @@ impl Wallets {
                 .unwrap_or_default();
             if !balance.is_zero() && balance >= amount {
                 let info = self.ledger.account_info(&block_tx, &source).unwrap();
-                let prv_key = wallet.store.fetch(tx, &source.into()).unwrap();
                 if work == 0 {
                     work = wallet
                         .store
                         .work_get(tx, &source.into())
                         .unwrap_or_default();
                 }
-                let keys = KeyPair::from(prv_key);
-                let state_block = BlockEnum::State(StateBlock::new(
+                block = self.unsigned_block(
+                    wallet,
+                    tx,
                     source,
                     info.head,
                     info.representative,
                     balance - amount,
                     account.into(),
-                    &keys,
                     work,
-                ));
-                block = Some(state_block);
+                );
                 details = BlockDetails::new(info.epoch, true, false, false);
             }
         }
 
-        let error = false;
-        let cached_block = false;
-        (block, error, cached_block, details)
+        (block, details)
     }
 
+    /// Sends are idempotent per `id`: if a block was already created for the id,
+    /// it is republished instead of creating a new one
     fn prepare_send_with_id(
         &self,
-        tx: &mut LmdbWriteTransaction,
         id: &str,
         wallet: &Arc<Wallet>,
         source: Account,
         account: Account,
         amount: Amount,
-        mut work: u64,
+        work: u64,
     ) -> (Option<BlockEnum>, bool, bool, BlockDetails) {
-        let block_tx = self.ledger.read_txn();
-        let mut details = BlockDetails::new(Epoch::Epoch0, true, false, false);
-
-        let mut block = match self.get_block_hash(tx, id) {
-            Ok(Some(hash)) => Some(self.ledger.any().get_block(&block_tx, &hash).unwrap()),
-            Ok(None) => None,
-            _ => {
-                return (None, true, false, details);
+        let details = BlockDetails::new(Epoch::Epoch0, true, false, false);
+        let (unsigned, details) = {
+            let tx = self.env.tx_begin_read();
+            match self.get_block_hash(&tx, id) {
+                Ok(Some(hash)) => return (Some(self.republish(&hash)), false, true, details),
+                Ok(None) => {}
+                Err(_) => return (None, true, false, details),
             }
+            self.prepare_send(&tx, wallet, source, account, amount, work)
         };
 
-        let cached_block: bool;
-        let mut error = false;
+        let Some(block) = unsigned.and_then(|u| self.sign_unsigned(&u)) else {
+            return (None, false, false, details);
+        };
 
-        if let Some(block) = &block {
-            cached_block = true;
-            let msg = Message::Publish(Publish::new_forward(block.clone()));
-            self.message_publisher
-                .lock()
-                .unwrap()
-                .flood(&msg, DropPolicy::ShouldNotDrop, 1.0);
-        } else {
-            cached_block = false;
-            if wallet.store.valid_password(tx) {
-                let balance = self
-                    .ledger
-                    .any()
-                    .account_balance(&block_tx, &source)
-                    .unwrap_or_default();
-                if !balance.is_zero() && balance >= amount {
-                    let info = self.ledger.account_info(&block_tx, &source).unwrap();
-                    let prv_key = wallet.store.fetch(tx, &source.into()).unwrap();
-                    if work == 0 {
-                        work = wallet
-                            .store
-                            .work_get(tx, &source.into())
-                            .unwrap_or_default();
-                    }
-                    let keys = KeyPair::from(prv_key);
-                    let state_block = BlockEnum::State(StateBlock::new(
-                        source,
-                        info.head,
-                        info.representative,
-                        balance - amount,
-                        account.into(),
-                        &keys,
-                        work,
-                    ));
-                    details = BlockDetails::new(info.epoch, true, false, false);
-                    if self.set_block_hash(tx, id, &state_block.hash()).is_err() {
-                        error = true;
-                    } else {
-                        block = Some(state_block);
-                    }
+        // Another send with the same id could have completed while the block was signed
+        let mut tx = self.env.tx_begin_write();
+        match self.get_block_hash(&tx, id) {
+            Ok(Some(hash)) => {
+                drop(tx);
+                (Some(self.republish(&hash)), false, true, details)
+            }
+            Ok(None) => {
+                if self.set_block_hash(&mut tx, id, &block.hash()).is_err() {
+                    (None, true, false, details)
+                } else {
+                    (Some(block), false, false, details)
                 }
             }
+            Err(_) => (None, true, false, details),
         }
+    }
 
-        (block, error, cached_block, details)
+    fn republish(&self, hash: &BlockHash) -> BlockEnum {
+        let block = self
+            .ledger
+            .any()
+            .get_block(&self.ledger.read_txn(), hash)
+            .unwrap();
+        let msg = Message::Publish(Publish::new_forward(block.clone()));
+        self.message_publisher
+            .lock()
+            .unwrap()
+            .flood(&msg, DropPolicy::ShouldNotDrop, 1.0);
+        block
     }
 
     pub fn work_get(&self, wallet_id: &WalletId, pub_key: &PublicKey) -> u64 {
@@ -791,7 +1184,7 @@ impl Wallets {
             &mut tx,
             self.node_config.password_fanout as usize,
             self.kdf.clone(),
-            &PathBuf::from(wallet_id.to_string()),
+            wallet_id,
             json,
         )?;
         Ok(())
@@ -836,6 +1229,16 @@ impl Wallets {
         Ok(wallet.store.seed(&tx))
     }
 
+    pub fn get_mnemonic(&self, wallet_id: WalletId) -> Result<Mnemonic, WalletsError> {
+        let guard = self.mutex.lock().unwrap();
+        let wallet = Self::get_wallet(&guard, &wallet_id)?;
+        let tx = self.env.tx_begin_read();
+        if !wallet.store.valid_password(&tx) {
+            return Err(WalletsError::WalletLocked);
+        }
+        wallet.store.mnemonic(&tx).ok_or(WalletsError::NoMnemonic)
+    }
+
     pub fn key_type(&self, wallet_id: WalletId, pub_key: &PublicKey) -> KeyType {
         let guard = self.mutex.lock().unwrap();
         match guard.get(&wallet_id) {
@@ -913,6 +1316,18 @@ impl Drop for Wallets {
     }
 }
 
+/// A state block which still has to be signed. Signing happens after all transactions
+/// were released, because an external signer can take until its timeout to respond
+struct UnsignedBlock {
+    signer: Arc<dyn Signer>,
+    account: Account,
+    previous: BlockHash,
+    representative: PublicKey,
+    balance: Amount,
+    link: Link,
+    work: u64,
+}
+
 const GENERATE_PRIORITY: Amount = Amount::MAX;
 const HIGH_PRIORITY: Amount = Amount::raw(u128::MAX - 1);
 
@@ -974,6 +1389,21 @@ pub trait WalletsExt {
         count: u32,
     ) -> PublicKey;
 
+    fn deterministic_restore(
+        &self,
+        wallet: &Arc<Wallet>,
+        tx: &mut LmdbWriteTransaction,
+        count: u32,
+    ) -> PublicKey;
+
+    fn change_mnemonic(
+        &self,
+        wallet_id: WalletId,
+        mnemonic: &Mnemonic,
+        passphrase: &str,
+        count: u32,
+    ) -> Result<(u32, Account), WalletsError>;
+
     fn send_action(
         &self,
         wallet: &Arc<Wallet>,
@@ -1171,19 +1601,22 @@ impl WalletsExt for Arc<Wallets> {
         work: u64,
         generate_work: bool,
     ) -> Result<Option<BlockEnum>, WalletsError> {
-        let guard = self.mutex.lock().unwrap();
-        let wallet = Wallets::get_wallet(&guard, wallet_id)?;
-        let tx = self.env.tx_begin_read();
-        if !wallet.store.valid_password(&tx) {
-            return Err(WalletsError::WalletLocked);
-        }
+        let wallet = {
+            let guard = self.mutex.lock().unwrap();
+            let wallet = Arc::clone(Wallets::get_wallet(&guard, wallet_id)?);
+            let tx = self.env.tx_begin_read();
+            if !wallet.store.valid_password(&tx) {
+                return Err(WalletsError::WalletLocked);
+            }
 
-        if wallet.store.find(&tx, &account.into()).is_end() {
-            return Err(WalletsError::AccountNotFound);
-        }
+            if wallet.store.find(&tx, &account.into()).is_end() {
+                return Err(WalletsError::AccountNotFound);
+            }
+            wallet
+        };
 
         Ok(self.receive_action(
-            wallet,
+            &wallet,
             send_hash,
             representative,
             amount,
@@ -1349,7 +1782,7 @@ impl WalletsExt for Arc<Wallets> {
         }
         let result = self.block_processor.add_blocking(block, BlockSource::Local);
 
-        if !matches!(result, Some(BlockStatus::Progress)) {
+        if !matches!(result, Ok(BlockStatus::Progress)) {
             bail!("block processor failed: {:?}", result);
         }
 
@@ -1386,9 +1819,18 @@ impl WalletsExt for Arc<Wallets> {
         wallet: &Arc<Wallet>,
         tx: &mut LmdbWriteTransaction,
         prv_key: &RawKey,
-        mut count: u32,
+        count: u32,
     ) -> PublicKey {
         wallet.store.set_seed(tx, prv_key);
+        self.deterministic_restore(wallet, tx, count)
+    }
+
+    fn deterministic_restore(
+        &self,
+        wallet: &Arc<Wallet>,
+        tx: &mut LmdbWriteTransaction,
+        mut count: u32,
+    ) -> PublicKey {
         let mut account = self.deterministic_insert(wallet, tx, true);
         if count == 0 {
             count = wallet.deterministic_check(tx, 0);
@@ -1417,6 +1859,25 @@ impl WalletsExt for Arc<Wallets> {
         Ok((restored_count, first_account.into()))
     }
 
+    fn change_mnemonic(
+        &self,
+        wallet_id: WalletId,
+        mnemonic: &Mnemonic,
+        passphrase: &str,
+        count: u32,
+    ) -> Result<(u32, Account), WalletsError> {
+        let guard = self.mutex.lock().unwrap();
+        let wallet = Wallets::get_wallet(&guard, &wallet_id)?;
+        let mut tx = self.env.tx_begin_write();
+        if !wallet.store.valid_password(&tx) {
+            return Err(WalletsError::WalletLocked);
+        }
+        wallet.store.set_mnemonic(&mut tx, mnemonic, passphrase);
+        let first_account = self.deterministic_restore(wallet, &mut tx, count);
+        let restored_count = wallet.store.deterministic_index_get(&tx);
+        Ok((restored_count, first_account.into()))
+    }
+
     fn send_action2(
         &self,
         wallet_id: &WalletId,
@@ -1427,9 +1888,11 @@ impl WalletsExt for Arc<Wallets> {
         generate_work: bool,
         id: Option<String>,
     ) -> Result<BlockEnum, WalletsError> {
-        let guard = self.mutex.lock().unwrap();
-        let wallet = Wallets::get_wallet(&guard, &wallet_id)?;
-        self.send_action(wallet, source, account, amount, work, generate_work, id)
+        let wallet = {
+            let guard = self.mutex.lock().unwrap();
+            Arc::clone(Wallets::get_wallet(&guard, &wallet_id)?)
+        };
+        self.send_action(&wallet, source, account, amount, work, generate_work, id)
             .ok_or(WalletsError::Generic)
     }
 
@@ -1444,13 +1907,14 @@ impl WalletsExt for Arc<Wallets> {
         id: Option<String>,
     ) -> Option<BlockEnum> {
         let (mut block, error, cached_block, details) = match id {
-            Some(id) => {
-                let mut tx = self.env.tx_begin_write();
-                self.prepare_send_with_id(&mut tx, &id, wallet, source, account, amount, work)
-            }
+            Some(id) => self.prepare_send_with_id(&id, wallet, source, account, amount, work),
             None => {
-                let tx = self.env.tx_begin_read();
-                self.prepare_send(&tx, wallet, source, account, amount, work)
+                let (unsigned, details) = {
+                    let tx = self.env.tx_begin_read();
+                    self.prepare_send(&tx, wallet, source, account, amount, work)
+                };
+                let block = unsigned.and_then(|u| self.sign_unsigned(&u));
+                (block, false, false, details)
             }
         };
 
@@ -1488,7 +1952,7 @@ impl WalletsExt for Arc<Wallets> {
         generate_work: bool,
     ) -> Option<BlockEnum> {
         let mut epoch = Epoch::Epoch0;
-        let mut block = None;
+        let mut unsigned = None;
         {
             let wallet_tx = self.env.tx_begin_read();
             let block_tx = self.ledger.read_txn();
@@ -1499,28 +1963,28 @@ impl WalletsExt for Arc<Wallets> {
             let existing = wallet.store.find(&wallet_tx, &source.into());
             if !existing.is_end() && self.ledger.any().account_head(&block_tx, &source).is_some() {
                 let info = self.ledger.account_info(&block_tx, &source).unwrap();
-                let prv = wallet.store.fetch(&wallet_tx, &source.into()).unwrap();
                 if work == 0 {
                     work = wallet
                         .store
                         .work_get(&wallet_tx, &source.into())
                         .unwrap_or_default();
                 }
-                let keys = KeyPair::from(prv);
-                let state_block = BlockEnum::State(StateBlock::new(
+                unsigned = self.unsigned_block(
+                    wallet,
+                    &wallet_tx,
                     source,
                     info.head,
                     representative,
                     info.balance,
                     Link::zero(),
-                    &keys,
                     work,
-                ));
-                block = Some(state_block);
+                );
                 epoch = info.epoch;
             }
         }
 
+        let mut block = unsigned.and_then(|u| self.sign_unsigned(&u));
+
         if let Some(b) = block {
             let details = BlockDetails::new(epoch, false, false, false);
             let arc_block = Arc::new(b);
@@ -1552,8 +2016,10 @@ impl WalletsExt for Arc<Wallets> {
         work: u64,
         generate_work: bool,
     ) -> Option<BlockEnum> {
-        let guard = self.mutex.lock().unwrap();
-        let wallet = Wallets::get_wallet(&guard, &wallet_id).ok()?;
+        let wallet = {
+            let guard = self.mutex.lock().unwrap();
+            Arc::clone(Wallets::get_wallet(&guard, &wallet_id).ok()?)
+        };
         self.change_action(&wallet, source, representative, work, generate_work)
     }
 
@@ -1575,7 +2041,7 @@ impl WalletsExt for Arc<Wallets> {
             return None;
         }
 
-        let mut block = None;
+        let mut unsigned = None;
         let mut epoch = Epoch::Epoch0;
         let block_tx = self.ledger.read_txn();
         let wallet_tx = self.env.tx_begin_read();
@@ -1589,37 +2055,37 @@ impl WalletsExt for Arc<Wallets> {
                 .any()
                 .get_pending(&block_tx, &PendingKey::new(account, send_hash))
             {
-                if let Ok(prv) = wallet.store.fetch(&wallet_tx, &account.into()) {
+                if let Some(signer) = self.signer(wallet, &wallet_tx, &account.into()) {
                     if work == 0 {
                         work = wallet
                             .store
                             .work_get(&wallet_tx, &account.into())
                             .unwrap_or_default();
                     }
-                    let keys = KeyPair::from(prv);
-                    if let Some(info) = self.ledger.account_info(&block_tx, &account) {
-                        block = Some(BlockEnum::State(StateBlock::new(
-                            account,
-                            info.head,
-                            info.representative,
-                            info.balance + pending_info.amount,
-                            send_hash.into(),
-                            &keys,
-                            work,
-                        )));
-                        epoch = std::cmp::max(info.epoch, pending_info.epoch);
-                    } else {
-                        block = Some(BlockEnum::State(StateBlock::new(
-                            account,
-                            BlockHash::zero(),
-                            representative,
-                            pending_info.amount,
-                            send_hash.into(),
-                            &keys,
-                            work,
-                        )));
-                        epoch = pending_info.epoch;
-                    }
+                    let (previous, representative, balance) =
+                        match self.ledger.account_info(&block_tx, &account) {
+                            Some(info) => {
+                                epoch = std::cmp::max(info.epoch, pending_info.epoch);
+                                (
+                                    info.head,
+                                    info.representative,
+                                    info.balance + pending_info.amount,
+                                )
+                            }
+                            None => {
+                                epoch = pending_info.epoch;
+                                (BlockHash::zero(), representative, pending_info.amount)
+                            }
+                        };
+                    unsigned = Some(UnsignedBlock {
+                        signer,
+                        account,
+                        previous,
+                        representative,
+                        balance,
+                        link: send_hash.into(),
+                        work,
+                    });
                 } else {
                     warn!("Unable to receive, wallet locked");
                 }
@@ -1629,7 +2095,10 @@ impl WalletsExt for Arc<Wallets> {
         } else {
             // Ledger doesn't have this block anymore.
         }
+        drop(wallet_tx);
+        drop(block_tx);
 
+        let mut block = unsigned.and_then(|u| self.sign_unsigned(&u));
         if let Some(b) = block {
             let details = BlockDetails::new(epoch, false, true, false);
             let arc_block = Arc::new(b);
@@ -1861,6 +2330,7 @@ impl WalletsExt for Arc<Wallets> {
 
         info!("Beginning receivable block search");
 
+        let policies = self.get_receive_policies(wallet_tx, &wallet.id);
         let mut it = wallet.store.begin(wallet_tx);
         while let Some((account, wallet_value)) = it.current() {
             let block_tx = self.ledger.read_txn();
@@ -1873,7 +2343,12 @@ impl WalletsExt for Arc<Wallets> {
                 ) {
                     let hash = key.send_block_hash;
                     let amount = info.amount;
-                    if self.node_config.receive_minimum <= amount {
+                    if self.node_config.receive_minimum <= amount
+                        && policies
+                            .effective(&account.into())
+                            .0
+                            .allows(&info.source, amount)
+                    {
                         info!(
                             "Found a receivable block {} for account {}",
                             hash,
@@ -1884,6 +2359,15 @@ impl WalletsExt for Arc<Wallets> {
                             .confirmed()
                             .block_exists_or_pruned(&block_tx, &hash)
                         {
+                            if !self.auto_receive_allowed(
+                                wallet,
+                                &policies,
+                                &account.into(),
+                                &info.source,
+                                amount,
+                            ) {
+                                continue;
+                            }
                             let representative = wallet.store.representative(wallet_tx);
                             // Receive confirmed block
                             self.receive_async_wallet(
@@ -1933,6 +2417,16 @@ impl WalletsExt for Arc<Wallets> {
                     .get_pending(&self.ledger.read_txn(), &PendingKey::new(destination, hash));
                 if let Some(pending) = pending {
                     let amount = pending.amount;
+                    let policies = self.get_receive_policies(&wallet_tx, &wallet.id);
+                    if !self.auto_receive_allowed(
+                        &wallet,
+                        &policies,
+                        &destination,
+                        &pending.source,
+                        amount,
+                    ) {
+                        continue;
+                    }
                     self.receive_async_wallet(
                         wallet,
                         hash,
@@ -2040,7 +2534,7 @@ impl WalletsExt for Arc<Wallets> {
                 self.node_config.password_fanout as usize,
                 self.kdf.clone(),
                 self.node_config.random_representative(),
-                &PathBuf::from(wallet_id.to_string()),
+                wallet_id,
             ) else {
                 return;
             };
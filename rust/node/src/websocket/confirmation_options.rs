@@ -1,6 +1,6 @@
+use super::ConfirmationJsonOptions;
 use crate::wallets::Wallets;
 use rsnano_core::{utils::PropertyTree, Account};
-use serde::Deserialize;
 use serde_json::Value;
 use std::{collections::HashSet, sync::Arc};
 use tracing::warn;
@@ -18,17 +18,6 @@ pub struct ConfirmationOptions {
     wallets: Arc<Wallets>,
 }
 
-#[derive(Deserialize, Default)]
-pub struct ConfirmationJsonOptions {
-    pub include_block: Option<bool>,
-    pub include_election_info: Option<bool>,
-    pub include_election_info_with_votes: Option<bool>,
-    pub include_sideband_info: Option<bool>,
-    pub confirmation_type: Option<String>,
-    pub all_local_accounts: Option<bool>,
-    pub accounts: Option<Vec<String>>,
-}
-
 impl ConfirmationOptions {
     const TYPE_ACTIVE_QUORUM: u8 = 1;
     const TYPE_ACTIVE_CONFIRMATION_HEIGHT: u8 = 2;
@@ -1,14 +1,12 @@
 use super::{
-    ConfirmationJsonOptions, ConfirmationOptions, Options, OutgoingMessageEnvelope, Topic,
-    WebsocketSessionEntry,
+    BlockConfirmed, ConfirmationJsonOptions, ConfirmationOptions, ElectionInfo, JsonSideband,
+    JsonVoteSummary, Options, OutgoingMessageEnvelope, Topic, WebsocketSessionEntry,
 };
 use crate::{consensus::ElectionStatus, wallets::Wallets, websocket::WebsocketSession};
 use rsnano_core::{
     utils::{PropertyTree, SerdePropertyTree},
     Account, Amount, BlockEnum, BlockSideband, VoteWithWeightInfo,
 };
-use serde::Serialize;
-use serde_json::Value;
 use std::{
     borrow::Cow,
     net::SocketAddr,
@@ -272,9 +270,9 @@ fn block_confirmed_message(
 ) -> OutgoingMessageEnvelope {
     let election_info = if options.include_election_info || options.include_election_info_with_votes
     {
-        let mut info = ElectionInfo::from(election_status);
+        let mut info = election_info(election_status);
         if options.include_election_info_with_votes {
-            info.votes = Some(election_votes.iter().map(|v| v.into()).collect());
+            info.votes = Some(election_votes.iter().map(vote_summary).collect());
         }
         Some(info)
     } else {
@@ -293,7 +291,7 @@ fn block_confirmed_message(
     };
 
     let sideband = if options.include_sideband_info {
-        Some(block.sideband().unwrap().into())
+        Some(json_sideband(block.sideband().unwrap()))
     } else {
         None
     };
@@ -312,84 +310,36 @@ fn block_confirmed_message(
     )
 }
 
-#[derive(Serialize)]
-struct JsonSideband {
-    height: String,
-    local_timestamp: String,
-}
-
-impl From<&BlockSideband> for JsonSideband {
-    fn from(value: &BlockSideband) -> Self {
-        Self {
-            height: value.height.to_string(),
-            local_timestamp: value.timestamp.to_string(),
-        }
+fn json_sideband(sideband: &BlockSideband) -> JsonSideband {
+    JsonSideband {
+        height: sideband.height.to_string(),
+        local_timestamp: sideband.timestamp.to_string(),
     }
 }
 
-#[derive(Serialize)]
-struct BlockConfirmed {
-    account: String,
-    amount: String,
-    hash: String,
-    confirmation_type: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    election_info: Option<ElectionInfo>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    block: Option<Value>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    sideband: Option<JsonSideband>,
-}
-
-#[derive(Serialize)]
-struct ElectionInfo {
-    duration: String,
-    time: String,
-    tally: String,
-    #[serde(rename = "final")]
-    final_tally: String,
-    blocks: String,
-    voters: String,
-    request_count: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    votes: Option<Vec<JsonVoteSummary>>,
-}
-
-impl From<&ElectionStatus> for ElectionInfo {
-    fn from(value: &ElectionStatus) -> Self {
-        Self {
-            duration: value.election_duration.as_millis().to_string(),
-            time: value
-                .election_end
-                .duration_since(UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_millis()
-                .to_string(),
-            tally: value.tally.to_string_dec(),
-            final_tally: value.final_tally.to_string_dec(),
-            blocks: value.block_count.to_string(),
-            voters: value.voter_count.to_string(),
-            request_count: value.confirmation_request_count.to_string(),
-            votes: None,
-        }
+fn election_info(status: &ElectionStatus) -> ElectionInfo {
+    ElectionInfo {
+        duration: status.election_duration.as_millis().to_string(),
+        time: status
+            .election_end
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis()
+            .to_string(),
+        tally: status.tally.to_string_dec(),
+        final_tally: status.final_tally.to_string_dec(),
+        blocks: status.block_count.to_string(),
+        voters: status.voter_count.to_string(),
+        request_count: status.confirmation_request_count.to_string(),
+        votes: None,
     }
 }
 
-#[derive(Serialize)]
-struct JsonVoteSummary {
-    representative: String,
-    timestamp: String,
-    hash: String,
-    weight: String,
-}
-
-impl From<&VoteWithWeightInfo> for JsonVoteSummary {
-    fn from(v: &VoteWithWeightInfo) -> Self {
-        Self {
-            representative: Account::from(v.representative).encode_account(),
-            timestamp: v.timestamp.to_string(),
-            hash: v.hash.to_string(),
-            weight: v.weight.to_string_dec(),
-        }
+fn vote_summary(vote: &VoteWithWeightInfo) -> JsonVoteSummary {
+    JsonVoteSummary {
+        representative: Account::from(vote.representative).encode_account(),
+        timestamp: vote.timestamp.to_string(),
+        hash: vote.hash.to_string(),
+        weight: vote.weight.to_string_dec(),
     }
 }
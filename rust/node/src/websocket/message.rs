@@ -1,33 +1,9 @@
+use super::{OutgoingMessageEnvelope, Topic};
 use crate::DEV_NETWORK_PARAMS;
-use rsnano_core::{utils::milliseconds_since_epoch, BlockHash, DifficultyV1, WorkVersion};
+use rsnano_core::{BlockHash, DifficultyV1, WorkVersion};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::{fmt::Debug, hash::Hash, time::Duration};
-
-#[derive(Clone, Copy, FromPrimitive, PartialEq, Eq, Hash, Serialize, Debug, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum Topic {
-    Invalid = 0,
-    /// Acknowledgement of prior incoming message
-    Ack,
-    /// A confirmation message
-    Confirmation,
-    StartedElection,
-    /// Stopped election message (dropped elections due to bounding or block lost the elections)
-    StoppedElection,
-    /// A vote message
-    Vote,
-    /// Work generation message
-    Work,
-    /// A bootstrap message
-    Bootstrap,
-    /// A telemetry message
-    Telemetry,
-    /// New block arrival message
-    NewUnconfirmedBlock,
-    /// Auxiliary length, not a valid topic, must be the last enum
-    Length,
-}
+use std::time::Duration;
 
 #[derive(Deserialize)]
 pub struct IncomingMessage<'a> {
@@ -43,41 +19,6 @@ pub struct IncomingMessage<'a> {
     pub accounts_del: Vec<&'a str>,
 }
 
-#[derive(Serialize, Clone, Debug, Deserialize)]
-pub struct OutgoingMessageEnvelope {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub id: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub ack: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub topic: Option<Topic>,
-    pub time: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub message: Option<Value>,
-}
-
-impl OutgoingMessageEnvelope {
-    pub fn new(topic: Topic, message: impl Serialize) -> Self {
-        Self {
-            id: None,
-            ack: None,
-            topic: Some(topic),
-            time: milliseconds_since_epoch().to_string(),
-            message: Some(serde_json::to_value(message).expect("could not serialize message")),
-        }
-    }
-
-    pub fn new_ack(id: Option<String>, action: String) -> Self {
-        Self {
-            id,
-            topic: None,
-            ack: Some(action),
-            time: milliseconds_since_epoch().to_string(),
-            message: None,
-        }
-    }
-}
-
 #[derive(Serialize)]
 struct WorkGeneration<'a> {
     success: &'a str,
@@ -173,6 +114,7 @@ pub fn to_topic(topic: impl AsRef<str>) -> Topic {
         "bootstrap" => Topic::Bootstrap,
         "telemetry" => Topic::Telemetry,
         "new_unconfirmed_block" => Topic::NewUnconfirmedBlock,
+        "representative_change" => Topic::RepresentativeChange,
         _ => Topic::Invalid,
     }
 }
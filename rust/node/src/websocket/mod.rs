@@ -7,6 +7,8 @@ mod websocket_config;
 mod websocket_server;
 mod websocket_session;
 
+pub use rsnano_rpc_messages::websocket::*;
+
 pub use confirmation_options::*;
 pub use listener::*;
 pub use message::*;
@@ -1,5 +1,5 @@
+use super::VoteJsonOptions;
 use rsnano_core::Account;
-use serde::Deserialize;
 use serde_json::Value;
 use std::collections::HashSet;
 use tracing::warn;
@@ -11,13 +11,6 @@ pub struct VoteOptions {
     include_indeterminate: bool,
 }
 
-#[derive(Deserialize)]
-pub struct VoteJsonOptions {
-    include_replays: Option<bool>,
-    include_indeterminate: Option<bool>,
-    representatives: Option<Vec<String>>,
-}
-
 impl VoteOptions {
     pub fn new(options_a: VoteJsonOptions) -> Self {
         let mut result = Self {
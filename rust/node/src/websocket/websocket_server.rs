@@ -1,13 +1,17 @@
-use super::{OutgoingMessageEnvelope, WebsocketConfig, WebsocketListener};
+use super::{
+    OutgoingMessageEnvelope, RepresentativeChanged, StartedElection, StoppedElection,
+    TelemetryReceived, VoteReceived, WebsocketConfig, WebsocketListener,
+};
 use crate::{
     consensus::{ActiveElections, ElectionStatus, ElectionStatusType, VoteProcessor},
     wallets::Wallets,
     websocket::Topic,
     Telemetry,
 };
-use rsnano_core::{Account, Amount, BlockHash, BlockType, Vote, VoteCode, VoteWithWeightInfo};
+use rsnano_core::{
+    Account, Amount, BlockHash, BlockType, PublicKey, Vote, VoteCode, VoteWithWeightInfo, WalletId,
+};
 use rsnano_messages::TelemetryData;
-use serde::Serialize;
 use std::{
     net::{IpAddr, SocketAddr, SocketAddrV6},
     sync::Arc,
@@ -142,30 +146,6 @@ fn telemetry_received(data: &TelemetryData, endpoint: SocketAddrV6) -> OutgoingM
     )
 }
 
-#[derive(Serialize)]
-struct TelemetryReceived {
-    block_count: String,
-    cemented_count: String,
-    unchecked_count: String,
-    account_count: String,
-    bandwidth_cap: String,
-    peer_count: String,
-    protocol_version: String,
-    uptime: String,
-    genesis_block: String,
-    major_version: String,
-    minor_version: String,
-    patch_version: String,
-    pre_release_version: String,
-    maker: String,
-    timestamp: String,
-    active_difficulty: String,
-    node_id: String,
-    signature: String,
-    address: String,
-    port: String,
-}
-
 fn started_election(hash: &BlockHash) -> OutgoingMessageEnvelope {
     OutgoingMessageEnvelope::new(
         Topic::StartedElection,
@@ -175,11 +155,6 @@ fn started_election(hash: &BlockHash) -> OutgoingMessageEnvelope {
     )
 }
 
-#[derive(Serialize)]
-struct StartedElection {
-    hash: String,
-}
-
 fn stopped_election(hash: &BlockHash) -> OutgoingMessageEnvelope {
     OutgoingMessageEnvelope::new(
         Topic::StoppedElection,
@@ -189,11 +164,6 @@ fn stopped_election(hash: &BlockHash) -> OutgoingMessageEnvelope {
     )
 }
 
-#[derive(Serialize)]
-struct StoppedElection {
-    hash: String,
-}
-
 pub fn vote_received(vote: &Vote, code: VoteCode) -> OutgoingMessageEnvelope {
     OutgoingMessageEnvelope::new(
         Topic::Vote,
@@ -209,14 +179,21 @@ pub fn vote_received(vote: &Vote, code: VoteCode) -> OutgoingMessageEnvelope {
     )
 }
 
-#[derive(Serialize)]
-struct VoteReceived {
-    account: String,
-    signature: String,
-    sequence: String,
-    timestamp: String,
-    duration: String,
-    blocks: Vec<String>,
-    #[serde(rename = "type")]
-    vote_type: String,
+pub fn representative_changed(
+    wallet: &WalletId,
+    old_representative: &PublicKey,
+    new_representative: &PublicKey,
+    reason: &str,
+    accounts: &[Account],
+) -> OutgoingMessageEnvelope {
+    OutgoingMessageEnvelope::new(
+        Topic::RepresentativeChange,
+        RepresentativeChanged {
+            wallet: wallet.encode_hex(),
+            old_representative: Account::from(*old_representative).encode_account(),
+            new_representative: Account::from(*new_representative).encode_account(),
+            reason: reason.to_string(),
+            accounts: accounts.iter().map(|a| a.encode_account()).collect(),
+        },
+    )
 }
@@ -313,7 +313,7 @@ fn inactive_votes_cache_existing_vote() {
 
     let cached = node.vote_cache.lock().unwrap().find(&send.hash());
     assert_eq!(cached.len(), 1);
-    node.vote_router.vote(&cached[0], VoteSource::Live);
+    node.vote_router.vote(&cached[0], VoteSource::Live, None);
 
     // Check that election data is not changed
     assert_eq!(election.vote_count(), 2);
@@ -5,9 +5,11 @@ use rsnano_core::{
 use rsnano_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH};
 use rsnano_messages::BulkPull;
 use rsnano_network::{
-    bandwidth_limiter::OutboundBandwidthLimiter, Channel, ChannelInfo, NullNetworkObserver,
+    bandwidth_limiter::OutboundBandwidthLimiter, Channel, ChannelId, ChannelInfo,
+    NullNetworkObserver,
 };
 use rsnano_node::{
+    block_processing::BlockSource,
     bootstrap::{BootstrapAttemptTrait, BootstrapInitiatorExt, BootstrapStrategy, BulkPullServer},
     config::{FrontiersConfirmationMode, NodeConfig, NodeFlags},
     stats::{DetailType, Direction, StatType},
@@ -1610,6 +1612,31 @@ fn bulk_genesis_pruning() {
     });
 }
 
+#[test]
+fn ascending_bootstrap_measures_round_trip_time() {
+    let mut system = System::new();
+    // Telemetry acks would measure round trip times as well
+    let server_flags = NodeFlags {
+        disable_providing_telemetry_metrics: true,
+        ..Default::default()
+    };
+    let node_server = system.build_node().flags(server_flags).finish();
+    let node_client = system.make_node();
+
+    let channel = node_client
+        .network_info
+        .read()
+        .unwrap()
+        .find_node_id(&node_server.get_node_id())
+        .unwrap()
+        .clone();
+
+    assert_timely(Duration::from_secs(10), || {
+        !channel.round_trip_times().is_empty()
+    });
+    assert!(channel.round_trip_time().is_some());
+}
+
 fn create_response_server(node: &Node) -> Arc<ResponseServer> {
     let channel = Channel::create(
         Arc::new(ChannelInfo::new_test_instance()),
@@ -1639,3 +1666,64 @@ fn create_response_server(node: &Node) -> Arc<ResponseServer> {
         Arc::new(Mutex::new(LatestKeepalives::default())),
     ))
 }
+
+mod gap_resolver {
+    use super::*;
+    use rsnano_ledger::DEV_GENESIS_PUB_KEY;
+
+    #[test]
+    fn request_missing_previous_from_peers() {
+        let mut system = System::new();
+        let node1 = system.make_node();
+        let node2 = system.make_node();
+        let key = KeyPair::new();
+
+        let send1 = BlockEnum::State(StateBlock::new(
+            *DEV_GENESIS_ACCOUNT,
+            *DEV_GENESIS_HASH,
+            *DEV_GENESIS_PUB_KEY,
+            Amount::MAX - Amount::raw(1),
+            key.account().into(),
+            &DEV_GENESIS_KEY,
+            node1.work_generate_dev((*DEV_GENESIS_HASH).into()),
+        ));
+
+        let send2 = BlockEnum::State(StateBlock::new(
+            *DEV_GENESIS_ACCOUNT,
+            send1.hash(),
+            *DEV_GENESIS_PUB_KEY,
+            Amount::MAX - Amount::raw(2),
+            key.account().into(),
+            &DEV_GENESIS_KEY,
+            node1.work_generate_dev(send1.hash().into()),
+        ));
+
+        node1.process(send1.clone()).unwrap();
+        node1.process(send2.clone()).unwrap();
+
+        // send2 arrives as live traffic, but its previous block is missing
+        node2.block_processor.add(
+            Arc::new(send2.clone()),
+            BlockSource::Live,
+            ChannelId::LOOPBACK,
+        );
+
+        assert_timely(Duration::from_secs(10), || {
+            node2.block_exists(&send1.hash()) && node2.block_exists(&send2.hash())
+        });
+        assert_timely(Duration::from_secs(5), || {
+            node2.stats.count(
+                StatType::BootstrapAscendingGap,
+                DetailType::Process,
+                Direction::In,
+            ) > 0
+        });
+        assert!(
+            node2.stats.count(
+                StatType::BootstrapAscendingGap,
+                DetailType::Request,
+                Direction::In
+            ) > 0
+        );
+    }
+}
@@ -86,7 +86,7 @@ fn quorum_minimum_update_weight_before_quorum_checks() {
     assert_eq!(1, election.mutex.lock().unwrap().last_blocks.len());
 
     let vote1 = Arc::new(Vote::new_final(&DEV_GENESIS_KEY, vec![send1.hash()]));
-    node1.vote_router.vote(&vote1, VoteSource::Live);
+    node1.vote_router.vote(&vote1, VoteSource::Live, None);
 
     let channel = node1
         .network_info
@@ -108,7 +108,7 @@ fn quorum_minimum_update_weight_before_quorum_checks() {
         .lock()
         .unwrap()
         .set_online(config.online_weight_minimum + Amount::raw(20));
-    node1.vote_router.vote(&vote2, VoteSource::Live);
+    node1.vote_router.vote(&vote2, VoteSource::Live, None);
     assert_timely(Duration::from_secs(5), || node1.active.confirmed(&election));
     assert!(node1.block(&send1.hash()).is_some());
 }
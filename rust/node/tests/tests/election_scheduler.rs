@@ -1,10 +1,10 @@
 use rsnano_core::Amount;
-use rsnano_node::consensus::{Bucket, PriorityBucketConfig};
+use rsnano_node::consensus::{Bucket, PriorityBucketConfig, PriorityBucketLayout};
 use test_helpers::System;
 
 mod bucket {
     use super::*;
-    use rsnano_core::BlockEnum;
+    use rsnano_core::{utils::seconds_since_epoch, BlockEnum};
     use std::sync::Arc;
 
     #[test]
@@ -122,4 +122,73 @@ mod bucket {
         assert_eq!(blocks[0], block1);
         assert_eq!(blocks[1], block0);
     }
+
+    #[test]
+    fn info() {
+        let mut system = System::new();
+        let node = system.make_node();
+
+        let bucket = Bucket::new(
+            Amount::nano(1000),
+            PriorityBucketConfig::default(),
+            node.active.clone(),
+            node.stats.clone(),
+        );
+
+        let queued_after = seconds_since_epoch();
+        assert!(bucket.push(2000, Arc::new(BlockEnum::new_test_instance_with_key(1))));
+        assert!(bucket.push(1000, Arc::new(BlockEnum::new_test_instance_with_key(2))));
+
+        let info = bucket.info();
+        assert_eq!(info.minimum_balance, Amount::nano(1000));
+        assert_eq!(info.blocks, 2);
+        // The time when the blocks were queued and not their priority
+        let oldest = info.oldest_block_time.unwrap();
+        assert!(oldest >= queued_after && oldest <= seconds_since_epoch());
+        assert_eq!(info.elections, 0);
+        assert_eq!(info.cancellations, 0);
+    }
+}
+
+mod priority_scheduler {
+    use super::*;
+
+    #[test]
+    fn default_layout() {
+        let mut system = System::new();
+        let node = system.make_node();
+
+        let buckets = node.election_schedulers.priority.bucket_infos();
+
+        assert_eq!(buckets.len(), 63);
+        assert_eq!(buckets[0].minimum_balance, Amount::zero());
+        assert!(buckets
+            .windows(2)
+            .all(|b| b[0].minimum_balance < b[1].minimum_balance));
+    }
+
+    #[test]
+    fn custom_layout() {
+        let mut system = System::new();
+        let mut config = System::default_config();
+        config.priority_bucket.max_blocks = 100;
+        config.priority_bucket.buckets = vec![
+            PriorityBucketLayout {
+                max_elections: Some(5),
+                ..PriorityBucketLayout::new(Amount::nano(1000))
+            },
+            PriorityBucketLayout::new(Amount::zero()),
+        ];
+        let node = system.build_node().config(config).finish();
+
+        let buckets = node.election_schedulers.priority.bucket_infos();
+
+        assert_eq!(buckets.len(), 2);
+        assert_eq!(buckets[0].minimum_balance, Amount::zero());
+        assert_eq!(buckets[0].max_blocks, 100);
+        assert_eq!(buckets[0].max_elections, 150);
+        assert_eq!(buckets[1].minimum_balance, Amount::nano(1000));
+        assert_eq!(buckets[1].max_blocks, 100);
+        assert_eq!(buckets[1].max_elections, 5);
+    }
 }
@@ -9,7 +9,9 @@ mod node;
 mod peer_container;
 mod rep_crawler;
 mod request_aggregator;
+mod simulator;
 mod telemetry;
 mod vote_processor;
 mod votes;
+mod wallets;
 mod websocket;
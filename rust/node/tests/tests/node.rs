@@ -798,6 +798,7 @@ fn rollback_vote_self() {
             0,
             &fork.hash(),
             VoteSource::Live,
+            None,
         );
         assert_eq!(1, node.active.votes_with_weight(&election).len());
         // The winner changed
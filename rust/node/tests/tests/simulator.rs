@@ -0,0 +1,67 @@
+use rsnano_core::{Amount, BlockEnum, KeyPair, StateBlock, DEV_GENESIS_KEY};
+use rsnano_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH, DEV_GENESIS_PUB_KEY};
+use rsnano_node::{wallets::WalletsExt, Node};
+use std::time::Duration;
+use test_helpers::{SimNodeId, Simulator};
+
+fn setup(simulator: &mut Simulator) -> [SimNodeId; 3] {
+    let nodes = [
+        simulator.add_node(),
+        simulator.add_node(),
+        simulator.add_node(),
+    ];
+    let node = simulator.node(nodes[0]);
+    let wallet_id = node.wallets.wallet_ids()[0];
+    node.wallets
+        .insert_adhoc2(&wallet_id, &DEV_GENESIS_KEY.private_key(), true)
+        .unwrap();
+    nodes
+}
+
+fn send(node: &Node) -> BlockEnum {
+    let send = BlockEnum::State(StateBlock::new(
+        *DEV_GENESIS_ACCOUNT,
+        *DEV_GENESIS_HASH,
+        *DEV_GENESIS_PUB_KEY,
+        Amount::MAX - Amount::nano(1),
+        KeyPair::new().account().into(),
+        &DEV_GENESIS_KEY,
+        node.work_generate_dev((*DEV_GENESIS_HASH).into()),
+    ));
+    node.process_active(send.clone());
+    send
+}
+
+#[test]
+fn confirm_block_on_all_nodes() {
+    let mut simulator = Simulator::new(42);
+    let nodes = setup(&mut simulator);
+
+    let send = send(simulator.node(nodes[0]));
+
+    let confirmed = simulator.run_until(Duration::from_secs(30), |sim| {
+        nodes
+            .iter()
+            .all(|id| sim.node(*id).block_confirmed(&send.hash()))
+    });
+    assert!(confirmed, "block not confirmed on all nodes");
+    assert!(simulator.network().delivered > 0);
+}
+
+#[test]
+fn partitioned_node_does_not_receive_block() {
+    let mut simulator = Simulator::new(42);
+    let nodes = setup(&mut simulator);
+    simulator
+        .network()
+        .partition(&[&[nodes[0], nodes[1]], &[nodes[2]]]);
+
+    let send = send(simulator.node(nodes[0]));
+
+    let confirmed = simulator.run_until(Duration::from_secs(30), |sim| {
+        sim.node(nodes[1]).block_confirmed(&send.hash())
+    });
+    assert!(confirmed, "block not confirmed in the majority partition");
+    simulator.run_for(Duration::from_secs(1));
+    assert!(!simulator.node(nodes[2]).block_exists(&send.hash()));
+}
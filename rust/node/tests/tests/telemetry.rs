@@ -128,6 +128,34 @@ fn disconnected() {
     });
 }
 
+#[test]
+fn telemetry_measures_round_trip_time() {
+    let mut system = System::new();
+    // Ascending bootstrap responses would measure round trip times as well
+    let mut config = System::default_config();
+    config.bootstrap_ascending.enable = false;
+    let node_client = system.build_node().config(config).finish();
+    let node_server = system.make_node();
+
+    let channel = node_client
+        .network_info
+        .read()
+        .unwrap()
+        .find_node_id(&node_server.get_node_id())
+        .unwrap()
+        .clone();
+
+    assert_timely(Duration::from_secs(5), || {
+        node_client
+            .telemetry
+            .get_telemetry(&channel.peer_addr())
+            .is_some()
+    });
+    assert_timely(Duration::from_secs(5), || {
+        !channel.round_trip_times().is_empty()
+    });
+}
+
 #[test]
 fn disable_metrics() {
     let mut system = System::new();
@@ -0,0 +1,314 @@
+use rsnano_core::{Amount, Argon2idParams, KeyPair, WalletId, DEV_GENESIS_KEY};
+use rsnano_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_PUB_KEY};
+use rsnano_node::{
+    stats::{DetailType, Direction, StatType},
+    wallets::{
+        EncryptedWalletBackup, LocalSigner, ReceivePolicy, RepHealthPolicy, SignRequest,
+        SignResponse, Signer, WalletsExt,
+    },
+};
+use std::{
+    io::{BufRead, BufReader, Write},
+    os::unix::{fs::PermissionsExt, net::UnixListener},
+    thread,
+    time::{Duration, Instant},
+};
+use test_helpers::{assert_never, assert_timely_eq, System};
+
+#[test]
+fn encrypted_export_and_import() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = WalletId::random();
+    node.wallets.create(wallet_id);
+    let key = KeyPair::new();
+    node.wallets
+        .insert_adhoc2(&wallet_id, &key.private_key(), false)
+        .unwrap();
+    node.wallets
+        .work_set(&wallet_id, &key.public_key(), 42)
+        .unwrap();
+    let deterministic = node
+        .wallets
+        .deterministic_insert2(&wallet_id, false)
+        .unwrap();
+    node.wallets.rekey(&wallet_id, "wallet password").unwrap();
+
+    let backup = node
+        .wallets
+        .export_encrypted(&wallet_id, "backup password", &Argon2idParams::new_dev())
+        .unwrap();
+    assert!(node
+        .wallets
+        .import_encrypted(wallet_id, &backup, "backup password")
+        .is_err());
+
+    let imported_id = WalletId::random();
+    assert!(node
+        .wallets
+        .import_encrypted(imported_id, &backup, "wrong password")
+        .is_err());
+    node.wallets
+        .import_encrypted(imported_id, &backup, "backup password")
+        .unwrap();
+
+    node.wallets.enter_password(imported_id, "").unwrap_err();
+    node.wallets
+        .enter_password(imported_id, "wallet password")
+        .unwrap();
+    assert_eq!(
+        node.wallets.fetch(&imported_id, &key.public_key()).unwrap(),
+        key.private_key()
+    );
+    assert_eq!(node.wallets.work_get(&imported_id, &key.public_key()), 42);
+    assert!(node.wallets.fetch(&imported_id, &deterministic).is_ok());
+    assert_eq!(node.wallets.deterministic_index_get(&imported_id), Ok(1));
+}
+
+#[test]
+fn periodic_backup_is_encrypted() {
+    let mut system = System::new();
+    let mut config = System::default_config();
+    config.wallet_backup.password = "backup password".to_string();
+    let node = system.build_node().config(config).finish();
+    let wallet_id = WalletId::random();
+    node.wallets.create(wallet_id);
+    let path = node.data_path.join("backup_test");
+
+    node.wallets.backup(&path).unwrap();
+
+    let contents = std::fs::read_to_string(path.join(format!("{}.wallet", wallet_id))).unwrap();
+    let backup = EncryptedWalletBackup::from_json(&contents).unwrap();
+    assert_eq!(backup.metadata.wallet, wallet_id);
+    assert!(backup.decrypt(&node.config.wallet_backup.password).is_ok());
+    let mode = std::fs::metadata(path.join(format!("{}.wallet", wallet_id)))
+        .unwrap()
+        .permissions()
+        .mode();
+    assert_eq!(mode & 0o777, 0o600);
+
+    // a second backup replaces the existing file
+    node.wallets.backup(&path).unwrap();
+}
+
+#[test]
+fn backup_without_password_is_unencrypted() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = WalletId::random();
+    node.wallets.create(wallet_id);
+    let path = node.data_path.join("backup_test");
+
+    node.wallets.backup(&path).unwrap();
+
+    assert!(path.join(format!("{}.json", wallet_id)).exists());
+    assert!(!path.join(format!("{}.wallet", wallet_id)).exists());
+}
+
+#[test]
+fn watch_only_account_is_signed_by_external_signer() {
+    let socket = std::env::temp_dir().join(format!("signer-{}.sock", rand::random::<u64>()));
+    let listener = UnixListener::bind(&socket).unwrap();
+    let key = KeyPair::new();
+    let signer_key = key.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut line = String::new();
+            BufReader::new(stream.try_clone().unwrap())
+                .read_line(&mut line)
+                .unwrap();
+            let request: SignRequest = serde_json::from_str(&line).unwrap();
+            let response = match LocalSigner::new(signer_key.clone()).sign(&request) {
+                Ok(signature) => SignResponse::signature(signature),
+                Err(e) => SignResponse::error(e.to_string()),
+            };
+            serde_json::to_writer(&mut stream, &response).unwrap();
+            stream.write_all(b"\n").unwrap();
+        }
+    });
+
+    let mut system = System::new();
+    let mut config = System::default_config();
+    config.external_signer.address = format!("unix:{}", socket.display());
+    let node = system.build_node().config(config).finish();
+    let wallet_id = WalletId::random();
+    node.wallets.create(wallet_id);
+    node.wallets
+        .insert_adhoc2(&wallet_id, &DEV_GENESIS_KEY.private_key(), false)
+        .unwrap();
+    node.wallets
+        .insert_watch(&wallet_id, &[key.account()])
+        .unwrap();
+
+    node.wallets.send_sync(
+        wallet_id,
+        *DEV_GENESIS_ACCOUNT,
+        key.account(),
+        Amount::nano(1000),
+    );
+
+    // The wallet receives the send with a block that was signed by the external signer
+    assert_timely_eq(
+        Duration::from_secs(5),
+        || {
+            node.ledger
+                .any()
+                .account_balance(&node.ledger.read_txn(), &key.account())
+                .unwrap_or_default()
+        },
+        Amount::nano(1000),
+    );
+    std::fs::remove_file(socket).unwrap();
+}
+
+#[test]
+fn receive_policy_blocks_auto_receive() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = WalletId::random();
+    node.wallets.create(wallet_id);
+    node.wallets
+        .insert_adhoc2(&wallet_id, &DEV_GENESIS_KEY.private_key(), false)
+        .unwrap();
+    let key = KeyPair::new();
+    node.wallets
+        .insert_adhoc2(&wallet_id, &key.private_key(), false)
+        .unwrap();
+    node.wallets
+        .set_receive_policy(
+            &wallet_id,
+            Some(key.account()),
+            Some(ReceivePolicy {
+                deny: vec![*DEV_GENESIS_ACCOUNT],
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+
+    node.wallets.send_sync(
+        wallet_id,
+        *DEV_GENESIS_ACCOUNT,
+        key.account(),
+        Amount::nano(1000),
+    );
+    let balance = || {
+        node.ledger
+            .any()
+            .account_balance(&node.ledger.read_txn(), &key.account())
+            .unwrap_or_default()
+    };
+    assert_never(Duration::from_secs(1), || !balance().is_zero());
+
+    node.wallets
+        .set_receive_policy(&wallet_id, Some(key.account()), None)
+        .unwrap();
+    node.wallets.search_receivable_wallet(wallet_id).unwrap();
+    assert_timely_eq(Duration::from_secs(5), balance, Amount::nano(1000));
+}
+
+#[test]
+fn replace_offline_representative() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = WalletId::random();
+    node.wallets.create(wallet_id);
+    node.wallets
+        .insert_adhoc2(&wallet_id, &DEV_GENESIS_KEY.private_key(), false)
+        .unwrap();
+    let offline_rep = KeyPair::new();
+    node.wallets
+        .set_representative(wallet_id, offline_rep.public_key(), false)
+        .unwrap();
+    let key = KeyPair::new();
+    node.wallets
+        .insert_adhoc2(&wallet_id, &key.private_key(), false)
+        .unwrap();
+    node.wallets.send_sync(
+        wallet_id,
+        *DEV_GENESIS_ACCOUNT,
+        key.account(),
+        Amount::nano(1000),
+    );
+    let representative = || {
+        node.ledger
+            .account_info(&node.ledger.read_txn(), &key.account())
+            .map(|info| info.representative)
+    };
+    assert_timely_eq(
+        Duration::from_secs(5),
+        representative,
+        Some(offline_rep.public_key()),
+    );
+
+    node.online_reps
+        .lock()
+        .unwrap()
+        .vote_observed(*DEV_GENESIS_PUB_KEY, node.steady_clock.now());
+    node.wallets
+        .set_rep_health_policy(
+            &wallet_id,
+            Some(RepHealthPolicy {
+                fallback_representatives: vec![*DEV_GENESIS_ACCOUNT],
+                grace_period: 0,
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+    node.rep_health_monitor.check_all(Instant::now());
+
+    assert_timely_eq(
+        Duration::from_secs(5),
+        representative,
+        Some(*DEV_GENESIS_PUB_KEY),
+    );
+    assert_eq!(
+        node.wallets.get_representative(wallet_id).unwrap(),
+        *DEV_GENESIS_PUB_KEY
+    );
+    assert!(
+        node.stats
+            .count(StatType::RepHealth, DetailType::Redelegated, Direction::In)
+            >= 1
+    );
+}
+
+#[test]
+fn keep_representative_during_grace_period() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let wallet_id = WalletId::random();
+    node.wallets.create(wallet_id);
+    let offline_rep = KeyPair::new();
+    node.wallets
+        .set_representative(wallet_id, offline_rep.public_key(), false)
+        .unwrap();
+    node.online_reps
+        .lock()
+        .unwrap()
+        .vote_observed(*DEV_GENESIS_PUB_KEY, node.steady_clock.now());
+    node.wallets
+        .set_rep_health_policy(
+            &wallet_id,
+            Some(RepHealthPolicy {
+                fallback_representatives: vec![*DEV_GENESIS_ACCOUNT],
+                grace_period: 60,
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+
+    let now = Instant::now();
+    node.rep_health_monitor.check_all(now);
+    assert_eq!(
+        node.wallets.get_representative(wallet_id).unwrap(),
+        offline_rep.public_key()
+    );
+
+    node.rep_health_monitor
+        .check_all(now + Duration::from_secs(60));
+    assert_eq!(
+        node.wallets.get_representative(wallet_id).unwrap(),
+        *DEV_GENESIS_PUB_KEY
+    );
+}
@@ -1,5 +1,9 @@
 use std::{
     ops::{Add, Sub},
+    sync::{
+        atomic::{AtomicI64, Ordering},
+        Arc,
+    },
     time::{Duration, Instant},
 };
 
@@ -14,11 +18,43 @@ impl SteadyClock {
         }
     }
 
+    /// A clock which follows the given virtual clock
+    pub fn new_virtual(clock: VirtualClock) -> Self {
+        Self {
+            time_source: TimeSource::Virtual(clock),
+        }
+    }
+
     pub fn now(&self) -> Timestamp {
         Timestamp(self.time_source.now())
     }
 }
 
+/// Time which only moves when it gets advanced explicitly. All clones share the same time
+#[derive(Clone)]
+pub struct VirtualClock(Arc<AtomicI64>);
+
+impl VirtualClock {
+    pub fn new() -> Self {
+        Self(Arc::new(AtomicI64::new(DEFAULT_STUB_DURATION)))
+    }
+
+    pub fn now(&self) -> Timestamp {
+        Timestamp(self.0.load(Ordering::SeqCst))
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        self.0
+            .fetch_add(duration.as_millis() as i64, Ordering::SeqCst);
+    }
+}
+
+impl Default for VirtualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Default for SteadyClock {
     fn default() -> Self {
         SteadyClock {
@@ -30,6 +66,7 @@ impl Default for SteadyClock {
 enum TimeSource {
     System(Instant),
     Stub(i64),
+    Virtual(VirtualClock),
 }
 
 impl TimeSource {
@@ -37,6 +74,7 @@ impl TimeSource {
         match self {
             TimeSource::System(instant) => instant.elapsed().as_millis() as i64,
             TimeSource::Stub(value) => *value,
+            TimeSource::Virtual(clock) => clock.now().0,
         }
     }
 }
@@ -141,5 +179,14 @@ mod tests {
             let now2 = clock.now();
             assert_eq!(now1, now2);
         }
+
+        #[test]
+        fn virtual_clock() {
+            let virtual_clock = VirtualClock::new();
+            let clock = SteadyClock::new_virtual(virtual_clock.clone());
+            let now1 = clock.now();
+            virtual_clock.advance(Duration::from_millis(250));
+            assert_eq!(clock.now(), now1 + Duration::from_millis(250));
+        }
     }
 }
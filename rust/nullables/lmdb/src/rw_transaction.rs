@@ -95,6 +95,12 @@ impl RwTransaction {
         }
         Ok(())
     }
+
+    pub fn abort(self) {
+        if let RwTransactionStrategy::Real(s) = self.strategy {
+            s.abort();
+        }
+    }
 }
 
 enum RwTransactionStrategy {
@@ -136,6 +142,10 @@ impl RwTransactionWrapper {
         lmdb::Transaction::commit(self.0)
     }
 
+    fn abort(self) {
+        lmdb::Transaction::abort(self.0)
+    }
+
     fn open_ro_cursor<'txn>(&'txn self, database: LmdbDatabase) -> lmdb::Result<RoCursor<'txn>> {
         let cursor = lmdb::Transaction::open_ro_cursor(&self.0, database.as_real());
         cursor.map(|c| RoCursor::new(c))
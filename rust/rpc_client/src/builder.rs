@@ -0,0 +1,99 @@
+use crate::NanoRpcClient;
+use anyhow::Result;
+use reqwest::{
+    header::{HeaderMap, HeaderValue, AUTHORIZATION},
+    Certificate, Url,
+};
+use std::time::Duration;
+
+pub struct NanoRpcClientBuilder {
+    url: Url,
+    timeout: Duration,
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    api_key: Option<String>,
+    root_certificates: Vec<Vec<u8>>,
+    accept_invalid_certs: bool,
+}
+
+impl NanoRpcClientBuilder {
+    pub(crate) fn new(url: Url) -> Self {
+        Self {
+            url,
+            timeout: Duration::from_secs(5),
+            max_retries: 0,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+            api_key: None,
+            root_certificates: Vec::new(),
+            accept_invalid_certs: false,
+        }
+    }
+
+    /// Timeout for a single request attempt
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// How often a request is repeated after a connection error, a timeout
+    /// or a 5xx/429 response. Errors returned by the node are never retried.
+    /// Only enable this if the commands you send are idempotent (i.e. `send` with an `id`)
+    pub fn max_retries(mut self, retries: u32) -> Self {
+        self.max_retries = retries;
+        self
+    }
+
+    /// The delay before the first retry. It is doubled for each further retry
+    /// until it reaches `max_backoff`
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Sent as `Authorization` header, as expected by most public RPC providers
+    pub fn api_key(mut self, key: impl Into<String>) -> Self {
+        self.api_key = Some(key.into());
+        self
+    }
+
+    /// Trust an additional PEM encoded root certificate for https connections
+    pub fn root_certificate(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.root_certificates.push(pem.into());
+        self
+    }
+
+    /// Disables certificate validation. Only use this for testing!
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.accept_invalid_certs = accept;
+        self
+    }
+
+    pub fn build(self) -> Result<NanoRpcClient> {
+        let mut headers = HeaderMap::new();
+        if let Some(key) = &self.api_key {
+            let mut value = HeaderValue::from_str(key)?;
+            value.set_sensitive(true);
+            headers.insert(AUTHORIZATION, value);
+        }
+
+        let mut builder = reqwest::ClientBuilder::new()
+            .timeout(self.timeout)
+            .default_headers(headers)
+            .danger_accept_invalid_certs(self.accept_invalid_certs);
+
+        for pem in &self.root_certificates {
+            builder = builder.add_root_certificate(Certificate::from_pem(pem)?);
+        }
+
+        Ok(NanoRpcClient {
+            url: self.url,
+            client: builder.build()?,
+            max_retries: self.max_retries,
+            initial_backoff: self.initial_backoff,
+            max_backoff: self.max_backoff,
+        })
+    }
+}
@@ -0,0 +1,86 @@
+use reqwest::StatusCode;
+use rsnano_rpc_messages::ErrorDto;
+use std::fmt;
+
+/// Errors returned by `NanoRpcClient`.
+/// The client methods return `anyhow::Result`, so use `downcast_ref::<RpcClientError>()`
+/// to inspect the cause of a failed request.
+#[derive(Debug)]
+pub enum RpcClientError {
+    /// The node processed the request and answered with a known error
+    Node(ErrorDto),
+    /// The node answered with an error message that has no `ErrorDto` counterpart
+    UnknownNode(String),
+    /// The HTTP response had a non-success status code
+    Status(StatusCode),
+    /// The request could not be sent or the response could not be received
+    Transport(reqwest::Error),
+    /// The response body could not be parsed
+    InvalidResponse(serde_json::Error),
+}
+
+impl RpcClientError {
+    pub fn node_error(&self) -> Option<ErrorDto> {
+        match self {
+            RpcClientError::Node(e) => Some(*e),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the request may succeed when it is sent again
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            RpcClientError::Status(status) => {
+                status.is_server_error() || *status == StatusCode::TOO_MANY_REQUESTS
+            }
+            RpcClientError::Transport(e) => e.is_timeout() || e.is_connect(),
+            _ => false,
+        }
+    }
+
+    pub(crate) fn from_message(message: String) -> Self {
+        match ErrorDto::from_message(&message) {
+            Some(error) => RpcClientError::Node(error),
+            None => RpcClientError::UnknownNode(message),
+        }
+    }
+}
+
+impl fmt::Display for RpcClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RpcClientError::Node(e) => write!(f, "node returned error: {:?}", e.message()),
+            RpcClientError::UnknownNode(message) => {
+                write!(f, "node returned error: {:?}", message)
+            }
+            RpcClientError::Status(status) => write!(f, "node returned HTTP status {}", status),
+            RpcClientError::Transport(e) => write!(f, "RPC request failed: {}", e),
+            RpcClientError::InvalidResponse(e) => write!(f, "invalid RPC response: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for RpcClientError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RpcClientError::Transport(e) => Some(e),
+            RpcClientError::InvalidResponse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for RpcClientError {
+    fn from(value: reqwest::Error) -> Self {
+        match value.status() {
+            Some(status) => RpcClientError::Status(status),
+            None => RpcClientError::Transport(value),
+        }
+    }
+}
+
+impl From<serde_json::Error> for RpcClientError {
+    fn from(value: serde_json::Error) -> Self {
+        RpcClientError::InvalidResponse(value)
+    }
+}
@@ -1,9 +1,18 @@
+mod builder;
+mod error;
+mod websocket;
+
+pub use builder::*;
+pub use error::*;
+pub use websocket::*;
+
 use crate::AccountBalanceDto;
-use anyhow::{bail, Result};
+use anyhow::Result;
 use reqwest::Client;
 pub use reqwest::Url;
 use rsnano_core::{
-    Account, Amount, BlockHash, HashOrAccount, JsonBlock, PublicKey, RawKey, WalletId, WorkNonce,
+    Account, Amount, BlockHash, HashOrAccount, JsonBlock, PublicKey, QualifiedRoot, RawKey,
+    WalletId, WorkNonce,
 };
 use rsnano_rpc_messages::*;
 use serde::Serialize;
@@ -13,17 +22,18 @@ use std::{net::Ipv6Addr, time::Duration};
 pub struct NanoRpcClient {
     url: Url,
     client: Client,
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
 }
 
 impl NanoRpcClient {
     pub fn new(url: Url) -> Self {
-        Self {
-            url,
-            client: reqwest::ClientBuilder::new()
-                .timeout(Duration::from_secs(5))
-                .build()
-                .unwrap(),
-        }
+        Self::builder(url).build().unwrap()
+    }
+
+    pub fn builder(url: Url) -> NanoRpcClientBuilder {
+        NanoRpcClientBuilder::new(url)
     }
 
     pub async fn account_get(&self, key: PublicKey) -> Result<AccountRpcMessage> {
@@ -68,6 +78,48 @@ impl NanoRpcClient {
         Ok(serde_json::from_value(result)?)
     }
 
+    pub async fn election_trace(&self, root: QualifiedRoot) -> Result<ElectionTraceDto> {
+        let cmd = RpcCommand::election_trace(root);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn election_start(&self, args: impl Into<ElectionStartArgs>) -> Result<StartedDto> {
+        let cmd = RpcCommand::election_start(args.into());
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn election_cancel(&self, root: QualifiedRoot) -> Result<SuccessDto> {
+        let cmd = RpcCommand::election_cancel(root);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn election_erase(&self, root: QualifiedRoot) -> Result<SuccessDto> {
+        let cmd = RpcCommand::election_erase(root);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn election_info(&self, hash: BlockHash) -> Result<ElectionInfoDto> {
+        let cmd = RpcCommand::election_info(hash);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn cdc_read(&self, args: impl Into<CdcReadArgs>) -> Result<CdcReadDto> {
+        let cmd = RpcCommand::cdc_read(args.into());
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn scheduler_buckets(&self) -> Result<SchedulerBucketsDto> {
+        let cmd = RpcCommand::scheduler_buckets();
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
     pub async fn unchecked_keys(&self, key: HashOrAccount, count: u64) -> Result<UncheckedKeysDto> {
         let cmd = RpcCommand::unchecked_keys(key, count);
         let result = self.rpc_request(&cmd).await?;
@@ -95,6 +147,21 @@ impl NanoRpcClient {
         Ok(serde_json::from_value(result)?)
     }
 
+    pub async fn representatives_details(
+        &self,
+        args: RepresentativesDetailsArgs,
+    ) -> Result<RepresentativesDetailsDto> {
+        let cmd = RpcCommand::representatives_details(args);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn final_votes(&self, args: FinalVotesArgs) -> Result<FinalVotesDto> {
+        let cmd = RpcCommand::final_votes(args);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
     pub async fn receivable_exists(
         &self,
         args: impl Into<ReceivableExistsArgs>,
@@ -188,7 +255,7 @@ impl NanoRpcClient {
         Ok(serde_json::from_value(result)?)
     }
 
-    pub async fn process(&self, process_args: impl Into<ProcessArgs>) -> Result<HashRpcMessage> {
+    pub async fn process(&self, process_args: impl Into<ProcessArgs>) -> Result<ProcessDto> {
         let cmd = RpcCommand::process(process_args.into());
         let result = self.rpc_request(&cmd).await?;
         Ok(serde_json::from_value(result)?)
@@ -265,6 +332,64 @@ impl NanoRpcClient {
         Ok(serde_json::from_value(result)?)
     }
 
+    pub async fn wallet_create_with_args(&self, args: WalletCreateArgs) -> Result<WalletCreateDto> {
+        let cmd = RpcCommand::WalletCreate(args);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn wallet_create_from_mnemonic(
+        &self,
+        mnemonic: String,
+        passphrase: Option<String>,
+    ) -> Result<WalletCreateDto> {
+        let cmd = RpcCommand::wallet_create_from_mnemonic(mnemonic, passphrase);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn wallet_mnemonic(&self, wallet: WalletId) -> Result<MnemonicDto> {
+        let cmd = RpcCommand::wallet_mnemonic(wallet);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn receive_policy_set(&self, args: ReceivePolicySetArgs) -> Result<SuccessDto> {
+        let cmd = RpcCommand::receive_policy_set(args);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn receive_policy_get(&self, args: ReceivePolicyArgs) -> Result<ReceivePolicyDto> {
+        let cmd = RpcCommand::receive_policy_get(args);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn receive_policy_remove(&self, args: ReceivePolicyArgs) -> Result<SuccessDto> {
+        let cmd = RpcCommand::receive_policy_remove(args);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn rep_health_policy_set(&self, args: RepHealthPolicySetArgs) -> Result<SuccessDto> {
+        let cmd = RpcCommand::rep_health_policy_set(args);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn rep_health_policy_get(&self, wallet: WalletId) -> Result<RepHealthPolicyDto> {
+        let cmd = RpcCommand::rep_health_policy_get(wallet);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn rep_health_policy_remove(&self, wallet: WalletId) -> Result<SuccessDto> {
+        let cmd = RpcCommand::rep_health_policy_remove(wallet);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
     pub async fn wallet_contains(&self, wallet: WalletId, account: Account) -> Result<ExistsDto> {
         let cmd = RpcCommand::wallet_contains(wallet, account);
         let result = self.rpc_request(&cmd).await?;
@@ -322,6 +447,25 @@ impl NanoRpcClient {
         Ok(serde_json::from_value(result)?)
     }
 
+    pub async fn account_balance_at(
+        &self,
+        args: AccountBalanceAtArgs,
+    ) -> Result<AccountBalanceAtDto> {
+        let cmd = RpcCommand::account_balance_at(args);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn representative_weight_at(
+        &self,
+        account: Account,
+        timestamp: u64,
+    ) -> Result<WeightDto> {
+        let cmd = RpcCommand::representative_weight_at(account, timestamp);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
     pub async fn available_supply(&self) -> Result<AvailableSupplyDto> {
         let cmd = RpcCommand::AvailableSupply;
         let result = self.rpc_request(&cmd).await?;
@@ -469,6 +613,17 @@ impl NanoRpcClient {
         Ok(serde_json::from_value(result)?)
     }
 
+    pub async fn deterministic_key_bip44(
+        &self,
+        mnemonic: String,
+        passphrase: Option<String>,
+        index: u32,
+    ) -> Result<KeyPairDto> {
+        let cmd = RpcCommand::deterministic_key_bip44(mnemonic, passphrase, index);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
     pub async fn key_expand(&self, key: RawKey) -> Result<KeyPairDto> {
         let cmd = RpcCommand::key_expand(key);
         let result = self.rpc_request(&cmd).await?;
@@ -663,12 +818,23 @@ impl NanoRpcClient {
         Ok(())
     }
 
-    pub async fn send(&self, args: SendArgs) -> Result<BlockDto> {
+    pub async fn send(&self, args: SendArgs) -> Result<SendDto> {
         let request = RpcCommand::send(args);
         let result = self.rpc_request(&request).await?;
         Ok(serde_json::from_value(result)?)
     }
 
+    pub async fn receive(
+        &self,
+        wallet: WalletId,
+        account: Account,
+        block: JsonBlock,
+    ) -> Result<BlockDto> {
+        let cmd = RpcCommand::receive(wallet, account, block);
+        let result = self.rpc_request(&cmd).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
     pub async fn send_block(
         &self,
         wallet: WalletId,
@@ -682,6 +848,8 @@ impl NanoRpcClient {
             amount: Amount::raw(1),
             work: None,
             id: None,
+            wait_confirmation: None,
+            timeout: None,
         });
         let json = self.rpc_request(&request).await?;
         let block = json["block"].as_str().unwrap().to_owned();
@@ -722,6 +890,22 @@ impl NanoRpcClient {
     }
 
     async fn rpc_request<T>(&self, request: &T) -> Result<serde_json::Value>
+    where
+        T: Serialize,
+    {
+        let mut retries = 0;
+        loop {
+            match self.try_rpc_request(request).await {
+                Err(e) if retries < self.max_retries && e.is_retryable() => {
+                    tokio::time::sleep(self.backoff(retries)).await;
+                    retries += 1;
+                }
+                result => return Ok(result?),
+            }
+        }
+    }
+
+    async fn try_rpc_request<T>(&self, request: &T) -> Result<Value, RpcClientError>
     where
         T: Serialize,
     {
@@ -736,9 +920,19 @@ impl NanoRpcClient {
             .await?;
 
         if let Some(error) = result.get("error") {
-            bail!("node returned error: {}", error);
+            let message = match error {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            return Err(RpcClientError::from_message(message));
         }
 
         Ok(result)
     }
+
+    fn backoff(&self, retries: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(retries))
+            .min(self.max_backoff)
+    }
 }
@@ -0,0 +1,213 @@
+use anyhow::{anyhow, bail, Result};
+use futures_util::{stream, SinkExt, Stream, StreamExt};
+use reqwest::Url;
+use rsnano_rpc_messages::websocket::{
+    BlockConfirmed, ConfirmationJsonOptions, OutgoingMessageEnvelope, RepresentativeChanged,
+    StartedElection, StoppedElection, TelemetryReceived, Topic, VoteJsonOptions, VoteReceived,
+};
+use serde::Serialize;
+use serde_json::{from_value, Value};
+use std::{collections::VecDeque, time::Duration};
+use tokio::{net::TcpStream, time::timeout};
+use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+
+/// A typed message received from the node's websocket server
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WebsocketEvent {
+    Confirmation(BlockConfirmed),
+    Vote(VoteReceived),
+    StartedElection(StartedElection),
+    StoppedElection(StoppedElection),
+    Telemetry(TelemetryReceived),
+    NewUnconfirmedBlock(Value),
+    RepresentativeChange(RepresentativeChanged),
+    /// Topics without a typed message (work, bootstrap)
+    Other {
+        topic: Topic,
+        message: Value,
+    },
+}
+
+impl WebsocketEvent {
+    fn from_envelope(envelope: OutgoingMessageEnvelope) -> Result<Self> {
+        let topic = envelope
+            .topic
+            .ok_or_else(|| anyhow!("websocket message without topic"))?;
+        let message = envelope.message.unwrap_or_default();
+        let event = match topic {
+            Topic::Confirmation => Self::Confirmation(from_value(message)?),
+            Topic::Vote => Self::Vote(from_value(message)?),
+            Topic::StartedElection => Self::StartedElection(from_value(message)?),
+            Topic::StoppedElection => Self::StoppedElection(from_value(message)?),
+            Topic::Telemetry => Self::Telemetry(from_value(message)?),
+            Topic::NewUnconfirmedBlock => Self::NewUnconfirmedBlock(message),
+            Topic::RepresentativeChange => Self::RepresentativeChange(from_value(message)?),
+            _ => Self::Other { topic, message },
+        };
+        Ok(event)
+    }
+}
+
+#[derive(Serialize)]
+struct WebsocketRequest<'a> {
+    action: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    topic: Option<Topic>,
+    ack: bool,
+    id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    options: Option<Value>,
+}
+
+/// Async client for the node's websocket server.
+/// Subscription requests wait for the acknowledgement of the node. Events that
+/// arrive in the meantime are buffered and returned by `next_event`.
+pub struct NanoWebsocketClient {
+    stream: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    pending: VecDeque<WebsocketEvent>,
+    next_id: u64,
+    ack_timeout: Duration,
+}
+
+impl NanoWebsocketClient {
+    pub async fn connect(url: Url) -> Result<Self> {
+        let (stream, _) = connect_async(url.as_str()).await?;
+        Ok(Self {
+            stream,
+            pending: VecDeque::new(),
+            next_id: 0,
+            ack_timeout: Duration::from_secs(5),
+        })
+    }
+
+    /// How long to wait for the acknowledgement of a request
+    pub fn set_ack_timeout(&mut self, ack_timeout: Duration) {
+        self.ack_timeout = ack_timeout;
+    }
+
+    /// Subscribe to a topic without options
+    pub async fn subscribe(&mut self, topic: Topic) -> Result<()> {
+        self.request("subscribe", Some(topic), None).await
+    }
+
+    pub async fn subscribe_confirmations(
+        &mut self,
+        options: ConfirmationJsonOptions,
+    ) -> Result<()> {
+        let options = serde_json::to_value(options)?;
+        self.request("subscribe", Some(Topic::Confirmation), Some(options))
+            .await
+    }
+
+    pub async fn subscribe_votes(&mut self, options: VoteJsonOptions) -> Result<()> {
+        let options = serde_json::to_value(options)?;
+        self.request("subscribe", Some(Topic::Vote), Some(options))
+            .await
+    }
+
+    /// Changes the options of an existing confirmation subscription
+    pub async fn update_confirmations(
+        &mut self,
+        accounts_add: Vec<String>,
+        accounts_del: Vec<String>,
+    ) -> Result<()> {
+        let options = serde_json::json!({
+            "accounts_add": accounts_add,
+            "accounts_del": accounts_del,
+        });
+        self.request("update", Some(Topic::Confirmation), Some(options))
+            .await
+    }
+
+    pub async fn unsubscribe(&mut self, topic: Topic) -> Result<()> {
+        self.request("unsubscribe", Some(topic), None).await
+    }
+
+    pub async fn ping(&mut self) -> Result<()> {
+        self.request("ping", None, None).await
+    }
+
+    /// Waits for the next event. Returns None when the connection was closed
+    pub async fn next_event(&mut self) -> Option<Result<WebsocketEvent>> {
+        if let Some(event) = self.pending.pop_front() {
+            return Some(Ok(event));
+        }
+
+        loop {
+            match self.read_envelope().await? {
+                Ok(envelope) if envelope.ack.is_some() => continue,
+                Ok(envelope) => return Some(WebsocketEvent::from_envelope(envelope)),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+
+    pub fn into_stream(self) -> impl Stream<Item = Result<WebsocketEvent>> {
+        stream::unfold(self, |mut client| async move {
+            client.next_event().await.map(|event| (event, client))
+        })
+    }
+
+    pub async fn close(mut self) -> Result<()> {
+        self.stream.close(None).await?;
+        Ok(())
+    }
+
+    async fn request(
+        &mut self,
+        action: &str,
+        topic: Option<Topic>,
+        options: Option<Value>,
+    ) -> Result<()> {
+        self.next_id += 1;
+        let id = self.next_id.to_string();
+        let request = WebsocketRequest {
+            action,
+            topic,
+            ack: true,
+            id: id.clone(),
+            options,
+        };
+        self.stream
+            .send(Message::Text(serde_json::to_string(&request)?))
+            .await?;
+
+        match timeout(self.ack_timeout, self.wait_for_ack(&id)).await {
+            Ok(result) => result,
+            Err(_) => bail!("no acknowledgement received for websocket action '{action}'"),
+        }
+    }
+
+    async fn wait_for_ack(&mut self, id: &str) -> Result<()> {
+        loop {
+            let Some(envelope) = self.read_envelope().await else {
+                bail!("websocket connection closed");
+            };
+            let envelope = envelope?;
+            if envelope.ack.is_some() {
+                if envelope.id.as_deref() == Some(id) {
+                    return Ok(());
+                }
+            } else {
+                let event = WebsocketEvent::from_envelope(envelope)?;
+                self.pending.push_back(event);
+            }
+        }
+    }
+
+    async fn read_envelope(&mut self) -> Option<Result<OutgoingMessageEnvelope>> {
+        loop {
+            let message = match self.stream.next().await? {
+                Ok(m) => m,
+                Err(e) => return Some(Err(e.into())),
+            };
+            match message {
+                Message::Text(text) => {
+                    return Some(serde_json::from_str(&text).map_err(|e| e.into()));
+                }
+                Message::Close(_) => return None,
+                _ => continue,
+            }
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use rsnano_core::Amount;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfirmationOutcomeDto {
+    Confirmed,
+    /// The block wasn't confirmed within the timeout
+    Timeout,
+    /// The block was replaced by a fork which won the election
+    Fork,
+    /// The block was rolled back together with one of its dependencies
+    RolledBack,
+}
+
+/// The result of waiting for the confirmation of a block.
+/// The election details are only available for confirmed blocks.
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ConfirmationDto {
+    pub outcome: ConfirmationOutcomeDto,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmation_type: Option<String>,
+    /// Duration of the election in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tally: Option<Amount>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub final_tally: Option<Amount>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub voters: Option<u32>,
+}
+
+impl ConfirmationDto {
+    pub fn new(outcome: ConfirmationOutcomeDto) -> Self {
+        Self {
+            outcome,
+            confirmation_type: None,
+            duration: None,
+            tally: None,
+            final_tally: None,
+            voters: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_confirmed() {
+        let dto = ConfirmationDto {
+            outcome: ConfirmationOutcomeDto::Confirmed,
+            confirmation_type: Some("active_quorum".to_string()),
+            duration: Some(250),
+            tally: Some(Amount::raw(10)),
+            final_tally: Some(Amount::raw(8)),
+            voters: Some(3),
+        };
+
+        assert_eq!(
+            serde_json::to_value(&dto).unwrap(),
+            json!({
+                "outcome": "confirmed",
+                "confirmation_type": "active_quorum",
+                "duration": 250,
+                "tally": "10",
+                "final_tally": "8",
+                "voters": 3
+            })
+        );
+    }
+
+    #[test]
+    fn serialize_timeout() {
+        let dto = ConfirmationDto::new(ConfirmationOutcomeDto::Timeout);
+        assert_eq!(
+            serde_json::to_value(&dto).unwrap(),
+            json!({ "outcome": "timeout" })
+        );
+    }
+}
@@ -1,40 +1,88 @@
-use rsnano_node::wallets::WalletsError;
-use serde::{ser::SerializeMap, Deserialize, Serialize};
+use rsnano_core::WalletsError;
+use serde::{de::Error, ser::SerializeMap, Deserialize, Deserializer, Serialize};
 
-#[derive(Debug, Deserialize)]
-pub enum ErrorDto {
-    WalletsError(WalletsError),
-    RPCControlDisabled,
-    AccountNotFound,
-    BlockNotFound,
-    PeerNotFound,
-    BlockError,
-    MissingAccountInformation,
-    WorkLow,
-    GapPrevious,
-    GapSource,
-    Old,
-    BadSignature,
-    NegativeSpend,
-    BalanceMismatch,
-    Unreceivable,
-    BlockPosition,
-    GapEpochOpenPending,
-    Fork,
-    InsufficientWork,
-    OpenedBurnAccount,
-    Other,
-    Stopped,
-    NotStateBlock,
-    LegacyBootstrapDisabled,
-    LazyBootstrapDisabled,
-    ConfirmationInfoNotFound,
-    InvalidRoot,
-    DifficultyOutOfRange,
-    BlockRootMismatch,
-    BlockWorkVersioMismatch,
-    AccountHeadNotFound,
-    InsufficientBalance,
+/// Declares the `ErrorDto` variants together with the messages which are sent to the
+/// RPC client, so that a message can be mapped back to exactly one variant
+macro_rules! error_dtos {
+    ($($variant:ident => $message:literal,)*) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum ErrorDto {
+            WalletsError(WalletsError),
+            $($variant,)*
+        }
+
+        impl ErrorDto {
+            /// The error message as it is sent to the RPC client
+            pub fn message(&self) -> String {
+                match self {
+                    ErrorDto::WalletsError(e) => e.to_string(),
+                    $(ErrorDto::$variant => $message.to_string(),)*
+                }
+            }
+
+            /// Maps an error message received from a node back to its `ErrorDto`
+            pub fn from_message(message: &str) -> Option<Self> {
+                match message {
+                    $($message => Some(ErrorDto::$variant),)*
+                    _ => WalletsError::ALL
+                        .into_iter()
+                        .find(|e| e.to_string() == message)
+                        .map(ErrorDto::WalletsError),
+                }
+            }
+        }
+    };
+}
+
+error_dtos! {
+    RPCControlDisabled => "RPC control is disabled",
+    AccountNotFound => "Account not found",
+    BlockNotFound => "Block not found",
+    PeerNotFound => "Peer not found",
+    BlockError => "Block error",
+    MissingAccountInformation => "Missing account information",
+    WorkLow => "Work low",
+    GapPrevious => "Gap previous",
+    GapSource => "Gap source",
+    Old => "Old",
+    BadSignature => "Bad signature",
+    NegativeSpend => "Negative spend",
+    BalanceMismatch => "Balance mismatch",
+    Unreceivable => "Unreceivable",
+    BlockPosition => "Block position",
+    GapEpochOpenPending => "Gap epoch open pending",
+    Fork => "Fork",
+    InsufficientWork => "Insufficient work",
+    OpenedBurnAccount => "Opened burn account",
+    Other => "Other",
+    Stopped => "Stopped",
+    QueueFull => "Block processor queue is full",
+    NotStateBlock => "Is not state block",
+    LegacyBootstrapDisabled => "Legacy bootstrap is disabled",
+    LazyBootstrapDisabled => "Lazy bootstrap is disabled",
+    ConfirmationInfoNotFound => "Confirmation info not found",
+    InvalidRoot => "Invalid root",
+    DifficultyOutOfRange => "Difficulty out of valid range",
+    BlockRootMismatch => "Block root mismatch",
+    BlockWorkVersioMismatch => "Block work version mismatch",
+    AccountHeadNotFound => "Account head not found",
+    InsufficientBalance => "Insufficient balance",
+    BadMnemonic => "Bad mnemonic",
+    MnemonicAndSeed => "Either mnemonic or seed can be given, not both",
+    BadSeed => "Bad seed",
+    ElectionTraceNotFound => "Election trace not found",
+    ElectionNotFound => "Election not found",
+    ElectionConfirmed => "Election is already confirmed",
+    InvalidElectionBehavior => "Invalid election behavior",
+    AccountHistoryIndexDisabled => "Account history index is disabled",
+    AccountHistoryIndexIncomplete => "Account history index is still being built",
+    CdcDisabled => "CDC is disabled",
+    CdcReadFailed => "Could not read CDC log",
+    BlockSelectorRequired => "Exactly one of height, hash or timestamp is required",
+    BlockAccountMismatch => "Block does not belong to account",
+    FallbackRepresentativeRequired => "At least one fallback representative is required",
+    InvalidMinimumTier => "Minimum tier must be between 0 and 3",
+    InvalidMinimumUptime => "Minimum uptime must be between 0 and 100",
 }
 
 impl Serialize for ErrorDto {
@@ -42,43 +90,72 @@ impl Serialize for ErrorDto {
     where
         S: serde::Serializer,
     {
-        let error_message = match self {
-            ErrorDto::WalletsError(e) => e.to_string(),
-            ErrorDto::RPCControlDisabled => "RPC control is disabled".to_string(),
-            ErrorDto::AccountNotFound => "Account not found".to_string(),
-            ErrorDto::BlockNotFound => "Block not found".to_string(),
-            ErrorDto::PeerNotFound => "Peer not found".to_string(),
-            ErrorDto::BlockError => "Block error".to_string(),
-            ErrorDto::MissingAccountInformation => "Missing account information".to_string(),
-            ErrorDto::WorkLow => "Work low".to_string(),
-            ErrorDto::GapPrevious => "Gap previous".to_string(),
-            ErrorDto::GapSource => "Gap source".to_string(),
-            ErrorDto::Old => "Old".to_string(),
-            ErrorDto::BadSignature => "Bad signature".to_string(),
-            ErrorDto::NegativeSpend => "Negative spend".to_string(),
-            ErrorDto::BalanceMismatch => "Balance mismatch".to_string(),
-            ErrorDto::Unreceivable => "Unreceivable".to_string(),
-            ErrorDto::BlockPosition => "Block position".to_string(),
-            ErrorDto::GapEpochOpenPending => "Gap epoch open pending".to_string(),
-            ErrorDto::Fork => "Fork".to_string(),
-            ErrorDto::InsufficientWork => "Insufficient work".to_string(),
-            ErrorDto::OpenedBurnAccount => "Opened burn account".to_string(),
-            ErrorDto::Other => "Other".to_string(),
-            ErrorDto::Stopped => "Stopped".to_string(),
-            ErrorDto::NotStateBlock => "Is not state block".to_string(),
-            ErrorDto::LegacyBootstrapDisabled => "Legacy bootstrap is disabled".to_string(),
-            ErrorDto::LazyBootstrapDisabled => "Lazy bootstrap is disabled".to_string(),
-            ErrorDto::ConfirmationInfoNotFound => "Confirmation info not found".to_string(),
-            ErrorDto::InvalidRoot => "Invalid root".to_string(),
-            ErrorDto::DifficultyOutOfRange => "Difficulty out of valid range".to_string(),
-            ErrorDto::BlockRootMismatch => "Block root mismatch".to_string(),
-            ErrorDto::BlockWorkVersioMismatch => "Block work version mismatch".to_string(),
-            ErrorDto::AccountHeadNotFound => "Account head not found".to_string(),
-            ErrorDto::InsufficientBalance => "Insufficient balance".to_string(),
-        };
-
         let mut map = serializer.serialize_map(Some(1))?;
-        map.serialize_entry("error", &error_message)?;
+        map.serialize_entry("error", &self.message())?;
         map.end()
     }
 }
+
+impl<'de> Deserialize<'de> for ErrorDto {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct ErrorMessage {
+            error: String,
+        }
+
+        let message = ErrorMessage::deserialize(deserializer)?;
+        ErrorDto::from_message(&message.error)
+            .ok_or_else(|| D::Error::custom(format!("unknown error message: {}", message.error)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, to_string};
+
+    #[test]
+    fn serialize_error_dto() {
+        assert_eq!(
+            to_string(&ErrorDto::BlockNotFound).unwrap(),
+            r#"{"error":"Block not found"}"#
+        );
+    }
+
+    #[test]
+    fn deserialize_error_dto() {
+        let error: ErrorDto = from_str(r#"{"error":"Block not found"}"#).unwrap();
+        assert_eq!(error, ErrorDto::BlockNotFound);
+    }
+
+    #[test]
+    fn deserialize_wallets_error() {
+        let error: ErrorDto = from_str(r#"{"error":"Wallet is locked"}"#).unwrap();
+        assert_eq!(error, ErrorDto::WalletsError(WalletsError::WalletLocked));
+    }
+
+    #[test]
+    fn deserialize_wallet_account_not_found() {
+        let error = ErrorDto::WalletsError(WalletsError::AccountNotFound);
+        let deserialized: ErrorDto = from_str(&to_string(&error).unwrap()).unwrap();
+        assert_eq!(deserialized, error);
+    }
+
+    #[test]
+    fn wallets_errors_dont_clash_with_other_errors() {
+        for error in WalletsError::ALL {
+            assert_eq!(
+                ErrorDto::from_message(&error.to_string()),
+                Some(ErrorDto::WalletsError(error))
+            );
+        }
+    }
+
+    #[test]
+    fn deserialize_unknown_error_fails() {
+        assert!(from_str::<ErrorDto>(r#"{"error":"foobar"}"#).is_err());
+    }
+}
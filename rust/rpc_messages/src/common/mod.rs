@@ -7,6 +7,7 @@ mod address_with_port;
 mod amount;
 mod block;
 mod blocks;
+mod confirmation;
 mod count;
 mod destroyed;
 mod error;
@@ -18,6 +19,7 @@ mod key_pair;
 mod locked;
 mod moved;
 mod public_key;
+mod qualified_root;
 mod receivable;
 mod removed;
 mod started;
@@ -38,6 +40,7 @@ pub use address_with_port::*;
 pub use amount::*;
 pub use block::*;
 pub use blocks::*;
+pub use confirmation::*;
 pub use count::*;
 pub use destroyed::*;
 pub use error::*;
@@ -49,6 +52,7 @@ pub use key_pair::*;
 pub use locked::*;
 pub use moved::*;
 pub use public_key::*;
+pub use qualified_root::*;
 pub use receivable::*;
 pub use removed::*;
 pub use started::*;
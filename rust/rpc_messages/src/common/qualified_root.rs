@@ -0,0 +1,13 @@
+use rsnano_core::QualifiedRoot;
+use serde::{Deserialize, Serialize};
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct QualifiedRootRpcMessage {
+    pub root: QualifiedRoot,
+}
+
+impl QualifiedRootRpcMessage {
+    pub fn new(root: QualifiedRoot) -> Self {
+        Self { root }
+    }
+}
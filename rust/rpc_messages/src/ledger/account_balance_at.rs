@@ -0,0 +1,120 @@
+use crate::RpcCommand;
+use rsnano_core::{Account, Amount, BlockHash};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn account_balance_at(args: AccountBalanceAtArgs) -> Self {
+        Self::AccountBalanceAt(args)
+    }
+}
+
+/// Selects the point in the account chain by exactly one of height, block hash or local timestamp
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+pub struct AccountBalanceAtArgs {
+    pub account: Account,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hash: Option<BlockHash>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+}
+
+impl AccountBalanceAtArgs {
+    pub fn builder(account: Account) -> AccountBalanceAtArgsBuilder {
+        AccountBalanceAtArgsBuilder {
+            args: AccountBalanceAtArgs {
+                account,
+                height: None,
+                hash: None,
+                timestamp: None,
+            },
+        }
+    }
+}
+
+pub struct AccountBalanceAtArgsBuilder {
+    args: AccountBalanceAtArgs,
+}
+
+impl AccountBalanceAtArgsBuilder {
+    pub fn height(mut self, height: u64) -> Self {
+        self.args.height = Some(height);
+        self
+    }
+
+    pub fn hash(mut self, hash: BlockHash) -> Self {
+        self.args.hash = Some(hash);
+        self
+    }
+
+    pub fn timestamp(mut self, timestamp: u64) -> Self {
+        self.args.timestamp = Some(timestamp);
+        self
+    }
+
+    pub fn build(self) -> AccountBalanceAtArgs {
+        self.args
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct AccountBalanceAtDto {
+    /// The confirmed block which determines the balance
+    pub hash: BlockHash,
+    pub height: u64,
+    pub local_timestamp: u64,
+    pub balance: Amount,
+    pub representative: Account,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_command() {
+        let command = RpcCommand::account_balance_at(
+            AccountBalanceAtArgs::builder(Account::from(1))
+                .height(5)
+                .build(),
+        );
+        assert_eq!(
+            serde_json::to_value(&command).unwrap(),
+            json!({
+                "action": "account_balance_at",
+                "account": Account::from(1),
+                "height": 5
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_command() {
+        let command = RpcCommand::account_balance_at(
+            AccountBalanceAtArgs::builder(Account::from(1))
+                .timestamp(1234)
+                .build(),
+        );
+        let serialized = serde_json::to_string(&command).unwrap();
+        let deserialized: RpcCommand = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, command);
+    }
+
+    #[test]
+    fn serialize_dto() {
+        let dto = AccountBalanceAtDto {
+            hash: BlockHash::from(1),
+            height: 2,
+            local_timestamp: 3,
+            balance: Amount::raw(4),
+            representative: Account::from(5),
+        };
+        let serialized = serde_json::to_value(&dto).unwrap();
+        assert_eq!(serialized["balance"], "4");
+
+        let deserialized: AccountBalanceAtDto = serde_json::from_value(serialized).unwrap();
+        assert_eq!(deserialized, dto);
+    }
+}
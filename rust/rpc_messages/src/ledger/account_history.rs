@@ -22,6 +22,16 @@ pub struct AccountHistoryArgs {
     pub reverse: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub account_filter: Option<Vec<Account>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_height: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_height: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subtype: Option<Vec<BlockSubType>>,
 }
 
 impl AccountHistoryArgs {
@@ -34,9 +44,22 @@ impl AccountHistoryArgs {
             offset: None,
             reverse: None,
             account_filter: None,
+            from_height: None,
+            to_height: None,
+            since: None,
+            until: None,
+            subtype: None,
         }
     }
 
+    /// Height and time ranges can only be served by the account history index
+    pub fn requires_index(&self) -> bool {
+        self.from_height.is_some()
+            || self.to_height.is_some()
+            || self.since.is_some()
+            || self.until.is_some()
+    }
+
     pub fn builder(account: Account, count: u64) -> AccountHistoryArgsBuilder {
         AccountHistoryArgsBuilder::new(account, count)
     }
@@ -57,6 +80,11 @@ impl AccountHistoryArgsBuilder {
                 offset: None,
                 reverse: None,
                 account_filter: None,
+                from_height: None,
+                to_height: None,
+                since: None,
+                until: None,
+                subtype: None,
             },
         }
     }
@@ -86,6 +114,30 @@ impl AccountHistoryArgsBuilder {
         self
     }
 
+    /// Only return blocks in the given (inclusive) range of heights
+    pub fn heights(mut self, from_height: u64, to_height: u64) -> Self {
+        self.args.from_height = Some(from_height);
+        self.args.to_height = Some(to_height);
+        self
+    }
+
+    /// Only return blocks with a local timestamp at or after `since`
+    pub fn since(mut self, since: u64) -> Self {
+        self.args.since = Some(since);
+        self
+    }
+
+    /// Only return blocks with a local timestamp at or before `until`
+    pub fn until(mut self, until: u64) -> Self {
+        self.args.until = Some(until);
+        self
+    }
+
+    pub fn subtype(mut self, subtype: Vec<BlockSubType>) -> Self {
+        self.args.subtype = Some(subtype);
+        self
+    }
+
     pub fn build(self) -> AccountHistoryArgs {
         self.args
     }
@@ -168,8 +220,55 @@ mod tests {
             assert_eq!(args.offset, Some(10));
             assert_eq!(args.reverse, Some(false));
             assert_eq!(args.account_filter, Some(vec![Account::zero()]));
+            assert!(!args.requires_index());
         } else {
             panic!("Deserialized to wrong RpcCommand variant");
         }
     }
+
+    #[test]
+    fn serialize_account_history_ranges() {
+        let args = AccountHistoryArgs::builder(Account::zero(), 10)
+            .heights(2, 5)
+            .since(100)
+            .until(200)
+            .subtype(vec![BlockSubType::Send, BlockSubType::Epoch])
+            .build();
+
+        assert_eq!(
+            serde_json::to_value(RpcCommand::account_history(args)).unwrap(),
+            serde_json::json!({
+                "action": "account_history",
+                "account": "nano_1111111111111111111111111111111111111111111111111111hifc8npp",
+                "count": 10,
+                "from_height": 2,
+                "to_height": 5,
+                "since": 100,
+                "until": 200,
+                "subtype": ["send", "epoch"]
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_account_history_ranges() {
+        let json = r#"{
+            "action": "account_history",
+            "account": "nano_1111111111111111111111111111111111111111111111111111hifc8npp",
+            "count": 10,
+            "since": 100,
+            "subtype": ["receive", "change"]
+        }"#;
+
+        let RpcCommand::AccountHistory(args) = serde_json::from_str(json).unwrap() else {
+            panic!("Deserialized to wrong RpcCommand variant");
+        };
+        assert_eq!(args.since, Some(100));
+        assert_eq!(args.until, None);
+        assert_eq!(
+            args.subtype,
+            Some(vec![BlockSubType::Receive, BlockSubType::Change])
+        );
+        assert!(args.requires_index());
+    }
 }
@@ -0,0 +1,39 @@
+use crate::RpcCommand;
+use rsnano_core::Account;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn representative_weight_at(account: Account, timestamp: u64) -> Self {
+        Self::RepresentativeWeightAt(RepresentativeWeightAtArgs { account, timestamp })
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct RepresentativeWeightAtArgs {
+    pub account: Account,
+    /// Local timestamp in seconds since the epoch
+    pub timestamp: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_command() {
+        let command = RpcCommand::representative_weight_at(Account::from(1), 1234);
+        let serialized = serde_json::to_value(&command).unwrap();
+        assert_eq!(
+            serialized,
+            json!({
+                "action": "representative_weight_at",
+                "account": Account::from(1),
+                "timestamp": 1234
+            })
+        );
+
+        let deserialized: RpcCommand = serde_json::from_value(serialized).unwrap();
+        assert_eq!(deserialized, command);
+    }
+}
@@ -3,6 +3,7 @@ mod ledger;
 mod node;
 mod utils;
 mod wallets;
+pub mod websocket;
 
 pub use common::*;
 pub use ledger::*;
@@ -38,6 +39,8 @@ pub enum RpcCommand {
     AccountGet(KeyRpcMessage),
     AccountRepresentative(AccountRpcMessage),
     AccountWeight(AccountWeightArgs),
+    AccountBalanceAt(AccountBalanceAtArgs),
+    RepresentativeWeightAt(RepresentativeWeightAtArgs),
     AvailableSupply,
     BlockAccount(HashRpcMessage),
     BlockConfirm(HashRpcMessage),
@@ -103,6 +106,8 @@ pub enum RpcCommand {
     Receivable(ReceivableArgs),
     ReceivableExists(ReceivableExistsArgs),
     RepresentativesOnline(RepresentativesOnlineArgs),
+    RepresentativesDetails(RepresentativesDetailsArgs),
+    FinalVotes(FinalVotesArgs),
     Unchecked(CountRpcMessage),
     UncheckedGet(HashRpcMessage),
     UncheckedKeys(UncheckedKeysArgs),
@@ -111,6 +116,20 @@ pub enum RpcCommand {
     WorkGenerate(WorkGenerateArgs),
     Republish(RepublishArgs),
     BlockCreate(BlockCreateArgs),
+    WalletMnemonic(WalletRpcMessage),
+    ReceivePolicySet(ReceivePolicySetArgs),
+    ReceivePolicyGet(ReceivePolicyArgs),
+    ReceivePolicyRemove(ReceivePolicyArgs),
+    RepHealthPolicySet(RepHealthPolicySetArgs),
+    RepHealthPolicyGet(WalletRpcMessage),
+    RepHealthPolicyRemove(WalletRpcMessage),
+    ElectionTrace(ElectionTraceArgs),
+    ElectionStart(ElectionStartArgs),
+    ElectionCancel(QualifiedRootRpcMessage),
+    ElectionErase(QualifiedRootRpcMessage),
+    ElectionInfo(HashRpcMessage),
+    CdcRead(CdcReadArgs),
+    SchedulerBuckets,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -134,6 +153,8 @@ pub enum RpcDto {
     AccountGet(AccountRpcMessage),
     AccountRepresentative(AccountRepresentativeDto),
     AccountWeight(WeightDto),
+    AccountBalanceAt(AccountBalanceAtDto),
+    RepresentativeWeightAt(WeightDto),
     AvailableSupply(AvailableSupplyDto),
     BlockConfirm(StartedDto),
     BlockCount(BlockCountDto),
@@ -167,7 +188,7 @@ pub enum RpcDto {
     UncheckedClear(SuccessDto),
     Unopened(UnopenedDto),
     NodeId(NodeIdDto),
-    Send(BlockDto),
+    Send(SendDto),
     SearchReceivableAll(SuccessDto),
     ReceiveMinimum(AmountRpcMessage),
     WalletChangeSeed(WalletChangeSeedDto),
@@ -185,7 +206,7 @@ pub enum RpcDto {
     AccountInfo(AccountInfoDto),
     AccountHistory(AccountHistoryDto),
     Sign(SignDto),
-    Process(HashRpcMessage),
+    Process(ProcessDto),
     WalletBalances(AccountsBalancesDto),
     WorkCancel(SuccessDto),
     Bootstrap(SuccessDto),
@@ -201,6 +222,8 @@ pub enum RpcDto {
     Receivable(ReceivableDto),
     ReceivableExists(ExistsDto),
     RepresentativesOnline(RepresentativesOnlineDto),
+    RepresentativesDetails(RepresentativesDetailsDto),
+    FinalVotes(FinalVotesDto),
     Unchecked(UncheckedDto),
     UncheckedGet(UncheckedGetDto),
     UncheckedKeys(UncheckedKeysDto),
@@ -209,4 +232,18 @@ pub enum RpcDto {
     WorkGenerate(WorkGenerateDto),
     Republish(BlockHashesDto),
     BlockCreate(BlockCreateDto),
+    WalletMnemonic(MnemonicDto),
+    ReceivePolicySet(SuccessDto),
+    ReceivePolicy(ReceivePolicyDto),
+    ReceivePolicyRemove(SuccessDto),
+    RepHealthPolicySet(SuccessDto),
+    RepHealthPolicy(RepHealthPolicyDto),
+    RepHealthPolicyRemove(SuccessDto),
+    ElectionTrace(ElectionTraceDto),
+    ElectionStart(StartedDto),
+    ElectionCancel(SuccessDto),
+    ElectionErase(SuccessDto),
+    ElectionInfo(ElectionInfoDto),
+    CdcRead(CdcReadDto),
+    SchedulerBuckets(SchedulerBucketsDto),
 }
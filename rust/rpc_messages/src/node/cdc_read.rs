@@ -0,0 +1,144 @@
+use crate::RpcCommand;
+use rsnano_core::{Account, Amount, BlockHash, BlockSubType, JsonBlock};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn cdc_read(args: CdcReadArgs) -> Self {
+        Self::CdcRead(args)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CdcReadArgs {
+    /// Sequence number of the first entry to return
+    pub from: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u64>,
+}
+
+impl CdcReadArgs {
+    pub fn builder(from: u64) -> CdcReadArgsBuilder {
+        CdcReadArgsBuilder {
+            args: CdcReadArgs { from, count: None },
+        }
+    }
+}
+
+impl From<u64> for CdcReadArgs {
+    fn from(from: u64) -> Self {
+        Self::builder(from).build()
+    }
+}
+
+pub struct CdcReadArgsBuilder {
+    args: CdcReadArgs,
+}
+
+impl CdcReadArgsBuilder {
+    pub fn count(mut self, count: u64) -> Self {
+        self.args.count = Some(count);
+        self
+    }
+
+    pub fn build(self) -> CdcReadArgs {
+        self.args
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CdcReadDto {
+    pub entries: Vec<CdcEntryDto>,
+    /// Sequence number to resume reading from
+    pub next: u64,
+    /// Sequence number of the oldest entry which is still available. If it is greater
+    /// than the requested `from`, the entries in between were already deleted
+    pub first_available: u64,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CdcEntryDto {
+    pub sequence: u64,
+    pub hash: BlockHash,
+    pub account: Account,
+    pub height: u64,
+    pub local_timestamp: u64,
+    pub subtype: BlockSubType,
+    pub amount: Amount,
+    /// The block whose confirmation cemented this block
+    pub confirmation_root: BlockHash,
+    pub contents: JsonBlock,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub election: Option<CdcElectionDto>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CdcElectionDto {
+    pub tally: Amount,
+    pub final_tally: Amount,
+    pub confirmation_request_count: u32,
+    pub voter_count: u32,
+    /// Duration of the election in milliseconds
+    pub duration: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsnano_core::BlockEnum;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_command() {
+        let command = RpcCommand::cdc_read(CdcReadArgs::builder(5).count(10).build());
+        let serialized = serde_json::to_value(&command).unwrap();
+        assert_eq!(
+            serialized,
+            json!({
+                "action": "cdc_read",
+                "from": 5,
+                "count": 10
+            })
+        );
+    }
+
+    #[test]
+    fn serialize_command_without_count() {
+        let serialized = serde_json::to_value(&RpcCommand::cdc_read(5.into())).unwrap();
+        assert_eq!(serialized, json!({ "action": "cdc_read", "from": 5 }));
+    }
+
+    #[test]
+    fn serialize_dto() {
+        let block = BlockEnum::new_test_instance();
+        let dto = CdcReadDto {
+            entries: vec![CdcEntryDto {
+                sequence: 3,
+                hash: block.hash(),
+                account: Account::from(1),
+                height: 2,
+                local_timestamp: 1234,
+                subtype: BlockSubType::Send,
+                amount: Amount::raw(10),
+                confirmation_root: block.hash(),
+                contents: block.json_representation(),
+                election: Some(CdcElectionDto {
+                    tally: Amount::raw(100),
+                    final_tally: Amount::raw(90),
+                    confirmation_request_count: 1,
+                    voter_count: 2,
+                    duration: 500,
+                }),
+            }],
+            next: 4,
+            first_available: 0,
+        };
+        let serialized = serde_json::to_value(&dto).unwrap();
+        assert_eq!(serialized["entries"][0]["subtype"], "send");
+        assert_eq!(serialized["entries"][0]["amount"], "10");
+        assert_eq!(serialized["next"], 4);
+        assert_eq!(serialized["first_available"], 0);
+
+        let deserialized: CdcReadDto = serde_json::from_value(serialized).unwrap();
+        assert_eq!(deserialized, dto);
+    }
+}
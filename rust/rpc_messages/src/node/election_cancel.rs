@@ -0,0 +1,31 @@
+use crate::{QualifiedRootRpcMessage, RpcCommand};
+use rsnano_core::QualifiedRoot;
+
+impl RpcCommand {
+    /// Marks the election as cancelled. It is removed with the next round of confirmation requests
+    pub fn election_cancel(root: QualifiedRoot) -> Self {
+        Self::ElectionCancel(QualifiedRootRpcMessage::new(root))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_command() {
+        let command = RpcCommand::election_cancel(QualifiedRoot::default());
+        let serialized = serde_json::to_value(&command).unwrap();
+        assert_eq!(
+            serialized,
+            json!({
+                "action": "election_cancel",
+                "root": QualifiedRoot::default()
+            })
+        );
+
+        let deserialized: RpcCommand = serde_json::from_value(serialized).unwrap();
+        assert_eq!(deserialized, command);
+    }
+}
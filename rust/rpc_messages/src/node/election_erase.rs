@@ -0,0 +1,24 @@
+use crate::{QualifiedRootRpcMessage, RpcCommand};
+use rsnano_core::QualifiedRoot;
+
+impl RpcCommand {
+    /// Removes the election from the active elections immediately
+    pub fn election_erase(root: QualifiedRoot) -> Self {
+        Self::ElectionErase(QualifiedRootRpcMessage::new(root))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize_command() {
+        let command = RpcCommand::election_erase(QualifiedRoot::default());
+        let serialized = serde_json::to_value(&command).unwrap();
+        assert_eq!(serialized["action"], "election_erase");
+
+        let deserialized: RpcCommand = serde_json::from_value(serialized).unwrap();
+        assert_eq!(deserialized, command);
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{common::HashRpcMessage, RpcCommand};
+use rsnano_core::{Account, Amount, BlockHash, QualifiedRoot};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn election_info(hash: BlockHash) -> Self {
+        Self::ElectionInfo(HashRpcMessage::new(hash))
+    }
+}
+
+/// Live state of the active election which contains the requested block
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ElectionInfoDto {
+    pub root: QualifiedRoot,
+    pub behavior: String,
+    pub state: String,
+    pub winner: Option<BlockHash>,
+    /// Sum of the tallies of all blocks
+    pub tally: Amount,
+    pub final_tally: Amount,
+    pub confirmation_request_count: u32,
+    /// Milliseconds since the election started
+    pub duration: u64,
+    pub blocks: Vec<ElectionBlockDto>,
+    pub votes: Vec<ElectionVoteDto>,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ElectionBlockDto {
+    pub hash: BlockHash,
+    pub tally: Amount,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ElectionVoteDto {
+    pub representative: Account,
+    pub hash: BlockHash,
+    pub weight: Amount,
+    pub timestamp: u64,
+    #[serde(rename = "final")]
+    pub is_final: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_command() {
+        let command = RpcCommand::election_info(BlockHash::from(1));
+        let serialized = serde_json::to_value(&command).unwrap();
+        assert_eq!(
+            serialized,
+            json!({
+                "action": "election_info",
+                "hash": "0000000000000000000000000000000000000000000000000000000000000001"
+            })
+        );
+    }
+
+    #[test]
+    fn serialize_dto() {
+        let dto = ElectionInfoDto {
+            root: QualifiedRoot::default(),
+            behavior: "manual".to_string(),
+            state: "active".to_string(),
+            winner: Some(BlockHash::from(1)),
+            tally: Amount::raw(10),
+            final_tally: Amount::zero(),
+            confirmation_request_count: 2,
+            duration: 1500,
+            blocks: vec![ElectionBlockDto {
+                hash: BlockHash::from(1),
+                tally: Amount::raw(10),
+            }],
+            votes: vec![ElectionVoteDto {
+                representative: Account::from(2),
+                hash: BlockHash::from(1),
+                weight: Amount::raw(10),
+                timestamp: 3,
+                is_final: false,
+            }],
+        };
+        let serialized = serde_json::to_value(&dto).unwrap();
+        assert_eq!(serialized["state"], "active");
+        assert_eq!(serialized["tally"], "10");
+        assert_eq!(serialized["votes"][0]["final"], false);
+
+        let deserialized: ElectionInfoDto = serde_json::from_value(serialized).unwrap();
+        assert_eq!(deserialized, dto);
+    }
+}
@@ -0,0 +1,85 @@
+use crate::RpcCommand;
+use rsnano_core::BlockHash;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn election_start(args: ElectionStartArgs) -> Self {
+        Self::ElectionStart(args)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElectionStartArgs {
+    pub hash: BlockHash,
+    /// One of "manual", "priority", "hinted" or "optimistic". Defaults to "manual".
+    /// The behavior decides the lifetime and the confirmation request interval of the election
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub behavior: Option<String>,
+}
+
+impl ElectionStartArgs {
+    pub fn builder(hash: BlockHash) -> ElectionStartArgsBuilder {
+        ElectionStartArgsBuilder {
+            args: ElectionStartArgs {
+                hash,
+                behavior: None,
+            },
+        }
+    }
+}
+
+impl From<BlockHash> for ElectionStartArgs {
+    fn from(hash: BlockHash) -> Self {
+        Self::builder(hash).build()
+    }
+}
+
+pub struct ElectionStartArgsBuilder {
+    args: ElectionStartArgs,
+}
+
+impl ElectionStartArgsBuilder {
+    pub fn behavior(mut self, behavior: impl Into<String>) -> Self {
+        self.args.behavior = Some(behavior.into());
+        self
+    }
+
+    pub fn build(self) -> ElectionStartArgs {
+        self.args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_command_without_behavior() {
+        let command = RpcCommand::election_start(BlockHash::from(1).into());
+        let serialized = serde_json::to_value(command).unwrap();
+        assert_eq!(
+            serialized,
+            json!({
+                "action": "election_start",
+                "hash": "0000000000000000000000000000000000000000000000000000000000000001"
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_command_with_behavior() {
+        let json = r#"{
+            "action": "election_start",
+            "hash": "0000000000000000000000000000000000000000000000000000000000000001",
+            "behavior": "hinted"
+        }"#;
+        let deserialized: RpcCommand = serde_json::from_str(json).unwrap();
+        let expected = RpcCommand::election_start(
+            ElectionStartArgs::builder(BlockHash::from(1))
+                .behavior("hinted")
+                .build(),
+        );
+        assert_eq!(deserialized, expected);
+    }
+}
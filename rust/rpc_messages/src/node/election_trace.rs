@@ -0,0 +1,145 @@
+use crate::RpcCommand;
+use rsnano_core::{Account, Amount, BlockHash, QualifiedRoot};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn election_trace(root: QualifiedRoot) -> Self {
+        Self::ElectionTrace(root.into())
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ElectionTraceArgs {
+    pub root: QualifiedRoot,
+}
+
+impl From<QualifiedRoot> for ElectionTraceArgs {
+    fn from(root: QualifiedRoot) -> Self {
+        Self { root }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ElectionTraceDto {
+    pub root: QualifiedRoot,
+    pub behavior: String,
+    pub state: String,
+    pub winner: Option<BlockHash>,
+    /// Milliseconds since the election started
+    pub duration: u64,
+    pub dropped_events: usize,
+    pub events: Vec<ElectionTraceEntryDto>,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ElectionTraceEntryDto {
+    /// Milliseconds since the election started
+    pub time: u64,
+    #[serde(flatten)]
+    pub event: ElectionTraceEventDto,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ElectionTraceEventDto {
+    StateChange {
+        from: String,
+        to: String,
+    },
+    BlockAdded {
+        hash: BlockHash,
+    },
+    Vote {
+        representative: Account,
+        weight: Amount,
+        hash: BlockHash,
+        #[serde(rename = "final")]
+        is_final: bool,
+        timestamp: u64,
+        source: String,
+        channel: Option<usize>,
+    },
+    Tally {
+        tally: Vec<(BlockHash, Amount)>,
+        final_tally: Amount,
+    },
+    ConfirmReq {
+        count: u32,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::to_string_pretty;
+
+    #[test]
+    fn serialize_election_trace_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::election_trace(QualifiedRoot::default())).unwrap(),
+            r#"{
+  "action": "election_trace",
+  "root": {
+    "root": "0000000000000000000000000000000000000000000000000000000000000000",
+    "previous": "0000000000000000000000000000000000000000000000000000000000000000"
+  }
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_election_trace_command() {
+        let cmd = RpcCommand::election_trace(QualifiedRoot::new(1.into(), 2.into()));
+        let serialized = serde_json::to_string(&cmd).unwrap();
+        let deserialized: RpcCommand = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+
+    #[test]
+    fn serialize_election_trace_event() {
+        let entry = ElectionTraceEntryDto {
+            time: 12,
+            event: ElectionTraceEventDto::ConfirmReq { count: 3 },
+        };
+        assert_eq!(
+            serde_json::to_string(&entry).unwrap(),
+            r#"{"time":12,"type":"confirm_req","count":3}"#
+        );
+    }
+
+    #[test]
+    fn deserialize_election_trace_dto() {
+        let dto = ElectionTraceDto {
+            root: QualifiedRoot::new(1.into(), 2.into()),
+            behavior: "priority".to_owned(),
+            state: "confirmed".to_owned(),
+            winner: Some(BlockHash::from(3)),
+            duration: 100,
+            dropped_events: 0,
+            events: vec![
+                ElectionTraceEntryDto {
+                    time: 1,
+                    event: ElectionTraceEventDto::Vote {
+                        representative: Account::from(4),
+                        weight: Amount::raw(5),
+                        hash: BlockHash::from(3),
+                        is_final: true,
+                        timestamp: u64::MAX,
+                        source: "live".to_owned(),
+                        channel: Some(6),
+                    },
+                },
+                ElectionTraceEntryDto {
+                    time: 2,
+                    event: ElectionTraceEventDto::Tally {
+                        tally: vec![(BlockHash::from(3), Amount::raw(5))],
+                        final_tally: Amount::raw(5),
+                    },
+                },
+            ],
+        };
+        let serialized = serde_json::to_string(&dto).unwrap();
+        let deserialized: ElectionTraceDto = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(dto, deserialized);
+    }
+}
@@ -0,0 +1,137 @@
+use crate::RpcCommand;
+use rsnano_core::{Account, BlockHash, Root};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn final_votes(args: FinalVotesArgs) -> Self {
+        Self::FinalVotes(args)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct FinalVotesArgs {
+    /// Only return the final votes for this root
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub root: Option<Root>,
+    /// Only return final votes for roots of accounts greater than or equal to this account.
+    /// The final votes are stored by root, so filtering by account scans all of them
+    /// until `count` matches were found
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_start: Option<Account>,
+    /// Only return final votes for roots of accounts less than or equal to this account
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_end: Option<Account>,
+    /// The maximum number of final votes to return. Defaults to and is capped at 1024
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u64>,
+}
+
+impl FinalVotesArgs {
+    pub fn builder() -> FinalVotesArgsBuilder {
+        FinalVotesArgsBuilder {
+            args: FinalVotesArgs::default(),
+        }
+    }
+}
+
+pub struct FinalVotesArgsBuilder {
+    args: FinalVotesArgs,
+}
+
+impl FinalVotesArgsBuilder {
+    pub fn root(mut self, root: Root) -> Self {
+        self.args.root = Some(root);
+        self
+    }
+
+    pub fn account_range(mut self, start: Account, end: Account) -> Self {
+        self.args.account_start = Some(start);
+        self.args.account_end = Some(end);
+        self
+    }
+
+    pub fn count(mut self, count: u64) -> Self {
+        self.args.count = Some(count);
+        self
+    }
+
+    pub fn build(self) -> FinalVotesArgs {
+        self.args
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FinalVotesDto {
+    pub final_votes: Vec<FinalVoteDto>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FinalVoteDto {
+    pub root: Root,
+    pub previous: BlockHash,
+    /// The block this node has finally voted for
+    pub hash: BlockHash,
+    /// `None` if the previous block is not in the ledger
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account: Option<Account>,
+    /// The block which is cemented at this root, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cemented: Option<BlockHash>,
+    /// A different block than the finally voted one was cemented at this root
+    pub conflicting: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_command_options_none() {
+        let command = RpcCommand::final_votes(FinalVotesArgs::default());
+        let serialized = serde_json::to_value(command).unwrap();
+        assert_eq!(serialized, json!({"action": "final_votes"}));
+    }
+
+    #[test]
+    fn deserialize_command_options_some() {
+        let json = r#"{
+            "action": "final_votes",
+            "root": "000000000000000000000000000000000000000000000000000000000000007B",
+            "count": 10
+        }"#;
+        let deserialized: RpcCommand = serde_json::from_str(json).unwrap();
+        let expected = RpcCommand::final_votes(
+            FinalVotesArgs::builder()
+                .root(Root::from(123))
+                .count(10)
+                .build(),
+        );
+        assert_eq!(deserialized, expected);
+    }
+
+    #[test]
+    fn serialize_dto() {
+        let dto = FinalVotesDto {
+            final_votes: vec![FinalVoteDto {
+                root: Root::from(1),
+                previous: BlockHash::from(1),
+                hash: BlockHash::from(2),
+                account: Some(Account::from(3)),
+                cemented: None,
+                conflicting: false,
+            }],
+        };
+        let serialized = serde_json::to_value(&dto).unwrap();
+        let vote = &serialized["final_votes"][0];
+        assert_eq!(
+            vote["hash"],
+            "0000000000000000000000000000000000000000000000000000000000000002"
+        );
+        assert_eq!(vote["conflicting"], false);
+        assert!(vote.get("cemented").is_none());
+
+        let deserialized: FinalVotesDto = serde_json::from_value(serialized).unwrap();
+        assert_eq!(deserialized, dto);
+    }
+}
@@ -2,9 +2,16 @@ mod block_create;
 mod bootstrap;
 mod bootstrap_any;
 mod bootstrap_lazy;
+mod cdc_read;
 mod confirmation_active;
 mod confirmation_info;
 mod confirmation_quorum;
+mod election_cancel;
+mod election_erase;
+mod election_info;
+mod election_start;
+mod election_trace;
+mod final_votes;
 mod keepalive;
 mod node_id;
 mod peers;
@@ -12,8 +19,10 @@ mod populate_backlog;
 mod process;
 mod receivable;
 mod receivable_exists;
+mod representatives_details;
 mod representatives_online;
 mod republish;
+mod scheduler_buckets;
 mod sign;
 mod stats_clear;
 mod stop;
@@ -30,16 +39,23 @@ pub use block_create::*;
 pub use bootstrap::*;
 pub use bootstrap_any::*;
 pub use bootstrap_lazy::*;
+pub use cdc_read::*;
 pub use confirmation_active::*;
 pub use confirmation_info::*;
 pub use confirmation_quorum::*;
+pub use election_info::*;
+pub use election_start::*;
+pub use election_trace::*;
+pub use final_votes::*;
 pub use node_id::*;
 pub use peers::*;
 pub use process::*;
 pub use receivable::*;
 pub use receivable_exists::*;
+pub use representatives_details::*;
 pub use representatives_online::*;
 pub use republish::*;
+pub use scheduler_buckets::*;
 pub use sign::*;
 pub use unchecked::*;
 pub use unchecked_get::*;
@@ -33,9 +33,25 @@ pub enum PeerInfo {
         node_id: Account,
         #[serde(rename = "type")]
         connection_type: String,
+        /// How much the peer is preferred when channels are picked at random (0-100)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        score: Option<u8>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        round_trip_time: Option<RoundTripTimeDto>,
     },
 }
 
+/// Round trip times in milliseconds, measured from request/response pairs
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct RoundTripTimeDto {
+    pub samples: u64,
+    /// Exponentially weighted moving average
+    pub average: u64,
+    pub p50: u64,
+    pub p90: u64,
+    pub p99: u64,
+}
+
 fn serialize_node_id<S>(account: &Account, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
@@ -111,6 +127,14 @@ mod tests {
                 )
                 .unwrap(),
                 connection_type: "tcp".to_string(),
+                score: Some(50),
+                round_trip_time: Some(RoundTripTimeDto {
+                    samples: 3,
+                    average: 120,
+                    p50: 100,
+                    p90: 200,
+                    p99: 200,
+                }),
             },
         );
 
@@ -121,13 +145,13 @@ mod tests {
         let json = serde_json::to_string(&peers).unwrap();
         assert_eq!(
             json,
-            r#"{"peers":{"[::ffff:172.17.0.1]:7075":{"protocol_version":18,"node_id":"node_1y7j5rdqhg99uyab1145gu3yur1ax35a3b6qr417yt8cd6n86uiw3d4whty3","type":"tcp"}}}"#
+            r#"{"peers":{"[::ffff:172.17.0.1]:7075":{"protocol_version":18,"node_id":"node_1y7j5rdqhg99uyab1145gu3yur1ax35a3b6qr417yt8cd6n86uiw3d4whty3","type":"tcp","score":50,"round_trip_time":{"samples":3,"average":120,"p50":100,"p90":200,"p99":200}}}}"#
         );
     }
 
     #[test]
     fn deserialize_detailed_peers() {
-        let json = r#"{"peers":{"[::ffff:172.17.0.1]:7075":{"protocol_version":18,"node_id":"node_1y7j5rdqhg99uyab1145gu3yur1ax35a3b6qr417yt8cd6n86uiw3d4whty3","type":"tcp"}}}"#;
+        let json = r#"{"peers":{"[::ffff:172.17.0.1]:7075":{"protocol_version":18,"node_id":"node_1y7j5rdqhg99uyab1145gu3yur1ax35a3b6qr417yt8cd6n86uiw3d4whty3","type":"tcp","score":50}}}"#;
         let peers: PeersDto = serde_json::from_str(json).unwrap();
 
         match peers.peers {
@@ -139,6 +163,8 @@ mod tests {
                         protocol_version,
                         node_id,
                         connection_type,
+                        score,
+                        round_trip_time,
                     } => {
                         assert_eq!(protocol_version, &18);
                         assert_eq!(
@@ -149,6 +175,8 @@ mod tests {
                             .unwrap()
                         );
                         assert_eq!(connection_type, "tcp");
+                        assert_eq!(score, &Some(50));
+                        assert_eq!(round_trip_time, &None);
                     }
                     PeerInfo::Simple(_) => panic!("Expected Detailed, got Simple"),
                 }
@@ -1,5 +1,5 @@
-use crate::RpcCommand;
-use rsnano_core::{BlockSubType, JsonBlock};
+use crate::{ConfirmationDto, RpcCommand};
+use rsnano_core::{BlockHash, BlockSubType, JsonBlock};
 use serde::{Deserialize, Serialize};
 
 impl RpcCommand {
@@ -25,6 +25,12 @@ pub struct ProcessArgs {
     pub watch_work: Option<bool>,
     #[serde(rename = "async", skip_serializing_if = "Option::is_none")]
     pub is_async: Option<bool>,
+    /// Waits until the block is confirmed, rolled back or the timeout expires
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wait_confirmation: Option<bool>,
+    /// Milliseconds to wait for the confirmation, 30 seconds by default and at most 5 minutes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
 }
 
 impl ProcessArgs {
@@ -36,6 +42,8 @@ impl ProcessArgs {
                 force: None,
                 watch_work: None,
                 is_async: None,
+                wait_confirmation: None,
+                timeout: None,
             },
         }
     }
@@ -66,11 +74,33 @@ impl ProcessArgsBuilder {
         self
     }
 
+    pub fn wait_confirmation(mut self, timeout_ms: u64) -> Self {
+        self.args.wait_confirmation = Some(true);
+        self.args.timeout = Some(timeout_ms);
+        self
+    }
+
     pub fn build(self) -> ProcessArgs {
         self.args
     }
 }
 
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ProcessDto {
+    pub hash: BlockHash,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmation: Option<ConfirmationDto>,
+}
+
+impl ProcessDto {
+    pub fn new(hash: BlockHash) -> Self {
+        Self {
+            hash,
+            confirmation: None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -147,4 +177,16 @@ mod tests {
             panic!("Deserialized to wrong variant");
         }
     }
+
+    #[test]
+    fn serialize_wait_confirmation() {
+        let args = ProcessArgs::builder(BlockEnum::new_test_instance().json_representation())
+            .wait_confirmation(5000)
+            .build();
+
+        let serialized = serde_json::to_value(&args).unwrap();
+
+        assert_eq!(serialized["wait_confirmation"], json!(true));
+        assert_eq!(serialized["timeout"], json!(5000));
+    }
 }
@@ -0,0 +1,189 @@
+use crate::RpcCommand;
+use rsnano_core::{Account, Amount};
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddrV6;
+
+impl RpcCommand {
+    pub fn representatives_details(args: RepresentativesDetailsArgs) -> Self {
+        Self::RepresentativesDetails(args)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct RepresentativesDetailsArgs {
+    /// Only return these representatives
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accounts: Option<Vec<Account>>,
+    /// Only return the representatives with the most weight
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u64>,
+}
+
+impl RepresentativesDetailsArgs {
+    pub fn builder() -> RepresentativesDetailsArgsBuilder {
+        RepresentativesDetailsArgsBuilder {
+            args: RepresentativesDetailsArgs::default(),
+        }
+    }
+}
+
+pub struct RepresentativesDetailsArgsBuilder {
+    args: RepresentativesDetailsArgs,
+}
+
+impl RepresentativesDetailsArgsBuilder {
+    pub fn accounts(mut self, accounts: Vec<Account>) -> Self {
+        self.args.accounts = Some(accounts);
+        self
+    }
+
+    pub fn count(mut self, count: u64) -> Self {
+        self.args.count = Some(count);
+        self
+    }
+
+    pub fn build(self) -> RepresentativesDetailsArgs {
+        self.args
+    }
+}
+
+/// Representatives in descending order of weight
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RepresentativesDetailsDto {
+    pub representatives: Vec<RepresentativeDetailsDto>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RepresentativeDetailsDto {
+    pub account: Account,
+    pub weight: Amount,
+    /// Node ids in "node_..." format, most recent last
+    pub node_ids: Vec<String>,
+    /// Most recent last
+    pub endpoints: Vec<SocketAddrV6>,
+    /// Seconds since epoch
+    pub first_seen: u64,
+    /// Seconds since epoch
+    pub last_seen: u64,
+    pub votes: u64,
+    pub uptime: RepUptimeDto,
+    pub latency: RepLatencyDto,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub telemetry: Option<RepTelemetryDto>,
+}
+
+/// Percentage of hours in which the representative voted
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RepUptimeDto {
+    pub day: f64,
+    pub week: f64,
+    pub month: f64,
+}
+
+/// Vote latencies in milliseconds. The percentiles are the upper bounds of the histogram buckets
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepLatencyDto {
+    pub samples: u64,
+    pub p50: Option<u64>,
+    pub p90: Option<u64>,
+    pub p99: Option<u64>,
+    pub histogram: Vec<RepLatencyBucketDto>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepLatencyBucketDto {
+    /// Upper bound in milliseconds, `None` for the last bucket
+    pub le: Option<u64>,
+    pub count: u64,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepTelemetryDto {
+    /// Seconds since epoch
+    pub received: u64,
+    pub node_id: String,
+    pub protocol_version: u8,
+    pub major_version: u8,
+    pub minor_version: u8,
+    pub patch_version: u8,
+    pub pre_release_version: u8,
+    pub maker: u8,
+    pub block_count: u64,
+    pub cemented_count: u64,
+    pub peer_count: u32,
+    pub uptime: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_command_options_none() {
+        let command = RpcCommand::representatives_details(RepresentativesDetailsArgs::default());
+        let serialized = serde_json::to_value(command).unwrap();
+        assert_eq!(serialized, json!({"action": "representatives_details"}));
+    }
+
+    #[test]
+    fn deserialize_command_options_some() {
+        let json = r#"{
+            "action": "representatives_details",
+            "accounts": ["nano_1jg8zygjg3pp5w644emqcbmjqpnzmubfni3kfe1s8pooeuxsw49fdq1mco9j"],
+            "count": 10
+        }"#;
+        let deserialized: RpcCommand = serde_json::from_str(json).unwrap();
+        let expected = RpcCommand::representatives_details(
+            RepresentativesDetailsArgs::builder()
+                .accounts(vec![Account::decode_account(
+                    "nano_1jg8zygjg3pp5w644emqcbmjqpnzmubfni3kfe1s8pooeuxsw49fdq1mco9j",
+                )
+                .unwrap()])
+                .count(10)
+                .build(),
+        );
+        assert_eq!(deserialized, expected);
+    }
+
+    #[test]
+    fn serialize_dto() {
+        let dto = RepresentativesDetailsDto {
+            representatives: vec![RepresentativeDetailsDto {
+                account: Account::from(1),
+                weight: Amount::raw(1000),
+                node_ids: vec![Account::from(2).to_node_id()],
+                endpoints: vec!["[::ffff:10.0.0.1]:7075".parse().unwrap()],
+                first_seen: 100,
+                last_seen: 200,
+                votes: 3,
+                uptime: RepUptimeDto {
+                    day: 50.0,
+                    week: 25.0,
+                    month: 12.5,
+                },
+                latency: RepLatencyDto {
+                    samples: 1,
+                    p50: Some(250),
+                    p90: Some(250),
+                    p99: Some(250),
+                    histogram: vec![RepLatencyBucketDto {
+                        le: Some(250),
+                        count: 1,
+                    }],
+                },
+                telemetry: None,
+            }],
+        };
+        let serialized = serde_json::to_value(&dto).unwrap();
+        let rep = &serialized["representatives"][0];
+        assert_eq!(rep["weight"], "1000");
+        assert_eq!(rep["endpoints"][0], "[::ffff:10.0.0.1]:7075");
+        assert_eq!(rep["uptime"]["day"], 50.0);
+        assert_eq!(rep["latency"]["histogram"][0]["le"], 250);
+        assert!(rep.get("telemetry").is_none());
+
+        let deserialized: RepresentativesDetailsDto = serde_json::from_value(serialized).unwrap();
+        assert_eq!(deserialized, dto);
+    }
+}
@@ -0,0 +1,77 @@
+use crate::RpcCommand;
+use rsnano_core::Amount;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn scheduler_buckets() -> Self {
+        Self::SchedulerBuckets
+    }
+}
+
+/// Live state of the buckets of the priority scheduler, ordered by their minimum balance
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct SchedulerBucketsDto {
+    pub buckets: Vec<SchedulerBucketDto>,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct SchedulerBucketDto {
+    pub minimum_balance: Amount,
+    pub max_blocks: usize,
+    pub reserved_elections: usize,
+    pub max_elections: usize,
+    /// Number of queued blocks
+    pub blocks: usize,
+    /// Time when the longest waiting block was queued, in seconds since epoch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oldest_block_time: Option<u64>,
+    pub elections: usize,
+    /// Number of elections which were cancelled to make room for blocks with higher priority
+    pub cancellations: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_command() {
+        assert_eq!(
+            serde_json::to_value(RpcCommand::scheduler_buckets()).unwrap(),
+            json!({ "action": "scheduler_buckets" })
+        );
+    }
+
+    #[test]
+    fn serialize_dto() {
+        let dto = SchedulerBucketsDto {
+            buckets: vec![SchedulerBucketDto {
+                minimum_balance: Amount::raw(1000),
+                max_blocks: 8192,
+                reserved_elections: 100,
+                max_elections: 150,
+                blocks: 2,
+                oldest_block_time: Some(1234),
+                elections: 1,
+                cancellations: 3,
+            }],
+        };
+
+        assert_eq!(
+            serde_json::to_value(&dto).unwrap(),
+            json!({
+                "buckets": [{
+                    "minimum_balance": "1000",
+                    "max_blocks": 8192,
+                    "reserved_elections": 100,
+                    "max_elections": 150,
+                    "blocks": 2,
+                    "oldest_block_time": 1234,
+                    "elections": 1,
+                    "cancellations": 3
+                }]
+            })
+        );
+    }
+}
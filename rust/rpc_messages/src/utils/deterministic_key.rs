@@ -6,17 +6,46 @@ impl RpcCommand {
     pub fn deterministic_key(seed: RawKey, index: u32) -> Self {
         Self::DeterministicKey(DeterministicKeyArgs::new(seed, index))
     }
+
+    pub fn deterministic_key_bip44(
+        mnemonic: String,
+        passphrase: Option<String>,
+        index: u32,
+    ) -> Self {
+        Self::DeterministicKey(DeterministicKeyArgs::bip44(mnemonic, passphrase, index))
+    }
 }
 
+/// Derives a key either from a `seed` or, in BIP44 mode, from a `mnemonic`
+/// via the path 44'/165'/index'
 #[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct DeterministicKeyArgs {
-    pub seed: RawKey,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<RawKey>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mnemonic: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passphrase: Option<String>,
     pub index: u32,
 }
 
 impl DeterministicKeyArgs {
     pub fn new(seed: RawKey, index: u32) -> Self {
-        Self { seed, index }
+        Self {
+            seed: Some(seed),
+            mnemonic: None,
+            passphrase: None,
+            index,
+        }
+    }
+
+    pub fn bip44(mnemonic: String, passphrase: Option<String>, index: u32) -> Self {
+        Self {
+            seed: None,
+            mnemonic: Some(mnemonic),
+            passphrase,
+            index,
+        }
     }
 }
 
@@ -49,4 +78,21 @@ mod tests {
         let deserialized: RpcCommand = serde_json::from_str(&serialized).unwrap();
         assert_eq!(cmd, deserialized)
     }
+
+    #[test]
+    fn serialize_deterministic_key_bip44_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::deterministic_key_bip44(
+                "abandon ability".to_string(),
+                None,
+                3
+            ))
+            .unwrap(),
+            r#"{
+  "action": "deterministic_key",
+  "mnemonic": "abandon ability",
+  "index": 3
+}"#
+        )
+    }
 }
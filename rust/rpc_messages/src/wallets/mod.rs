@@ -8,6 +8,8 @@ mod password_enter;
 mod password_valid;
 mod receive;
 mod receive_minimum;
+mod receive_policy;
+mod rep_health_policy;
 mod search_receivable;
 mod search_receivable_all;
 mod send;
@@ -25,6 +27,7 @@ mod wallet_info;
 mod wallet_ledger;
 mod wallet_lock;
 mod wallet_locked;
+mod wallet_mnemonic;
 mod wallet_receivable;
 mod wallet_representative;
 mod wallet_representative_set;
@@ -37,6 +40,8 @@ pub use account_create::*;
 pub use account_move::*;
 pub use accounts_create::*;
 pub use receive::*;
+pub use receive_policy::*;
+pub use rep_health_policy::*;
 pub use send::*;
 pub use wallet_add::*;
 pub use wallet_add_watch::*;
@@ -47,6 +52,7 @@ pub use wallet_export::*;
 pub use wallet_history::*;
 pub use wallet_info::*;
 pub use wallet_ledger::*;
+pub use wallet_mnemonic::*;
 pub use wallet_receivable::*;
 pub use wallet_representative::*;
 pub use wallet_representative_set::*;
@@ -1,9 +1,40 @@
+use crate::RpcCommand;
 use rsnano_core::{Account, JsonBlock, WalletId};
 use serde::{Deserialize, Serialize};
 
+impl RpcCommand {
+    pub fn receive(wallet: WalletId, account: Account, block: JsonBlock) -> Self {
+        Self::Receive(ReceiveArgs {
+            wallet,
+            account,
+            block,
+        })
+    }
+}
+
 #[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct ReceiveArgs {
     pub wallet: WalletId,
     pub account: Account,
     pub block: JsonBlock,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsnano_core::BlockEnum;
+
+    #[test]
+    fn serialize_receive_command() {
+        let command = RpcCommand::receive(
+            WalletId::from(1),
+            Account::from(2),
+            BlockEnum::new_test_instance().into(),
+        );
+        let serialized = serde_json::to_value(&command).unwrap();
+        assert_eq!(serialized["action"], "receive");
+        assert_eq!(serialized["account"], Account::from(2).encode_account());
+        let deserialized: RpcCommand = serde_json::from_value(serialized).unwrap();
+        assert_eq!(deserialized, command);
+    }
+}
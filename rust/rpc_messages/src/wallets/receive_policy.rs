@@ -0,0 +1,212 @@
+use crate::RpcCommand;
+use rsnano_core::{Account, Amount, WalletId};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn receive_policy_set(args: ReceivePolicySetArgs) -> Self {
+        Self::ReceivePolicySet(args)
+    }
+
+    pub fn receive_policy_get(args: ReceivePolicyArgs) -> Self {
+        Self::ReceivePolicyGet(args)
+    }
+
+    pub fn receive_policy_remove(args: ReceivePolicyArgs) -> Self {
+        Self::ReceivePolicyRemove(args)
+    }
+}
+
+/// Identifies the policy of an account or, without account, the policy of the wallet
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ReceivePolicyArgs {
+    pub wallet: WalletId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account: Option<Account>,
+}
+
+impl ReceivePolicyArgs {
+    pub fn wallet(wallet: WalletId) -> Self {
+        Self {
+            wallet,
+            account: None,
+        }
+    }
+
+    pub fn account(wallet: WalletId, account: Account) -> Self {
+        Self {
+            wallet,
+            account: Some(account),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ReceivePolicySetArgs {
+    pub wallet: WalletId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account: Option<Account>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<Amount>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow: Option<Vec<Account>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deny: Option<Vec<Account>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_per_minute: Option<u32>,
+}
+
+impl ReceivePolicySetArgs {
+    pub fn builder(wallet: WalletId) -> ReceivePolicySetArgsBuilder {
+        ReceivePolicySetArgsBuilder {
+            args: ReceivePolicySetArgs {
+                wallet,
+                account: None,
+                minimum: None,
+                allow: None,
+                deny: None,
+                disabled: None,
+                max_per_minute: None,
+            },
+        }
+    }
+}
+
+pub struct ReceivePolicySetArgsBuilder {
+    args: ReceivePolicySetArgs,
+}
+
+impl ReceivePolicySetArgsBuilder {
+    pub fn account(mut self, account: Account) -> Self {
+        self.args.account = Some(account);
+        self
+    }
+
+    pub fn minimum(mut self, minimum: Amount) -> Self {
+        self.args.minimum = Some(minimum);
+        self
+    }
+
+    pub fn allow(mut self, accounts: Vec<Account>) -> Self {
+        self.args.allow = Some(accounts);
+        self
+    }
+
+    pub fn deny(mut self, accounts: Vec<Account>) -> Self {
+        self.args.deny = Some(accounts);
+        self
+    }
+
+    pub fn disabled(mut self) -> Self {
+        self.args.disabled = Some(true);
+        self
+    }
+
+    pub fn max_per_minute(mut self, max: u32) -> Self {
+        self.args.max_per_minute = Some(max);
+        self
+    }
+
+    pub fn build(self) -> ReceivePolicySetArgs {
+        self.args
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ReceivePolicyDto {
+    /// Where the policy is defined: "account", "wallet" or "default"
+    pub scope: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<Amount>,
+    pub allow: Vec<Account>,
+    pub deny: Vec<Account>,
+    pub disabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_per_minute: Option<u32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::to_string_pretty;
+
+    #[test]
+    fn serialize_receive_policy_set_command() {
+        let cmd = RpcCommand::receive_policy_set(
+            ReceivePolicySetArgs::builder(WalletId::zero())
+                .account(Account::zero())
+                .minimum(Amount::raw(1000))
+                .deny(vec![Account::zero()])
+                .max_per_minute(10)
+                .build(),
+        );
+        assert_eq!(
+            to_string_pretty(&cmd).unwrap(),
+            r#"{
+  "action": "receive_policy_set",
+  "wallet": "0000000000000000000000000000000000000000000000000000000000000000",
+  "account": "nano_1111111111111111111111111111111111111111111111111111hifc8npp",
+  "minimum": "1000",
+  "deny": [
+    "nano_1111111111111111111111111111111111111111111111111111hifc8npp"
+  ],
+  "max_per_minute": 10
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_receive_policy_set_command() {
+        let cmd = RpcCommand::receive_policy_set(
+            ReceivePolicySetArgs::builder(WalletId::zero())
+                .allow(vec![Account::from(1)])
+                .disabled()
+                .build(),
+        );
+        let serialized = serde_json::to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+
+    #[test]
+    fn serialize_receive_policy_get_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::receive_policy_get(ReceivePolicyArgs::wallet(
+                WalletId::zero()
+            )))
+            .unwrap(),
+            r#"{
+  "action": "receive_policy_get",
+  "wallet": "0000000000000000000000000000000000000000000000000000000000000000"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_receive_policy_remove_command() {
+        let cmd = RpcCommand::receive_policy_remove(ReceivePolicyArgs::account(
+            WalletId::zero(),
+            Account::from(1),
+        ));
+        let serialized = serde_json::to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+
+    #[test]
+    fn serialize_receive_policy_dto() {
+        let dto = ReceivePolicyDto {
+            scope: "wallet".to_owned(),
+            minimum: Some(Amount::raw(5)),
+            allow: Vec::new(),
+            deny: Vec::new(),
+            disabled: false,
+            max_per_minute: None,
+        };
+        assert_eq!(
+            serde_json::to_string(&dto).unwrap(),
+            r#"{"scope":"wallet","minimum":"5","allow":[],"deny":[],"disabled":false}"#
+        );
+    }
+}
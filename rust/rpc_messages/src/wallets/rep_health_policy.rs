@@ -0,0 +1,177 @@
+use crate::{RpcCommand, WalletRpcMessage};
+use rsnano_core::{Account, Amount, WalletId};
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn rep_health_policy_set(args: RepHealthPolicySetArgs) -> Self {
+        Self::RepHealthPolicySet(args)
+    }
+
+    pub fn rep_health_policy_get(wallet: WalletId) -> Self {
+        Self::RepHealthPolicyGet(WalletRpcMessage::new(wallet))
+    }
+
+    pub fn rep_health_policy_remove(wallet: WalletId) -> Self {
+        Self::RepHealthPolicyRemove(WalletRpcMessage::new(wallet))
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct RepHealthPolicySetArgs {
+    pub wallet: WalletId,
+    pub fallback_representatives: Vec<Account>,
+    /// Seconds, defaults to one hour
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grace_period: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum_weight: Option<Amount>,
+    /// 1, 2 or 3
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum_tier: Option<u8>,
+    /// Percentage of the last 24 hours
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum_uptime: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_changes_per_hour: Option<u32>,
+}
+
+impl RepHealthPolicySetArgs {
+    pub fn builder(
+        wallet: WalletId,
+        fallback_representatives: Vec<Account>,
+    ) -> RepHealthPolicySetArgsBuilder {
+        RepHealthPolicySetArgsBuilder {
+            args: RepHealthPolicySetArgs {
+                wallet,
+                fallback_representatives,
+                grace_period: None,
+                minimum_weight: None,
+                minimum_tier: None,
+                minimum_uptime: None,
+                max_changes_per_hour: None,
+            },
+        }
+    }
+}
+
+pub struct RepHealthPolicySetArgsBuilder {
+    args: RepHealthPolicySetArgs,
+}
+
+impl RepHealthPolicySetArgsBuilder {
+    pub fn grace_period(mut self, seconds: u64) -> Self {
+        self.args.grace_period = Some(seconds);
+        self
+    }
+
+    pub fn minimum_weight(mut self, weight: Amount) -> Self {
+        self.args.minimum_weight = Some(weight);
+        self
+    }
+
+    pub fn minimum_tier(mut self, tier: u8) -> Self {
+        self.args.minimum_tier = Some(tier);
+        self
+    }
+
+    pub fn minimum_uptime(mut self, percent: u8) -> Self {
+        self.args.minimum_uptime = Some(percent);
+        self
+    }
+
+    pub fn max_changes_per_hour(mut self, max: u32) -> Self {
+        self.args.max_changes_per_hour = Some(max);
+        self
+    }
+
+    pub fn build(self) -> RepHealthPolicySetArgs {
+        self.args
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct RepHealthPolicyDto {
+    /// False if the wallet has no policy. All other fields are empty in that case
+    pub enabled: bool,
+    pub fallback_representatives: Vec<Account>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grace_period: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum_weight: Option<Amount>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum_tier: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum_uptime: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_changes_per_hour: Option<u32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::to_string_pretty;
+
+    #[test]
+    fn serialize_rep_health_policy_set_command() {
+        let cmd = RpcCommand::rep_health_policy_set(
+            RepHealthPolicySetArgs::builder(WalletId::zero(), vec![Account::zero()])
+                .grace_period(600)
+                .minimum_tier(1)
+                .build(),
+        );
+        assert_eq!(
+            to_string_pretty(&cmd).unwrap(),
+            r#"{
+  "action": "rep_health_policy_set",
+  "wallet": "0000000000000000000000000000000000000000000000000000000000000000",
+  "fallback_representatives": [
+    "nano_1111111111111111111111111111111111111111111111111111hifc8npp"
+  ],
+  "grace_period": 600,
+  "minimum_tier": 1
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_rep_health_policy_set_command() {
+        let cmd = RpcCommand::rep_health_policy_set(
+            RepHealthPolicySetArgs::builder(WalletId::zero(), vec![Account::from(1)])
+                .minimum_weight(Amount::nano(1))
+                .minimum_uptime(90)
+                .max_changes_per_hour(5)
+                .build(),
+        );
+        let serialized = to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+
+    #[test]
+    fn serialize_rep_health_policy_get_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::rep_health_policy_get(WalletId::zero())).unwrap(),
+            r#"{
+  "action": "rep_health_policy_get",
+  "wallet": "0000000000000000000000000000000000000000000000000000000000000000"
+}"#
+        )
+    }
+
+    #[test]
+    fn serialize_rep_health_policy_dto() {
+        let dto = RepHealthPolicyDto {
+            enabled: true,
+            fallback_representatives: Vec::new(),
+            grace_period: Some(3600),
+            minimum_weight: None,
+            minimum_tier: None,
+            minimum_uptime: None,
+            max_changes_per_hour: None,
+        };
+        assert_eq!(
+            serde_json::to_string(&dto).unwrap(),
+            r#"{"enabled":true,"fallback_representatives":[],"grace_period":3600}"#
+        );
+    }
+}
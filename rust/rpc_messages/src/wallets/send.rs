@@ -1,5 +1,5 @@
-use crate::RpcCommand;
-use rsnano_core::{Account, Amount, WalletId};
+use crate::{ConfirmationDto, RpcCommand};
+use rsnano_core::{Account, Amount, BlockHash, WalletId};
 use serde::{Deserialize, Serialize};
 
 impl RpcCommand {
@@ -18,6 +18,12 @@ pub struct SendArgs {
     pub work: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
+    /// Waits until the send block is confirmed, rolled back or the timeout expires
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wait_confirmation: Option<bool>,
+    /// Milliseconds to wait for the confirmation, 30 seconds by default and at most 5 minutes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
 }
 
 impl SendArgs {
@@ -34,6 +40,8 @@ impl SendArgs {
             amount,
             work: None,
             id: None,
+            wait_confirmation: None,
+            timeout: None,
         }
     }
 
@@ -61,6 +69,8 @@ impl SendArgsBuilder {
                 amount,
                 work: None,
                 id: None,
+                wait_confirmation: None,
+                timeout: None,
             },
         }
     }
@@ -75,11 +85,33 @@ impl SendArgsBuilder {
         self
     }
 
+    pub fn wait_confirmation(mut self, timeout_ms: u64) -> Self {
+        self.args.wait_confirmation = Some(true);
+        self.args.timeout = Some(timeout_ms);
+        self
+    }
+
     pub fn build(self) -> SendArgs {
         self.args
     }
 }
 
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct SendDto {
+    pub block: BlockHash,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmation: Option<ConfirmationDto>,
+}
+
+impl SendDto {
+    pub fn new(block: BlockHash) -> Self {
+        Self {
+            block,
+            confirmation: None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
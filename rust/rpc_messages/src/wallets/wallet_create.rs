@@ -6,17 +6,39 @@ impl RpcCommand {
     pub fn wallet_create(seed: Option<RawKey>) -> Self {
         Self::WalletCreate(WalletCreateArgs::new(seed))
     }
+
+    pub fn wallet_create_from_mnemonic(mnemonic: String, passphrase: Option<String>) -> Self {
+        Self::WalletCreate(WalletCreateArgs::from_mnemonic(mnemonic, passphrase))
+    }
 }
 
 #[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct WalletCreateArgs {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub seed: Option<RawKey>,
+    /// BIP39 mnemonic. Accounts are derived via the path 44'/165'/n'
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mnemonic: Option<String>,
+    /// Optional BIP39 passphrase of the mnemonic
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passphrase: Option<String>,
 }
 
 impl WalletCreateArgs {
     pub fn new(seed: Option<RawKey>) -> Self {
-        WalletCreateArgs { seed }
+        WalletCreateArgs {
+            seed,
+            mnemonic: None,
+            passphrase: None,
+        }
+    }
+
+    pub fn from_mnemonic(mnemonic: String, passphrase: Option<String>) -> Self {
+        WalletCreateArgs {
+            seed: None,
+            mnemonic: Some(mnemonic),
+            passphrase,
+        }
     }
 }
 
@@ -58,6 +80,22 @@ mod tests {
         )
     }
 
+    #[test]
+    fn serialize_wallet_create_command_mnemonic() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::wallet_create_from_mnemonic(
+                "abandon ability".to_string(),
+                Some("secret".to_string())
+            ))
+            .unwrap(),
+            r#"{
+  "action": "wallet_create",
+  "mnemonic": "abandon ability",
+  "passphrase": "secret"
+}"#
+        )
+    }
+
     #[test]
     fn deserialize_wallet_create_command_seed_none() {
         let cmd = RpcCommand::wallet_create(None);
@@ -0,0 +1,54 @@
+use crate::{common::WalletRpcMessage, RpcCommand};
+use rsnano_core::WalletId;
+use serde::{Deserialize, Serialize};
+
+impl RpcCommand {
+    pub fn wallet_mnemonic(wallet: WalletId) -> Self {
+        Self::WalletMnemonic(WalletRpcMessage::new(wallet))
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct MnemonicDto {
+    pub mnemonic: String,
+}
+
+impl MnemonicDto {
+    pub fn new(mnemonic: String) -> Self {
+        Self { mnemonic }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::to_string_pretty;
+
+    #[test]
+    fn serialize_wallet_mnemonic_command() {
+        assert_eq!(
+            to_string_pretty(&RpcCommand::wallet_mnemonic(WalletId::zero())).unwrap(),
+            r#"{
+  "action": "wallet_mnemonic",
+  "wallet": "0000000000000000000000000000000000000000000000000000000000000000"
+}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_wallet_mnemonic_command() {
+        let cmd = RpcCommand::wallet_mnemonic(WalletId::zero());
+        let serialized = serde_json::to_string_pretty(&cmd).unwrap();
+        let deserialized: RpcCommand = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(cmd, deserialized)
+    }
+
+    #[test]
+    fn serialize_mnemonic_dto() {
+        let dto = MnemonicDto::new("abandon ability".to_string());
+        assert_eq!(
+            serde_json::to_string(&dto).unwrap(),
+            r#"{"mnemonic":"abandon ability"}"#
+        );
+    }
+}
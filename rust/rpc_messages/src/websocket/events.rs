@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct JsonSideband {
+    pub height: String,
+    pub local_timestamp: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BlockConfirmed {
+    pub account: String,
+    pub amount: String,
+    pub hash: String,
+    pub confirmation_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub election_info: Option<ElectionInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sideband: Option<JsonSideband>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ElectionInfo {
+    pub duration: String,
+    pub time: String,
+    pub tally: String,
+    #[serde(rename = "final")]
+    pub final_tally: String,
+    pub blocks: String,
+    pub voters: String,
+    pub request_count: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub votes: Option<Vec<JsonVoteSummary>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct JsonVoteSummary {
+    pub representative: String,
+    pub timestamp: String,
+    pub hash: String,
+    pub weight: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TelemetryReceived {
+    pub block_count: String,
+    pub cemented_count: String,
+    pub unchecked_count: String,
+    pub account_count: String,
+    pub bandwidth_cap: String,
+    pub peer_count: String,
+    pub protocol_version: String,
+    pub uptime: String,
+    pub genesis_block: String,
+    pub major_version: String,
+    pub minor_version: String,
+    pub patch_version: String,
+    pub pre_release_version: String,
+    pub maker: String,
+    pub timestamp: String,
+    pub active_difficulty: String,
+    pub node_id: String,
+    pub signature: String,
+    pub address: String,
+    pub port: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct StartedElection {
+    pub hash: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct StoppedElection {
+    pub hash: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct VoteReceived {
+    pub account: String,
+    pub signature: String,
+    pub sequence: String,
+    pub timestamp: String,
+    pub duration: String,
+    pub blocks: Vec<String>,
+    #[serde(rename = "type")]
+    pub vote_type: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RepresentativeChanged {
+    pub wallet: String,
+    pub old_representative: String,
+    pub new_representative: String,
+    /// Why the old representative was replaced: "offline", "low_weight", "low_tier" or "low_uptime"
+    pub reason: String,
+    /// Accounts for which a change block was published
+    pub accounts: Vec<String>,
+}
@@ -0,0 +1,7 @@
+mod envelope;
+mod events;
+mod options;
+
+pub use envelope::*;
+pub use events::*;
+pub use options::*;
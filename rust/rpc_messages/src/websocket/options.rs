@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq)]
+pub struct ConfirmationJsonOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_block: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_election_info: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_election_info_with_votes: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_sideband_info: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmation_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub all_local_accounts: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accounts: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq)]
+pub struct VoteJsonOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_replays: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_indeterminate: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub representatives: Option<Vec<String>>,
+}
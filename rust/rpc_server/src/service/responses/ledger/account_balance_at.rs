@@ -0,0 +1,49 @@
+use super::account_history_index_error;
+use rsnano_node::Node;
+use rsnano_rpc_messages::{AccountBalanceAtArgs, AccountBalanceAtDto, ErrorDto, RpcDto};
+use std::sync::Arc;
+
+pub async fn account_balance_at(node: Arc<Node>, args: AccountBalanceAtArgs) -> RpcDto {
+    let selectors = [
+        args.height.is_some(),
+        args.hash.is_some(),
+        args.timestamp.is_some(),
+    ];
+    if selectors.iter().filter(|i| **i).count() != 1 {
+        return RpcDto::Error(ErrorDto::BlockSelectorRequired);
+    }
+
+    let tx = node.ledger.read_txn();
+    let Some(finder) = node.ledger.historical_blocks(&tx) else {
+        return account_history_index_error(&node);
+    };
+
+    let block = if let Some(height) = args.height {
+        finder.at_height(&args.account, height)
+    } else if let Some(hash) = args.hash {
+        match node.ledger.confirmed().get_block(&tx, &hash) {
+            Some(block) if block.account() != args.account => {
+                return RpcDto::Error(ErrorDto::BlockAccountMismatch)
+            }
+            block => block,
+        }
+    } else {
+        finder.at_timestamp(&args.account, args.timestamp.unwrap())
+    };
+
+    let Some(block) = block else {
+        return RpcDto::Error(ErrorDto::BlockNotFound);
+    };
+
+    let sideband = block.sideband().unwrap();
+    RpcDto::AccountBalanceAt(AccountBalanceAtDto {
+        hash: block.hash(),
+        height: sideband.height,
+        local_timestamp: sideband.timestamp,
+        balance: block.balance(),
+        representative: finder
+            .representative(&block)
+            .unwrap_or_default()
+            .as_account(),
+    })
+}
@@ -1,9 +1,16 @@
 use rsnano_core::{Account, Amount, Block, BlockEnum, BlockHash, BlockSubType};
 use rsnano_node::Node;
-use rsnano_rpc_messages::{AccountHistoryArgs, AccountHistoryDto, HistoryEntry, RpcDto};
+use rsnano_rpc_messages::{AccountHistoryArgs, AccountHistoryDto, ErrorDto, HistoryEntry, RpcDto};
 use std::sync::Arc;
 
 pub async fn account_history(node: Arc<Node>, args: AccountHistoryArgs) -> RpcDto {
+    if node.ledger.account_history_index_complete() {
+        return indexed_account_history(node, args);
+    }
+    if args.requires_index() {
+        return account_history_index_error(&node);
+    }
+
     let transaction = node.store.tx_begin_read();
     let mut history = Vec::new();
     let reverse = args.reverse.unwrap_or(false);
@@ -23,16 +30,12 @@ pub async fn account_history(node: Arc<Node>, args: AccountHistoryArgs) -> RpcDt
     };
     let mut count = args.count;
     let mut offset = args.offset.unwrap_or(0);
-    let raw = args.raw.unwrap_or(false);
-    let account_filter = args.account_filter.clone();
 
     while let Some(block) = node.ledger.get_block(&transaction, &hash) {
         if offset > 0 {
             offset -= 1;
         } else if count > 0 {
-            if let Some(entry) =
-                create_history_entry(node.clone(), &block, &hash, raw, &account_filter)
-            {
+            if let Some(entry) = create_history_entry(node.clone(), &block, &hash, &args) {
                 history.push(entry);
                 count -= 1;
             }
@@ -62,6 +65,95 @@ pub async fn account_history(node: Arc<Node>, args: AccountHistoryArgs) -> RpcDt
 
     let next = if !hash.is_zero() { Some(hash) } else { None };
 
+    account_history_dto(args.account, history, reverse, next)
+}
+
+/// The error for requests which can only be served by a complete account history index
+pub(crate) fn account_history_index_error(node: &Node) -> RpcDto {
+    if node.ledger.account_history_index_enabled() {
+        RpcDto::Error(ErrorDto::AccountHistoryIndexIncomplete)
+    } else {
+        RpcDto::Error(ErrorDto::AccountHistoryIndexDisabled)
+    }
+}
+
+/// Looks up the blocks by height in the account history index instead of
+/// walking the chain, which also allows to select height and time ranges
+fn indexed_account_history(node: Arc<Node>, args: AccountHistoryArgs) -> RpcDto {
+    let transaction = node.store.tx_begin_read();
+    let reverse = args.reverse.unwrap_or(false);
+    let Some(account_info) = node.ledger.any().get_account(&transaction, &args.account) else {
+        return account_history_dto(args.account, Vec::new(), reverse, None);
+    };
+    let index = &node.store.account_history;
+
+    let mut lowest = args.from_height.unwrap_or(1).max(1);
+    let mut highest = args
+        .to_height
+        .unwrap_or(u64::MAX)
+        .min(account_info.block_count);
+    if let (Some(head), false) = (args.head, reverse) {
+        highest = match node.ledger.get_block(&transaction, &head) {
+            Some(block) if block.account() == args.account => highest.min(block.height()),
+            _ => 0,
+        };
+    }
+    // The local timestamps grow with the height, because a block can only
+    // be inserted after its predecessor
+    if let Some(since) = args.since {
+        lowest = index
+            .first_height_since(&transaction, &args.account, since)
+            .map_or(u64::MAX, |height| lowest.max(height));
+    }
+    if let Some(until) = args.until {
+        if let Some(height) =
+            index.first_height_since(&transaction, &args.account, until.saturating_add(1))
+        {
+            highest = highest.min(height - 1);
+        }
+    }
+
+    let heights: Box<dyn Iterator<Item = u64>> = if reverse {
+        Box::new(lowest..=highest)
+    } else {
+        Box::new((lowest..=highest).rev())
+    };
+    let offset = args.offset.unwrap_or(0) as usize;
+    let mut history = Vec::new();
+    let mut next = None;
+
+    for height in heights.skip(offset) {
+        // Pruned blocks are not part of the index
+        let Some(hash) = index.get(&transaction, &args.account, height) else {
+            break;
+        };
+        if history.len() as u64 >= args.count {
+            next = Some(hash);
+            break;
+        }
+        let Some(block) = node.ledger.get_block(&transaction, &hash) else {
+            break;
+        };
+        let timestamp = block.sideband().unwrap().timestamp;
+        if args.since.map_or(false, |since| timestamp < since)
+            || args.until.map_or(false, |until| timestamp > until)
+        {
+            continue;
+        }
+        if let Some(entry) = create_history_entry(node.clone(), &block, &hash, &args) {
+            history.push(entry);
+        }
+    }
+
+    account_history_dto(args.account, history, reverse, next)
+}
+
+fn account_history_dto(
+    account: Account,
+    history: Vec<HistoryEntry>,
+    reverse: bool,
+    next: Option<BlockHash>,
+) -> RpcDto {
     let previous = if !history.is_empty() {
         Some(if reverse {
             history.last().unwrap().hash
@@ -72,23 +164,29 @@ pub async fn account_history(node: Arc<Node>, args: AccountHistoryArgs) -> RpcDt
         None
     };
 
-    let account_history = AccountHistoryDto {
-        account: args.account,
+    RpcDto::AccountHistory(AccountHistoryDto {
+        account,
         history,
         previous,
         next,
-    };
-
-    RpcDto::AccountHistory(account_history)
+    })
 }
 
 fn create_history_entry(
     node: Arc<Node>,
     block: &BlockEnum,
     hash: &BlockHash,
-    raw: bool,
-    account_filter: &Option<Vec<Account>>,
+    args: &AccountHistoryArgs,
 ) -> Option<HistoryEntry> {
+    let raw = args.raw.unwrap_or(false);
+    let account_filter = &args.account_filter;
+    // Change and epoch blocks are only listed in raw mode or when they are requested explicitly
+    let listed = |subtype: BlockSubType| {
+        raw || args
+            .subtype
+            .as_ref()
+            .map_or(false, |subtypes| subtypes.contains(&subtype))
+    };
     let transaction = node.store.tx_begin_read();
     let confirmed = node
         .ledger
@@ -163,14 +261,20 @@ fn create_history_entry(
             (BlockSubType::Receive, source_account, amount)
         }
         BlockEnum::LegacyChange(_) => {
-            if raw {
+            if listed(BlockSubType::Change) {
                 (BlockSubType::Change, Account::default(), Amount::zero())
             } else {
                 return None; // Skip change blocks if not raw
             }
         }
         BlockEnum::State(state_block) => {
-            if state_block.previous().is_zero() {
+            if node.ledger.is_epoch_link(&state_block.link()) {
+                if listed(BlockSubType::Epoch) {
+                    (BlockSubType::Epoch, Account::default(), Amount::zero())
+                } else {
+                    return None; // Skip epoch blocks if not raw
+                }
+            } else if state_block.previous().is_zero() {
                 // Open block
                 let source_account = node
                     .ledger
@@ -206,7 +310,7 @@ fn create_history_entry(
                     )
                 } else if state_block.link().is_zero() {
                     // Change block
-                    if raw {
+                    if listed(BlockSubType::Change) {
                         (BlockSubType::Change, Account::default(), Amount::zero())
                     } else {
                         return None; // Skip change blocks if not raw
@@ -234,6 +338,12 @@ fn create_history_entry(
         }
     };
 
+    if let Some(subtypes) = &args.subtype {
+        if !subtypes.contains(&block_type) {
+            return None;
+        }
+    }
+
     Some(HistoryEntry {
         block_type,
         account,
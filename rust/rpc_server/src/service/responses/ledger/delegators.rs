@@ -1,28 +1,32 @@
-use rsnano_core::{Account, Amount};
+use rsnano_core::{Account, Amount, PublicKey};
 use rsnano_node::Node;
 use rsnano_rpc_messages::{DelegatorsArgs, DelegatorsDto, RpcDto};
 use std::{collections::HashMap, sync::Arc};
 
 pub async fn delegators(node: Arc<Node>, args: DelegatorsArgs) -> RpcDto {
-    let representative = args.account;
-    let count = args.count.unwrap_or(1024);
+    let representative: PublicKey = args.account.into();
+    let count = args.count.unwrap_or(1024) as usize;
     let threshold = args.threshold.unwrap_or(Amount::zero());
-    let start_account = args.start.unwrap_or(Account::zero());
+    // The start account is exclusive, so that the last account of the previous page can be passed
+    let start_account = match args.start {
+        Some(start) => match start.inc() {
+            Some(next) => next,
+            None => return RpcDto::Delegators(DelegatorsDto::new(HashMap::new())),
+        },
+        None => Account::zero(),
+    };
 
-    let mut delegators: HashMap<Account, Amount> = HashMap::new();
     let tx = node.ledger.read_txn();
-    let mut iter = node.store.account.begin_account(&tx, &start_account);
+    let delegators: HashMap<Account, Amount> = node
+        .store
+        .delegators
+        .iter(&tx, &representative, &start_account)
+        .filter_map(|account| {
+            let info = node.store.account.get(&tx, &account)?;
+            (info.balance >= threshold).then_some((account, info.balance))
+        })
+        .take(count)
+        .collect();
 
-    while let Some((account, info)) = iter.current() {
-        if delegators.len() >= count as usize {
-            break;
-        }
-
-        if info.representative == representative.into() && info.balance >= threshold {
-            delegators.insert(*account, info.balance);
-        }
-
-        iter.next();
-    }
     RpcDto::Delegators(DelegatorsDto::new(delegators))
 }
@@ -1,4 +1,5 @@
 mod account_balance;
+mod account_balance_at;
 mod account_block_count;
 mod account_history;
 mod account_info;
@@ -21,11 +22,13 @@ mod delegators_count;
 mod frontier_count;
 mod frontiers;
 mod ledger;
+mod representative_weight_at;
 mod representatives;
 mod successors;
 mod unopened;
 
 pub use account_balance::*;
+pub use account_balance_at::*;
 pub use account_block_count::*;
 pub use account_history::*;
 pub use account_info::*;
@@ -48,6 +51,7 @@ pub use delegators_count::*;
 pub use frontier_count::*;
 pub use frontiers::*;
 pub use ledger::*;
+pub use representative_weight_at::*;
 pub use representatives::*;
 pub use successors::*;
 pub use unopened::*;
@@ -0,0 +1,29 @@
+use super::account_history_index_error;
+use rsnano_node::Node;
+use rsnano_rpc_messages::{ErrorDto, RepresentativeWeightAtArgs, RpcDto, WeightDto};
+use std::sync::Arc;
+use tokio::task::spawn_blocking;
+
+/// Checks every account which ever delegated to the representative, so it is only
+/// available with enabled control and runs on a blocking thread
+pub async fn representative_weight_at(
+    node: Arc<Node>,
+    enable_control: bool,
+    args: RepresentativeWeightAtArgs,
+) -> RpcDto {
+    if !enable_control {
+        return RpcDto::Error(ErrorDto::RPCControlDisabled);
+    }
+
+    spawn_blocking(move || {
+        let Some(weight) = node
+            .ledger
+            .representative_weight_at(&args.account.into(), args.timestamp)
+        else {
+            return account_history_index_error(&node);
+        };
+        RpcDto::RepresentativeWeightAt(WeightDto::new(weight))
+    })
+    .await
+    .unwrap()
+}
@@ -0,0 +1,50 @@
+use rsnano_node::{cdc::CdcEntry, Node};
+use rsnano_rpc_messages::{CdcElectionDto, CdcEntryDto, CdcReadArgs, CdcReadDto, ErrorDto, RpcDto};
+use std::sync::Arc;
+
+/// Default and maximum number of entries per request
+const MAX_COUNT: u64 = 1000;
+
+pub async fn cdc_read(node: Arc<Node>, args: CdcReadArgs) -> RpcDto {
+    let Some(cdc) = &node.cdc else {
+        return RpcDto::Error(ErrorDto::CdcDisabled);
+    };
+
+    let count = args.count.unwrap_or(MAX_COUNT).min(MAX_COUNT) as usize;
+    let Ok(read) = cdc.read(args.from, count) else {
+        return RpcDto::Error(ErrorDto::CdcReadFailed);
+    };
+
+    let next = match read.entries.last() {
+        Some(last) => last.sequence + 1,
+        None => args.from.max(read.first_available),
+    };
+
+    RpcDto::CdcRead(CdcReadDto {
+        entries: read.entries.iter().map(cdc_entry_dto).collect(),
+        next,
+        first_available: read.first_available,
+    })
+}
+
+fn cdc_entry_dto(entry: &CdcEntry) -> CdcEntryDto {
+    let sideband = entry.block.sideband().unwrap();
+    CdcEntryDto {
+        sequence: entry.sequence,
+        hash: entry.block.hash(),
+        account: sideband.account,
+        height: sideband.height,
+        local_timestamp: sideband.timestamp,
+        subtype: entry.subtype,
+        amount: entry.amount,
+        confirmation_root: entry.confirmation_root,
+        contents: entry.block.json_representation(),
+        election: entry.election.as_ref().map(|election| CdcElectionDto {
+            tally: election.tally,
+            final_tally: election.final_tally,
+            confirmation_request_count: election.confirmation_request_count,
+            voter_count: election.voter_count,
+            duration: election.duration.as_millis() as u64,
+        }),
+    }
+}
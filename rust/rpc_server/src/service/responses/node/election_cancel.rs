@@ -0,0 +1,35 @@
+use rsnano_node::{consensus::ElectionState, Node};
+use rsnano_rpc_messages::{ErrorDto, QualifiedRootRpcMessage, RpcDto, SuccessDto};
+use std::sync::Arc;
+
+pub async fn election_cancel(
+    node: Arc<Node>,
+    enable_control: bool,
+    args: QualifiedRootRpcMessage,
+) -> RpcDto {
+    if !enable_control {
+        return RpcDto::Error(ErrorDto::RPCControlDisabled);
+    }
+
+    let Some(election) = node.active.election(&args.root) else {
+        return RpcDto::Error(ErrorDto::ElectionNotFound);
+    };
+
+    if is_confirmed(election.state()) {
+        return RpcDto::Error(ErrorDto::ElectionConfirmed);
+    }
+
+    election.cancel();
+    // The election may have been confirmed in the meantime, which cancel() doesn't overwrite
+    if is_confirmed(election.state()) {
+        return RpcDto::Error(ErrorDto::ElectionConfirmed);
+    }
+    RpcDto::ElectionCancel(SuccessDto::new())
+}
+
+fn is_confirmed(state: ElectionState) -> bool {
+    matches!(
+        state,
+        ElectionState::Confirmed | ElectionState::ExpiredConfirmed
+    )
+}
@@ -0,0 +1,19 @@
+use rsnano_node::Node;
+use rsnano_rpc_messages::{ErrorDto, QualifiedRootRpcMessage, RpcDto, SuccessDto};
+use std::sync::Arc;
+
+pub async fn election_erase(
+    node: Arc<Node>,
+    enable_control: bool,
+    args: QualifiedRootRpcMessage,
+) -> RpcDto {
+    if !enable_control {
+        return RpcDto::Error(ErrorDto::RPCControlDisabled);
+    }
+
+    if node.active.erase(&args.root) {
+        RpcDto::ElectionErase(SuccessDto::new())
+    } else {
+        RpcDto::Error(ErrorDto::ElectionNotFound)
+    }
+}
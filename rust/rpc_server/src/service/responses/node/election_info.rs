@@ -0,0 +1,59 @@
+use rsnano_core::{Amount, Vote};
+use rsnano_node::{stats::DetailType, Node};
+use rsnano_rpc_messages::{
+    ElectionBlockDto, ElectionInfoDto, ElectionVoteDto, ErrorDto, HashRpcMessage, RpcDto,
+};
+use std::sync::{atomic::Ordering, Arc};
+
+pub async fn election_info(node: Arc<Node>, args: HashRpcMessage) -> RpcDto {
+    let block = {
+        let tx = node.ledger.read_txn();
+        node.ledger.any().get_block(&tx, &args.hash)
+    };
+    let Some(block) = block else {
+        return RpcDto::Error(ErrorDto::BlockNotFound);
+    };
+
+    let Some(election) = node.active.election(&block.qualified_root()) else {
+        return RpcDto::Error(ErrorDto::ElectionNotFound);
+    };
+
+    let votes = node
+        .active
+        .votes_with_weight(&election)
+        .into_iter()
+        .map(|vote| ElectionVoteDto {
+            representative: vote.representative.into(),
+            hash: vote.hash,
+            weight: vote.weight,
+            timestamp: vote.timestamp,
+            is_final: vote.timestamp == Vote::FINAL_TIMESTAMP,
+        })
+        .collect();
+
+    let guard = election.mutex.lock().unwrap();
+    let blocks: Vec<_> = guard
+        .last_blocks
+        .keys()
+        .map(|hash| ElectionBlockDto {
+            hash: *hash,
+            tally: guard.last_tally.get(hash).cloned().unwrap_or_default(),
+        })
+        .collect();
+    let tally = blocks
+        .iter()
+        .fold(Amount::zero(), |total, block| total + block.tally);
+
+    RpcDto::ElectionInfo(ElectionInfoDto {
+        root: election.qualified_root.clone(),
+        behavior: DetailType::from(election.behavior).as_str().to_owned(),
+        state: DetailType::from(guard.state).as_str().to_owned(),
+        winner: guard.status.winner.as_ref().map(|w| w.hash()),
+        tally,
+        final_tally: guard.status.final_tally,
+        confirmation_request_count: election.confirmation_request_count.load(Ordering::SeqCst),
+        duration: election.duration().as_millis() as u64,
+        blocks,
+        votes,
+    })
+}
@@ -0,0 +1,41 @@
+use rsnano_node::{consensus::ElectionBehavior, Node};
+use rsnano_rpc_messages::{ElectionStartArgs, ErrorDto, RpcDto, StartedDto};
+use std::sync::Arc;
+
+pub async fn election_start(
+    node: Arc<Node>,
+    enable_control: bool,
+    args: ElectionStartArgs,
+) -> RpcDto {
+    if !enable_control {
+        return RpcDto::Error(ErrorDto::RPCControlDisabled);
+    }
+
+    let behavior = match args.behavior.as_deref() {
+        Some(behavior) => match behavior.parse() {
+            Ok(behavior) => behavior,
+            Err(_) => return RpcDto::Error(ErrorDto::InvalidElectionBehavior),
+        },
+        None => ElectionBehavior::Manual,
+    };
+
+    let tx = node.ledger.read_txn();
+    let Some(block) = node.ledger.any().get_block(&tx, &args.hash) else {
+        return RpcDto::Error(ErrorDto::BlockNotFound);
+    };
+
+    if node
+        .ledger
+        .confirmed()
+        .block_exists_or_pruned(&tx, &args.hash)
+        || node.confirming_set.exists(&args.hash)
+        || node.active.active(&block)
+    {
+        return RpcDto::ElectionStart(StartedDto::new(false));
+    }
+
+    node.election_schedulers
+        .manual
+        .push_with_behavior(Arc::new(block), None, behavior);
+    RpcDto::ElectionStart(StartedDto::new(true))
+}
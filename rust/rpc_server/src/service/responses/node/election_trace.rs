@@ -0,0 +1,60 @@
+use rsnano_node::{
+    consensus::{ElectionTraceEntry, ElectionTraceEvent},
+    Node,
+};
+use rsnano_rpc_messages::{
+    ElectionTraceArgs, ElectionTraceDto, ElectionTraceEntryDto, ElectionTraceEventDto, ErrorDto,
+    RpcDto,
+};
+use std::sync::Arc;
+
+pub async fn election_trace(node: Arc<Node>, args: ElectionTraceArgs) -> RpcDto {
+    let Some(trace) = node.active.election_trace(&args.root) else {
+        return RpcDto::Error(ErrorDto::ElectionTraceNotFound);
+    };
+
+    RpcDto::ElectionTrace(ElectionTraceDto {
+        root: trace.root,
+        behavior: trace.behavior.to_owned(),
+        state: trace.state.to_owned(),
+        winner: trace.winner,
+        duration: trace.duration,
+        dropped_events: trace.dropped_events,
+        events: trace.events.into_iter().map(entry_dto).collect(),
+    })
+}
+
+fn entry_dto(entry: ElectionTraceEntry) -> ElectionTraceEntryDto {
+    let event = match entry.event {
+        ElectionTraceEvent::StateChange { from, to } => ElectionTraceEventDto::StateChange {
+            from: from.to_owned(),
+            to: to.to_owned(),
+        },
+        ElectionTraceEvent::BlockAdded { hash } => ElectionTraceEventDto::BlockAdded { hash },
+        ElectionTraceEvent::Vote {
+            representative,
+            weight,
+            hash,
+            is_final,
+            timestamp,
+            source,
+            channel,
+        } => ElectionTraceEventDto::Vote {
+            representative,
+            weight,
+            hash,
+            is_final,
+            timestamp,
+            source: source.to_owned(),
+            channel: channel.map(|c| c.as_usize()),
+        },
+        ElectionTraceEvent::Tally { tally, final_tally } => {
+            ElectionTraceEventDto::Tally { tally, final_tally }
+        }
+        ElectionTraceEvent::ConfirmReq { count } => ElectionTraceEventDto::ConfirmReq { count },
+    };
+    ElectionTraceEntryDto {
+        time: entry.time,
+        event,
+    }
+}
@@ -0,0 +1,35 @@
+use rsnano_ledger::FinalVoteFilter;
+use rsnano_node::Node;
+use rsnano_rpc_messages::{FinalVoteDto, FinalVotesArgs, FinalVotesDto, RpcDto};
+use std::sync::Arc;
+
+/// The number of final votes which is returned at most, also if no count is given
+const MAX_COUNT: u64 = 1024;
+
+pub async fn final_votes(node: Arc<Node>, args: FinalVotesArgs) -> RpcDto {
+    let count = args.count.unwrap_or(MAX_COUNT).min(MAX_COUNT);
+    let filter = FinalVoteFilter {
+        root: args.root,
+        account_start: args.account_start,
+        account_end: args.account_end,
+        count: Some(count as usize),
+    };
+
+    let tx = node.ledger.read_txn();
+    let final_votes = node
+        .ledger
+        .final_votes()
+        .list(&tx, &filter)
+        .into_iter()
+        .map(|entry| FinalVoteDto {
+            root: entry.qualified_root.root,
+            previous: entry.qualified_root.previous,
+            hash: entry.hash,
+            account: entry.account,
+            cemented: entry.cemented,
+            conflicting: entry.is_conflicting(),
+        })
+        .collect();
+
+    RpcDto::FinalVotes(FinalVotesDto { final_votes })
+}
@@ -1,5 +1,6 @@
+use rsnano_network::RoundTripTimes;
 use rsnano_node::Node;
-use rsnano_rpc_messages::{PeerData, PeerInfo, PeersArgs, PeersDto, RpcDto};
+use rsnano_rpc_messages::{PeerData, PeerInfo, PeersArgs, PeersDto, RoundTripTimeDto, RpcDto};
 use std::{collections::HashMap, sync::Arc};
 
 pub async fn peers(node: Arc<Node>, args: PeersArgs) -> RpcDto {
@@ -18,6 +19,8 @@ pub async fn peers(node: Arc<Node>, args: PeersArgs) -> RpcDto {
                     protocol_version: channel.protocol_version(),
                     node_id: channel.node_id().unwrap().into(),
                     connection_type: "tcp".to_string(),
+                    score: Some(channel.score()),
+                    round_trip_time: round_trip_time_dto(&channel.round_trip_times()),
                 },
             );
         });
@@ -32,3 +35,14 @@ pub async fn peers(node: Arc<Node>, args: PeersArgs) -> RpcDto {
 
     RpcDto::Peers(peer_dto)
 }
+
+fn round_trip_time_dto(rtt: &RoundTripTimes) -> Option<RoundTripTimeDto> {
+    let millis = |percentile| rtt.percentile(percentile).unwrap_or_default().as_millis() as u64;
+    Some(RoundTripTimeDto {
+        samples: rtt.len() as u64,
+        average: rtt.ewma()?.as_millis() as u64,
+        p50: millis(50),
+        p90: millis(90),
+        p99: millis(99),
+    })
+}
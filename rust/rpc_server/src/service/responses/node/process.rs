@@ -1,11 +1,21 @@
-use rsnano_core::BlockEnum;
+use rsnano_core::{BlockEnum, BlockHash};
 use rsnano_ledger::BlockStatus;
-use rsnano_node::Node;
-use rsnano_rpc_messages::{ErrorDto, HashRpcMessage, ProcessArgs, RpcDto};
-use std::sync::Arc;
+use rsnano_node::{
+    block_processing::{AddBlockingError, LocalClient},
+    consensus::{ConfirmationOutcome, ConfirmationReceiver, ElectionStatus},
+    Node,
+};
+use rsnano_rpc_messages::{
+    ConfirmationDto, ConfirmationOutcomeDto, ErrorDto, ProcessArgs, ProcessDto, RpcDto,
+};
+use std::{net::IpAddr, sync::Arc, time::Duration};
 
-pub async fn process(node: Arc<Node>, args: ProcessArgs) -> RpcDto {
+const DEFAULT_CONFIRMATION_TIMEOUT_MS: u64 = 30_000;
+const MAX_CONFIRMATION_TIMEOUT_MS: u64 = 5 * 60_000;
+
+pub async fn process(node: Arc<Node>, args: ProcessArgs, client: IpAddr) -> RpcDto {
     let is_async = args.is_async.unwrap_or(false);
+    let wait_confirmation = args.wait_confirmation.unwrap_or(false);
     let block: BlockEnum = args.block.into();
 
     if node
@@ -16,12 +26,36 @@ pub async fn process(node: Arc<Node>, args: ProcessArgs) -> RpcDto {
         return RpcDto::Error(ErrorDto::WorkLow);
     }
 
+    // Registered before the block is processed, so that a quick confirmation isn't missed
+    let waiter = wait_confirmation.then(|| ConfirmationWaiter::register(&node, block.hash()));
+
+    let result = process_block(&node, block, args.force.unwrap_or(false), is_async, client);
+
+    match (result, waiter) {
+        (RpcDto::Process(mut dto), Some(waiter)) => {
+            dto.confirmation = Some(confirmation(&node, waiter, args.timeout).await);
+            RpcDto::Process(dto)
+        }
+        (result, _) => result,
+    }
+}
+
+fn process_block(
+    node: &Node,
+    block: BlockEnum,
+    force: bool,
+    is_async: bool,
+    client: IpAddr,
+) -> RpcDto {
     if !is_async {
-        match node.process_local(block.clone()) {
-            Some(result) => match result {
+        match node
+            .block_processor
+            .add_local_blocking(Arc::new(block.clone()), LocalClient::Rpc(client))
+        {
+            Ok(result) => match result {
                 BlockStatus::Progress => {
                     let hash = block.hash();
-                    RpcDto::Process(HashRpcMessage::new(hash))
+                    RpcDto::Process(ProcessDto::new(hash))
                 }
                 BlockStatus::GapPrevious => RpcDto::Error(ErrorDto::GapPrevious),
                 BlockStatus::GapSource => RpcDto::Error(ErrorDto::GapSource),
@@ -33,11 +67,11 @@ pub async fn process(node: Arc<Node>, args: ProcessArgs) -> RpcDto {
                 BlockStatus::BlockPosition => RpcDto::Error(ErrorDto::BlockPosition),
                 BlockStatus::GapEpochOpenPending => RpcDto::Error(ErrorDto::GapEpochOpenPending),
                 BlockStatus::Fork => {
-                    if args.force.unwrap_or(false) {
+                    if force {
                         node.active.erase(&block.qualified_root());
                         node.block_processor.force(Arc::new(block.clone()));
                         let hash = block.hash();
-                        RpcDto::Process(HashRpcMessage::new(hash))
+                        RpcDto::Process(ProcessDto::new(hash))
                     } else {
                         RpcDto::Error(ErrorDto::Fork)
                     }
@@ -46,14 +80,95 @@ pub async fn process(node: Arc<Node>, args: ProcessArgs) -> RpcDto {
                 BlockStatus::OpenedBurnAccount => RpcDto::Error(ErrorDto::OpenedBurnAccount),
                 _ => RpcDto::Error(ErrorDto::Other),
             },
-            None => RpcDto::Error(ErrorDto::Stopped),
+            Err(AddBlockingError::QueueFull) => RpcDto::Error(ErrorDto::QueueFull),
+            Err(AddBlockingError::Stopped) => RpcDto::Error(ErrorDto::Stopped),
         }
     } else {
         if let BlockEnum::State(_) = block {
             node.process(block.clone()).unwrap(); // TODO add error handling!
-            RpcDto::Process(HashRpcMessage::new(block.hash()))
+            RpcDto::Process(ProcessDto::new(block.hash()))
         } else {
             RpcDto::Error(ErrorDto::BlockError)
         }
     }
 }
+
+/// A registered confirmation waiter of a request. It is removed from the node when the
+/// request ends, also when the client disconnects and the request future gets dropped
+pub(crate) struct ConfirmationWaiter<'a> {
+    node: &'a Node,
+    hash: BlockHash,
+    receiver: Option<ConfirmationReceiver>,
+}
+
+impl<'a> ConfirmationWaiter<'a> {
+    pub fn register(node: &'a Node, hash: BlockHash) -> Self {
+        Self {
+            node,
+            hash,
+            receiver: Some(node.confirmation_waiters.register(hash)),
+        }
+    }
+}
+
+impl Drop for ConfirmationWaiter<'_> {
+    fn drop(&mut self) {
+        // Only waiters with a dropped receiver get cancelled
+        drop(self.receiver.take());
+        self.node.confirmation_waiters.cancel(&self.hash);
+    }
+}
+
+/// Waits until the block is cemented, rolled back or the timeout expires.
+/// The waiter has to be registered before the block gets processed.
+pub(crate) async fn confirmation(
+    node: &Node,
+    mut waiter: ConfirmationWaiter<'_>,
+    timeout_ms: Option<u64>,
+) -> ConfirmationDto {
+    let hash = waiter.hash;
+    // The block may already have been cemented before the waiter was registered
+    if node
+        .ledger
+        .confirmed()
+        .block_exists_or_pruned(&node.ledger.read_txn(), &hash)
+    {
+        return match node
+            .active
+            .recently_cemented_list()
+            .iter()
+            .find(|status| status.winner.as_ref().map(|b| b.hash()) == Some(hash))
+        {
+            Some(status) => confirmed(status),
+            None => ConfirmationDto::new(ConfirmationOutcomeDto::Confirmed),
+        };
+    }
+
+    let timeout_ms = timeout_ms
+        .unwrap_or(DEFAULT_CONFIRMATION_TIMEOUT_MS)
+        .min(MAX_CONFIRMATION_TIMEOUT_MS);
+    let receiver = waiter.receiver.as_mut().unwrap();
+    let outcome = node
+        .confirmation_waiters
+        .wait(receiver, Duration::from_millis(timeout_ms))
+        .await;
+    match outcome {
+        Some(ConfirmationOutcome::Confirmed(status)) => confirmed(&status),
+        Some(ConfirmationOutcome::Fork) => ConfirmationDto::new(ConfirmationOutcomeDto::Fork),
+        Some(ConfirmationOutcome::RolledBack) => {
+            ConfirmationDto::new(ConfirmationOutcomeDto::RolledBack)
+        }
+        None => ConfirmationDto::new(ConfirmationOutcomeDto::Timeout),
+    }
+}
+
+fn confirmed(status: &ElectionStatus) -> ConfirmationDto {
+    ConfirmationDto {
+        outcome: ConfirmationOutcomeDto::Confirmed,
+        confirmation_type: Some(status.election_status_type.as_str().to_string()),
+        duration: Some(status.election_duration.as_millis() as u64),
+        tally: Some(status.tally),
+        final_tally: Some(status.final_tally),
+        voters: Some(status.voter_count),
+    }
+}
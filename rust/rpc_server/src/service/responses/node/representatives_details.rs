@@ -0,0 +1,93 @@
+use rsnano_core::{Account, Amount, PublicKey, RepDetails, REP_LATENCY_BUCKETS};
+use rsnano_node::Node;
+use rsnano_rpc_messages::{
+    RepLatencyBucketDto, RepLatencyDto, RepTelemetryDto, RepUptimeDto, RepresentativeDetailsDto,
+    RepresentativesDetailsArgs, RepresentativesDetailsDto, RpcDto,
+};
+use std::{
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+pub async fn representatives_details(node: Arc<Node>, args: RepresentativesDetailsArgs) -> RpcDto {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    let mut reps: Vec<_> = node
+        .rep_registry
+        .all()
+        .into_iter()
+        .filter(|(rep, _)| match &args.accounts {
+            Some(accounts) => accounts.contains(&Account::from(*rep)),
+            None => true,
+        })
+        .map(|(rep, details)| (node.ledger.weight(&rep), rep, details))
+        .collect();
+    reps.sort_by_key(|(weight, _, _)| std::cmp::Reverse(*weight));
+    if let Some(count) = args.count {
+        reps.truncate(count as usize);
+    }
+
+    let representatives = reps
+        .into_iter()
+        .map(|(weight, rep, details)| to_dto(&rep, weight, &details, now))
+        .collect();
+
+    RpcDto::RepresentativesDetails(RepresentativesDetailsDto { representatives })
+}
+
+fn to_dto(
+    rep: &PublicKey,
+    weight: Amount,
+    details: &RepDetails,
+    now: u64,
+) -> RepresentativeDetailsDto {
+    RepresentativeDetailsDto {
+        account: Account::from(*rep),
+        weight,
+        node_ids: details
+            .node_ids
+            .iter()
+            .map(|id| Account::from(*id).to_node_id())
+            .collect(),
+        endpoints: details.endpoints.clone(),
+        first_seen: details.first_seen,
+        last_seen: details.last_seen,
+        votes: details.vote_count,
+        uptime: RepUptimeDto {
+            day: details.uptime(24, now),
+            week: details.uptime(24 * 7, now),
+            month: details.uptime(24 * 30, now),
+        },
+        latency: RepLatencyDto {
+            samples: details.latency_samples(),
+            p50: details.latency_percentile(50),
+            p90: details.latency_percentile(90),
+            p99: details.latency_percentile(99),
+            histogram: REP_LATENCY_BUCKETS
+                .iter()
+                .zip(details.latency_histogram.iter())
+                .map(|(bound, count)| RepLatencyBucketDto {
+                    le: (*bound != u64::MAX).then_some(*bound),
+                    count: *count,
+                })
+                .collect(),
+        },
+        telemetry: details.telemetry.as_ref().map(|t| RepTelemetryDto {
+            received: t.received,
+            node_id: Account::from(t.node_id).to_node_id(),
+            protocol_version: t.protocol_version,
+            major_version: t.major_version,
+            minor_version: t.minor_version,
+            patch_version: t.patch_version,
+            pre_release_version: t.pre_release_version,
+            maker: t.maker,
+            block_count: t.block_count,
+            cemented_count: t.cemented_count,
+            peer_count: t.peer_count,
+            uptime: t.uptime,
+        }),
+    }
+}
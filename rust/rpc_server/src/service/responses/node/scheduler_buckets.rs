@@ -0,0 +1,23 @@
+use rsnano_node::Node;
+use rsnano_rpc_messages::{RpcDto, SchedulerBucketDto, SchedulerBucketsDto};
+use std::sync::Arc;
+
+pub async fn scheduler_buckets(node: Arc<Node>) -> RpcDto {
+    let buckets = node
+        .election_schedulers
+        .priority
+        .bucket_infos()
+        .into_iter()
+        .map(|info| SchedulerBucketDto {
+            minimum_balance: info.minimum_balance,
+            max_blocks: info.max_blocks,
+            reserved_elections: info.reserved_elections,
+            max_elections: info.max_elections,
+            blocks: info.blocks,
+            oldest_block_time: info.oldest_block_time,
+            elections: info.elections,
+            cancellations: info.cancellations,
+        })
+        .collect();
+    RpcDto::SchedulerBuckets(SchedulerBucketsDto { buckets })
+}
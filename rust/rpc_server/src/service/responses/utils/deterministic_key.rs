@@ -1,8 +1,18 @@
-use rsnano_core::{Account, PublicKey};
-use rsnano_rpc_messages::{DeterministicKeyArgs, KeyPairDto, RpcDto};
+use rsnano_core::{bip44_key, Account, Mnemonic, PublicKey};
+use rsnano_rpc_messages::{DeterministicKeyArgs, ErrorDto, KeyPairDto, RpcDto};
 
 pub async fn deterministic_key(args: DeterministicKeyArgs) -> RpcDto {
-    let private = rsnano_core::deterministic_key(&args.seed, args.index);
+    let private = if let Some(phrase) = &args.mnemonic {
+        let Ok(mnemonic) = Mnemonic::parse(phrase) else {
+            return RpcDto::Error(ErrorDto::BadMnemonic);
+        };
+        let passphrase = args.passphrase.as_deref().unwrap_or_default();
+        bip44_key(&mnemonic, passphrase, args.index)
+    } else if let Some(seed) = &args.seed {
+        rsnano_core::deterministic_key(seed, args.index)
+    } else {
+        return RpcDto::Error(ErrorDto::BadSeed);
+    };
     let public: PublicKey = (&private).try_into().unwrap();
     let account = Account::from(public);
 
@@ -7,6 +7,8 @@ mod password_change;
 mod password_enter;
 mod password_valid;
 mod receive_minimum;
+mod receive_policy;
+mod rep_health_policy;
 mod search_receivable;
 mod search_receivable_all;
 mod send;
@@ -24,6 +26,7 @@ mod wallet_info;
 mod wallet_ledger;
 mod wallet_lock;
 mod wallet_locked;
+mod wallet_mnemonic;
 mod wallet_receivable;
 mod wallet_representative;
 mod wallet_representative_set;
@@ -41,6 +44,8 @@ pub use password_change::*;
 pub use password_enter::*;
 pub use password_valid::*;
 pub use receive_minimum::*;
+pub use receive_policy::*;
+pub use rep_health_policy::*;
 pub use search_receivable::*;
 pub use search_receivable_all::*;
 pub use send::*;
@@ -58,6 +63,7 @@ pub use wallet_info::*;
 pub use wallet_ledger::*;
 pub use wallet_lock::*;
 pub use wallet_locked::*;
+pub use wallet_mnemonic::*;
 pub use wallet_receivable::*;
 pub use wallet_representative::*;
 pub use wallet_representative_set::*;
@@ -0,0 +1,72 @@
+use rsnano_node::{
+    wallets::{ReceivePolicy, ReceivePolicyScope},
+    Node,
+};
+use rsnano_rpc_messages::{
+    ErrorDto, ReceivePolicyArgs, ReceivePolicyDto, ReceivePolicySetArgs, RpcDto, SuccessDto,
+};
+use std::sync::Arc;
+
+pub async fn receive_policy_set(
+    node: Arc<Node>,
+    enable_control: bool,
+    args: ReceivePolicySetArgs,
+) -> RpcDto {
+    if !enable_control {
+        return RpcDto::Error(ErrorDto::RPCControlDisabled);
+    }
+
+    let policy = ReceivePolicy {
+        minimum: args.minimum,
+        allow: args.allow.unwrap_or_default(),
+        deny: args.deny.unwrap_or_default(),
+        disabled: args.disabled.unwrap_or(false),
+        max_per_minute: args.max_per_minute,
+    };
+    match node
+        .wallets
+        .set_receive_policy(&args.wallet, args.account, Some(policy))
+    {
+        Ok(_) => RpcDto::ReceivePolicySet(SuccessDto::new()),
+        Err(e) => RpcDto::Error(ErrorDto::WalletsError(e)),
+    }
+}
+
+pub async fn receive_policy_get(node: Arc<Node>, args: ReceivePolicyArgs) -> RpcDto {
+    match node.wallets.receive_policy(&args.wallet, args.account) {
+        Ok((policy, scope)) => {
+            let scope = match scope {
+                ReceivePolicyScope::Account => "account",
+                ReceivePolicyScope::Wallet => "wallet",
+                ReceivePolicyScope::Default => "default",
+            };
+            RpcDto::ReceivePolicy(ReceivePolicyDto {
+                scope: scope.to_owned(),
+                minimum: policy.minimum,
+                allow: policy.allow,
+                deny: policy.deny,
+                disabled: policy.disabled,
+                max_per_minute: policy.max_per_minute,
+            })
+        }
+        Err(e) => RpcDto::Error(ErrorDto::WalletsError(e)),
+    }
+}
+
+pub async fn receive_policy_remove(
+    node: Arc<Node>,
+    enable_control: bool,
+    args: ReceivePolicyArgs,
+) -> RpcDto {
+    if !enable_control {
+        return RpcDto::Error(ErrorDto::RPCControlDisabled);
+    }
+
+    match node
+        .wallets
+        .set_receive_policy(&args.wallet, args.account, None)
+    {
+        Ok(_) => RpcDto::ReceivePolicyRemove(SuccessDto::new()),
+        Err(e) => RpcDto::Error(ErrorDto::WalletsError(e)),
+    }
+}
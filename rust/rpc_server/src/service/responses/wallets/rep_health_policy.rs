@@ -0,0 +1,80 @@
+use rsnano_node::{wallets::RepHealthPolicy, Node};
+use rsnano_rpc_messages::{
+    ErrorDto, RepHealthPolicyDto, RepHealthPolicySetArgs, RpcDto, SuccessDto, WalletRpcMessage,
+};
+use std::sync::Arc;
+
+pub async fn rep_health_policy_set(
+    node: Arc<Node>,
+    enable_control: bool,
+    args: RepHealthPolicySetArgs,
+) -> RpcDto {
+    if !enable_control {
+        return RpcDto::Error(ErrorDto::RPCControlDisabled);
+    }
+    if args.fallback_representatives.is_empty() {
+        return RpcDto::Error(ErrorDto::FallbackRepresentativeRequired);
+    }
+    if args.minimum_tier.is_some_and(|tier| tier > 3) {
+        return RpcDto::Error(ErrorDto::InvalidMinimumTier);
+    }
+    if args.minimum_uptime.is_some_and(|uptime| uptime > 100) {
+        return RpcDto::Error(ErrorDto::InvalidMinimumUptime);
+    }
+
+    let default = RepHealthPolicy::default();
+    let policy = RepHealthPolicy {
+        fallback_representatives: args.fallback_representatives,
+        grace_period: args.grace_period.unwrap_or(default.grace_period),
+        minimum_weight: args.minimum_weight,
+        minimum_tier: args.minimum_tier,
+        minimum_uptime: args.minimum_uptime,
+        max_changes_per_hour: args.max_changes_per_hour,
+    };
+    match node
+        .wallets
+        .set_rep_health_policy(&args.wallet, Some(policy))
+    {
+        Ok(_) => RpcDto::RepHealthPolicySet(SuccessDto::new()),
+        Err(e) => RpcDto::Error(ErrorDto::WalletsError(e)),
+    }
+}
+
+pub async fn rep_health_policy_get(node: Arc<Node>, args: WalletRpcMessage) -> RpcDto {
+    match node.wallets.rep_health_policy(&args.wallet) {
+        Ok(Some(policy)) => RpcDto::RepHealthPolicy(RepHealthPolicyDto {
+            enabled: true,
+            fallback_representatives: policy.fallback_representatives,
+            grace_period: Some(policy.grace_period),
+            minimum_weight: policy.minimum_weight,
+            minimum_tier: policy.minimum_tier,
+            minimum_uptime: policy.minimum_uptime,
+            max_changes_per_hour: policy.max_changes_per_hour,
+        }),
+        Ok(None) => RpcDto::RepHealthPolicy(RepHealthPolicyDto {
+            enabled: false,
+            fallback_representatives: Vec::new(),
+            grace_period: None,
+            minimum_weight: None,
+            minimum_tier: None,
+            minimum_uptime: None,
+            max_changes_per_hour: None,
+        }),
+        Err(e) => RpcDto::Error(ErrorDto::WalletsError(e)),
+    }
+}
+
+pub async fn rep_health_policy_remove(
+    node: Arc<Node>,
+    enable_control: bool,
+    args: WalletRpcMessage,
+) -> RpcDto {
+    if !enable_control {
+        return RpcDto::Error(ErrorDto::RPCControlDisabled);
+    }
+
+    match node.wallets.set_rep_health_policy(&args.wallet, None) {
+        Ok(_) => RpcDto::RepHealthPolicyRemove(SuccessDto::new()),
+        Err(e) => RpcDto::Error(ErrorDto::WalletsError(e)),
+    }
+}
@@ -1,5 +1,6 @@
+use crate::service::responses::{confirmation, ConfirmationWaiter};
 use rsnano_node::{wallets::WalletsExt, Node};
-use rsnano_rpc_messages::{BlockDto, ErrorDto, RpcDto, SendArgs};
+use rsnano_rpc_messages::{ErrorDto, RpcDto, SendArgs, SendDto};
 use std::sync::Arc;
 
 pub async fn send(node: Arc<Node>, enable_control: bool, args: SendArgs) -> RpcDto {
@@ -7,7 +8,12 @@ pub async fn send(node: Arc<Node>, enable_control: bool, args: SendArgs) -> RpcD
         let block_hash =
             node.wallets
                 .send_sync(args.wallet, args.source, args.destination, args.amount);
-        RpcDto::Send(BlockDto::new(block_hash))
+        let mut dto = SendDto::new(block_hash);
+        if args.wait_confirmation.unwrap_or(false) && !block_hash.is_zero() {
+            let waiter = ConfirmationWaiter::register(&node, block_hash);
+            dto.confirmation = Some(confirmation(&node, waiter, args.timeout).await);
+        }
+        RpcDto::Send(dto)
     } else {
         RpcDto::Error(ErrorDto::RPCControlDisabled)
     }
@@ -1,4 +1,4 @@
-use rsnano_core::WalletId;
+use rsnano_core::{Mnemonic, WalletId};
 use rsnano_node::{wallets::WalletsExt, Node};
 use rsnano_rpc_messages::{ErrorDto, RpcDto, WalletCreateArgs, WalletRpcMessage};
 use std::sync::Arc;
@@ -12,11 +12,32 @@ pub async fn wallet_create(
         return RpcDto::Error(ErrorDto::RPCControlDisabled);
     }
 
+    if args.mnemonic.is_some() && args.seed.is_some() {
+        return RpcDto::Error(ErrorDto::MnemonicAndSeed);
+    }
+
+    let mnemonic = match &args.mnemonic {
+        Some(phrase) => match Mnemonic::parse(phrase) {
+            Ok(mnemonic) => Some(mnemonic),
+            Err(_) => return RpcDto::Error(ErrorDto::BadMnemonic),
+        },
+        None => None,
+    };
+
     let wallet = WalletId::random();
     node.wallets.create(wallet);
     let wallet_create_dto = WalletRpcMessage::new(wallet);
 
-    if let Some(seed) = args.seed {
+    if let Some(mnemonic) = mnemonic {
+        node.wallets
+            .change_mnemonic(
+                wallet,
+                &mnemonic,
+                args.passphrase.as_deref().unwrap_or_default(),
+                0,
+            )
+            .expect("This should not fail since the wallet was just created");
+    } else if let Some(seed) = args.seed {
         node.wallets
             .change_seed(wallet, &seed, 0)
             .expect("This should not fail since the wallet was just created");
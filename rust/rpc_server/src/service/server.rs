@@ -1,24 +1,28 @@
 use super::{
-    account_balance, account_block_count, account_create, account_get, account_history,
-    account_info, account_key, account_list, account_move, account_remove, account_representative,
-    account_weight, accounts_balances, accounts_create, accounts_frontiers, accounts_receivable,
-    accounts_representatives, available_supply, block_account, block_confirm, block_count,
-    block_create, block_hash, block_info, blocks, blocks_info, bootstrap, bootstrap_any,
-    bootstrap_lazy, chain, confirmation_active, confirmation_info, confirmation_quorum, delegators,
-    delegators_count, deterministic_key, frontier_count, frontiers, keepalive, key_create,
-    key_expand, ledger, nano_to_raw, node_id, password_change, password_enter, password_valid,
-    peers, populate_backlog, process, raw_to_nano, receivable, receivable_exists, receive_minimum,
-    representatives, representatives_online, republish, search_receivable, search_receivable_all,
-    send, sign, stats_clear, stop, unchecked, unchecked_clear, unchecked_get, unchecked_keys,
-    unopened, uptime, validate_account_number, wallet_add, wallet_add_watch, wallet_balances,
-    wallet_change_seed, wallet_contains, wallet_create, wallet_destroy, wallet_export,
-    wallet_frontiers, wallet_history, wallet_info, wallet_ledger, wallet_lock, wallet_locked,
+    account_balance, account_balance_at, account_block_count, account_create, account_get,
+    account_history, account_info, account_key, account_list, account_move, account_remove,
+    account_representative, account_weight, accounts_balances, accounts_create, accounts_frontiers,
+    accounts_receivable, accounts_representatives, available_supply, block_account, block_confirm,
+    block_count, block_create, block_hash, block_info, blocks, blocks_info, bootstrap,
+    bootstrap_any, bootstrap_lazy, cdc_read, chain, confirmation_active, confirmation_info,
+    confirmation_quorum, delegators, delegators_count, deterministic_key, election_cancel,
+    election_erase, election_info, election_start, election_trace, final_votes, frontier_count,
+    frontiers, keepalive, key_create, key_expand, ledger, nano_to_raw, node_id, password_change,
+    password_enter, password_valid, peers, populate_backlog, process, raw_to_nano, receivable,
+    receivable_exists, receive_minimum, receive_policy_get, receive_policy_remove,
+    receive_policy_set, rep_health_policy_get, rep_health_policy_remove, rep_health_policy_set,
+    representative_weight_at, representatives, representatives_details, representatives_online,
+    republish, scheduler_buckets, search_receivable, search_receivable_all, send, sign,
+    stats_clear, stop, unchecked, unchecked_clear, unchecked_get, unchecked_keys, unopened, uptime,
+    validate_account_number, wallet_add, wallet_add_watch, wallet_balances, wallet_change_seed,
+    wallet_contains, wallet_create, wallet_destroy, wallet_export, wallet_frontiers,
+    wallet_history, wallet_info, wallet_ledger, wallet_lock, wallet_locked, wallet_mnemonic,
     wallet_receivable, wallet_representative, wallet_representative_set, wallet_republish,
     wallet_work_get, work_cancel, work_generate, work_get, work_set, work_validate,
 };
 use anyhow::{Context, Result};
 use axum::{
-    extract::State,
+    extract::{ConnectInfo, State},
     http::{Request, StatusCode},
     middleware::map_request,
     response::{IntoResponse, Response},
@@ -28,7 +32,7 @@ use axum::{
 use rsnano_node::Node;
 use rsnano_rpc_messages::RpcCommand;
 use serde_json::to_string_pretty;
-use std::sync::Arc;
+use std::{net::SocketAddr, sync::Arc};
 use tokio::net::TcpListener;
 use tracing::info;
 
@@ -55,15 +59,19 @@ pub async fn run_rpc_server(
 
     info!("RPC listening address: {}", listener.local_addr()?);
 
-    axum::serve(listener, app)
-        .await
-        .context("Failed to run the server")?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .context("Failed to run the server")?;
 
     Ok(())
 }
 
 async fn handle_rpc(
     State(rpc_service): State<RpcService>,
+    ConnectInfo(remote_addr): ConnectInfo<SocketAddr>,
     Json(rpc_command): Json<RpcCommand>,
 ) -> Response {
     let response = match rpc_command {
@@ -96,6 +104,23 @@ async fn handle_rpc(
             wallet_lock(rpc_service.node, rpc_service.enable_control, args).await
         }
         RpcCommand::WalletLocked(args) => wallet_locked(rpc_service.node, args).await,
+        RpcCommand::WalletMnemonic(args) => {
+            wallet_mnemonic(rpc_service.node, rpc_service.enable_control, args).await
+        }
+        RpcCommand::ReceivePolicySet(args) => {
+            receive_policy_set(rpc_service.node, rpc_service.enable_control, args).await
+        }
+        RpcCommand::ReceivePolicyGet(args) => receive_policy_get(rpc_service.node, args).await,
+        RpcCommand::ReceivePolicyRemove(args) => {
+            receive_policy_remove(rpc_service.node, rpc_service.enable_control, args).await
+        }
+        RpcCommand::RepHealthPolicySet(args) => {
+            rep_health_policy_set(rpc_service.node, rpc_service.enable_control, args).await
+        }
+        RpcCommand::RepHealthPolicyGet(args) => rep_health_policy_get(rpc_service.node, args).await,
+        RpcCommand::RepHealthPolicyRemove(args) => {
+            rep_health_policy_remove(rpc_service.node, rpc_service.enable_control, args).await
+        }
         RpcCommand::Stop => stop(rpc_service.node, rpc_service.enable_control).await,
         RpcCommand::AccountBlockCount(args) => account_block_count(rpc_service.node, args).await,
         RpcCommand::AccountKey(args) => account_key(args).await,
@@ -104,6 +129,10 @@ async fn handle_rpc(
             account_representative(rpc_service.node, args).await
         }
         RpcCommand::AccountWeight(args) => account_weight(rpc_service.node, args).await,
+        RpcCommand::AccountBalanceAt(args) => account_balance_at(rpc_service.node, args).await,
+        RpcCommand::RepresentativeWeightAt(args) => {
+            representative_weight_at(rpc_service.node, rpc_service.enable_control, args).await
+        }
         RpcCommand::AvailableSupply => available_supply(rpc_service.node).await,
         RpcCommand::BlockConfirm(args) => block_confirm(rpc_service.node, args).await,
         RpcCommand::BlockCount => block_count(rpc_service.node).await,
@@ -180,7 +209,7 @@ async fn handle_rpc(
         RpcCommand::AccountInfo(args) => account_info(rpc_service.node, args).await,
         RpcCommand::AccountHistory(args) => account_history(rpc_service.node, args).await,
         RpcCommand::Sign(args) => sign(rpc_service.node, args).await,
-        RpcCommand::Process(args) => process(rpc_service.node, args).await,
+        RpcCommand::Process(args) => process(rpc_service.node, args, remote_addr.ip()).await,
         RpcCommand::WorkCancel(args) => {
             work_cancel(rpc_service.node, rpc_service.enable_control, args).await
         }
@@ -210,10 +239,27 @@ async fn handle_rpc(
         RpcCommand::RepresentativesOnline(args) => {
             representatives_online(rpc_service.node, args).await
         }
+        RpcCommand::RepresentativesDetails(args) => {
+            representatives_details(rpc_service.node, args).await
+        }
+        RpcCommand::FinalVotes(args) => final_votes(rpc_service.node, args).await,
         RpcCommand::Unchecked(args) => unchecked(rpc_service.node, args).await,
         RpcCommand::UncheckedGet(args) => unchecked_get(rpc_service.node, args).await,
         RpcCommand::UncheckedKeys(args) => unchecked_keys(rpc_service.node, args).await,
         RpcCommand::ConfirmationInfo(args) => confirmation_info(rpc_service.node, args).await,
+        RpcCommand::ElectionTrace(args) => election_trace(rpc_service.node, args).await,
+        RpcCommand::ElectionStart(args) => {
+            election_start(rpc_service.node, rpc_service.enable_control, args).await
+        }
+        RpcCommand::ElectionCancel(args) => {
+            election_cancel(rpc_service.node, rpc_service.enable_control, args).await
+        }
+        RpcCommand::ElectionErase(args) => {
+            election_erase(rpc_service.node, rpc_service.enable_control, args).await
+        }
+        RpcCommand::ElectionInfo(args) => election_info(rpc_service.node, args).await,
+        RpcCommand::CdcRead(args) => cdc_read(rpc_service.node, args).await,
+        RpcCommand::SchedulerBuckets => scheduler_buckets(rpc_service.node).await,
         RpcCommand::Ledger(args) => {
             ledger(rpc_service.node, rpc_service.enable_control, args).await
         }
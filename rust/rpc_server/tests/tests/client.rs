@@ -0,0 +1,115 @@
+use rsnano_core::{Amount, BlockEnum, KeyPair, Networks, StateBlock, WalletId, DEV_GENESIS_KEY};
+use rsnano_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH, DEV_GENESIS_PUB_KEY};
+use rsnano_messages::{Message, Publish};
+use rsnano_node::{
+    config::{NetworkConstants, NodeConfig},
+    wallets::WalletsError,
+    websocket::{Topic, WebsocketConfig},
+};
+use rsnano_rpc_client::{NanoRpcClient, NanoWebsocketClient, RpcClientError, Url, WebsocketEvent};
+use rsnano_rpc_messages::ErrorDto;
+use std::time::{Duration, Instant};
+use test_helpers::{
+    assert_timely, get_available_port, make_fake_channel, setup_rpc_client_and_server, System,
+};
+use tokio::time::timeout;
+
+#[test]
+fn node_error_is_mapped_to_error_dto() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.wallet_lock(WalletId::zero()).await });
+
+    let error = result.unwrap_err();
+    let error = error.downcast_ref::<RpcClientError>().unwrap();
+    assert_eq!(
+        error.node_error(),
+        Some(ErrorDto::WalletsError(WalletsError::WalletNotFound))
+    );
+    assert!(!error.is_retryable());
+
+    server.abort();
+}
+
+#[test]
+fn connection_errors_are_retried_with_backoff() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let port = get_available_port();
+    let url = Url::parse(&format!("http://[::1]:{}/", port)).unwrap();
+    let client = NanoRpcClient::builder(url)
+        .max_retries(2)
+        .backoff(Duration::from_millis(50), Duration::from_secs(1))
+        .build()
+        .unwrap();
+
+    let start = Instant::now();
+    let result = node.runtime.block_on(async { client.block_count().await });
+
+    let error = result.unwrap_err();
+    let error = error.downcast_ref::<RpcClientError>().unwrap();
+    assert!(matches!(error, RpcClientError::Transport(_)));
+    // 50ms + 100ms backoff
+    assert!(start.elapsed() >= Duration::from_millis(150));
+}
+
+#[test]
+fn websocket_client_receives_started_election() {
+    let mut system = System::new();
+    let websocket_port = get_available_port();
+    let config = NodeConfig {
+        websocket_config: WebsocketConfig {
+            enabled: true,
+            port: websocket_port,
+            ..WebsocketConfig::new(&NetworkConstants::default_for(Networks::NanoDevNetwork))
+        },
+        ..System::default_config()
+    };
+    let node = system.build_node().config(config).finish();
+    let channel = make_fake_channel(&node);
+
+    node.runtime.block_on(async {
+        let url = Url::parse(&format!("ws://[::1]:{}", websocket_port)).unwrap();
+        let mut client = NanoWebsocketClient::connect(url).await.unwrap();
+        client.subscribe(Topic::StartedElection).await.unwrap();
+
+        assert_eq!(
+            node.websocket
+                .as_ref()
+                .unwrap()
+                .subscriber_count(Topic::StartedElection),
+            1
+        );
+
+        let key = KeyPair::new();
+        let send = BlockEnum::State(StateBlock::new(
+            *DEV_GENESIS_ACCOUNT,
+            *DEV_GENESIS_HASH,
+            *DEV_GENESIS_PUB_KEY,
+            Amount::zero(),
+            key.account().into(),
+            &DEV_GENESIS_KEY,
+            node.work_generate_dev((*DEV_GENESIS_HASH).into()),
+        ));
+        node.inbound_message_queue.put(
+            Message::Publish(Publish::new_forward(send.clone())),
+            channel.info.clone(),
+        );
+        assert_timely(Duration::from_secs(1), || {
+            node.active.election(&send.qualified_root()).is_some()
+        });
+
+        let Ok(event) = timeout(Duration::from_secs(5), client.next_event()).await else {
+            panic!("timeout");
+        };
+        let WebsocketEvent::StartedElection(started) = event.unwrap().unwrap() else {
+            panic!("unexpected event");
+        };
+        assert_eq!(started.hash, send.hash().to_string());
+    });
+}
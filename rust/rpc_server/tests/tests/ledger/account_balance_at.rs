@@ -0,0 +1,170 @@
+use rsnano_core::{Account, Amount};
+use rsnano_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH};
+use rsnano_node::{config::NodeFlags, Node};
+use rsnano_rpc_messages::AccountBalanceAtArgs;
+use std::{sync::Arc, time::Duration};
+use test_helpers::{
+    assert_timely, assert_timely_msg, process_block_local, setup_rpc_client_and_server, System,
+};
+
+/// Historical queries need a complete account history index
+pub(crate) fn indexed_node(system: &mut System) -> Arc<Node> {
+    let node = system
+        .build_node()
+        .flags(NodeFlags {
+            enable_account_history_index: true,
+            ..NodeFlags::new()
+        })
+        .finish();
+    assert_timely(Duration::from_secs(5), || {
+        node.ledger.account_history_index_complete()
+    });
+    node
+}
+
+#[test]
+fn account_balance_at() {
+    let mut system = System::new();
+    let node = indexed_node(&mut system);
+    let send = process_block_local(node.clone(), Account::from(1), Amount::raw(1));
+    node.confirming_set.add(send.hash());
+    assert_timely_msg(
+        Duration::from_secs(5),
+        || node.block_confirmed(&send.hash()),
+        "send not confirmed",
+    );
+    let unconfirmed = process_block_local(node.clone(), Account::from(1), Amount::raw(1));
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let by_height = |height| {
+        node.runtime.block_on(async {
+            rpc_client
+                .account_balance_at(
+                    AccountBalanceAtArgs::builder(*DEV_GENESIS_ACCOUNT)
+                        .height(height)
+                        .build(),
+                )
+                .await
+        })
+    };
+
+    let result = by_height(1).unwrap();
+    assert_eq!(result.hash, *DEV_GENESIS_HASH);
+    assert_eq!(result.height, 1);
+    assert_eq!(result.balance, Amount::MAX);
+    assert_eq!(result.representative, *DEV_GENESIS_ACCOUNT);
+
+    let result = by_height(2).unwrap();
+    assert_eq!(result.hash, send.hash());
+    assert_eq!(result.balance, Amount::MAX - Amount::raw(1));
+
+    assert_eq!(
+        by_height(3).err().map(|e| e.to_string()),
+        Some("node returned error: \"Block not found\"".to_string())
+    );
+
+    let result = node
+        .runtime
+        .block_on(async {
+            rpc_client
+                .account_balance_at(
+                    AccountBalanceAtArgs::builder(*DEV_GENESIS_ACCOUNT)
+                        .timestamp(u64::MAX)
+                        .build(),
+                )
+                .await
+        })
+        .unwrap();
+    assert_eq!(result.hash, send.hash());
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .account_balance_at(
+                AccountBalanceAtArgs::builder(*DEV_GENESIS_ACCOUNT)
+                    .hash(unconfirmed.hash())
+                    .build(),
+            )
+            .await
+    });
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Block not found\"".to_string())
+    );
+
+    server.abort();
+}
+
+#[test]
+fn account_balance_at_requires_one_selector() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .account_balance_at(AccountBalanceAtArgs::builder(*DEV_GENESIS_ACCOUNT).build())
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some(
+            "node returned error: \"Exactly one of height, hash or timestamp is required\""
+                .to_string()
+        )
+    );
+
+    server.abort();
+}
+
+#[test]
+fn account_balance_at_hash_of_other_account() {
+    let mut system = System::new();
+    let node = indexed_node(&mut system);
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .account_balance_at(
+                AccountBalanceAtArgs::builder(Account::from(1))
+                    .hash(*DEV_GENESIS_HASH)
+                    .build(),
+            )
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Block does not belong to account\"".to_string())
+    );
+
+    server.abort();
+}
+
+#[test]
+fn account_balance_at_without_index() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .account_balance_at(
+                AccountBalanceAtArgs::builder(*DEV_GENESIS_ACCOUNT)
+                    .height(1)
+                    .build(),
+            )
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Account history index is disabled\"".to_string())
+    );
+
+    server.abort();
+}
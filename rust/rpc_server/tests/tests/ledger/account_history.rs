@@ -1,8 +1,9 @@
 use rsnano_core::{Account, Amount, BlockSubType, PublicKey, WalletId, DEV_GENESIS_KEY};
 use rsnano_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH, DEV_GENESIS_PUB_KEY};
-use rsnano_node::wallets::WalletsExt;
+use rsnano_node::{config::NodeFlags, wallets::WalletsExt};
 use rsnano_rpc_messages::AccountHistoryArgs;
-use test_helpers::{setup_rpc_client_and_server, System};
+use std::time::Duration;
+use test_helpers::{assert_timely, process_block_local, setup_rpc_client_and_server, System};
 
 #[test]
 fn account_history() {
@@ -223,3 +224,103 @@ fn account_history() {
 
     server.abort();
 }
+
+#[test]
+fn account_history_ranges_require_index() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let args = AccountHistoryArgs::builder(*DEV_GENESIS_ACCOUNT, 10)
+        .heights(1, 2)
+        .build();
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.account_history(args).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Account history index is disabled\"".to_string())
+    );
+
+    server.abort();
+}
+
+#[test]
+fn account_history_with_index() {
+    let mut system = System::new();
+    let node = system
+        .build_node()
+        .flags(NodeFlags {
+            enable_account_history_index: true,
+            ..NodeFlags::new()
+        })
+        .finish();
+    assert_timely(Duration::from_secs(5), || {
+        node.ledger.account_history_index_complete()
+    });
+    let sends: Vec<_> = (1..=4)
+        .map(|i| process_block_local(node.clone(), Account::from(i), Amount::raw(1)))
+        .collect();
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    // Height range, newest first
+    let args = AccountHistoryArgs::builder(*DEV_GENESIS_ACCOUNT, 2)
+        .heights(2, 4)
+        .build();
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.account_history(args).await.unwrap() });
+
+    let hashes: Vec<_> = result.history.iter().map(|e| e.hash).collect();
+    assert_eq!(hashes, vec![sends[2].hash(), sends[1].hash()]);
+    assert_eq!(result.next, Some(sends[0].hash()));
+
+    // Paging with an offset jumps directly to the height
+    let args = AccountHistoryArgs::builder(*DEV_GENESIS_ACCOUNT, 10)
+        .offset(3)
+        .reverse()
+        .build();
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.account_history(args).await.unwrap() });
+
+    let heights: Vec<_> = result.history.iter().map(|e| e.height).collect();
+    assert_eq!(heights, vec![4, 5]);
+    assert_eq!(result.next, None);
+
+    // Time range
+    let timestamp = node
+        .ledger
+        .get_block(&node.ledger.read_txn(), &sends[3].hash())
+        .unwrap()
+        .sideband()
+        .unwrap()
+        .timestamp;
+    let args = AccountHistoryArgs::builder(*DEV_GENESIS_ACCOUNT, 10)
+        .since(timestamp)
+        .build();
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.account_history(args).await.unwrap() });
+
+    assert!(result
+        .history
+        .iter()
+        .all(|e| e.local_timestamp >= timestamp));
+    assert_eq!(result.history[0].hash, sends[3].hash());
+
+    // Subtype filter
+    let args = AccountHistoryArgs::builder(*DEV_GENESIS_ACCOUNT, 10)
+        .subtype(vec![BlockSubType::Receive])
+        .build();
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.account_history(args).await.unwrap() });
+
+    assert_eq!(result.history.len(), 1);
+    assert_eq!(result.history[0].hash, *DEV_GENESIS_HASH);
+
+    server.abort();
+}
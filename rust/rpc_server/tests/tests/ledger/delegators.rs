@@ -1,5 +1,7 @@
-use rsnano_core::Amount;
-use rsnano_ledger::DEV_GENESIS_ACCOUNT;
+use rsnano_core::{Amount, BlockEnum, BlockHash, KeyPair, StateBlock, DEV_GENESIS_KEY};
+use rsnano_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH, DEV_GENESIS_PUB_KEY};
+use rsnano_node::Node;
+use rsnano_rpc_messages::DelegatorsArgs;
 use std::collections::HashMap;
 use test_helpers::{setup_rpc_client_and_server, System};
 
@@ -21,3 +23,73 @@ fn delegators_rpc_response() {
 
     server.abort();
 }
+
+fn open_delegating_account(node: &Node, previous: BlockHash, balance: Amount) -> BlockEnum {
+    let key = KeyPair::new();
+    let send = BlockEnum::State(StateBlock::new(
+        *DEV_GENESIS_ACCOUNT,
+        previous,
+        *DEV_GENESIS_PUB_KEY,
+        balance,
+        key.account().into(),
+        &DEV_GENESIS_KEY,
+        node.work_generate_dev(previous.into()),
+    ));
+    node.process(send.clone()).unwrap();
+
+    let open = BlockEnum::State(StateBlock::new(
+        key.account(),
+        BlockHash::zero(),
+        *DEV_GENESIS_PUB_KEY,
+        Amount::raw(1),
+        send.hash().into(),
+        &key,
+        node.work_generate_dev(key.public_key().into()),
+    ));
+    node.process(open).unwrap();
+    send
+}
+
+#[test]
+fn delegators_paging() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let send1 = open_delegating_account(&node, *DEV_GENESIS_HASH, Amount::MAX - Amount::raw(1));
+    open_delegating_account(&node, send1.hash(), Amount::MAX - Amount::raw(2));
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let mut all_delegators = HashMap::new();
+    let mut start = None;
+    loop {
+        let mut args = DelegatorsArgs::builder(*DEV_GENESIS_ACCOUNT).count(1);
+        if let Some(start) = start {
+            args = args.start_from(start);
+        }
+        let page = node
+            .runtime
+            .block_on(async { rpc_client.delegators(args.build()).await })
+            .unwrap()
+            .delegators;
+        let Some(last) = page.keys().max().cloned() else {
+            break;
+        };
+        assert_eq!(page.len(), 1);
+        all_delegators.extend(page);
+        start = Some(last);
+    }
+
+    assert_eq!(all_delegators.len(), 3);
+    assert_eq!(
+        all_delegators.get(&*DEV_GENESIS_ACCOUNT),
+        Some(&(Amount::MAX - Amount::raw(2)))
+    );
+
+    let count = node
+        .runtime
+        .block_on(async { rpc_client.delegators_count(*DEV_GENESIS_ACCOUNT).await })
+        .unwrap();
+    assert_eq!(count.count, 3);
+
+    server.abort();
+}
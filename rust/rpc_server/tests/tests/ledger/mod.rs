@@ -1,4 +1,5 @@
 mod account_balance;
+mod account_balance_at;
 mod account_block_count;
 mod account_history;
 mod account_info;
@@ -21,6 +22,7 @@ mod delegators_count;
 mod frontier_count;
 mod frontiers;
 mod ledger;
+mod representative_weight_at;
 mod representatives;
 mod successors;
 mod unopened;
@@ -0,0 +1,45 @@
+use super::account_balance_at::indexed_node;
+use rsnano_core::{Account, Amount};
+use rsnano_ledger::DEV_GENESIS_ACCOUNT;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn representative_weight_at() {
+    let mut system = System::new();
+    let node = indexed_node(&mut system);
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let weight = |account| {
+        node.runtime
+            .block_on(async { rpc_client.representative_weight_at(account, u64::MAX).await })
+            .unwrap()
+            .weight
+    };
+
+    assert_eq!(weight(*DEV_GENESIS_ACCOUNT), Amount::MAX);
+    assert_eq!(weight(Account::from(1)), Amount::zero());
+
+    server.abort();
+}
+
+#[test]
+fn representative_weight_at_requires_control() {
+    let mut system = System::new();
+    let node = indexed_node(&mut system);
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .representative_weight_at(*DEV_GENESIS_ACCOUNT, u64::MAX)
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
+
+    server.abort();
+}
@@ -1,3 +1,4 @@
+mod client;
 mod ledger;
 mod node;
 mod utils;
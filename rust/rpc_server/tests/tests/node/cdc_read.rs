@@ -0,0 +1,80 @@
+use rsnano_core::{Account, Amount, BlockBuilder, BlockSubType, DEV_GENESIS_KEY};
+use rsnano_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH};
+use rsnano_rpc_messages::CdcReadArgs;
+use std::time::Duration;
+use test_helpers::{assert_timely_msg, setup_rpc_client_and_server, System};
+
+#[test]
+fn cdc_read() {
+    let mut system = System::new();
+    let mut config = System::default_config();
+    config.cdc.enabled = true;
+    let node = system.build_node().config(config).finish();
+    node.insert_into_wallet(&DEV_GENESIS_KEY);
+    let send = BlockBuilder::legacy_send()
+        .previous(*DEV_GENESIS_HASH)
+        .destination(Account::zero())
+        .balance(Amount::MAX - Amount::raw(100))
+        .sign((*DEV_GENESIS_KEY).clone())
+        .work(node.work_generate_dev((*DEV_GENESIS_HASH).into()))
+        .build();
+    node.process_active(send.clone());
+    assert_timely_msg(
+        Duration::from_secs(5),
+        || node.block_confirmed(&send.hash()),
+        "send not confirmed",
+    );
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.cdc_read(0).await })
+        .unwrap();
+
+    assert_eq!(result.entries.len(), 1);
+    assert_eq!(result.next, 1);
+    assert_eq!(result.first_available, 0);
+    let entry = &result.entries[0];
+    assert_eq!(entry.sequence, 0);
+    assert_eq!(entry.hash, send.hash());
+    assert_eq!(entry.account, *DEV_GENESIS_ACCOUNT);
+    assert_eq!(entry.height, 2);
+    assert_eq!(entry.subtype, BlockSubType::Send);
+    assert_eq!(entry.amount, Amount::raw(100));
+    assert_eq!(entry.confirmation_root, send.hash());
+    assert!(entry.election.is_some());
+
+    let result = node
+        .runtime
+        .block_on(async {
+            rpc_client
+                .cdc_read(CdcReadArgs::builder(1).count(10).build())
+                .await
+        })
+        .unwrap();
+
+    assert!(result.entries.is_empty());
+    assert_eq!(result.next, 1);
+
+    server.abort();
+}
+
+#[test]
+fn cdc_read_fails_when_disabled() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.cdc_read(0).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"CDC is disabled\"".to_string())
+    );
+
+    server.abort();
+}
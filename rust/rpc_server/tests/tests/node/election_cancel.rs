@@ -0,0 +1,78 @@
+use rsnano_core::{Account, Amount, BlockBuilder, BlockEnum, QualifiedRoot, DEV_GENESIS_KEY};
+use rsnano_ledger::DEV_GENESIS_HASH;
+use rsnano_node::{consensus::ElectionState, Node};
+use std::time::Duration;
+use test_helpers::{assert_timely_msg, setup_rpc_client_and_server, start_election, System};
+
+fn process_send(node: &Node) -> BlockEnum {
+    let send = BlockBuilder::legacy_send()
+        .previous(*DEV_GENESIS_HASH)
+        .destination(Account::zero())
+        .balance(Amount::MAX - Amount::raw(100))
+        .sign((*DEV_GENESIS_KEY).clone())
+        .work(node.work_generate_dev((*DEV_GENESIS_HASH).into()))
+        .build();
+    node.process(send.clone()).unwrap();
+    send
+}
+
+#[test]
+fn election_cancel() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let send = process_send(&node);
+    let election = start_election(&node, &send.hash());
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    node.runtime
+        .block_on(async { rpc_client.election_cancel(send.qualified_root()).await })
+        .unwrap();
+
+    assert_eq!(election.state(), ElectionState::Cancelled);
+    assert_timely_msg(
+        Duration::from_secs(5),
+        || node.active.election(&send.qualified_root()).is_none(),
+        "cancelled election not removed",
+    );
+
+    server.abort();
+}
+
+#[test]
+fn election_cancel_not_found() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.election_cancel(QualifiedRoot::default()).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Election not found\"".to_string())
+    );
+
+    server.abort();
+}
+
+#[test]
+fn election_cancel_fails_without_enable_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.election_cancel(QualifiedRoot::default()).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
+
+    server.abort();
+}
@@ -0,0 +1,53 @@
+use rsnano_core::{Account, Amount, BlockBuilder, BlockEnum, QualifiedRoot, DEV_GENESIS_KEY};
+use rsnano_ledger::DEV_GENESIS_HASH;
+use rsnano_node::Node;
+use test_helpers::{setup_rpc_client_and_server, start_election, System};
+
+fn process_send(node: &Node) -> BlockEnum {
+    let send = BlockBuilder::legacy_send()
+        .previous(*DEV_GENESIS_HASH)
+        .destination(Account::zero())
+        .balance(Amount::MAX - Amount::raw(100))
+        .sign((*DEV_GENESIS_KEY).clone())
+        .work(node.work_generate_dev((*DEV_GENESIS_HASH).into()))
+        .build();
+    node.process(send.clone()).unwrap();
+    send
+}
+
+#[test]
+fn election_erase() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let send = process_send(&node);
+    start_election(&node, &send.hash());
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    node.runtime
+        .block_on(async { rpc_client.election_erase(send.qualified_root()).await })
+        .unwrap();
+
+    assert!(node.active.election(&send.qualified_root()).is_none());
+
+    server.abort();
+}
+
+#[test]
+fn election_erase_not_found() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.election_erase(QualifiedRoot::default()).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Election not found\"".to_string())
+    );
+
+    server.abort();
+}
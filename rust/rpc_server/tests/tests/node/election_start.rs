@@ -0,0 +1,114 @@
+use rsnano_core::{Account, Amount, BlockBuilder, BlockEnum, DEV_GENESIS_KEY};
+use rsnano_ledger::DEV_GENESIS_HASH;
+use rsnano_node::{consensus::ElectionBehavior, Node};
+use rsnano_rpc_messages::ElectionStartArgs;
+use std::time::Duration;
+use test_helpers::{assert_timely_msg, setup_rpc_client_and_server, System};
+
+fn process_send(node: &Node) -> BlockEnum {
+    let send = BlockBuilder::legacy_send()
+        .previous(*DEV_GENESIS_HASH)
+        .destination(Account::zero())
+        .balance(Amount::MAX - Amount::raw(100))
+        .sign((*DEV_GENESIS_KEY).clone())
+        .work(node.work_generate_dev((*DEV_GENESIS_HASH).into()))
+        .build();
+    node.process(send.clone()).unwrap();
+    send
+}
+
+#[test]
+fn election_start_with_behavior() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let send = process_send(&node);
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node
+        .runtime
+        .block_on(async {
+            rpc_client
+                .election_start(
+                    ElectionStartArgs::builder(send.hash())
+                        .behavior("hinted")
+                        .build(),
+                )
+                .await
+        })
+        .unwrap();
+
+    assert_eq!(result.started, true);
+    assert_timely_msg(
+        Duration::from_secs(5),
+        || node.active.election(&send.qualified_root()).is_some(),
+        "election not started",
+    );
+    let election = node.active.election(&send.qualified_root()).unwrap();
+    assert!(matches!(election.behavior, ElectionBehavior::Hinted));
+
+    server.abort();
+}
+
+#[test]
+fn election_start_already_confirmed() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.election_start(*DEV_GENESIS_HASH).await })
+        .unwrap();
+
+    assert_eq!(result.started, false);
+
+    server.abort();
+}
+
+#[test]
+fn election_start_invalid_behavior() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let send = process_send(&node);
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .election_start(
+                ElectionStartArgs::builder(send.hash())
+                    .behavior("unknown")
+                    .build(),
+            )
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Invalid election behavior\"".to_string())
+    );
+
+    server.abort();
+}
+
+#[test]
+fn election_start_fails_without_enable_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let send = process_send(&node);
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.election_start(send.hash()).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
+
+    server.abort();
+}
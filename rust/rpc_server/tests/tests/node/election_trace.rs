@@ -0,0 +1,128 @@
+use rsnano_core::{Account, Amount, BlockBuilder, BlockEnum, QualifiedRoot, DEV_GENESIS_KEY};
+use rsnano_ledger::DEV_GENESIS_HASH;
+use rsnano_node::Node;
+use rsnano_rpc_messages::ElectionTraceEventDto;
+use std::{sync::Arc, time::Duration};
+use test_helpers::{assert_timely_msg, setup_rpc_client_and_server, System};
+
+fn send_block(node: &Node) -> BlockEnum {
+    BlockBuilder::legacy_send()
+        .previous(*DEV_GENESIS_HASH)
+        .destination(Account::zero())
+        .balance(Amount::MAX - Amount::raw(100))
+        .sign((*DEV_GENESIS_KEY).clone())
+        .work(node.work_generate_dev((*DEV_GENESIS_HASH).into()))
+        .build()
+}
+
+fn confirm_send(node: &Arc<Node>) -> BlockEnum {
+    node.insert_into_wallet(&DEV_GENESIS_KEY);
+    let send = send_block(node);
+    node.process_active(send.clone());
+    assert_timely_msg(
+        Duration::from_secs(5),
+        || node.block_confirmed(&send.hash()),
+        "send not confirmed",
+    );
+    send
+}
+
+#[test]
+fn election_trace() {
+    let mut system = System::new();
+    let mut config = System::default_config();
+    config.election_trace.enabled = true;
+    let node = system.build_node().config(config).finish();
+    let send = confirm_send(&node);
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.election_trace(send.qualified_root()).await })
+        .unwrap();
+
+    assert_eq!(result.root, send.qualified_root());
+    assert_eq!(result.winner, Some(send.hash()));
+    assert_eq!(
+        result.events[0].event,
+        ElectionTraceEventDto::BlockAdded { hash: send.hash() }
+    );
+    assert!(result.events.iter().any(|e| matches!(
+        &e.event,
+        ElectionTraceEventDto::Vote { representative, is_final: true, .. }
+            if *representative == DEV_GENESIS_KEY.account()
+    )));
+    assert!(result.events.iter().any(|e| matches!(
+        &e.event,
+        ElectionTraceEventDto::Tally { tally, .. } if tally.len() == 1
+    )));
+    assert!(result.events.iter().any(|e| matches!(
+        &e.event,
+        ElectionTraceEventDto::StateChange { to, .. } if to == "confirmed"
+    )));
+
+    server.abort();
+}
+
+#[test]
+fn election_trace_of_finished_election() {
+    let mut system = System::new();
+    let mut config = System::default_config();
+    config.election_trace.enabled = true;
+    let node = system.build_node().config(config).finish();
+    let send = confirm_send(&node);
+
+    assert_timely_msg(
+        Duration::from_secs(5),
+        || node.active.election(&send.qualified_root()).is_none(),
+        "election not erased",
+    );
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.election_trace(send.qualified_root()).await })
+        .unwrap();
+
+    assert_eq!(result.state, "confirmed");
+
+    server.abort();
+}
+
+#[test]
+fn election_trace_not_found() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.election_trace(QualifiedRoot::default()).await });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Election trace not found\"".to_string())
+    );
+
+    server.abort();
+}
+
+#[test]
+fn election_trace_disabled() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let send = confirm_send(&node);
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.election_trace(send.qualified_root()).await });
+
+    assert!(result.is_err());
+
+    server.abort();
+}
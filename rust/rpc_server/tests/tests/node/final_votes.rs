@@ -0,0 +1,69 @@
+use rsnano_core::{Account, Amount, BlockBuilder, Root, DEV_GENESIS_KEY};
+use rsnano_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH};
+use rsnano_rpc_messages::FinalVotesArgs;
+use std::time::Duration;
+use test_helpers::{assert_timely_msg, setup_rpc_client_and_server, System};
+
+#[test]
+fn final_votes() {
+    let mut system = System::new();
+    let node = system.make_node();
+    node.insert_into_wallet(&DEV_GENESIS_KEY);
+
+    let send = BlockBuilder::legacy_send()
+        .previous(*DEV_GENESIS_HASH)
+        .destination(Account::zero())
+        .balance(Amount::MAX - Amount::raw(100))
+        .sign((*DEV_GENESIS_KEY).clone())
+        .work(node.work_generate_dev((*DEV_GENESIS_HASH).into()))
+        .build();
+    node.process_active(send.clone());
+    assert_timely_msg(
+        Duration::from_secs(5),
+        || {
+            node.block_confirmed(&send.hash())
+                && !node
+                    .store
+                    .final_vote
+                    .get(&node.ledger.read_txn(), send.root())
+                    .is_empty()
+        },
+        "no final vote generated",
+    );
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async {
+            rpc_client
+                .final_votes(
+                    FinalVotesArgs::builder()
+                        .account_range(*DEV_GENESIS_ACCOUNT, *DEV_GENESIS_ACCOUNT)
+                        .build(),
+                )
+                .await
+        })
+        .unwrap();
+
+    assert_eq!(result.final_votes.len(), 1);
+    let vote = &result.final_votes[0];
+    assert_eq!(vote.root, Root::from(*DEV_GENESIS_HASH));
+    assert_eq!(vote.previous, *DEV_GENESIS_HASH);
+    assert_eq!(vote.hash, send.hash());
+    assert_eq!(vote.account, Some(*DEV_GENESIS_ACCOUNT));
+    assert_eq!(vote.cemented, Some(send.hash()));
+    assert_eq!(vote.conflicting, false);
+
+    let result = node
+        .runtime
+        .block_on(async {
+            rpc_client
+                .final_votes(FinalVotesArgs::builder().root(Root::from(1)).build())
+                .await
+        })
+        .unwrap();
+    assert!(result.final_votes.is_empty());
+
+    server.abort();
+}
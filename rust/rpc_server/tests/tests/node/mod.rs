@@ -2,9 +2,16 @@ mod block_create;
 mod bootstrap;
 mod bootstrap_any;
 mod bootstrap_lazy;
+mod cdc_read;
 mod confirmation_active;
 mod confirmation_info;
 mod confirmation_quorum;
+mod election_cancel;
+mod election_erase;
+mod election_info;
+mod election_start;
+mod election_trace;
+mod final_votes;
 mod keepalive;
 mod node_id;
 mod peers;
@@ -12,8 +19,10 @@ mod populate_backlog;
 mod process;
 mod receivable;
 mod receivable_exists;
+mod representatives_details;
 mod representatives_online;
 mod republish;
+mod scheduler_buckets;
 mod sign;
 mod stats_clear;
 mod stop;
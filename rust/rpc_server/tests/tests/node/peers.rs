@@ -1,4 +1,4 @@
-use rsnano_rpc_messages::PeerData;
+use rsnano_rpc_messages::{PeerData, PeerInfo};
 use test_helpers::{setup_rpc_client_and_server, System};
 
 #[test]
@@ -40,6 +40,12 @@ fn peers_with_details() {
     match result.peers {
         PeerData::Detailed(peers) => {
             assert!(!peers.is_empty());
+            for peer in peers.values() {
+                let PeerInfo::Detailed { score, .. } = peer else {
+                    panic!("Expected detailed peer info");
+                };
+                assert!(score.unwrap() <= 100);
+            }
         }
         PeerData::Simple(_) => panic!("Expected Detailed peer data"),
     }
@@ -1,7 +1,9 @@
 use rsnano_core::{Amount, BlockEnum, BlockSubType, StateBlock, DEV_GENESIS_KEY};
 use rsnano_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH, DEV_GENESIS_PUB_KEY};
-use rsnano_rpc_messages::ProcessArgs;
-use test_helpers::{setup_rpc_client_and_server, System};
+use rsnano_rpc_messages::{ConfirmationOutcomeDto, ProcessArgs};
+use std::time::Duration;
+use test_helpers::{assert_timely_eq, setup_rpc_client_and_server, System};
+use tokio::time::timeout;
 
 #[test]
 fn process() {
@@ -35,6 +37,79 @@ fn process() {
     server.abort();
 }
 
+#[test]
+fn process_wait_confirmation() {
+    let mut system = System::new();
+    let node = system.make_node();
+    node.insert_into_wallet(&DEV_GENESIS_KEY);
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let send1 = BlockEnum::State(StateBlock::new(
+        *DEV_GENESIS_ACCOUNT,
+        *DEV_GENESIS_HASH,
+        *DEV_GENESIS_PUB_KEY,
+        Amount::MAX - Amount::raw(100),
+        DEV_GENESIS_KEY.account().into(),
+        &DEV_GENESIS_KEY,
+        node.work_generate_dev((*DEV_GENESIS_HASH).into()),
+    ));
+
+    let args: ProcessArgs = ProcessArgs::builder(send1.json_representation())
+        .wait_confirmation(10_000)
+        .build();
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.process(args).await.unwrap() });
+
+    assert_eq!(result.hash, send1.hash());
+    let confirmation = result.confirmation.unwrap();
+    assert_eq!(confirmation.outcome, ConfirmationOutcomeDto::Confirmed);
+    assert!(confirmation.confirmation_type.is_some());
+    assert!(node.block_confirmed(&send1.hash()));
+    assert_eq!(node.confirmation_waiters.len(), 0);
+
+    server.abort();
+}
+
+#[test]
+fn process_wait_confirmation_removes_waiter_when_client_disconnects() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let send1 = BlockEnum::State(StateBlock::new(
+        *DEV_GENESIS_ACCOUNT,
+        *DEV_GENESIS_HASH,
+        *DEV_GENESIS_PUB_KEY,
+        Amount::MAX - Amount::raw(100),
+        DEV_GENESIS_KEY.account().into(),
+        &DEV_GENESIS_KEY,
+        node.work_generate_dev((*DEV_GENESIS_HASH).into()),
+    ));
+
+    // Without a voting representative the block never gets confirmed
+    let args: ProcessArgs = ProcessArgs::builder(send1.json_representation())
+        .wait_confirmation(60_000)
+        .build();
+
+    node.runtime.block_on(async {
+        let request = rpc_client.process(args);
+        assert!(timeout(Duration::from_secs(1), request).await.is_err());
+    });
+
+    assert!(node.block_exists(&send1.hash()));
+    assert_timely_eq(
+        Duration::from_secs(5),
+        || node.confirmation_waiters.len(),
+        0,
+    );
+
+    server.abort();
+}
+
 #[test]
 fn process_fails_with_low_work() {
     let mut system = System::new();
@@ -0,0 +1,101 @@
+use rsnano_core::{Account, Amount, BlockBuilder, DEV_GENESIS_KEY};
+use rsnano_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_HASH};
+use rsnano_rpc_messages::RepresentativesDetailsArgs;
+use std::time::Duration;
+use test_helpers::{assert_timely_msg, setup_rpc_client_and_server, System};
+
+#[test]
+fn representatives_details() {
+    let mut system = System::new();
+    let node1 = system.make_node();
+    let node2 = system.make_node();
+    node1.insert_into_wallet(&DEV_GENESIS_KEY);
+
+    let send = BlockBuilder::legacy_send()
+        .previous(*DEV_GENESIS_HASH)
+        .destination(Account::zero())
+        .balance(Amount::MAX - Amount::raw(100))
+        .sign((*DEV_GENESIS_KEY).clone())
+        .work(node1.work_generate_dev((*DEV_GENESIS_HASH).into()))
+        .build();
+    node1.process_active(send.clone());
+    assert_timely_msg(
+        Duration::from_secs(10),
+        || {
+            node2
+                .rep_registry
+                .get(&DEV_GENESIS_KEY.public_key())
+                .is_some()
+        },
+        "no vote observed",
+    );
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node2.clone(), false);
+
+    let result = node2
+        .runtime
+        .block_on(async {
+            rpc_client
+                .representatives_details(RepresentativesDetailsArgs::default())
+                .await
+        })
+        .unwrap();
+
+    assert_eq!(result.representatives.len(), 1);
+    let rep = &result.representatives[0];
+    assert_eq!(rep.account, *DEV_GENESIS_ACCOUNT);
+    assert_eq!(
+        rep.weight,
+        node2.ledger.weight(&DEV_GENESIS_KEY.public_key())
+    );
+    assert!(rep.votes > 0);
+    assert_eq!(
+        rep.node_ids,
+        vec![Account::from(node1.node_id.public_key()).to_node_id()]
+    );
+    assert_eq!(rep.uptime.day, 100.0);
+    assert_eq!(rep.latency.histogram.len(), 8);
+
+    server.abort();
+}
+
+#[test]
+fn representatives_details_filter() {
+    let mut system = System::new();
+    let node = system.make_node();
+    node.rep_registry.vote_observed(
+        DEV_GENESIS_KEY.public_key(),
+        None,
+        None,
+        std::time::SystemTime::now(),
+    );
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async {
+            rpc_client
+                .representatives_details(
+                    RepresentativesDetailsArgs::builder()
+                        .accounts(vec![Account::from(1)])
+                        .build(),
+                )
+                .await
+        })
+        .unwrap();
+    assert!(result.representatives.is_empty());
+
+    let result = node
+        .runtime
+        .block_on(async {
+            rpc_client
+                .representatives_details(RepresentativesDetailsArgs::builder().count(1).build())
+                .await
+        })
+        .unwrap();
+    assert_eq!(result.representatives.len(), 1);
+    assert_eq!(result.representatives[0].votes, 1);
+
+    server.abort();
+}
@@ -0,0 +1,32 @@
+use rsnano_core::Amount;
+use rsnano_node::consensus::PriorityBucketLayout;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn scheduler_buckets() {
+    let mut system = System::new();
+    let mut config = System::default_config();
+    config.priority_bucket.buckets = vec![
+        PriorityBucketLayout::new(Amount::zero()),
+        PriorityBucketLayout {
+            max_blocks: Some(10),
+            ..PriorityBucketLayout::new(Amount::nano(1000))
+        },
+    ];
+    let node = system.build_node().config(config).finish();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.scheduler_buckets().await })
+        .unwrap();
+
+    assert_eq!(result.buckets.len(), 2);
+    assert_eq!(result.buckets[0].minimum_balance, Amount::zero());
+    assert_eq!(result.buckets[1].minimum_balance, Amount::nano(1000));
+    assert_eq!(result.buckets[1].max_blocks, 10);
+    assert_eq!(result.buckets[1].cancellations, 0);
+
+    server.abort();
+}
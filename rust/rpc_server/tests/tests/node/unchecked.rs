@@ -1,4 +1,6 @@
-use rsnano_core::{BlockHash, KeyPair, StateBlockBuilder};
+use rsnano_core::{BlockEnum, BlockHash, KeyPair, StateBlockBuilder};
+use rsnano_node::{config::NodeConfig, unique_path, Node, NodeExt};
+use std::sync::Arc;
 use test_helpers::{assert_timely_msg, setup_rpc_client_and_server, System};
 use tokio::time::Duration;
 
@@ -50,3 +52,144 @@ fn test_unchecked() {
 
     server.abort();
 }
+
+fn persistent_config() -> NodeConfig {
+    let mut config = System::default_config();
+    config.persistent_unchecked.enabled = true;
+    config
+}
+
+/// Processes an open block whose source is unknown, so that it ends up in the unchecked store
+fn process_unchecked_open(node: &Arc<Node>, key: &KeyPair, balance: u128) -> BlockEnum {
+    let open = StateBlockBuilder::new()
+        .account(key.account())
+        .previous(BlockHash::zero())
+        .representative(key.account())
+        .balance(balance)
+        .link(key.account())
+        .sign(key)
+        .work(node.work_generate_dev(key.account().into()))
+        .build();
+    let expected_len = node.unchecked.len() + 1;
+    node.process_active(open.clone());
+    assert_timely_msg(
+        Duration::from_secs(10),
+        || node.unchecked.len() == expected_len,
+        "block not added to the unchecked store",
+    );
+    open
+}
+
+#[test]
+fn test_unchecked_persistent() {
+    let mut system = System::new();
+    let node = system.build_node().config(persistent_config()).finish();
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let open = process_unchecked_open(&node, &KeyPair::new(), 1);
+    assert!(node.data_path.join("unchecked.ldb").exists());
+
+    let unchecked_dto = node
+        .runtime
+        .block_on(async { rpc_client.unchecked(1).await.unwrap() });
+
+    assert_eq!(unchecked_dto.blocks.len(), 1);
+    assert!(unchecked_dto.blocks.contains_key(&open.hash()));
+
+    server.abort();
+}
+
+#[test]
+fn unchecked_get_persistent() {
+    let mut system = System::new();
+    let node = system.build_node().config(persistent_config()).finish();
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let open = process_unchecked_open(&node, &KeyPair::new(), 1);
+
+    let unchecked_dto = node
+        .runtime
+        .block_on(async { rpc_client.unchecked_get(open.hash()).await.unwrap() });
+
+    assert_eq!(BlockEnum::from(unchecked_dto.contents), open);
+
+    server.abort();
+}
+
+#[test]
+fn unchecked_keys_persistent() {
+    let mut system = System::new();
+    let node = system.build_node().config(persistent_config()).finish();
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let key = KeyPair::new();
+    let open = process_unchecked_open(&node, &key, 1);
+    let open2 = process_unchecked_open(&node, &key, 2);
+
+    let unchecked_dto = node.runtime.block_on(async {
+        rpc_client
+            .unchecked_keys(key.account().into(), 10)
+            .await
+            .unwrap()
+    });
+
+    let mut hashes: Vec<_> = unchecked_dto.unchecked.iter().map(|u| u.hash).collect();
+    hashes.sort();
+    let mut expected = vec![open.hash(), open2.hash()];
+    expected.sort();
+    assert_eq!(hashes, expected);
+
+    server.abort();
+}
+
+#[test]
+fn unchecked_persistent_survives_restart() {
+    let mut system = System::new();
+    let node = system.build_node().config(persistent_config()).finish();
+    let open = process_unchecked_open(&node, &KeyPair::new(), 1);
+    node.stop();
+
+    // The ledger of the stopped node stays open in this process, so the
+    // restarted node gets a new data directory with a copy of the unchecked store
+    let data_path = unique_path().unwrap();
+    std::fs::copy(
+        node.data_path.join("unchecked.ldb"),
+        data_path.join("unchecked.ldb"),
+    )
+    .unwrap();
+    let restarted = system
+        .build_node()
+        .config(persistent_config())
+        .data_path(data_path)
+        .disconnected()
+        .finish();
+    let (rpc_client, server) = setup_rpc_client_and_server(restarted.clone(), true);
+
+    assert_eq!(restarted.unchecked.len(), 1);
+    let unchecked_dto = restarted
+        .runtime
+        .block_on(async { rpc_client.unchecked_get(open.hash()).await.unwrap() });
+    assert_eq!(BlockEnum::from(unchecked_dto.contents), open);
+
+    server.abort();
+}
+
+#[test]
+fn unchecked_clear_persistent() {
+    let mut system = System::new();
+    let node = system.build_node().config(persistent_config()).finish();
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    process_unchecked_open(&node, &KeyPair::new(), 1);
+
+    node.runtime
+        .block_on(async { rpc_client.unchecked_clear().await.unwrap() });
+
+    assert!(node.unchecked.is_empty());
+    let unchecked_dto = node
+        .runtime
+        .block_on(async { rpc_client.unchecked(10).await.unwrap() });
+    assert!(unchecked_dto.blocks.is_empty());
+
+    server.abort();
+}
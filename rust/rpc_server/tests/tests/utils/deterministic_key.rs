@@ -37,3 +37,37 @@ fn deterministic_key() {
 
     server.abort();
 }
+
+#[test]
+fn deterministic_key_bip44() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .deterministic_key_bip44(
+                "edge defense waste choose enrich upon flee junk siren film clown finish luggage leader kid quick brick print evidence swap drill paddle truly occur".to_string(),
+                Some("some password".to_string()),
+                0,
+            )
+            .await
+            .unwrap()
+    });
+
+    assert_eq!(
+        result.private,
+        RawKey::decode_hex("3BE4FC2EF3F3B7374E6FC4FB6E7BB153F8A2998B3B3DAB50853EABE128024143")
+            .unwrap()
+    );
+    assert_eq!(
+        result.account,
+        Account::decode_account(
+            "nano_1pu7p5n3ghq1i1p4rhmek41f5add1uh34xpb94nkbxe8g4a6x1p69emk8y1d"
+        )
+        .unwrap()
+    );
+
+    server.abort();
+}
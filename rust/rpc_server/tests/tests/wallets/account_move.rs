@@ -210,7 +210,7 @@ fn account_move_fails_account_not_found() {
 
     assert_eq!(
         result.err().map(|e| e.to_string()),
-        Some("node returned error: \"Account not found\"".to_string())
+        Some("node returned error: \"Account not found in wallet\"".to_string())
     );
 
     server.abort();
@@ -7,6 +7,8 @@ mod password_change;
 mod password_enter;
 mod password_valid;
 mod receive_minimum;
+mod receive_policy;
+mod rep_health_policy;
 mod search_receivable;
 mod search_receivable_all;
 mod send;
@@ -0,0 +1,125 @@
+use rsnano_core::{Account, Amount, KeyPair, WalletId};
+use rsnano_node::wallets::WalletsExt;
+use rsnano_rpc_messages::{ReceivePolicyArgs, ReceivePolicySetArgs};
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn receive_policy_set_and_get() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+    let wallet = WalletId::random();
+    node.wallets.create(wallet);
+    let key = KeyPair::new();
+    node.wallets
+        .insert_adhoc2(&wallet, &key.private_key(), false)
+        .unwrap();
+    let other = KeyPair::new();
+    node.wallets
+        .insert_adhoc2(&wallet, &other.private_key(), false)
+        .unwrap();
+
+    node.runtime.block_on(async {
+        let policy = rpc_client
+            .receive_policy_get(ReceivePolicyArgs::wallet(wallet))
+            .await
+            .unwrap();
+        assert_eq!(policy.scope, "default");
+
+        rpc_client
+            .receive_policy_set(
+                ReceivePolicySetArgs::builder(wallet)
+                    .minimum(Amount::nano(1))
+                    .max_per_minute(10)
+                    .build(),
+            )
+            .await
+            .unwrap();
+        rpc_client
+            .receive_policy_set(
+                ReceivePolicySetArgs::builder(wallet)
+                    .account(key.account())
+                    .deny(vec![Account::from(42)])
+                    .disabled()
+                    .build(),
+            )
+            .await
+            .unwrap();
+
+        let policy = rpc_client
+            .receive_policy_get(ReceivePolicyArgs::account(wallet, key.account()))
+            .await
+            .unwrap();
+        assert_eq!(policy.scope, "account");
+        assert!(policy.disabled);
+        assert_eq!(policy.deny, vec![Account::from(42)]);
+        assert_eq!(policy.minimum, None);
+
+        let policy = rpc_client
+            .receive_policy_get(ReceivePolicyArgs::account(wallet, other.account()))
+            .await
+            .unwrap();
+        assert_eq!(policy.scope, "wallet");
+        assert_eq!(policy.minimum, Some(Amount::nano(1)));
+        assert_eq!(policy.max_per_minute, Some(10));
+
+        rpc_client
+            .receive_policy_remove(ReceivePolicyArgs::account(wallet, key.account()))
+            .await
+            .unwrap();
+        let policy = rpc_client
+            .receive_policy_get(ReceivePolicyArgs::account(wallet, key.account()))
+            .await
+            .unwrap();
+        assert_eq!(policy.scope, "wallet");
+    });
+
+    server.abort();
+}
+
+#[test]
+fn receive_policy_set_fails_with_account_not_found() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+    let wallet = WalletId::random();
+    node.wallets.create(wallet);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .receive_policy_set(
+                ReceivePolicySetArgs::builder(wallet)
+                    .account(Account::from(1))
+                    .disabled()
+                    .build(),
+            )
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Account not found in wallet\"".to_string())
+    );
+
+    server.abort();
+}
+
+#[test]
+fn receive_policy_set_fails_without_enable_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .receive_policy_set(ReceivePolicySetArgs::builder(WalletId::zero()).build())
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
+
+    server.abort();
+}
@@ -0,0 +1,104 @@
+use rsnano_core::{Account, Amount, WalletId};
+use rsnano_node::wallets::WalletsExt;
+use rsnano_rpc_messages::RepHealthPolicySetArgs;
+use test_helpers::{setup_rpc_client_and_server, System};
+
+#[test]
+fn rep_health_policy_set_get_and_remove() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+    let wallet = WalletId::random();
+    node.wallets.create(wallet);
+
+    node.runtime.block_on(async {
+        let policy = rpc_client.rep_health_policy_get(wallet).await.unwrap();
+        assert!(!policy.enabled);
+
+        rpc_client
+            .rep_health_policy_set(
+                RepHealthPolicySetArgs::builder(wallet, vec![Account::from(42)])
+                    .minimum_weight(Amount::nano(1000))
+                    .max_changes_per_hour(3)
+                    .build(),
+            )
+            .await
+            .unwrap();
+
+        let policy = rpc_client.rep_health_policy_get(wallet).await.unwrap();
+        assert!(policy.enabled);
+        assert_eq!(policy.fallback_representatives, vec![Account::from(42)]);
+        assert_eq!(policy.grace_period, Some(3600));
+        assert_eq!(policy.minimum_weight, Some(Amount::nano(1000)));
+        assert_eq!(policy.max_changes_per_hour, Some(3));
+
+        rpc_client.rep_health_policy_remove(wallet).await.unwrap();
+        let policy = rpc_client.rep_health_policy_get(wallet).await.unwrap();
+        assert!(!policy.enabled);
+    });
+
+    server.abort();
+}
+
+#[test]
+fn rep_health_policy_set_fails_without_enable_control() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), false);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .rep_health_policy_set(
+                RepHealthPolicySetArgs::builder(WalletId::zero(), Vec::new()).build(),
+            )
+            .await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"RPC control is disabled\"".to_string())
+    );
+
+    server.abort();
+}
+
+#[test]
+fn rep_health_policy_set_rejects_invalid_policy() {
+    let mut system = System::new();
+    let node = system.make_node();
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+    let wallet = WalletId::random();
+    node.wallets.create(wallet);
+
+    let invalid = [
+        (
+            RepHealthPolicySetArgs::builder(wallet, Vec::new()).build(),
+            "At least one fallback representative is required",
+        ),
+        (
+            RepHealthPolicySetArgs::builder(wallet, vec![Account::from(42)])
+                .minimum_tier(4)
+                .build(),
+            "Minimum tier must be between 0 and 3",
+        ),
+        (
+            RepHealthPolicySetArgs::builder(wallet, vec![Account::from(42)])
+                .minimum_uptime(101)
+                .build(),
+            "Minimum uptime must be between 0 and 100",
+        ),
+    ];
+
+    for (args, error) in invalid {
+        let result = node
+            .runtime
+            .block_on(async { rpc_client.rep_health_policy_set(args).await });
+        assert_eq!(
+            result.err().map(|e| e.to_string()),
+            Some(format!("node returned error: \"{}\"", error))
+        );
+    }
+    assert_eq!(node.wallets.rep_health_policy(&wallet), Ok(None));
+
+    server.abort();
+}
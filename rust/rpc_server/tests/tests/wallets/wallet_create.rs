@@ -1,4 +1,6 @@
-use rsnano_core::RawKey;
+use rsnano_core::{Account, PublicKey, RawKey};
+use rsnano_rpc_client::RpcClientError;
+use rsnano_rpc_messages::{ErrorDto, WalletCreateArgs};
 use test_helpers::{setup_rpc_client_and_server, System};
 
 #[test]
@@ -39,6 +41,112 @@ fn wallet_create_seed_some() {
     server.abort();
 }
 
+const MNEMONIC: &str = "edge defense waste choose enrich upon flee junk siren film clown finish luggage leader kid quick brick print evidence swap drill paddle truly occur";
+
+#[test]
+fn wallet_create_mnemonic() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .wallet_create_from_mnemonic(MNEMONIC.to_string(), Some("some password".to_string()))
+            .await
+            .unwrap()
+    });
+
+    let account = Account::decode_account(
+        "nano_1pu7p5n3ghq1i1p4rhmek41f5add1uh34xpb94nkbxe8g4a6x1p69emk8y1d",
+    )
+    .unwrap();
+    let accounts = node.wallets.get_accounts_of_wallet(&result.wallet).unwrap();
+    assert_eq!(accounts, vec![account]);
+    assert_eq!(
+        node.wallets
+            .fetch(&result.wallet, &PublicKey::from(account))
+            .unwrap(),
+        RawKey::decode_hex("3BE4FC2EF3F3B7374E6FC4FB6E7BB153F8A2998B3B3DAB50853EABE128024143")
+            .unwrap()
+    );
+
+    let exported = node
+        .runtime
+        .block_on(async { rpc_client.wallet_mnemonic(result.wallet).await.unwrap() });
+    assert_eq!(exported.mnemonic, MNEMONIC);
+
+    server.abort();
+}
+
+#[test]
+fn wallet_create_bad_mnemonic() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+    let wallet_count = node.wallets.wallet_ids().len();
+
+    let result = node.runtime.block_on(async {
+        rpc_client
+            .wallet_create_from_mnemonic("foo bar".to_string(), None)
+            .await
+    });
+
+    let error = result.unwrap_err();
+    assert_eq!(
+        error.downcast_ref::<RpcClientError>().unwrap().node_error(),
+        Some(ErrorDto::BadMnemonic)
+    );
+    assert_eq!(node.wallets.wallet_ids().len(), wallet_count);
+
+    server.abort();
+}
+
+#[test]
+fn wallet_create_mnemonic_and_seed() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+    let wallet_count = node.wallets.wallet_ids().len();
+
+    let mut args = WalletCreateArgs::from_mnemonic(MNEMONIC.to_string(), None);
+    args.seed = Some(RawKey::from(1));
+    let result = node
+        .runtime
+        .block_on(async { rpc_client.wallet_create_with_args(args).await });
+
+    let error = result.unwrap_err();
+    assert_eq!(
+        error.downcast_ref::<RpcClientError>().unwrap().node_error(),
+        Some(ErrorDto::MnemonicAndSeed)
+    );
+    assert_eq!(node.wallets.wallet_ids().len(), wallet_count);
+
+    server.abort();
+}
+
+#[test]
+fn wallet_mnemonic_without_mnemonic() {
+    let mut system = System::new();
+    let node = system.make_node();
+
+    let (rpc_client, server) = setup_rpc_client_and_server(node.clone(), true);
+
+    let result = node.runtime.block_on(async {
+        let wallet = rpc_client.wallet_create(None).await.unwrap().wallet;
+        rpc_client.wallet_mnemonic(wallet).await
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.to_string()),
+        Some("node returned error: \"Wallet has no mnemonic\"".to_string())
+    );
+
+    server.abort();
+}
+
 #[test]
 fn wallet_create_fails_without_enable_control() {
     let mut system = System::new();
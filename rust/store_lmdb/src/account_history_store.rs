@@ -0,0 +1,449 @@
+use crate::{LmdbDatabase, LmdbEnv, LmdbWriteTransaction, Transaction};
+use lmdb::{DatabaseFlags, WriteFlags};
+use lmdb_sys::{MDB_NEXT, MDB_SET_RANGE};
+use rsnano_core::{Account, BlockHash, PublicKey};
+use std::sync::Arc;
+
+/// Optional index of the account chains by height and by local timestamp, and of
+/// the representatives each account ever delegated to.
+/// It is only maintained when the account history index is enabled in the ledger.
+/// account_heights: nano::account ++ u64 (height) -> nano::block_hash
+/// account_timestamps: nano::account ++ u64 (local timestamp) ++ u64 (height) -> nano::block_hash
+/// representative_history: nano::public_key (representative) ++ nano::account -> (empty)
+/// account_history_state: u8 (0) -> u8 (state) [++ nano::account]
+pub struct LmdbAccountHistoryStore {
+    _env: Arc<LmdbEnv>,
+    heights_handle: LmdbDatabase,
+    timestamps_handle: LmdbDatabase,
+    representatives_handle: LmdbDatabase,
+    state_handle: LmdbDatabase,
+}
+
+/// The persisted state of the account history index
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AccountHistoryIndexState {
+    /// The index is neither maintained nor built
+    Disabled,
+    /// New blocks are indexed, but the chains of the accounts
+    /// starting at `next_account` weren't indexed yet
+    Building { next_account: Account },
+    /// All blocks are indexed
+    Complete,
+}
+
+const STATE_KEY: [u8; 1] = [0];
+const STATE_BUILDING: u8 = 1;
+const STATE_COMPLETE: u8 = 2;
+
+impl LmdbAccountHistoryStore {
+    pub fn new(env: Arc<LmdbEnv>) -> anyhow::Result<Self> {
+        let heights_handle = env
+            .environment
+            .create_db(Some("account_heights"), DatabaseFlags::empty())?;
+        let timestamps_handle = env
+            .environment
+            .create_db(Some("account_timestamps"), DatabaseFlags::empty())?;
+        let representatives_handle = env
+            .environment
+            .create_db(Some("representative_history"), DatabaseFlags::empty())?;
+        let state_handle = env
+            .environment
+            .create_db(Some("account_history_state"), DatabaseFlags::empty())?;
+        Ok(Self {
+            _env: env,
+            heights_handle,
+            timestamps_handle,
+            representatives_handle,
+            state_handle,
+        })
+    }
+
+    pub fn heights_database(&self) -> LmdbDatabase {
+        self.heights_handle
+    }
+
+    pub fn timestamps_database(&self) -> LmdbDatabase {
+        self.timestamps_handle
+    }
+
+    pub fn put(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        account: &Account,
+        height: u64,
+        timestamp: u64,
+        hash: &BlockHash,
+    ) {
+        txn.put(
+            self.heights_handle,
+            &height_key(account, height),
+            hash.as_bytes(),
+            WriteFlags::empty(),
+        )
+        .unwrap();
+        txn.put(
+            self.timestamps_handle,
+            &timestamp_key(account, timestamp, height),
+            hash.as_bytes(),
+            WriteFlags::empty(),
+        )
+        .unwrap();
+    }
+
+    pub fn del(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        account: &Account,
+        height: u64,
+        timestamp: u64,
+    ) {
+        let keys = [
+            (self.heights_handle, height_key(account, height).to_vec()),
+            (
+                self.timestamps_handle,
+                timestamp_key(account, timestamp, height).to_vec(),
+            ),
+        ];
+        for (database, key) in keys {
+            match txn.delete(database, &key, None) {
+                Ok(()) | Err(lmdb::Error::NotFound) => {}
+                Err(e) => panic!("Could not delete account history entry: {:?}", e),
+            }
+        }
+    }
+
+    /// The hash of the block at the given height of the account chain
+    pub fn get(&self, txn: &dyn Transaction, account: &Account, height: u64) -> Option<BlockHash> {
+        match txn.get(self.heights_handle, &height_key(account, height)) {
+            Ok(bytes) => Some(BlockHash::from_slice(bytes).unwrap()),
+            Err(lmdb::Error::NotFound) => None,
+            Err(e) => panic!("Could not load account history entry: {:?}", e),
+        }
+    }
+
+    /// The height of the first block of the account chain which was
+    /// inserted at or after the given local timestamp
+    pub fn first_height_since(
+        &self,
+        txn: &dyn Transaction,
+        account: &Account,
+        timestamp: u64,
+    ) -> Option<u64> {
+        let cursor = txn
+            .open_ro_cursor(self.timestamps_handle)
+            .expect("Could not read account timestamps database");
+        match cursor.get(
+            Some(&timestamp_key(account, timestamp, 0)),
+            None,
+            MDB_SET_RANGE,
+        ) {
+            Ok((Some(key), _)) if key[..32] == *account.as_bytes() => {
+                Some(u64::from_be_bytes(key[40..48].try_into().unwrap()))
+            }
+            Ok(_) | Err(lmdb::Error::NotFound) => None,
+            Err(e) => panic!("Could not read account timestamps: {:?}", e),
+        }
+    }
+
+    /// Records that the account delegated to the representative at some point.
+    /// Entries are kept when blocks are rolled back or pruned, so the recorded
+    /// delegators are a superset of the accounts which actually delegated
+    pub fn put_delegation(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        representative: &PublicKey,
+        account: &Account,
+    ) {
+        txn.put(
+            self.representatives_handle,
+            &delegation_key(representative, account),
+            &[],
+            WriteFlags::empty(),
+        )
+        .unwrap();
+    }
+
+    /// The accounts which ever delegated to the representative in ascending order,
+    /// starting with `start` (inclusive)
+    pub fn iter_delegators<'txn>(
+        &self,
+        txn: &'txn dyn Transaction,
+        representative: &PublicKey,
+        start: &Account,
+    ) -> impl Iterator<Item = Account> + 'txn {
+        let cursor = txn
+            .open_ro_cursor(self.representatives_handle)
+            .expect("Could not read representative history database");
+        let representative = *representative;
+        let start_key = delegation_key(&representative, start);
+        let mut initialized = false;
+
+        std::iter::from_fn(move || {
+            let result = if initialized {
+                cursor.get(None, None, MDB_NEXT)
+            } else {
+                initialized = true;
+                cursor.get(Some(&start_key), None, MDB_SET_RANGE)
+            };
+            match result {
+                Ok((Some(key), _)) if key[..32] == *representative.as_bytes() => {
+                    Some(Account::from_bytes(key[32..].try_into().unwrap()))
+                }
+                Ok(_) | Err(lmdb::Error::NotFound) => None,
+                Err(e) => panic!("Could not read representative history: {:?}", e),
+            }
+        })
+    }
+
+    pub fn count(&self, txn: &dyn Transaction) -> u64 {
+        txn.count(self.heights_handle)
+    }
+
+    pub fn state(&self, txn: &dyn Transaction) -> AccountHistoryIndexState {
+        match txn.get(self.state_handle, &STATE_KEY) {
+            Ok([STATE_BUILDING, account @ ..]) => AccountHistoryIndexState::Building {
+                next_account: Account::from_slice(account).unwrap(),
+            },
+            Ok([STATE_COMPLETE]) => AccountHistoryIndexState::Complete,
+            Ok(_) | Err(lmdb::Error::NotFound) => AccountHistoryIndexState::Disabled,
+            Err(e) => panic!("Could not load account history index state: {:?}", e),
+        }
+    }
+
+    pub fn set_state(&self, txn: &mut LmdbWriteTransaction, state: AccountHistoryIndexState) {
+        let value = match state {
+            AccountHistoryIndexState::Disabled => {
+                match txn.delete(self.state_handle, &STATE_KEY, None) {
+                    Ok(()) | Err(lmdb::Error::NotFound) => {}
+                    Err(e) => panic!("Could not delete account history index state: {:?}", e),
+                }
+                return;
+            }
+            AccountHistoryIndexState::Building { next_account } => {
+                let mut value = vec![STATE_BUILDING];
+                value.extend_from_slice(next_account.as_bytes());
+                value
+            }
+            AccountHistoryIndexState::Complete => vec![STATE_COMPLETE],
+        };
+        txn.put(self.state_handle, &STATE_KEY, &value, WriteFlags::empty())
+            .unwrap();
+    }
+
+    pub fn clear(&self, txn: &mut LmdbWriteTransaction) {
+        txn.clear_db(self.heights_handle).unwrap();
+        txn.clear_db(self.timestamps_handle).unwrap();
+        txn.clear_db(self.representatives_handle).unwrap();
+    }
+}
+
+fn height_key(account: &Account, height: u64) -> [u8; 40] {
+    let mut key = [0; 40];
+    key[..32].copy_from_slice(account.as_bytes());
+    key[32..].copy_from_slice(&height.to_be_bytes());
+    key
+}
+
+fn delegation_key(representative: &PublicKey, account: &Account) -> [u8; 64] {
+    let mut key = [0; 64];
+    key[..32].copy_from_slice(representative.as_bytes());
+    key[32..].copy_from_slice(account.as_bytes());
+    key
+}
+
+fn timestamp_key(account: &Account, timestamp: u64, height: u64) -> [u8; 48] {
+    let mut key = [0; 48];
+    key[..32].copy_from_slice(account.as_bytes());
+    key[32..40].copy_from_slice(&timestamp.to_be_bytes());
+    key[40..].copy_from_slice(&height.to_be_bytes());
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PutEvent;
+
+    const HEIGHTS_DATABASE: LmdbDatabase = LmdbDatabase::new_null(202);
+    const TIMESTAMPS_DATABASE: LmdbDatabase = LmdbDatabase::new_null(203);
+    const STATE_DATABASE: LmdbDatabase = LmdbDatabase::new_null(204);
+    const REPRESENTATIVES_DATABASE: LmdbDatabase = LmdbDatabase::new_null(205);
+
+    struct Fixture {
+        env: Arc<LmdbEnv>,
+        store: LmdbAccountHistoryStore,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            Self::with_stored_entries(Vec::new())
+        }
+
+        fn with_stored_entries(entries: Vec<(Account, u64, u64, BlockHash)>) -> Self {
+            let mut heights =
+                LmdbEnv::new_null_with().database("account_heights", HEIGHTS_DATABASE);
+            for (account, height, _, hash) in &entries {
+                heights = heights.entry(&height_key(account, *height), hash.as_bytes());
+            }
+            let mut timestamps = heights
+                .build()
+                .database("account_timestamps", TIMESTAMPS_DATABASE);
+            for (account, height, timestamp, hash) in &entries {
+                timestamps = timestamps.entry(
+                    &timestamp_key(account, *timestamp, *height),
+                    hash.as_bytes(),
+                );
+            }
+            let env = Arc::new(
+                timestamps
+                    .build()
+                    .database("representative_history", REPRESENTATIVES_DATABASE)
+                    .build()
+                    .build(),
+            );
+            Self {
+                env: env.clone(),
+                store: LmdbAccountHistoryStore::new(env).unwrap(),
+            }
+        }
+    }
+
+    #[test]
+    fn put() {
+        let fixture = Fixture::new();
+        let mut txn = fixture.env.tx_begin_write();
+        let put_tracker = txn.track_puts();
+        let account = Account::from(1);
+        let hash = BlockHash::from(4);
+
+        fixture.store.put(&mut txn, &account, 2, 3, &hash);
+
+        assert_eq!(
+            put_tracker.output(),
+            vec![
+                PutEvent {
+                    database: HEIGHTS_DATABASE,
+                    key: height_key(&account, 2).to_vec(),
+                    value: hash.as_bytes().to_vec(),
+                    flags: WriteFlags::empty(),
+                },
+                PutEvent {
+                    database: TIMESTAMPS_DATABASE,
+                    key: timestamp_key(&account, 3, 2).to_vec(),
+                    value: hash.as_bytes().to_vec(),
+                    flags: WriteFlags::empty(),
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn put_delegation() {
+        let fixture = Fixture::new();
+        let mut txn = fixture.env.tx_begin_write();
+        let put_tracker = txn.track_puts();
+        let representative = PublicKey::from(1);
+        let account = Account::from(2);
+
+        fixture
+            .store
+            .put_delegation(&mut txn, &representative, &account);
+
+        assert_eq!(
+            put_tracker.output(),
+            vec![PutEvent {
+                database: REPRESENTATIVES_DATABASE,
+                key: delegation_key(&representative, &account).to_vec(),
+                value: Vec::new(),
+                flags: WriteFlags::empty(),
+            }]
+        );
+    }
+
+    #[test]
+    fn get_by_height() {
+        let account = Account::from(1);
+        let fixture = Fixture::with_stored_entries(vec![
+            (account, 1, 100, BlockHash::from(10)),
+            (account, 2, 200, BlockHash::from(20)),
+        ]);
+        let txn = fixture.env.tx_begin_read();
+
+        assert_eq!(
+            fixture.store.get(&txn, &account, 2),
+            Some(BlockHash::from(20))
+        );
+        assert_eq!(fixture.store.get(&txn, &account, 3), None);
+        assert_eq!(fixture.store.get(&txn, &Account::from(2), 1), None);
+    }
+
+    #[test]
+    fn first_height_since() {
+        let account = Account::from(1);
+        let fixture = Fixture::with_stored_entries(vec![
+            (account, 1, 100, BlockHash::from(10)),
+            (account, 2, 200, BlockHash::from(20)),
+            (account, 3, 300, BlockHash::from(30)),
+            (Account::from(2), 1, 400, BlockHash::from(40)),
+        ]);
+        let txn = fixture.env.tx_begin_read();
+
+        assert_eq!(fixture.store.first_height_since(&txn, &account, 0), Some(1));
+        assert_eq!(
+            fixture.store.first_height_since(&txn, &account, 150),
+            Some(2)
+        );
+        assert_eq!(
+            fixture.store.first_height_since(&txn, &account, 300),
+            Some(3)
+        );
+        assert_eq!(fixture.store.first_height_since(&txn, &account, 301), None);
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let fixture = Fixture::new();
+        let txn = fixture.env.tx_begin_read();
+        assert_eq!(
+            fixture.store.state(&txn),
+            AccountHistoryIndexState::Disabled
+        );
+    }
+
+    #[test]
+    fn load_building_state() {
+        let mut value = vec![STATE_BUILDING];
+        value.extend_from_slice(Account::from(5).as_bytes());
+        let env = Arc::new(
+            LmdbEnv::new_null_with()
+                .database("account_history_state", STATE_DATABASE)
+                .entry(&STATE_KEY, &value)
+                .build()
+                .build(),
+        );
+        let store = LmdbAccountHistoryStore::new(env.clone()).unwrap();
+        let txn = env.tx_begin_read();
+
+        assert_eq!(
+            store.state(&txn),
+            AccountHistoryIndexState::Building {
+                next_account: Account::from(5)
+            }
+        );
+    }
+
+    #[test]
+    fn save_complete_state() {
+        let fixture = Fixture::new();
+        let mut txn = fixture.env.tx_begin_write();
+        let put_tracker = txn.track_puts();
+
+        fixture
+            .store
+            .set_state(&mut txn, AccountHistoryIndexState::Complete);
+
+        assert_eq!(put_tracker.output().len(), 1);
+        assert_eq!(put_tracker.output()[0].key, STATE_KEY.to_vec());
+        assert_eq!(put_tracker.output()[0].value, vec![STATE_COMPLETE]);
+    }
+}
@@ -0,0 +1,213 @@
+use crate::{LmdbDatabase, LmdbEnv, LmdbWriteTransaction, Transaction};
+use lmdb::{DatabaseFlags, WriteFlags};
+use lmdb_sys::{MDB_NEXT, MDB_SET_RANGE};
+use rsnano_core::{Account, PublicKey};
+use std::sync::Arc;
+
+/// Index of the accounts which delegate their weight to a representative.
+/// It is maintained together with the account table.
+/// nano::public_key (representative) ++ nano::account -> (empty)
+pub struct LmdbDelegatorStore {
+    _env: Arc<LmdbEnv>,
+    database: LmdbDatabase,
+}
+
+impl LmdbDelegatorStore {
+    pub fn new(env: Arc<LmdbEnv>) -> anyhow::Result<Self> {
+        let database = env
+            .environment
+            .create_db(Some("delegators"), DatabaseFlags::empty())?;
+        Ok(Self {
+            _env: env,
+            database,
+        })
+    }
+
+    pub fn database(&self) -> LmdbDatabase {
+        self.database
+    }
+
+    pub fn put(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        representative: &PublicKey,
+        account: &Account,
+    ) {
+        txn.put(
+            self.database,
+            &delegator_key(representative, account),
+            &[],
+            WriteFlags::empty(),
+        )
+        .unwrap();
+    }
+
+    pub fn del(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        representative: &PublicKey,
+        account: &Account,
+    ) {
+        match txn.delete(self.database, &delegator_key(representative, account), None) {
+            Ok(()) | Err(lmdb::Error::NotFound) => {}
+            Err(e) => panic!("Could not delete delegator: {:?}", e),
+        }
+    }
+
+    pub fn exists(
+        &self,
+        txn: &dyn Transaction,
+        representative: &PublicKey,
+        account: &Account,
+    ) -> bool {
+        match txn.get(self.database, &delegator_key(representative, account)) {
+            Ok(_) => true,
+            Err(lmdb::Error::NotFound) => false,
+            Err(e) => panic!("Could not load delegator: {:?}", e),
+        }
+    }
+
+    /// Accounts delegating to the representative in ascending order,
+    /// starting with `start` (inclusive)
+    pub fn iter<'txn>(
+        &self,
+        txn: &'txn dyn Transaction,
+        representative: &PublicKey,
+        start: &Account,
+    ) -> impl Iterator<Item = Account> + 'txn {
+        let cursor = txn
+            .open_ro_cursor(self.database)
+            .expect("Could not read delegators database");
+        let representative = *representative;
+        let start_key = delegator_key(&representative, start);
+        let mut initialized = false;
+
+        std::iter::from_fn(move || {
+            let result = if initialized {
+                cursor.get(None, None, MDB_NEXT)
+            } else {
+                initialized = true;
+                cursor.get(Some(&start_key), None, MDB_SET_RANGE)
+            };
+            match result {
+                Ok((Some(key), _)) if key[..32] == *representative.as_bytes() => {
+                    Some(Account::from_bytes(key[32..].try_into().unwrap()))
+                }
+                Ok(_) | Err(lmdb::Error::NotFound) => None,
+                Err(e) => panic!("Could not read delegators: {:?}", e),
+            }
+        })
+    }
+
+    pub fn count(&self, txn: &dyn Transaction, representative: &PublicKey) -> u64 {
+        self.iter(txn, representative, &Account::zero()).count() as u64
+    }
+
+    pub fn count_all(&self, txn: &dyn Transaction) -> u64 {
+        txn.count(self.database)
+    }
+
+    pub fn clear(&self, txn: &mut LmdbWriteTransaction) {
+        txn.clear_db(self.database).unwrap();
+    }
+}
+
+fn delegator_key(representative: &PublicKey, account: &Account) -> [u8; 64] {
+    let mut key = [0; 64];
+    key[..32].copy_from_slice(representative.as_bytes());
+    key[32..].copy_from_slice(account.as_bytes());
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PutEvent;
+
+    const TEST_DATABASE: LmdbDatabase = LmdbDatabase::new_null(201);
+
+    struct Fixture {
+        env: Arc<LmdbEnv>,
+        store: LmdbDelegatorStore,
+    }
+
+    impl Fixture {
+        fn with_stored_entries(entries: Vec<(PublicKey, Account)>) -> Self {
+            let mut env = LmdbEnv::new_null_with().database("delegators", TEST_DATABASE);
+            for (representative, account) in entries {
+                env = env.entry(&delegator_key(&representative, &account), &[]);
+            }
+            let env = Arc::new(env.build().build());
+            Self {
+                env: env.clone(),
+                store: LmdbDelegatorStore::new(env).unwrap(),
+            }
+        }
+    }
+
+    #[test]
+    fn put() {
+        let fixture = Fixture::with_stored_entries(Vec::new());
+        let mut txn = fixture.env.tx_begin_write();
+        let put_tracker = txn.track_puts();
+
+        fixture
+            .store
+            .put(&mut txn, &PublicKey::from(1), &Account::from(2));
+
+        let mut expected_key = [0; 64];
+        expected_key[31] = 1;
+        expected_key[63] = 2;
+        assert_eq!(
+            put_tracker.output(),
+            vec![PutEvent {
+                database: TEST_DATABASE,
+                key: expected_key.to_vec(),
+                value: Vec::new(),
+                flags: WriteFlags::empty(),
+            }]
+        );
+    }
+
+    #[test]
+    fn iter_delegators_of_representative() {
+        let fixture = Fixture::with_stored_entries(vec![
+            (PublicKey::from(1), Account::from(5)),
+            (PublicKey::from(2), Account::from(3)),
+            (PublicKey::from(2), Account::from(4)),
+            (PublicKey::from(2), Account::from(6)),
+            (PublicKey::from(3), Account::from(1)),
+        ]);
+        let txn = fixture.env.tx_begin_read();
+
+        assert_eq!(
+            fixture
+                .store
+                .iter(&txn, &PublicKey::from(2), &Account::zero())
+                .collect::<Vec<_>>(),
+            vec![Account::from(3), Account::from(4), Account::from(6)]
+        );
+        assert_eq!(
+            fixture
+                .store
+                .iter(&txn, &PublicKey::from(2), &Account::from(4))
+                .collect::<Vec<_>>(),
+            vec![Account::from(4), Account::from(6)]
+        );
+        assert_eq!(fixture.store.count(&txn, &PublicKey::from(2)), 3);
+        assert_eq!(fixture.store.count(&txn, &PublicKey::from(4)), 0);
+    }
+
+    #[test]
+    fn exists() {
+        let fixture = Fixture::with_stored_entries(vec![(PublicKey::from(1), Account::from(2))]);
+        let txn = fixture.env.tx_begin_read();
+
+        assert!(fixture
+            .store
+            .exists(&txn, &PublicKey::from(1), &Account::from(2)));
+        assert!(!fixture
+            .store
+            .exists(&txn, &PublicKey::from(2), &Account::from(1)));
+    }
+}
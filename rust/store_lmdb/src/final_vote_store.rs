@@ -1,8 +1,10 @@
 use crate::{
     BinaryDbIterator, LmdbDatabase, LmdbEnv, LmdbIteratorImpl, LmdbWriteTransaction, Transaction,
+    FINAL_VOTE_TEST_DATABASE,
 };
 use lmdb::{DatabaseFlags, WriteFlags};
 use rsnano_core::{BlockHash, QualifiedRoot, Root};
+use rsnano_nullable_lmdb::ConfiguredDatabase;
 use std::sync::Arc;
 
 pub type FinalVoteIterator<'txn> = BinaryDbIterator<'txn, QualifiedRoot, BlockHash>;
@@ -88,6 +90,26 @@ impl LmdbFinalVoteStore {
         result
     }
 
+    /// Returns all final votes for the given root together with their qualified roots
+    pub fn get_qualified(
+        &self,
+        txn: &dyn Transaction,
+        root: &Root,
+    ) -> Vec<(QualifiedRoot, BlockHash)> {
+        let mut result = Vec::new();
+        let mut i = self.begin_at_root(txn, &QualifiedRoot::new(*root, BlockHash::zero()));
+        while let Some((k, v)) = i.current() {
+            if k.root != *root {
+                break;
+            }
+
+            result.push((k.clone(), *v));
+            i.next();
+        }
+
+        result
+    }
+
     pub fn del(&self, txn: &mut LmdbWriteTransaction, root: &Root) {
         let mut final_vote_qualified_roots = Vec::new();
 
@@ -127,6 +149,29 @@ impl LmdbFinalVoteStore {
     }
 }
 
+pub struct ConfiguredFinalVoteDatabaseBuilder {
+    database: ConfiguredDatabase,
+}
+
+impl ConfiguredFinalVoteDatabaseBuilder {
+    pub fn new() -> Self {
+        Self {
+            database: ConfiguredDatabase::new(FINAL_VOTE_TEST_DATABASE, "final_votes"),
+        }
+    }
+
+    pub fn final_vote(mut self, root: &QualifiedRoot, hash: &BlockHash) -> Self {
+        self.database
+            .entries
+            .insert(root.to_bytes().to_vec(), hash.as_bytes().to_vec());
+        self
+    }
+
+    pub fn build(self) -> ConfiguredDatabase {
+        self.database
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -173,6 +218,21 @@ mod tests {
         assert_eq!(result, vec![hash])
     }
 
+    #[test]
+    fn get_qualified() {
+        let root = QualifiedRoot::new_test_instance();
+        let other = QualifiedRoot::new(Root::from(112), BlockHash::from(1));
+        let fixture = Fixture::with_stored_entries(vec![
+            (root.clone(), BlockHash::from(333)),
+            (other, BlockHash::from(444)),
+        ]);
+        let txn = fixture.env.tx_begin_read();
+
+        let result = fixture.store.get_qualified(&txn, &root.root);
+
+        assert_eq!(result, vec![(root, BlockHash::from(333))])
+    }
+
     #[test]
     fn delete() {
         let root = QualifiedRoot::new_test_instance();
@@ -4,9 +4,11 @@ extern crate num_derive;
 #[macro_use]
 extern crate anyhow;
 
+mod account_history_store;
 mod account_store;
 mod block_store;
 mod confirmation_height_store;
+mod delegator_store;
 mod fan;
 mod final_vote_store;
 mod iterator;
@@ -16,16 +18,20 @@ mod online_weight_store;
 mod peer_store;
 mod pending_store;
 mod pruned_store;
+mod rep_details_store;
 mod rep_weight_store;
 mod store;
+mod unchecked_store;
 mod version_store;
 mod wallet_store;
 
+pub use account_history_store::{AccountHistoryIndexState, LmdbAccountHistoryStore};
 pub use account_store::{ConfiguredAccountDatabaseBuilder, LmdbAccountStore};
 pub use block_store::{ConfiguredBlockDatabaseBuilder, LmdbBlockStore};
 pub use confirmation_height_store::*;
+pub use delegator_store::LmdbDelegatorStore;
 pub use fan::Fan;
-pub use final_vote_store::LmdbFinalVoteStore;
+pub use final_vote_store::{ConfiguredFinalVoteDatabaseBuilder, LmdbFinalVoteStore};
 pub use iterator::{BinaryDbIterator, LmdbIterator, LmdbIteratorImpl};
 pub use lmdb_config::{LmdbConfig, SyncStrategy};
 pub use lmdb_env::*;
@@ -33,11 +39,13 @@ pub use online_weight_store::LmdbOnlineWeightStore;
 pub use peer_store::*;
 pub use pending_store::{ConfiguredPendingDatabaseBuilder, LmdbPendingStore};
 pub use pruned_store::{ConfiguredPrunedDatabaseBuilder, LmdbPrunedStore};
+pub use rep_details_store::LmdbRepDetailsStore;
 pub use rep_weight_store::*;
 use rsnano_nullable_lmdb::{
     InactiveTransaction, LmdbDatabase, LmdbEnvironment, RoCursor, RoTransaction, RwTransaction,
 };
 pub use store::{create_backup_file, LedgerCache, LmdbStore};
+pub use unchecked_store::LmdbUncheckedStore;
 pub use version_store::LmdbVersionStore;
 pub use wallet_store::{Fans, KeyType, LmdbWalletStore, WalletValue};
 
@@ -321,6 +329,16 @@ impl LmdbWriteTransaction {
         self.txn = RwTxnState::Inactive;
     }
 
+    /// Discards all changes of the transaction
+    pub fn abort(&mut self) {
+        let t = mem::replace(&mut self.txn, RwTxnState::Transitioning);
+        if let RwTxnState::Active(t) = t {
+            t.abort();
+            self.callbacks.txn_end(self.txn_id, true);
+        }
+        self.txn = RwTxnState::Inactive;
+    }
+
     #[cfg(feature = "output_tracking")]
     pub fn track_puts(&self) -> Rc<OutputTracker<PutEvent>> {
         self.put_listener.track()
@@ -449,7 +467,7 @@ pub fn parallel_traversal(action: &(impl Fn(U256, U256, bool) + Send + Sync)) {
 }
 
 pub const STORE_VERSION_MINIMUM: i32 = 24;
-pub const STORE_VERSION_CURRENT: i32 = 24;
+pub const STORE_VERSION_CURRENT: i32 = 26;
 
 pub const BLOCK_TEST_DATABASE: LmdbDatabase = LmdbDatabase::new_null(1);
 pub const FRONTIER_TEST_DATABASE: LmdbDatabase = LmdbDatabase::new_null(2);
@@ -459,6 +477,7 @@ pub const PRUNED_TEST_DATABASE: LmdbDatabase = LmdbDatabase::new_null(5);
 pub const REP_WEIGHT_TEST_DATABASE: LmdbDatabase = LmdbDatabase::new_null(6);
 pub const CONFIRMATION_HEIGHT_TEST_DATABASE: LmdbDatabase = LmdbDatabase::new_null(7);
 pub const PEERS_TEST_DATABASE: LmdbDatabase = LmdbDatabase::new_null(8);
+pub const FINAL_VOTE_TEST_DATABASE: LmdbDatabase = LmdbDatabase::new_null(9);
 
 #[cfg(test)]
 mod test {
@@ -0,0 +1,154 @@
+use crate::{iterator::LmdbIterator, LmdbDatabase, LmdbEnv, LmdbWriteTransaction, Transaction};
+use lmdb::{DatabaseFlags, WriteFlags};
+use rsnano_core::{
+    utils::{BufferReader, Deserialize},
+    PublicKey, RepDetails,
+};
+use rsnano_output_tracker::{OutputListenerMt, OutputTrackerMt};
+use std::sync::Arc;
+
+/// Long term observations about representatives.
+/// nano::public_key -> nano::rep_details
+pub struct LmdbRepDetailsStore {
+    _env: Arc<LmdbEnv>,
+    database: LmdbDatabase,
+    put_listener: OutputListenerMt<(PublicKey, RepDetails)>,
+}
+
+impl LmdbRepDetailsStore {
+    pub fn new(env: Arc<LmdbEnv>) -> anyhow::Result<Self> {
+        let database = env
+            .environment
+            .create_db(Some("rep_details"), DatabaseFlags::empty())?;
+        Ok(Self {
+            _env: env,
+            database,
+            put_listener: OutputListenerMt::new(),
+        })
+    }
+
+    pub fn database(&self) -> LmdbDatabase {
+        self.database
+    }
+
+    pub fn track_puts(&self) -> Arc<OutputTrackerMt<(PublicKey, RepDetails)>> {
+        self.put_listener.track()
+    }
+
+    pub fn put(&self, txn: &mut LmdbWriteTransaction, rep: &PublicKey, details: &RepDetails) {
+        self.put_listener.emit((*rep, details.clone()));
+        txn.put(
+            self.database,
+            rep.as_bytes(),
+            &details.to_bytes(),
+            WriteFlags::empty(),
+        )
+        .unwrap();
+    }
+
+    pub fn get(&self, txn: &dyn Transaction, rep: &PublicKey) -> Option<RepDetails> {
+        match txn.get(self.database, rep.as_bytes()) {
+            Ok(bytes) => RepDetails::deserialize(&mut BufferReader::new(bytes)).ok(),
+            Err(lmdb::Error::NotFound) => None,
+            Err(e) => panic!("Could not load rep details: {:?}", e),
+        }
+    }
+
+    pub fn del(&self, txn: &mut LmdbWriteTransaction, rep: &PublicKey) {
+        match txn.delete(self.database, rep.as_bytes(), None) {
+            Ok(()) | Err(lmdb::Error::NotFound) => {}
+            Err(e) => panic!("Could not delete rep details: {:?}", e),
+        }
+    }
+
+    /// Entries which can't be decoded are skipped
+    pub fn iter<'txn>(
+        &self,
+        txn: &'txn dyn Transaction,
+    ) -> impl Iterator<Item = (PublicKey, RepDetails)> + 'txn {
+        let cursor = txn
+            .open_ro_cursor(self.database)
+            .expect("Could not read rep details database");
+        LmdbIterator::new(cursor, |k, v| {
+            let rep = PublicKey::from_bytes(k.try_into().unwrap());
+            let details = RepDetails::deserialize(&mut BufferReader::new(v)).ok();
+            (rep, details)
+        })
+        .filter_map(|(rep, details)| details.map(|d| (rep, d)))
+    }
+
+    pub fn count(&self, txn: &dyn Transaction) -> u64 {
+        txn.count(self.database)
+    }
+
+    pub fn clear(&self, txn: &mut LmdbWriteTransaction) {
+        txn.clear_db(self.database).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PutEvent;
+
+    const TEST_DATABASE: LmdbDatabase = LmdbDatabase::new_null(200);
+
+    struct Fixture {
+        env: Arc<LmdbEnv>,
+        store: LmdbRepDetailsStore,
+    }
+
+    impl Fixture {
+        fn with_stored_entries(entries: Vec<(PublicKey, Vec<u8>)>) -> Self {
+            let mut env = LmdbEnv::new_null_with().database("rep_details", TEST_DATABASE);
+            for (key, value) in entries {
+                env = env.entry(key.as_bytes(), &value);
+            }
+            let env = Arc::new(env.build().build());
+            Self {
+                env: env.clone(),
+                store: LmdbRepDetailsStore::new(env).unwrap(),
+            }
+        }
+    }
+
+    #[test]
+    fn put() {
+        let fixture = Fixture::with_stored_entries(Vec::new());
+        let mut txn = fixture.env.tx_begin_write();
+        let put_tracker = txn.track_puts();
+        let details = RepDetails::new_test_instance();
+
+        fixture.store.put(&mut txn, &PublicKey::from(1), &details);
+
+        assert_eq!(
+            put_tracker.output(),
+            vec![PutEvent {
+                database: TEST_DATABASE,
+                key: PublicKey::from(1).as_bytes().to_vec(),
+                value: details.to_bytes(),
+                flags: WriteFlags::empty(),
+            }]
+        );
+    }
+
+    #[test]
+    fn load() {
+        let details = RepDetails::new_test_instance();
+        let fixture = Fixture::with_stored_entries(vec![
+            (PublicKey::from(1), details.to_bytes()),
+            (PublicKey::from(2), vec![99, 1, 2]),
+        ]);
+        let txn = fixture.env.tx_begin_read();
+
+        assert_eq!(
+            fixture.store.get(&txn, &PublicKey::from(1)),
+            Some(details.clone())
+        );
+        assert_eq!(fixture.store.get(&txn, &PublicKey::from(3)), None);
+        assert_eq!(
+            fixture.store.iter(&txn).collect::<Vec<_>>(),
+            vec![(PublicKey::from(1), details)]
+        );
+    }
+}
@@ -1,13 +1,17 @@
 use crate::{
-    EnvOptions, LmdbAccountStore, LmdbBlockStore, LmdbConfirmationHeightStore, LmdbDatabase,
-    LmdbEnv, LmdbFinalVoteStore, LmdbOnlineWeightStore, LmdbPeerStore, LmdbPendingStore,
-    LmdbPrunedStore, LmdbReadTransaction, LmdbRepWeightStore, LmdbVersionStore,
+    AccountHistoryIndexState, EnvOptions, LmdbAccountHistoryStore, LmdbAccountStore,
+    LmdbBlockStore, LmdbConfirmationHeightStore, LmdbDatabase, LmdbDelegatorStore, LmdbEnv,
+    LmdbFinalVoteStore, LmdbOnlineWeightStore, LmdbPeerStore, LmdbPendingStore, LmdbPrunedStore,
+    LmdbReadTransaction, LmdbRepDetailsStore, LmdbRepWeightStore, LmdbVersionStore,
     LmdbWriteTransaction, NullTransactionTracker, TransactionTracker, STORE_VERSION_CURRENT,
     STORE_VERSION_MINIMUM,
 };
 use lmdb::{DatabaseFlags, WriteFlags};
 use lmdb_sys::{MDB_CP_COMPACT, MDB_SUCCESS};
-use rsnano_core::utils::{seconds_since_epoch, PropertyTree};
+use rsnano_core::{
+    utils::{seconds_since_epoch, PropertyTree},
+    Account,
+};
 use std::{
     ffi::CString,
     path::{Path, PathBuf},
@@ -55,10 +59,13 @@ pub struct LmdbStore {
     pub cache: Arc<LedgerCache>,
     pub block: Arc<LmdbBlockStore>,
     pub account: Arc<LmdbAccountStore>,
+    pub account_history: Arc<LmdbAccountHistoryStore>,
+    pub delegators: Arc<LmdbDelegatorStore>,
     pub pending: Arc<LmdbPendingStore>,
     pub online_weight: Arc<LmdbOnlineWeightStore>,
     pub pruned: Arc<LmdbPrunedStore>,
     pub rep_weight: Arc<LmdbRepWeightStore>,
+    pub rep_details: Arc<LmdbRepDetailsStore>,
     pub peer: Arc<LmdbPeerStore>,
     pub confirmation_height: Arc<LmdbConfirmationHeightStore>,
     pub final_vote: Arc<LmdbFinalVoteStore>,
@@ -137,10 +144,13 @@ impl LmdbStore {
             cache: Arc::new(LedgerCache::new()),
             block: Arc::new(LmdbBlockStore::new(env.clone())?),
             account: Arc::new(LmdbAccountStore::new(env.clone())?),
+            account_history: Arc::new(LmdbAccountHistoryStore::new(env.clone())?),
+            delegators: Arc::new(LmdbDelegatorStore::new(env.clone())?),
             pending: Arc::new(LmdbPendingStore::new(env.clone())?),
             online_weight: Arc::new(LmdbOnlineWeightStore::new(env.clone())?),
             pruned: Arc::new(LmdbPrunedStore::new(env.clone())?),
             rep_weight: Arc::new(LmdbRepWeightStore::new(env.clone())?),
+            rep_details: Arc::new(LmdbRepDetailsStore::new(env.clone())?),
             peer: Arc::new(LmdbPeerStore::new(env.clone())?),
             confirmation_height: Arc::new(LmdbConfirmationHeightStore::new(env.clone())?),
             final_vote: Arc::new(LmdbFinalVoteStore::new(env.clone())?),
@@ -295,10 +305,86 @@ fn do_upgrades(env: Arc<LmdbEnv>) -> anyhow::Result<Vacuuming> {
         bail!("version too high");
     }
 
+    if version < 25 {
+        upgrade_v24_to_v25(&env, &mut txn, &version_store)?;
+    }
+
+    if version < 26 {
+        upgrade_v25_to_v26(&env, &mut txn, &version_store)?;
+    }
+
     // most recent version
     Ok(Vacuuming::NotNeeded)
 }
 
+/// Builds the delegators index from the account table
+fn upgrade_v24_to_v25(
+    env: &Arc<LmdbEnv>,
+    txn: &mut LmdbWriteTransaction,
+    version_store: &LmdbVersionStore,
+) -> anyhow::Result<()> {
+    info!("Upgrading database from v24 to v25...");
+    // The index is built in batches and every batch is committed, so that neither the accounts
+    // nor the dirty pages of a single huge write transaction have to fit into memory.
+    // The version is bumped last, so an interrupted upgrade starts over with a cleared index
+    const BATCH_SIZE: usize = 100_000;
+    let account_store = LmdbAccountStore::new(env.clone())?;
+    let delegator_store = LmdbDelegatorStore::new(env.clone())?;
+    delegator_store.clear(txn);
+
+    let mut start = Some(Account::zero());
+    let mut indexed = 0;
+    while let Some(start_account) = start {
+        let batch: Vec<_> = account_store
+            .iter_range(txn, start_account..)
+            .take(BATCH_SIZE)
+            .map(|(account, info)| (info.representative, account))
+            .collect();
+        for (representative, account) in &batch {
+            delegator_store.put(txn, representative, account);
+        }
+        indexed += batch.len();
+        crate::Transaction::refresh(txn);
+        start = match batch.last() {
+            Some((_, last)) if batch.len() == BATCH_SIZE => last.inc(),
+            _ => None,
+        };
+        if start.is_some() {
+            info!("Indexed delegators of {} accounts...", indexed);
+        }
+    }
+
+    version_store.put(txn, 25);
+    info!(
+        "Finished building the delegators index for {} accounts",
+        indexed
+    );
+    Ok(())
+}
+
+/// Restarts building an existing account history index, so that the representative
+/// history which was added to it gets filled for all accounts
+fn upgrade_v25_to_v26(
+    env: &Arc<LmdbEnv>,
+    txn: &mut LmdbWriteTransaction,
+    version_store: &LmdbVersionStore,
+) -> anyhow::Result<()> {
+    info!("Upgrading database from v25 to v26...");
+    let account_history = LmdbAccountHistoryStore::new(env.clone())?;
+    if account_history.state(txn) != AccountHistoryIndexState::Disabled {
+        info!("The account history index will be rebuilt");
+        account_history.set_state(
+            txn,
+            AccountHistoryIndexState::Building {
+                next_account: Account::zero(),
+            },
+        );
+    }
+    version_store.put(txn, 26);
+    info!("Upgrading database from v25 to v26 completed");
+    Ok(())
+}
+
 fn vacuum_after_upgrade(env: Arc<LmdbEnv>, path: &Path) -> anyhow::Result<()> {
     // Vacuum the database. This is not a required step and may actually fail if there isn't enough storage space.
     let mut vacuum_path = path.to_owned();
@@ -395,6 +481,7 @@ fn backup_file_path(source_path: &Path) -> anyhow::Result<PathBuf> {
 mod tests {
     use super::*;
     use crate::TestDbFile;
+    use rsnano_core::{AccountInfo, PublicKey};
 
     #[test]
     fn create_store() -> anyhow::Result<()> {
@@ -424,7 +511,59 @@ mod tests {
         let file = TestDbFile::random();
         let store = LmdbStore::open(&file.path).build().unwrap();
         let txn = store.tx_begin_read();
-        assert_eq!(store.version.get(&txn), Some(STORE_VERSION_MINIMUM));
+        assert_eq!(store.version.get(&txn), Some(STORE_VERSION_CURRENT));
+    }
+
+    #[test]
+    fn upgrade_v24_builds_delegators_index() -> anyhow::Result<()> {
+        let file = TestDbFile::random();
+        let account = Account::from(1);
+        let representative = PublicKey::from(2);
+        {
+            let env = Arc::new(LmdbEnv::new(&file.path)?);
+            let account_store = LmdbAccountStore::new(env.clone())?;
+            let mut txn = env.tx_begin_write();
+            account_store.put(
+                &mut txn,
+                &account,
+                &AccountInfo {
+                    representative,
+                    ..AccountInfo::new_test_instance()
+                },
+            );
+        }
+        set_store_version(&file, 24)?;
+
+        let store = LmdbStore::open(&file.path).build()?;
+
+        let txn = store.tx_begin_read();
+        assert_eq!(store.version.get(&txn), Some(STORE_VERSION_CURRENT));
+        assert!(store.delegators.exists(&txn, &representative, &account));
+        Ok(())
+    }
+
+    #[test]
+    fn upgrade_v25_rebuilds_account_history_index() -> anyhow::Result<()> {
+        let file = TestDbFile::random();
+        {
+            let env = Arc::new(LmdbEnv::new(&file.path)?);
+            let account_history = LmdbAccountHistoryStore::new(env.clone())?;
+            let mut txn = env.tx_begin_write();
+            account_history.set_state(&mut txn, AccountHistoryIndexState::Complete);
+        }
+        set_store_version(&file, 25)?;
+
+        let store = LmdbStore::open(&file.path).build()?;
+
+        let txn = store.tx_begin_read();
+        assert_eq!(store.version.get(&txn), Some(STORE_VERSION_CURRENT));
+        assert_eq!(
+            store.account_history.state(&txn),
+            AccountHistoryIndexState::Building {
+                next_account: Account::zero()
+            }
+        );
+        Ok(())
     }
 
     fn assert_upgrade_fails(path: &Path, error_msg: &str) {
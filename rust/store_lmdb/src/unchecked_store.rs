@@ -0,0 +1,306 @@
+use crate::{LmdbDatabase, LmdbEnv, LmdbWriteTransaction, Transaction};
+use lmdb::{DatabaseFlags, WriteFlags};
+use lmdb_sys::{MDB_FIRST, MDB_LAST, MDB_NEXT, MDB_SET_RANGE};
+use rsnano_core::{
+    utils::{BufferReader, Deserialize},
+    BlockHash, UncheckedInfo, UncheckedKey,
+};
+use std::sync::Arc;
+
+/// Blocks with missing dependencies, persisted so that they survive a node restart.
+/// The sequence number keeps track of the insertion order, so that the oldest entries can be evicted first.
+/// unchecked: nano::block_hash (dependency) ++ nano::block_hash -> u64 (sequence) ++ nano::unchecked_info
+/// unchecked_order: u64 (sequence) -> nano::block_hash (dependency) ++ nano::block_hash
+pub struct LmdbUncheckedStore {
+    _env: Arc<LmdbEnv>,
+    blocks_handle: LmdbDatabase,
+    order_handle: LmdbDatabase,
+}
+
+impl LmdbUncheckedStore {
+    pub fn new(env: Arc<LmdbEnv>) -> anyhow::Result<Self> {
+        let blocks_handle = env
+            .environment
+            .create_db(Some("unchecked"), DatabaseFlags::empty())?;
+        let order_handle = env
+            .environment
+            .create_db(Some("unchecked_order"), DatabaseFlags::empty())?;
+        Ok(Self {
+            _env: env,
+            blocks_handle,
+            order_handle,
+        })
+    }
+
+    pub fn blocks_database(&self) -> LmdbDatabase {
+        self.blocks_handle
+    }
+
+    pub fn order_database(&self) -> LmdbDatabase {
+        self.order_handle
+    }
+
+    /// Returns the number of bytes that were written
+    pub fn put(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        sequence: u64,
+        key: &UncheckedKey,
+        info: &UncheckedInfo,
+    ) -> usize {
+        let key_bytes = key.to_bytes();
+        let mut value = sequence.to_be_bytes().to_vec();
+        value.extend_from_slice(&info.to_bytes());
+        txn.put(self.blocks_handle, &key_bytes, &value, WriteFlags::empty())
+            .unwrap();
+        txn.put(
+            self.order_handle,
+            &sequence.to_be_bytes(),
+            &key_bytes,
+            WriteFlags::empty(),
+        )
+        .unwrap();
+        stored_size(&key_bytes, &value)
+    }
+
+    /// Returns the number of bytes that were freed
+    pub fn del(&self, txn: &mut LmdbWriteTransaction, key: &UncheckedKey) -> Option<usize> {
+        let key_bytes = key.to_bytes();
+        let (sequence, size) = match txn.get(self.blocks_handle, &key_bytes) {
+            Ok(value) => (
+                u64::from_be_bytes(value[..8].try_into().unwrap()),
+                stored_size(&key_bytes, value),
+            ),
+            Err(lmdb::Error::NotFound) => return None,
+            Err(e) => panic!("Could not load unchecked block: {:?}", e),
+        };
+        txn.delete(self.blocks_handle, &key_bytes, None).unwrap();
+        match txn.delete(self.order_handle, &sequence.to_be_bytes(), None) {
+            Ok(()) | Err(lmdb::Error::NotFound) => {}
+            Err(e) => panic!("Could not delete unchecked order entry: {:?}", e),
+        }
+        Some(size)
+    }
+
+    pub fn get(&self, txn: &dyn Transaction, key: &UncheckedKey) -> Option<UncheckedInfo> {
+        match txn.get(self.blocks_handle, &key.to_bytes()) {
+            Ok(value) => Some(deserialize_info(value)),
+            Err(lmdb::Error::NotFound) => None,
+            Err(e) => panic!("Could not load unchecked block: {:?}", e),
+        }
+    }
+
+    pub fn exists(&self, txn: &dyn Transaction, key: &UncheckedKey) -> bool {
+        txn.exists(self.blocks_handle, &key.to_bytes())
+    }
+
+    /// All entries which wait for the given dependency, ordered by block hash
+    pub fn iter_dependency<'txn>(
+        &self,
+        txn: &'txn dyn Transaction,
+        dependency: &BlockHash,
+    ) -> impl Iterator<Item = (UncheckedKey, UncheckedInfo)> + 'txn {
+        let cursor = txn
+            .open_ro_cursor(self.blocks_handle)
+            .expect("Could not read unchecked database");
+        let dependency = *dependency;
+        let start_key = UncheckedKey::new(dependency, BlockHash::zero()).to_bytes();
+        let mut initialized = false;
+
+        std::iter::from_fn(move || {
+            let result = if initialized {
+                cursor.get(None, None, MDB_NEXT)
+            } else {
+                initialized = true;
+                cursor.get(Some(&start_key), None, MDB_SET_RANGE)
+            };
+            match result {
+                Ok((Some(key), value)) if key[..32] == *dependency.as_bytes() => {
+                    Some((deserialize_key(key), deserialize_info(value)))
+                }
+                Ok(_) | Err(lmdb::Error::NotFound) => None,
+                Err(e) => panic!("Could not read unchecked blocks: {:?}", e),
+            }
+        })
+    }
+
+    /// The keys of all entries, oldest first
+    pub fn iter_oldest<'txn>(
+        &self,
+        txn: &'txn dyn Transaction,
+    ) -> impl Iterator<Item = (u64, UncheckedKey)> + 'txn {
+        let cursor = txn
+            .open_ro_cursor(self.order_handle)
+            .expect("Could not read unchecked order database");
+        let mut initialized = false;
+
+        std::iter::from_fn(move || {
+            let result = if initialized {
+                cursor.get(None, None, MDB_NEXT)
+            } else {
+                initialized = true;
+                cursor.get(None, None, MDB_FIRST)
+            };
+            match result {
+                Ok((Some(key), value)) => Some((
+                    u64::from_be_bytes(key.try_into().unwrap()),
+                    deserialize_key(value),
+                )),
+                Ok(_) | Err(lmdb::Error::NotFound) => None,
+                Err(e) => panic!("Could not read unchecked order: {:?}", e),
+            }
+        })
+    }
+
+    pub fn last_sequence(&self, txn: &dyn Transaction) -> Option<u64> {
+        let cursor = txn
+            .open_ro_cursor(self.order_handle)
+            .expect("Could not read unchecked order database");
+        match cursor.get(None, None, MDB_LAST) {
+            Ok((Some(key), _)) => Some(u64::from_be_bytes(key.try_into().unwrap())),
+            Ok(_) | Err(lmdb::Error::NotFound) => None,
+            Err(e) => panic!("Could not read unchecked order: {:?}", e),
+        }
+    }
+
+    /// The number of bytes which are occupied by the stored entries
+    pub fn size(&self, txn: &dyn Transaction) -> u64 {
+        let cursor = txn
+            .open_ro_cursor(self.blocks_handle)
+            .expect("Could not read unchecked database");
+        let mut size = 0;
+        let mut result = cursor.get(None, None, MDB_FIRST);
+        loop {
+            match result {
+                Ok((Some(key), value)) => size += stored_size(key, value) as u64,
+                Ok(_) | Err(lmdb::Error::NotFound) => break,
+                Err(e) => panic!("Could not read unchecked blocks: {:?}", e),
+            }
+            result = cursor.get(None, None, MDB_NEXT);
+        }
+        size
+    }
+
+    pub fn count(&self, txn: &dyn Transaction) -> u64 {
+        txn.count(self.blocks_handle)
+    }
+
+    pub fn clear(&self, txn: &mut LmdbWriteTransaction) {
+        txn.clear_db(self.blocks_handle).unwrap();
+        txn.clear_db(self.order_handle).unwrap();
+    }
+}
+
+/// Both tables are accounted for
+fn stored_size(key: &[u8], value: &[u8]) -> usize {
+    2 * key.len() + value.len() + std::mem::size_of::<u64>()
+}
+
+fn deserialize_key(bytes: &[u8]) -> UncheckedKey {
+    let mut stream = BufferReader::new(bytes);
+    UncheckedKey::deserialize(&mut stream).unwrap()
+}
+
+fn deserialize_info(value: &[u8]) -> UncheckedInfo {
+    let mut stream = BufferReader::new(&value[8..]);
+    UncheckedInfo::deserialize(&mut stream).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestLmdbEnv;
+    use rsnano_core::BlockEnum;
+
+    struct Fixture {
+        env: TestLmdbEnv,
+        store: LmdbUncheckedStore,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let env = TestLmdbEnv::new();
+            let store = LmdbUncheckedStore::new(env.env()).unwrap();
+            Self { env, store }
+        }
+    }
+
+    fn test_info(key: u64) -> UncheckedInfo {
+        UncheckedInfo::new(Arc::new(BlockEnum::new_test_instance_with_key(key)))
+    }
+
+    #[test]
+    fn empty_store() {
+        let fixture = Fixture::new();
+        let txn = fixture.env.tx_begin_read();
+        assert_eq!(fixture.store.count(&txn), 0);
+        assert_eq!(fixture.store.size(&txn), 0);
+        assert_eq!(fixture.store.last_sequence(&txn), None);
+    }
+
+    #[test]
+    fn put_and_get() {
+        let fixture = Fixture::new();
+        let key = UncheckedKey::new(BlockHash::from(1), BlockHash::from(2));
+        let info = test_info(1);
+        let mut txn = fixture.env.tx_begin_write();
+
+        let written = fixture.store.put(&mut txn, 7, &key, &info);
+
+        assert!(fixture.store.exists(&txn, &key));
+        assert_eq!(fixture.store.get(&txn, &key).unwrap().block, info.block);
+        assert_eq!(fixture.store.size(&txn), written as u64);
+        assert_eq!(fixture.store.last_sequence(&txn), Some(7));
+    }
+
+    #[test]
+    fn del_frees_both_tables() {
+        let fixture = Fixture::new();
+        let key = UncheckedKey::new(BlockHash::from(1), BlockHash::from(2));
+        let mut txn = fixture.env.tx_begin_write();
+        let written = fixture.store.put(&mut txn, 1, &key, &test_info(1));
+
+        assert_eq!(fixture.store.del(&mut txn, &key), Some(written));
+        assert_eq!(fixture.store.del(&mut txn, &key), None);
+        assert_eq!(fixture.store.count(&txn), 0);
+        assert_eq!(fixture.store.iter_oldest(&txn).count(), 0);
+    }
+
+    #[test]
+    fn iter_dependency() {
+        let fixture = Fixture::new();
+        let mut txn = fixture.env.tx_begin_write();
+        let key1 = UncheckedKey::new(BlockHash::from(1), BlockHash::from(5));
+        let key2 = UncheckedKey::new(BlockHash::from(2), BlockHash::from(3));
+        let key3 = UncheckedKey::new(BlockHash::from(2), BlockHash::from(4));
+        let key4 = UncheckedKey::new(BlockHash::from(3), BlockHash::from(1));
+        for (i, key) in [&key1, &key2, &key3, &key4].into_iter().enumerate() {
+            fixture
+                .store
+                .put(&mut txn, i as u64, key, &test_info(i as u64));
+        }
+
+        let keys: Vec<_> = fixture
+            .store
+            .iter_dependency(&txn, &BlockHash::from(2))
+            .map(|(key, _)| key)
+            .collect();
+
+        assert_eq!(keys, vec![key2, key3]);
+    }
+
+    #[test]
+    fn iter_oldest_first() {
+        let fixture = Fixture::new();
+        let mut txn = fixture.env.tx_begin_write();
+        let key1 = UncheckedKey::new(BlockHash::from(3), BlockHash::from(1));
+        let key2 = UncheckedKey::new(BlockHash::from(1), BlockHash::from(2));
+        fixture.store.put(&mut txn, 256, &key2, &test_info(2));
+        fixture.store.put(&mut txn, 1, &key1, &test_info(1));
+
+        assert_eq!(
+            fixture.store.iter_oldest(&txn).collect::<Vec<_>>(),
+            vec![(1, key1), (256, key2)]
+        );
+    }
+}
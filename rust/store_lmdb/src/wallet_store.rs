@@ -4,12 +4,12 @@ use crate::{
 use anyhow::bail;
 use lmdb::{DatabaseFlags, WriteFlags};
 use rsnano_core::{
-    deterministic_key,
+    bip44_account_node, deterministic_key,
     utils::{
         BufferReader, BufferWriter, Deserialize, FixedSizeSerialize, MutStreamAdapter, Serialize,
         Stream, StreamExt,
     },
-    Account, KeyDerivationFunction, PublicKey, RawKey,
+    Account, KeyDerivationFunction, Mnemonic, PublicKey, RawKey, Slip10Node,
 };
 use std::io::Write;
 use std::{
@@ -80,6 +80,7 @@ pub enum KeyType {
     Unknown,
     Adhoc,
     Deterministic,
+    Bip44,
 }
 
 pub type WalletIterator<'txn> = BinaryDbIterator<'txn, PublicKey, WalletValue>;
@@ -91,7 +92,7 @@ pub struct LmdbWalletStore {
 }
 
 impl LmdbWalletStore {
-    pub const VERSION_CURRENT: u32 = 4;
+    pub const VERSION_CURRENT: u32 = 5;
     pub fn new(
         fanout: usize,
         kdf: KeyDerivationFunction,
@@ -157,26 +158,14 @@ impl LmdbWalletStore {
         wallet: &Path,
         json: &str,
     ) -> anyhow::Result<Self> {
-        let store = Self {
-            db_handle: Mutex::new(None),
-            fans: Mutex::new(Fans::new(fanout)),
-            kdf,
-        };
-        store.initialize(txn, wallet)?;
-        let handle = store.db_handle();
-        match txn.get(handle, Self::version_special().as_bytes()) {
-            Ok(_) => panic!("wallet store already initialized"),
-            Err(lmdb::Error::NotFound) => {}
-            Err(e) => panic!("unexpected wallet store error: {:?}", e),
-        }
-
+        let mut entries = Vec::new();
         let json: serde_json::Value = serde_json::from_str(json)?;
         if let serde_json::Value::Object(map) = json {
             for (k, v) in map.iter() {
                 if let serde_json::Value::String(v_str) = v {
                     let key = PublicKey::decode_hex(k)?;
                     let value = RawKey::decode_hex(v_str)?;
-                    store.entry_put_raw(txn, &key, &WalletValue::new(value, 0));
+                    entries.push((key, WalletValue::new(value, 0)));
                 } else {
                     bail!("expected string value");
                 }
@@ -185,11 +174,40 @@ impl LmdbWalletStore {
             bail!("invalid json")
         }
 
+        Self::new_from_entries(fanout, kdf, txn, wallet, &entries)
+    }
+
+    /// Creates a wallet store from raw entries, as returned by `entries`
+    pub fn new_from_entries(
+        fanout: usize,
+        kdf: KeyDerivationFunction,
+        txn: &mut LmdbWriteTransaction,
+        wallet: &Path,
+        entries: &[(PublicKey, WalletValue)],
+    ) -> anyhow::Result<Self> {
+        let store = Self {
+            db_handle: Mutex::new(None),
+            fans: Mutex::new(Fans::new(fanout)),
+            kdf,
+        };
+        store.initialize(txn, wallet)?;
+        let handle = store.db_handle();
+        match txn.get(handle, Self::version_special().as_bytes()) {
+            Ok(_) => bail!("wallet store already initialized"),
+            Err(lmdb::Error::NotFound) => {}
+            Err(e) => bail!("unexpected wallet store error: {:?}", e),
+        }
+
+        for (key, value) in entries {
+            store.entry_put_raw(txn, key, value);
+        }
+
         store.ensure_key_exists(txn, &Self::version_special())?;
         store.ensure_key_exists(txn, &Self::wallet_key_special())?;
         store.ensure_key_exists(txn, &Self::salt_special())?;
         store.ensure_key_exists(txn, &Self::check_special())?;
         store.ensure_key_exists(txn, &Self::representative_special())?;
+        store.upgrade(txn)?;
         let mut guard = store.fans.lock().unwrap();
         guard.password.value_set(RawKey::zero());
         let key = store.entry_get_raw(txn, &Self::wallet_key_special()).key;
@@ -242,10 +260,25 @@ impl LmdbWalletStore {
         PublicKey::from(6)
     }
 
-    pub fn special_count() -> PublicKey {
+    /// Entropy of the BIP39 mnemonic. The `work` field contains the entropy length
+    pub fn mnemonic_special() -> PublicKey {
         PublicKey::from(7)
     }
 
+    /// Private key of the SLIP-10 node m/44'/165'
+    pub fn bip44_key_special() -> PublicKey {
+        PublicKey::from(8)
+    }
+
+    /// Chain code of the SLIP-10 node m/44'/165'
+    pub fn bip44_chain_code_special() -> PublicKey {
+        PublicKey::from(9)
+    }
+
+    pub fn special_count() -> PublicKey {
+        PublicKey::from(10)
+    }
+
     pub fn initialize(&self, txn: &mut LmdbWriteTransaction, path: &Path) -> anyhow::Result<()> {
         let path_str = path
             .as_os_str()
@@ -320,15 +353,108 @@ impl LmdbWalletStore {
         let iv = self.salt(txn).initialization_vector_high();
         let ciphertext = prv.encrypt(&password_l, &iv);
         self.entry_put_raw(txn, &Self::seed_special(), &WalletValue::new(ciphertext, 0));
+        self.bip44_erase(txn);
+        self.deterministic_clear(txn);
+    }
+
+    /// Switches the wallet to BIP44 key derivation. All deterministic keys
+    /// of this wallet are removed
+    pub fn set_mnemonic(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        mnemonic: &Mnemonic,
+        passphrase: &str,
+    ) {
+        let (entropy, len) = mnemonic.to_entropy_array();
+        let mut padded = [0; 32];
+        padded[..len].copy_from_slice(&entropy[..len]);
+        let node = bip44_account_node(mnemonic, passphrase);
+        self.special_put_encrypted(
+            txn,
+            &Self::mnemonic_special(),
+            &RawKey::from_bytes(padded),
+            len as u64,
+        );
+        self.special_put_encrypted(txn, &Self::bip44_key_special(), &node.key, 0);
+        self.special_put_encrypted(txn, &Self::bip44_chain_code_special(), &node.chain_code, 0);
         self.deterministic_clear(txn);
     }
 
+    /// The mnemonic of the wallet, if it was created from one
+    pub fn mnemonic(&self, txn: &dyn Transaction) -> Option<Mnemonic> {
+        let (entropy, len) = self.special_get_encrypted(txn, &Self::mnemonic_special());
+        if len == 0 {
+            return None;
+        }
+        Mnemonic::from_entropy(&entropy.as_bytes()[..len as usize]).ok()
+    }
+
+    pub fn is_bip44(&self, txn: &dyn Transaction) -> bool {
+        self.entry_get_raw(txn, &Self::mnemonic_special()).work != 0
+    }
+
+    fn bip44_node(&self, txn: &dyn Transaction) -> Slip10Node {
+        let (key, _) = self.special_get_encrypted(txn, &Self::bip44_key_special());
+        let (chain_code, _) = self.special_get_encrypted(txn, &Self::bip44_chain_code_special());
+        Slip10Node::new(key, chain_code)
+    }
+
+    fn bip44_erase(&self, txn: &mut LmdbWriteTransaction) {
+        for special in [
+            Self::mnemonic_special(),
+            Self::bip44_key_special(),
+            Self::bip44_chain_code_special(),
+        ] {
+            self.entry_put_raw(txn, &special, &WalletValue::new(RawKey::zero(), 0));
+        }
+    }
+
+    fn special_put_encrypted(
+        &self,
+        txn: &mut LmdbWriteTransaction,
+        special: &PublicKey,
+        value: &RawKey,
+        work: u64,
+    ) {
+        let wallet_key = self.wallet_key(txn);
+        let ciphertext = value.encrypt(&wallet_key, &special.initialization_vector());
+        self.entry_put_raw(txn, special, &WalletValue::new(ciphertext, work));
+    }
+
+    fn special_get_encrypted(&self, txn: &dyn Transaction, special: &PublicKey) -> (RawKey, u64) {
+        let value = self.entry_get_raw(txn, special);
+        let wallet_key = self.wallet_key(txn);
+        let plain = value
+            .key
+            .decrypt(&wallet_key, &special.initialization_vector());
+        (plain, value.work)
+    }
+
+    /// Derives the key at `index`, either from the seed or via
+    /// the BIP44 path m/44'/165'/index' if the wallet uses a mnemonic
     pub fn deterministic_key(&self, txn: &dyn Transaction, index: u32) -> RawKey {
         debug_assert!(self.valid_password(txn));
+        if self.is_bip44(txn) {
+            self.bip44_key(txn, index)
+        } else {
+            self.seed_key(txn, index)
+        }
+    }
+
+    fn seed_key(&self, txn: &dyn Transaction, index: u32) -> RawKey {
         let seed = self.seed(txn);
         deterministic_key(&seed, index)
     }
 
+    fn bip44_key(&self, txn: &dyn Transaction, index: u32) -> RawKey {
+        self.bip44_node(txn).derive(index).key
+    }
+
+    fn deterministic_marker(&self, txn: &dyn Transaction, index: u32) -> RawKey {
+        let key_type = if self.is_bip44(txn) { 2u64 } else { 1u64 };
+        ((key_type << 32) | index as u64).into()
+    }
+
     pub fn deterministic_index_get(&self, txn: &dyn Transaction) -> u32 {
         let value = self.entry_get_raw(txn, &Self::deterministic_index_special());
         value.key.number().low_u32()
@@ -357,7 +483,7 @@ impl LmdbWalletStore {
         self.check(txn) == check
     }
 
-    pub fn derive_key(&self, txn: &dyn Transaction, password: &str) -> RawKey {
+    pub fn derive_key(&self, txn: &dyn Transaction, password: &str) -> anyhow::Result<RawKey> {
         let salt = self.salt(txn);
         self.kdf.hash_password(password, salt.as_bytes())
     }
@@ -365,7 +491,7 @@ impl LmdbWalletStore {
     pub fn rekey(&self, txn: &mut LmdbWriteTransaction, password: &str) -> anyhow::Result<()> {
         let mut guard = self.fans.lock().unwrap();
         if self.valid_password_locked(&guard, txn) {
-            let password_new = self.derive_key(txn, password);
+            let password_new = self.derive_key(txn, password)?;
             let wallet_key = self.wallet_key_locked(&guard, txn);
             guard.password.value_set(password_new);
             let iv = self.salt(txn).initialization_vector_low();
@@ -434,6 +560,8 @@ impl LmdbWalletStore {
             KeyType::Adhoc
         } else if (number >> 32).low_u32() == 1 {
             KeyType::Deterministic
+        } else if (number >> 32).low_u32() == 2 {
+            KeyType::Bip44
         } else {
             KeyType::Unknown
         }
@@ -444,7 +572,7 @@ impl LmdbWalletStore {
             let mut it = self.begin(txn);
             while let Some((&account, value)) = it.current() {
                 match Self::key_type(value) {
-                    KeyType::Deterministic => {
+                    KeyType::Deterministic | KeyType::Bip44 => {
                         drop(it);
                         self.erase(txn, &account);
                         it = self.begin_at_key(txn, &account);
@@ -475,10 +603,8 @@ impl LmdbWalletStore {
             result = PublicKey::try_from(&prv).unwrap();
         }
 
-        let mut marker = 1u64;
-        marker <<= 32;
-        marker |= index as u64;
-        self.entry_put_raw(txn, &result, &WalletValue::new(marker.into(), 0));
+        let marker = self.deterministic_marker(txn, index);
+        self.entry_put_raw(txn, &result, &WalletValue::new(marker, 0));
         index += 1;
         self.deterministic_index_set(txn, index);
         result
@@ -487,10 +613,8 @@ impl LmdbWalletStore {
     pub fn deterministic_insert_at(&self, txn: &mut LmdbWriteTransaction, index: u32) -> PublicKey {
         let prv = self.deterministic_key(txn, index);
         let result = PublicKey::try_from(&prv).unwrap();
-        let mut marker = 1u64;
-        marker <<= 32;
-        marker |= index as u64;
-        self.entry_put_raw(txn, &result, &WalletValue::new(marker.into(), 0));
+        let marker = self.deterministic_marker(txn, index);
+        self.entry_put_raw(txn, &result, &WalletValue::new(marker, 0));
         result
     }
 
@@ -499,15 +623,40 @@ impl LmdbWalletStore {
         value.key.as_bytes()[31] as u32
     }
 
+    /// Upgrades the wallet to the current version. Version 5 reserved the keys 7-9
+    /// for the mnemonic specials, so entries which version 4 stored there are dropped.
+    pub fn upgrade(&self, txn: &mut LmdbWriteTransaction) -> anyhow::Result<()> {
+        match self.version(txn) {
+            Self::VERSION_CURRENT => {}
+            4 => {
+                for key in [
+                    Self::mnemonic_special(),
+                    Self::bip44_key_special(),
+                    Self::bip44_chain_code_special(),
+                ] {
+                    match txn.delete(self.db_handle(), key.as_bytes(), None) {
+                        Ok(()) | Err(lmdb::Error::NotFound) => {}
+                        Err(e) => return Err(e.into()),
+                    }
+                }
+                self.version_put(txn, Self::VERSION_CURRENT);
+            }
+            version => bail!("unsupported wallet version {}", version),
+        }
+        Ok(())
+    }
+
     pub fn attempt_password(&self, txn: &dyn Transaction, password: &str) -> bool {
         let is_valid = {
             let mut guard = self.fans.lock().unwrap();
-            let password_key = self.derive_key(txn, password);
+            let Ok(password_key) = self.derive_key(txn, password) else {
+                return false;
+            };
             guard.password.value_set(password_key);
             self.valid_password_locked(&guard, txn)
         };
 
-        if is_valid && self.version(txn) != 4 {
+        if is_valid && self.version(txn) != Self::VERSION_CURRENT {
             panic!("invalid wallet store version!");
         }
 
@@ -578,7 +727,11 @@ impl LmdbWalletStore {
         let prv = match Self::key_type(&value) {
             KeyType::Deterministic => {
                 let index = value.key.number().low_u32();
-                self.deterministic_key(txn, index)
+                self.seed_key(txn, index)
+            }
+            KeyType::Bip44 => {
+                let index = value.key.number().low_u32();
+                self.bip44_key(txn, index)
             }
             KeyType::Adhoc => {
                 // Ad-hoc keys
@@ -597,6 +750,25 @@ impl LmdbWalletStore {
         Ok(prv)
     }
 
+    /// All raw entries including the special entries.
+    /// Private keys, the seed and the mnemonic stay encrypted with the wallet key
+    pub fn entries(&self, txn: &dyn Transaction) -> Vec<(PublicKey, WalletValue)> {
+        let mut result = Vec::new();
+        let mut it = LmdbIteratorImpl::new_iterator::<PublicKey, WalletValue>(
+            txn,
+            self.db_handle(),
+            None,
+            true,
+        );
+
+        while let Some((k, v)) = it.current() {
+            result.push((*k, WalletValue::new(v.key, v.work)));
+            it.next();
+        }
+
+        result
+    }
+
     pub fn serialize_json(&self, txn: &dyn Transaction) -> String {
         let mut map = serde_json::Map::new();
         let mut it = LmdbIteratorImpl::new_iterator::<Account, WalletValue>(
@@ -717,3 +889,81 @@ impl LmdbWalletStore {
         self.db_handle.lock().unwrap().is_some()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestLmdbEnv;
+
+    #[test]
+    fn upgrade_version_4() {
+        let env = TestLmdbEnv::new();
+        let mut txn = env.tx_begin_write();
+        let store = LmdbWalletStore::new(
+            1,
+            KeyDerivationFunction::new(8),
+            &mut txn,
+            &PublicKey::from(42),
+            Path::new("0"),
+        )
+        .unwrap();
+        store.version_put(&mut txn, 4);
+        store.entry_put_raw(
+            &mut txn,
+            &LmdbWalletStore::mnemonic_special(),
+            &WalletValue::new(RawKey::from(1), 0),
+        );
+
+        store.upgrade(&mut txn).unwrap();
+
+        assert_eq!(store.version(&txn), LmdbWalletStore::VERSION_CURRENT);
+        assert!(txn
+            .get(
+                store.db_handle(),
+                LmdbWalletStore::mnemonic_special().as_bytes()
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn reject_unknown_version() {
+        let env = TestLmdbEnv::new();
+        let mut txn = env.tx_begin_write();
+        let store = LmdbWalletStore::new(
+            1,
+            KeyDerivationFunction::new(8),
+            &mut txn,
+            &PublicKey::from(42),
+            Path::new("0"),
+        )
+        .unwrap();
+        store.version_put(&mut txn, 3);
+
+        assert!(store.upgrade(&mut txn).is_err());
+    }
+
+    #[test]
+    fn reject_import_into_existing_store() {
+        let env = TestLmdbEnv::new();
+        let mut txn = env.tx_begin_write();
+        let store = LmdbWalletStore::new(
+            1,
+            KeyDerivationFunction::new(8),
+            &mut txn,
+            &PublicKey::from(42),
+            Path::new("0"),
+        )
+        .unwrap();
+        let entries = store.entries(&txn);
+
+        let result = LmdbWalletStore::new_from_entries(
+            1,
+            KeyDerivationFunction::new(8),
+            &mut txn,
+            Path::new("0"),
+            &entries,
+        );
+
+        assert!(result.is_err());
+    }
+}
@@ -8,6 +8,7 @@ use std::{
     collections::{HashMap, HashSet},
     net::SocketAddrV6,
     sync::{Arc, RwLock},
+    time::Duration,
 };
 
 pub(crate) struct Channel {
@@ -17,6 +18,9 @@ pub(crate) struct Channel {
     pub telemetry: Option<TelemetryData>,
     pub rep_weight: Amount,
     pub rep_state: RepState,
+    /// Smoothed round trip time
+    pub round_trip_time: Option<Duration>,
+    pub round_trip_time_p90: Option<Duration>,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -60,6 +64,9 @@ impl Channels {
             for info in channels {
                 if let Some(channel) = self.channel_map.get_mut(&info.channel_id()) {
                     channel.telemetry = telemetries.get(&channel.remote_addr).cloned();
+                    let rtt = info.round_trip_times();
+                    channel.round_trip_time = rtt.ewma();
+                    channel.round_trip_time_p90 = rtt.percentile(90);
                     pending.remove(&info.channel_id());
                 } else {
                     self.channel_map.insert(
@@ -71,6 +78,8 @@ impl Channels {
                             telemetry: None,
                             rep_weight: Amount::zero(),
                             rep_state: RepState::NoRep,
+                            round_trip_time: None,
+                            round_trip_time_p90: None,
                         },
                     );
                     inserted = true;
@@ -27,8 +27,13 @@ impl<'a> ChannelsViewModel<'a> {
             bandwidth_cap: String::new(),
             rep_weight: channel.rep_weight.format_balance(0),
             rep_state: channel.rep_state,
+            round_trip_time: String::new(),
         };
 
+        if let (Some(avg), Some(p90)) = (channel.round_trip_time, channel.round_trip_time_p90) {
+            result.round_trip_time = format!("{}ms (p90 {}ms)", avg.as_millis(), p90.as_millis());
+        }
+
         if let Some(telemetry) = &channel.telemetry {
             result.block_count = telemetry.block_count.to_formatted_string(&Locale::en);
             result.cemented_count = telemetry.cemented_count.to_formatted_string(&Locale::en);
@@ -74,4 +79,5 @@ pub(crate) struct ChannelViewModel {
     pub bandwidth_cap: String,
     pub rep_weight: String,
     pub rep_state: RepState,
+    pub round_trip_time: String,
 }
@@ -24,6 +24,7 @@ impl<'a> ChannelsView<'a> {
             .sense(Sense::click())
             .column(Column::auto())
             .column(Column::auto()) // rep state
+            .column(Column::auto()) // round trip time
             .column(Column::remainder())
             .header(20.0, |mut header| {
                 header.col(|ui| {
@@ -32,6 +33,9 @@ impl<'a> ChannelsView<'a> {
                 header.col(|ui| {
                     ui.strong("Rep");
                 });
+                header.col(|ui| {
+                    ui.strong("RTT");
+                });
                 header.col(|ui| {
                     ui.strong("Remote Addr");
                 });
@@ -50,6 +54,9 @@ impl<'a> ChannelsView<'a> {
                     row.col(|ui| {
                         show_rep_state(ui, row_model.rep_state);
                     });
+                    row.col(|ui| {
+                        ui.add(Label::new(row_model.round_trip_time).selectable(false));
+                    });
                     row.col(|ui| {
                         ui.add(Label::new(row_model.remote_addr).selectable(false));
                     });
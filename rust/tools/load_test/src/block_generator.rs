@@ -0,0 +1,230 @@
+use crate::BlockKind;
+use rand::{rngs::ThreadRng, seq::IteratorRandom, Rng};
+use rsnano_core::{
+    Account, Amount, BlockEnum, BlockHash, KeyPair, PublicKey, StateBlock, DEV_GENESIS_KEY,
+};
+use rsnano_ledger::{DEV_GENESIS_ACCOUNT, DEV_GENESIS_PUB_KEY};
+use rsnano_node::Node;
+use std::{collections::VecDeque, sync::Arc};
+
+/// Amount of raw every load account gets from genesis before the load starts
+const FUNDING_AMOUNT: u128 = 1_000_000;
+
+/// Blocks that have to be submitted together, for example both sides of a fork
+pub struct GeneratedBlocks {
+    pub kind: BlockKind,
+    pub blocks: Vec<BlockEnum>,
+}
+
+struct LoadAccount {
+    key: KeyPair,
+    frontier: BlockHash,
+    balance: Amount,
+    representative: PublicKey,
+    receivable: VecDeque<(BlockHash, Amount)>,
+    /// Disabled accounts are not used anymore, because their frontier is unknown.
+    /// That happens after a fork or when a block could not be submitted
+    disabled: bool,
+}
+
+impl LoadAccount {
+    fn account(&self) -> Account {
+        self.key.account()
+    }
+}
+
+/// Generates valid blocks for a set of accounts by tracking their frontiers locally
+pub struct BlockGenerator {
+    node: Arc<Node>,
+    accounts: Vec<LoadAccount>,
+    rng: ThreadRng,
+}
+
+impl BlockGenerator {
+    pub fn new(node: Arc<Node>, account_count: usize) -> Self {
+        let accounts = (0..account_count)
+            .map(|_| {
+                let key = KeyPair::new();
+                LoadAccount {
+                    representative: *DEV_GENESIS_PUB_KEY,
+                    key,
+                    frontier: BlockHash::zero(),
+                    balance: Amount::zero(),
+                    receivable: VecDeque::new(),
+                    disabled: false,
+                }
+            })
+            .collect();
+
+        Self {
+            node,
+            accounts,
+            rng: rand::thread_rng(),
+        }
+    }
+
+    /// Sends funds from genesis to every load account and opens them
+    pub fn funding_blocks(&mut self) -> Vec<BlockEnum> {
+        let mut genesis_frontier = self.node.latest(&DEV_GENESIS_ACCOUNT);
+        let mut genesis_balance = self.node.balance(&DEV_GENESIS_ACCOUNT);
+        let amount = Amount::raw(FUNDING_AMOUNT);
+        let mut blocks = Vec::with_capacity(self.accounts.len() * 2);
+
+        for account in &mut self.accounts {
+            genesis_balance = genesis_balance - amount;
+            let send = BlockEnum::State(StateBlock::new(
+                *DEV_GENESIS_ACCOUNT,
+                genesis_frontier,
+                *DEV_GENESIS_PUB_KEY,
+                genesis_balance,
+                account.account().into(),
+                &DEV_GENESIS_KEY,
+                self.node.work_generate_dev(genesis_frontier.into()),
+            ));
+            genesis_frontier = send.hash();
+
+            let open = BlockEnum::State(StateBlock::new(
+                account.account(),
+                BlockHash::zero(),
+                account.representative,
+                amount,
+                send.hash().into(),
+                &account.key,
+                self.node.work_generate_dev(account.key.public_key().into()),
+            ));
+            account.frontier = open.hash();
+            account.balance = amount;
+
+            blocks.push(send);
+            blocks.push(open);
+        }
+
+        blocks
+    }
+
+    /// Returns None if no account is in a state that allows creating the requested kind of block
+    pub fn next(&mut self, kind: BlockKind) -> Option<GeneratedBlocks> {
+        let blocks = match kind {
+            BlockKind::Send => vec![self.send()?],
+            BlockKind::Receive => vec![self.receive()?],
+            BlockKind::Change => vec![self.change()?],
+            BlockKind::Fork => self.fork()?,
+        };
+        Some(GeneratedBlocks { kind, blocks })
+    }
+
+    /// Stops using the account of the given block, because its chain is in an unknown state.
+    /// Also forgets the receivable the block would have created
+    pub fn disable(&mut self, block: &BlockEnum) {
+        let account = block.account_field().unwrap_or_default();
+        let hash = block.hash();
+        for acc in &mut self.accounts {
+            if acc.account() == account {
+                acc.disabled = true;
+            }
+            acc.receivable.retain(|(source, _)| *source != hash);
+        }
+    }
+
+    pub fn disabled_accounts(&self) -> usize {
+        self.accounts.iter().filter(|a| a.disabled).count()
+    }
+
+    fn send(&mut self) -> Option<BlockEnum> {
+        let source = self.pick_account(|a| !a.balance.is_zero())?;
+        let destination = self.rng.gen_range(0..self.accounts.len());
+        let block = self.create_send(source, destination);
+        self.apply(source, &block);
+        self.accounts[destination]
+            .receivable
+            .push_back((block.hash(), Amount::raw(1)));
+        Some(block)
+    }
+
+    fn receive(&mut self) -> Option<BlockEnum> {
+        let index = self.pick_account(|a| !a.receivable.is_empty())?;
+        let account = &mut self.accounts[index];
+        let (source, amount) = account.receivable.pop_front().unwrap();
+        let block = BlockEnum::State(StateBlock::new(
+            account.account(),
+            account.frontier,
+            account.representative,
+            account.balance + amount,
+            source.into(),
+            &account.key,
+            self.node.work_generate_dev(account.frontier.into()),
+        ));
+        self.apply(index, &block);
+        Some(block)
+    }
+
+    fn change(&mut self) -> Option<BlockEnum> {
+        let index = self.pick_account(|_| true)?;
+        let representative = self.accounts[self.rng.gen_range(0..self.accounts.len())]
+            .key
+            .public_key();
+        let account = &mut self.accounts[index];
+        account.representative = representative;
+        let block = BlockEnum::State(StateBlock::new(
+            account.account(),
+            account.frontier,
+            account.representative,
+            account.balance,
+            Default::default(),
+            &account.key,
+            self.node.work_generate_dev(account.frontier.into()),
+        ));
+        self.apply(index, &block);
+        Some(block)
+    }
+
+    /// Two sends with the same previous block, but different destinations
+    fn fork(&mut self) -> Option<Vec<BlockEnum>> {
+        if self.accounts.len() < 3 {
+            return None;
+        }
+        let source = self.pick_account(|a| !a.balance.is_zero())?;
+        let (dest1, dest2) = loop {
+            let dest1 = self.rng.gen_range(0..self.accounts.len());
+            let dest2 = self.rng.gen_range(0..self.accounts.len());
+            if dest1 != dest2 {
+                break (dest1, dest2);
+            }
+        };
+        let blocks = vec![
+            self.create_send(source, dest1),
+            self.create_send(source, dest2),
+        ];
+        // We don't know which side of the fork wins
+        self.accounts[source].disabled = true;
+        Some(blocks)
+    }
+
+    fn create_send(&self, source: usize, destination: usize) -> BlockEnum {
+        let account = &self.accounts[source];
+        BlockEnum::State(StateBlock::new(
+            account.account(),
+            account.frontier,
+            account.representative,
+            account.balance - Amount::raw(1),
+            self.accounts[destination].account().into(),
+            &account.key,
+            self.node.work_generate_dev(account.frontier.into()),
+        ))
+    }
+
+    fn apply(&mut self, index: usize, block: &BlockEnum) {
+        let account = &mut self.accounts[index];
+        account.frontier = block.hash();
+        account.balance = block.balance();
+    }
+
+    fn pick_account(&mut self, predicate: impl Fn(&LoadAccount) -> bool) -> Option<usize> {
+        self.accounts
+            .iter()
+            .enumerate()
+            .filter(|(_, a)| !a.disabled && predicate(a))
+            .map(|(i, _)| i)
+            .choose(&mut self.rng)
+    }
+}
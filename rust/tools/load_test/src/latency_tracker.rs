@@ -0,0 +1,177 @@
+use crate::BlockKind;
+use rsnano_core::BlockHash;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+struct BlockTiming {
+    kind: BlockKind,
+    submitted: Instant,
+    /// Number of nodes that have added the block to their ledger
+    processed_by: usize,
+    published: Option<Duration>,
+    confirmed: Option<Duration>,
+}
+
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KindCounts {
+    pub submitted: usize,
+    pub published: usize,
+    pub confirmed: usize,
+}
+
+/// Measures for every submitted block how long it took until all nodes had
+/// it in their ledgers (time-to-publish) and until it was cemented on the
+/// primary node (time-to-confirm)
+pub struct LatencyTracker {
+    node_count: usize,
+    blocks: Mutex<HashMap<BlockHash, BlockTiming>>,
+}
+
+impl LatencyTracker {
+    pub fn new(node_count: usize) -> Self {
+        Self {
+            node_count,
+            blocks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn submitted(&self, hash: BlockHash, kind: BlockKind) {
+        self.blocks.lock().unwrap().insert(
+            hash,
+            BlockTiming {
+                kind,
+                submitted: Instant::now(),
+                processed_by: 0,
+                published: None,
+                confirmed: None,
+            },
+        );
+    }
+
+    /// Forgets a block that could not be submitted
+    pub fn remove(&self, hash: &BlockHash) {
+        self.blocks.lock().unwrap().remove(hash);
+    }
+
+    /// A node added the block to its ledger
+    pub fn processed(&self, hash: &BlockHash) {
+        let mut blocks = self.blocks.lock().unwrap();
+        if let Some(timing) = blocks.get_mut(hash) {
+            timing.processed_by += 1;
+            if timing.processed_by == self.node_count {
+                timing.published = Some(timing.submitted.elapsed());
+            }
+        }
+    }
+
+    pub fn cemented(&self, hash: &BlockHash) {
+        let mut blocks = self.blocks.lock().unwrap();
+        if let Some(timing) = blocks.get_mut(hash) {
+            if timing.confirmed.is_none() {
+                timing.confirmed = Some(timing.submitted.elapsed());
+            }
+        }
+    }
+
+    /// Number of submitted blocks that are neither confirmed nor lost a fork
+    pub fn unconfirmed(&self) -> usize {
+        self.blocks
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|t| t.confirmed.is_none() && t.kind != BlockKind::Fork)
+            .count()
+    }
+
+    pub fn publish_times(&self) -> Vec<Duration> {
+        self.blocks
+            .lock()
+            .unwrap()
+            .values()
+            .filter_map(|t| t.published)
+            .collect()
+    }
+
+    pub fn confirm_times(&self) -> Vec<Duration> {
+        self.blocks
+            .lock()
+            .unwrap()
+            .values()
+            .filter_map(|t| t.confirmed)
+            .collect()
+    }
+
+    pub fn counts_by_kind(&self) -> BTreeMap<BlockKind, KindCounts> {
+        let mut result: BTreeMap<BlockKind, KindCounts> = BTreeMap::new();
+        for timing in self.blocks.lock().unwrap().values() {
+            let counts = result.entry(timing.kind).or_default();
+            counts.submitted += 1;
+            if timing.published.is_some() {
+                counts.published += 1;
+            }
+            if timing.confirmed.is_some() {
+                counts.confirmed += 1;
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn published_when_processed_by_all_nodes() {
+        let tracker = LatencyTracker::new(2);
+        let hash = BlockHash::from(1);
+        tracker.submitted(hash, BlockKind::Send);
+
+        tracker.processed(&hash);
+        assert!(tracker.publish_times().is_empty());
+
+        tracker.processed(&hash);
+        assert_eq!(tracker.publish_times().len(), 1);
+    }
+
+    #[test]
+    fn confirmed() {
+        let tracker = LatencyTracker::new(1);
+        let hash = BlockHash::from(1);
+        tracker.submitted(hash, BlockKind::Send);
+        assert_eq!(tracker.unconfirmed(), 1);
+
+        tracker.cemented(&hash);
+
+        assert_eq!(tracker.unconfirmed(), 0);
+        assert_eq!(tracker.confirm_times().len(), 1);
+        assert_eq!(
+            tracker.counts_by_kind()[&BlockKind::Send],
+            KindCounts {
+                submitted: 1,
+                published: 0,
+                confirmed: 1
+            }
+        );
+    }
+
+    #[test]
+    fn ignore_unknown_blocks() {
+        let tracker = LatencyTracker::new(1);
+        tracker.processed(&BlockHash::from(1));
+        tracker.cemented(&BlockHash::from(1));
+        assert!(tracker.counts_by_kind().is_empty());
+    }
+
+    #[test]
+    fn fork_losers_are_not_waited_for() {
+        let tracker = LatencyTracker::new(1);
+        tracker.submitted(BlockHash::from(1), BlockKind::Fork);
+        tracker.submitted(BlockHash::from(2), BlockKind::Fork);
+        tracker.cemented(&BlockHash::from(1));
+        assert_eq!(tracker.unconfirmed(), 0);
+    }
+}
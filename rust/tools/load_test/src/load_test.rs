@@ -1,105 +1,202 @@
-use crate::{ProgramArgs, TestNode};
+use crate::{
+    BlockGenerator, KindReport, LatencyTracker, Percentiles, Report, ResourceMonitor, Scenario,
+};
 use anyhow::{bail, Result};
-use rsnano_core::Account;
-use rsnano_rpc_messages::AccountInfoDto;
-use std::{collections::HashMap, time::Duration};
-use tokio::time::{sleep, Instant};
+use rsnano_core::DEV_GENESIS_KEY;
+use rsnano_ledger::BlockStatus;
+use rsnano_network::ChannelId;
+use rsnano_node::{block_processing::BlockSource, Node};
+use std::{
+    collections::BTreeMap,
+    sync::Arc,
+    thread::sleep,
+    time::{Duration, Instant},
+};
+use test_helpers::System;
 
+/// The rate never drops below this, so that a ramp starting at 0 makes progress
+const MIN_TPS: f64 = 1.0;
+
+#[derive(Default)]
+struct LoadResult {
+    submitted: usize,
+    dropped: usize,
+    skipped: BTreeMap<String, usize>,
+}
+
+/// Runs a scenario against in-process nodes.
+/// All blocks are submitted to the first node, which also holds the genesis key
+/// and therefore confirms everything.
 pub struct LoadTest {
-    args: ProgramArgs,
-    nodes: Vec<TestNode>,
+    scenario: Scenario,
 }
 
 impl LoadTest {
-    pub fn new(args: ProgramArgs) -> Self {
-        let nodes = Vec::with_capacity(args.node_count);
-        Self { args, nodes }
+    pub fn new(scenario: Scenario) -> Self {
+        Self { scenario }
     }
 
-    pub async fn run(mut self) -> Result<()> {
-        self.start_nodes().await?;
-        self.wait_for_nodes_to_spin_up().await;
-        self.connect_nodes().await?;
-        let expected_account_info = self.create_send_and_receive_blocks().await?;
-        self.wait_for_nodes_to_catch_up(&expected_account_info)
-            .await?;
-        self.stop_nodes().await?;
-        Ok(())
-    }
+    pub fn run(&self) -> Result<Report> {
+        println!(
+            "Starting {} nodes for scenario '{}'...",
+            self.scenario.node_count, self.scenario.name
+        );
+        let mut system = System::new();
+        let nodes: Vec<_> = (0..self.scenario.node_count)
+            .map(|_| system.make_node())
+            .collect();
+        let primary = nodes[0].clone();
+        primary.insert_into_wallet(&DEV_GENESIS_KEY);
 
-    fn primary_node(&self) -> &TestNode {
-        &self.nodes[0]
-    }
+        let mut generator = BlockGenerator::new(primary.clone(), self.scenario.accounts);
+        self.fund_accounts(&nodes, &mut generator)?;
 
-    async fn start_nodes(&mut self) -> Result<()> {
-        for i in 0..self.args.node_count {
-            let mut node = TestNode::new(i)?;
-            println!(
-                "starting node, port {}, data dir {:?}",
-                node.rpc_port, node.data_path
-            );
-            node.start(&self.args.node_path, &self.args.rpc_path)
-                .await?;
-            self.nodes.push(node);
-        }
-        Ok(())
-    }
+        let tracker = Arc::new(LatencyTracker::new(nodes.len()));
+        observe_blocks(&nodes, &tracker);
+        primary.stats.clear();
+
+        println!(
+            "Generating load for {}s...",
+            self.scenario.duration().as_secs()
+        );
+        let mut monitor = ResourceMonitor::start(primary.clone());
+        let started = Instant::now();
+        let result = self.generate_load(&primary, &mut generator, &tracker);
+        let load_time = started.elapsed();
 
-    async fn wait_for_nodes_to_spin_up(&self) {
-        println!("Waiting for nodes to spin up...");
-        sleep(Duration::from_secs(7)).await;
+        println!("Waiting for confirmations...");
+        self.wait_for_confirmations(&tracker);
+        let total_time = started.elapsed();
+        monitor.stop();
+
+        let confirm_times = tracker.confirm_times();
+        let confirmed = confirm_times.len();
+        Ok(Report {
+            scenario: self.scenario.name.clone(),
+            node_count: nodes.len(),
+            accounts: self.scenario.accounts,
+            load_secs: load_time.as_secs_f64(),
+            total_secs: total_time.as_secs_f64(),
+            submitted: result.submitted,
+            dropped: result.dropped,
+            skipped: result.skipped,
+            disabled_accounts: generator.disabled_accounts(),
+            confirmed,
+            unconfirmed: tracker.unconfirmed(),
+            confirmed_tps: confirmed as f64 / total_time.as_secs_f64(),
+            blocks: tracker
+                .counts_by_kind()
+                .into_iter()
+                .map(|(kind, counts)| {
+                    (
+                        kind.as_str().to_string(),
+                        KindReport {
+                            submitted: counts.submitted,
+                            published: counts.published,
+                            confirmed: counts.confirmed,
+                        },
+                    )
+                })
+                .collect(),
+            time_to_publish_ms: Percentiles::from_durations(tracker.publish_times()),
+            time_to_confirm_ms: Percentiles::from_durations(confirm_times),
+            cpu: monitor.cpu(),
+            queues: monitor.queues(),
+            stats: monitor.stats(),
+        })
     }
 
-    async fn connect_nodes(&self) -> Result<()> {
-        println!("Connecting nodes...");
-        for node in &self.nodes {
-            self.primary_node().connect(node).await?;
+    /// Funds and opens all load accounts and waits until that is confirmed everywhere
+    fn fund_accounts(&self, nodes: &[Arc<Node>], generator: &mut BlockGenerator) -> Result<()> {
+        println!("Funding {} accounts...", self.scenario.accounts);
+        let primary = &nodes[0];
+        let blocks = generator.funding_blocks();
+        for block in &blocks {
+            match primary.process_local(block.clone()) {
+                Some(BlockStatus::Progress) => {}
+                status => bail!("could not process funding block: {:?}", status),
+            }
         }
-        Ok(())
-    }
 
-    async fn create_send_and_receive_blocks(&self) -> Result<HashMap<Account, AccountInfoDto>> {
-        println!("Beginning tests");
-        self.primary_node()
-            .create_send_and_receive_blocks(
-                self.args.destination_count,
-                self.args.send_count,
-                self.args.simultaneous_process_calls,
-            )
-            .await
+        let start = Instant::now();
+        while !primary.blocks_confirmed(&blocks)
+            || !nodes.iter().all(|node| node.blocks_exist(&blocks))
+        {
+            if start.elapsed() > self.scenario.confirm_timeout() {
+                bail!("funding blocks were not confirmed in time");
+            }
+            sleep(Duration::from_millis(100));
+        }
+        Ok(())
     }
 
-    async fn wait_for_nodes_to_catch_up(
+    fn generate_load(
         &self,
-        expected_account_info: &HashMap<Account, AccountInfoDto>,
-    ) -> Result<()> {
-        println!("Waiting for nodes to catch up...");
-        let timer = Instant::now();
-        for node in &self.nodes[1..] {
-            for (account, info) in expected_account_info {
-                loop {
-                    if let Ok(other_account_info) = node.account_info(*account).await {
-                        if info == &other_account_info {
-                            // Found the account in this node
-                            break;
-                        }
-                    }
-                    if timer.elapsed() > Duration::from_secs(120) {
-                        bail!("Timed out");
-                    }
-
-                    sleep(Duration::from_secs(1)).await;
+        primary: &Node,
+        generator: &mut BlockGenerator,
+        tracker: &LatencyTracker,
+    ) -> LoadResult {
+        let mut result = LoadResult::default();
+        let mut rng = rand::thread_rng();
+        let started = Instant::now();
+        let mut next_submission = started;
+
+        while started.elapsed() < self.scenario.duration() {
+            let now = Instant::now();
+            if now < next_submission {
+                sleep(next_submission - now);
+            }
+            let tps = self.scenario.rate.tps_at(started.elapsed()).max(MIN_TPS);
+            next_submission += Duration::from_secs_f64(1.0 / tps);
+
+            let kind = self.scenario.mix.pick(&mut rng);
+            let Some(generated) = generator.next(kind) else {
+                *result.skipped.entry(kind.as_str().to_string()).or_default() += 1;
+                continue;
+            };
+
+            for block in generated.blocks {
+                let hash = block.hash();
+                tracker.submitted(hash, generated.kind);
+                let added = primary.block_processor.add(
+                    Arc::new(block.clone()),
+                    BlockSource::Local,
+                    ChannelId::LOOPBACK,
+                );
+                if added {
+                    result.submitted += 1;
+                } else {
+                    tracker.remove(&hash);
+                    generator.disable(&block);
+                    result.dropped += 1;
                 }
             }
         }
-        println!("Done!");
-        Ok(())
+
+        result
     }
 
-    async fn stop_nodes(mut self) -> Result<()> {
-        for node in self.nodes.iter_mut() {
-            node.stop().await?;
+    fn wait_for_confirmations(&self, tracker: &LatencyTracker) {
+        let start = Instant::now();
+        while tracker.unconfirmed() > 0 && start.elapsed() < self.scenario.confirm_timeout() {
+            sleep(Duration::from_millis(100));
         }
-        Ok(())
     }
 }
+
+fn observe_blocks(nodes: &[Arc<Node>], tracker: &Arc<LatencyTracker>) {
+    for node in nodes {
+        let tracker_l = tracker.clone();
+        node.block_processor
+            .add_block_processed_observer(Box::new(move |status, context| {
+                if status == BlockStatus::Progress {
+                    tracker_l.processed(&context.block.hash());
+                }
+            }));
+    }
+
+    let tracker_l = tracker.clone();
+    nodes[0]
+        .confirming_set
+        .add_cemented_observer(Box::new(move |block| tracker_l.cemented(&block.hash())));
+}
@@ -1,19 +1,34 @@
-mod block_factory;
+mod block_generator;
+mod latency_tracker;
 mod load_test;
 mod program_args;
-mod test_node;
+mod report;
+mod resource_monitor;
+mod scenario;
 
 use anyhow::Result;
-pub use block_factory::*;
+pub use block_generator::*;
+pub use latency_tracker::*;
 use load_test::*;
 pub use program_args::*;
+pub use report::*;
+pub use resource_monitor::*;
 use rsnano_node::config::force_nano_dev_network;
-pub use test_node::*;
+pub use scenario::*;
 
-#[tokio::main]
-async fn main() -> Result<()> {
+fn main() -> Result<()> {
     force_nano_dev_network();
     let args = ProgramArgs::parse()?;
-    args.validate_paths()?;
-    LoadTest::new(args).run().await
+    let scenario = match &args.scenario_path {
+        Some(path) => Scenario::load(path)?,
+        None => Scenario::default(),
+    };
+
+    let report = LoadTest::new(scenario).run()?;
+    report.print();
+
+    if let Some(path) = &args.json_path {
+        std::fs::write(path, serde_json::to_string_pretty(&report)?)?;
+    }
+    Ok(())
 }
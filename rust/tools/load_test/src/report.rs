@@ -0,0 +1,163 @@
+use serde::Serialize;
+use std::{collections::BTreeMap, time::Duration};
+
+/// Machine readable result of a load test run
+#[derive(Serialize, Debug)]
+pub struct Report {
+    pub scenario: String,
+    pub node_count: usize,
+    pub accounts: usize,
+    /// Time in which blocks were submitted
+    pub load_secs: f64,
+    /// Time from the start of the load until the last confirmation
+    pub total_secs: f64,
+    pub submitted: usize,
+    /// Blocks that the block processor of the primary node rejected because its queue was full
+    pub dropped: usize,
+    /// Block kinds that could not be generated, because no account was in a suitable state
+    pub skipped: BTreeMap<String, usize>,
+    /// Accounts that were not used anymore after a fork or a dropped block
+    pub disabled_accounts: usize,
+    pub confirmed: usize,
+    /// Submitted blocks that were still unconfirmed when the confirm timeout was reached
+    pub unconfirmed: usize,
+    /// Confirmed blocks per second over the total time
+    pub confirmed_tps: f64,
+    pub blocks: BTreeMap<String, KindReport>,
+    pub time_to_publish_ms: Percentiles,
+    pub time_to_confirm_ms: Percentiles,
+    pub cpu: CpuReport,
+    /// Queue depths of the primary node, sampled during the run
+    pub queues: BTreeMap<String, QueueReport>,
+    /// Counters of the primary node
+    pub stats: BTreeMap<String, u64>,
+}
+
+impl Report {
+    pub fn print(&self) {
+        println!("Scenario: {}", self.scenario);
+        println!(
+            "Submitted {} blocks in {:.1}s ({} dropped), {} confirmed, {} unconfirmed",
+            self.submitted, self.load_secs, self.dropped, self.confirmed, self.unconfirmed
+        );
+        println!("Confirmed TPS: {:.1}", self.confirmed_tps);
+        for (kind, counts) in &self.blocks {
+            println!(
+                "  {:<8} submitted {:>7}  published {:>7}  confirmed {:>7}",
+                kind, counts.submitted, counts.published, counts.confirmed
+            );
+        }
+        for (kind, count) in &self.skipped {
+            println!("  {:<8} skipped {}", kind, count);
+        }
+        println!("Disabled accounts: {}", self.disabled_accounts);
+        println!("Time to publish: {}", self.time_to_publish_ms);
+        println!("Time to confirm: {}", self.time_to_confirm_ms);
+        println!(
+            "CPU: {:.1}s ({:.0}% of one core)",
+            self.cpu.process_secs, self.cpu.utilization_percent
+        );
+        println!("Queue depths (avg / max):");
+        for (name, queue) in &self.queues {
+            println!("  {:<20} {:>8.1} / {}", name, queue.avg, queue.max);
+        }
+        println!("Stats:");
+        for (name, count) in &self.stats {
+            println!("  {:<30} {}", name, count);
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Default, PartialEq, Eq)]
+pub struct KindReport {
+    pub submitted: usize,
+    pub published: usize,
+    pub confirmed: usize,
+}
+
+#[derive(Serialize, Debug, Default, PartialEq)]
+pub struct Percentiles {
+    pub count: usize,
+    pub p50: f64,
+    pub p90: f64,
+    pub p99: f64,
+    pub max: f64,
+}
+
+impl Percentiles {
+    /// Nearest-rank percentiles in milliseconds
+    pub fn from_durations(mut durations: Vec<Duration>) -> Self {
+        if durations.is_empty() {
+            return Self::default();
+        }
+        durations.sort_unstable();
+        let percentile = |p: usize| {
+            let rank = (p * durations.len()).div_ceil(100);
+            as_millis(durations[rank.saturating_sub(1)])
+        };
+        Self {
+            count: durations.len(),
+            p50: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+            max: as_millis(*durations.last().unwrap()),
+        }
+    }
+}
+
+impl std::fmt::Display for Percentiles {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "p50 {:.0}ms, p90 {:.0}ms, p99 {:.0}ms, max {:.0}ms ({} blocks)",
+            self.p50, self.p90, self.p99, self.max, self.count
+        )
+    }
+}
+
+fn as_millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct CpuReport {
+    /// CPU time used by the whole process (all nodes and the load generator)
+    pub process_secs: f64,
+    /// CPU time relative to the wall clock time. Can exceed 100 on multi core machines
+    pub utilization_percent: f64,
+}
+
+#[derive(Serialize, Debug, Default, PartialEq)]
+pub struct QueueReport {
+    pub avg: f64,
+    pub max: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles_of_empty_list() {
+        assert_eq!(
+            Percentiles::from_durations(Vec::new()),
+            Percentiles::default()
+        );
+    }
+
+    #[test]
+    fn percentiles() {
+        let durations = (1..=100).rev().map(Duration::from_millis).collect();
+        let percentiles = Percentiles::from_durations(durations);
+        assert_eq!(
+            percentiles,
+            Percentiles {
+                count: 100,
+                p50: 50.0,
+                p90: 90.0,
+                p99: 99.0,
+                max: 100.0
+            }
+        );
+    }
+}
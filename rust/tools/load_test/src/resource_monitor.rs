@@ -0,0 +1,174 @@
+use crate::{CpuReport, QueueReport};
+use rsnano_node::{
+    stats::{Direction, StatType},
+    Node,
+};
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Counters of the primary node that are included in the report
+const REPORTED_STATS: [StatType; 9] = [
+    StatType::Blockprocessor,
+    StatType::BlockprocessorOverfill,
+    StatType::Ledger,
+    StatType::Rollback,
+    StatType::ActiveElectionsConfirmed,
+    StatType::ActiveElectionsDropped,
+    StatType::ActiveElectionsTimeout,
+    StatType::ConfirmingSet,
+    StatType::Drop,
+];
+
+#[derive(Default)]
+struct QueueSamples {
+    sum: usize,
+    max: usize,
+    count: usize,
+}
+
+impl QueueSamples {
+    fn add(&mut self, depth: usize) {
+        self.sum += depth;
+        self.max = self.max.max(depth);
+        self.count += 1;
+    }
+
+    fn report(&self) -> QueueReport {
+        QueueReport {
+            avg: if self.count == 0 {
+                0.0
+            } else {
+                self.sum as f64 / self.count as f64
+            },
+            max: self.max,
+        }
+    }
+}
+
+/// Samples the queue depths of a node in a background thread and measures the CPU usage
+pub struct ResourceMonitor {
+    node: Arc<Node>,
+    started: Instant,
+    cpu_at_start: Option<Duration>,
+    samples: Arc<Mutex<BTreeMap<&'static str, QueueSamples>>>,
+    stopped: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ResourceMonitor {
+    pub fn start(node: Arc<Node>) -> Self {
+        let samples = Arc::new(Mutex::new(BTreeMap::new()));
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        let thread = {
+            let node = node.clone();
+            let samples = samples.clone();
+            let stopped = stopped.clone();
+            std::thread::Builder::new()
+                .name("Load monitor".to_string())
+                .spawn(move || {
+                    while !stopped.load(Ordering::SeqCst) {
+                        sample_queues(&node, &mut samples.lock().unwrap());
+                        std::thread::sleep(SAMPLE_INTERVAL);
+                    }
+                })
+                .unwrap()
+        };
+
+        Self {
+            node,
+            started: Instant::now(),
+            cpu_at_start: process_cpu_time(),
+            samples,
+            stopped,
+            thread: Some(thread),
+        }
+    }
+
+    pub fn stop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            thread.join().unwrap();
+        }
+    }
+
+    pub fn queues(&self) -> BTreeMap<String, QueueReport> {
+        self.samples
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(name, samples)| (name.to_string(), samples.report()))
+            .collect()
+    }
+
+    pub fn cpu(&self) -> CpuReport {
+        let used = process_cpu_time()
+            .zip(self.cpu_at_start)
+            .map(|(now, start)| now.saturating_sub(start))
+            .unwrap_or_default();
+        let wall = self.started.elapsed();
+        CpuReport {
+            process_secs: used.as_secs_f64(),
+            utilization_percent: if wall.is_zero() {
+                0.0
+            } else {
+                used.as_secs_f64() / wall.as_secs_f64() * 100.0
+            },
+        }
+    }
+
+    pub fn stats(&self) -> BTreeMap<String, u64> {
+        REPORTED_STATS
+            .iter()
+            .map(|stat_type| {
+                (
+                    stat_type.as_str().to_string(),
+                    self.node.stats.count_all(*stat_type, Direction::In),
+                )
+            })
+            .collect()
+    }
+}
+
+impl Drop for ResourceMonitor {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn sample_queues(node: &Node, samples: &mut BTreeMap<&'static str, QueueSamples>) {
+    let depths = [
+        ("block_processor", node.block_processor.total_queue_len()),
+        ("active_elections", node.active.len()),
+        ("confirming_set", node.confirming_set.len()),
+        ("vote_processor", node.vote_processor_queue.len()),
+    ];
+    for (name, depth) in depths {
+        samples.entry(name).or_default().add(depth);
+    }
+}
+
+/// User and system CPU time of this process. Only available on Linux
+fn process_cpu_time() -> Option<Duration> {
+    // The kernel reports the times in clock ticks, which are 1/100s on all common platforms
+    const TICKS_PER_SEC: u64 = 100;
+
+    let stat = std::fs::read_to_string("/proc/self/stat").ok()?;
+    // The process name can contain spaces, so we start parsing after its closing parenthesis
+    let fields: Vec<&str> = stat.rsplit_once(')')?.1.split_whitespace().collect();
+    // utime and stime are fields 14 and 15, the first field after the name is field 3
+    let utime: u64 = fields.get(11)?.parse().ok()?;
+    let stime: u64 = fields.get(12)?.parse().ok()?;
+    Some(Duration::from_millis(
+        (utime + stime) * 1000 / TICKS_PER_SEC,
+    ))
+}
@@ -0,0 +1,280 @@
+use anyhow::{bail, Context, Result};
+use rand::Rng;
+use serde::Deserialize;
+use std::{path::Path, time::Duration};
+
+/// Describes the load that gets generated. Can be loaded from a TOML file:
+///
+/// ```toml
+/// name = "mixed"
+/// node_count = 3
+/// accounts = 100
+/// duration_secs = 60
+///
+/// [rate]
+/// start_tps = 10
+/// end_tps = 200
+/// ramp_secs = 30
+///
+/// [mix]
+/// send = 60
+/// receive = 30
+/// change = 8
+/// fork = 2
+/// ```
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Scenario {
+    pub name: String,
+    /// Number of in-process nodes. The first node holds the genesis key and
+    /// is the one all blocks are submitted to
+    pub node_count: usize,
+    /// Number of accounts that get funded by genesis and then used for the load
+    pub accounts: usize,
+    /// How long blocks get submitted
+    pub duration_secs: u64,
+    /// How long to wait for outstanding confirmations after the load stopped
+    pub confirm_timeout_secs: u64,
+    pub rate: RateRamp,
+    pub mix: BlockMix,
+}
+
+impl Scenario {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("could not read scenario file {:?}", path))?;
+        let mut scenario: Scenario = toml::from_str(&content)
+            .with_context(|| format!("could not parse scenario file {:?}", path))?;
+        if scenario.name.is_empty() {
+            if let Some(stem) = path.file_stem() {
+                scenario.name = stem.to_string_lossy().into_owned();
+            }
+        }
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.node_count == 0 {
+            bail!("node_count must be at least 1");
+        }
+        if self.accounts < 2 {
+            bail!("at least 2 accounts are required");
+        }
+        if self.rate.start_tps <= 0.0 && self.rate.end_tps <= 0.0 {
+            bail!("start_tps or end_tps must be greater than 0");
+        }
+        if self.mix.total() == 0 {
+            bail!("block mix must not be empty");
+        }
+        Ok(())
+    }
+
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs(self.duration_secs)
+    }
+
+    pub fn confirm_timeout(&self) -> Duration {
+        Duration::from_secs(self.confirm_timeout_secs)
+    }
+}
+
+impl Default for Scenario {
+    fn default() -> Self {
+        Self {
+            name: "default".to_string(),
+            node_count: 2,
+            accounts: 20,
+            duration_secs: 30,
+            confirm_timeout_secs: 60,
+            rate: RateRamp::default(),
+            mix: BlockMix::default(),
+        }
+    }
+}
+
+/// The target rate grows linearly from `start_tps` to `end_tps` within `ramp_secs`
+/// and stays at `end_tps` afterwards
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RateRamp {
+    pub start_tps: f64,
+    pub end_tps: f64,
+    pub ramp_secs: u64,
+}
+
+impl RateRamp {
+    pub fn tps_at(&self, elapsed: Duration) -> f64 {
+        if self.ramp_secs == 0 {
+            return self.end_tps;
+        }
+        let progress = (elapsed.as_secs_f64() / self.ramp_secs as f64).min(1.0);
+        self.start_tps + (self.end_tps - self.start_tps) * progress
+    }
+}
+
+impl Default for RateRamp {
+    fn default() -> Self {
+        Self {
+            start_tps: 10.0,
+            end_tps: 50.0,
+            ramp_secs: 10,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum BlockKind {
+    Send,
+    Receive,
+    Change,
+    Fork,
+}
+
+impl BlockKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BlockKind::Send => "send",
+            BlockKind::Receive => "receive",
+            BlockKind::Change => "change",
+            BlockKind::Fork => "fork",
+        }
+    }
+}
+
+/// Relative weights of the generated block kinds
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BlockMix {
+    pub send: u32,
+    pub receive: u32,
+    pub change: u32,
+    pub fork: u32,
+}
+
+impl BlockMix {
+    pub fn total(&self) -> u32 {
+        self.send + self.receive + self.change + self.fork
+    }
+
+    pub fn pick(&self, rng: &mut impl Rng) -> BlockKind {
+        self.kind_at(rng.gen_range(0..self.total()))
+    }
+
+    fn kind_at(&self, mut value: u32) -> BlockKind {
+        for (kind, weight) in [
+            (BlockKind::Send, self.send),
+            (BlockKind::Receive, self.receive),
+            (BlockKind::Change, self.change),
+        ] {
+            if value < weight {
+                return kind;
+            }
+            value -= weight;
+        }
+        BlockKind::Fork
+    }
+}
+
+impl Default for BlockMix {
+    fn default() -> Self {
+        Self {
+            send: 60,
+            receive: 35,
+            change: 5,
+            fork: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_scenario() {
+        let toml = r#"
+            name = "mixed"
+            node_count = 3
+            accounts = 100
+            duration_secs = 60
+
+            [rate]
+            start_tps = 10
+            end_tps = 200
+            ramp_secs = 30
+
+            [mix]
+            send = 60
+            receive = 30
+            change = 8
+            fork = 2
+        "#;
+        let scenario: Scenario = toml::from_str(toml).unwrap();
+        assert_eq!(scenario.name, "mixed");
+        assert_eq!(scenario.node_count, 3);
+        assert_eq!(scenario.accounts, 100);
+        assert_eq!(scenario.confirm_timeout_secs, 60);
+        assert_eq!(scenario.rate.end_tps, 200.0);
+        assert_eq!(scenario.mix.fork, 2);
+        assert!(scenario.validate().is_ok());
+    }
+
+    #[test]
+    fn missing_values_use_defaults() {
+        let scenario: Scenario = toml::from_str("accounts = 5").unwrap();
+        assert_eq!(
+            scenario,
+            Scenario {
+                accounts: 5,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn reject_unknown_fields() {
+        assert!(toml::from_str::<Scenario>("unknown = 1").is_err());
+    }
+
+    #[test]
+    fn empty_mix_is_invalid() {
+        let scenario = Scenario {
+            mix: BlockMix {
+                send: 0,
+                receive: 0,
+                change: 0,
+                fork: 0,
+            },
+            ..Default::default()
+        };
+        assert!(scenario.validate().is_err());
+    }
+
+    #[test]
+    fn rate_ramp() {
+        let ramp = RateRamp {
+            start_tps: 10.0,
+            end_tps: 110.0,
+            ramp_secs: 10,
+        };
+        assert_eq!(ramp.tps_at(Duration::ZERO), 10.0);
+        assert_eq!(ramp.tps_at(Duration::from_secs(5)), 60.0);
+        assert_eq!(ramp.tps_at(Duration::from_secs(10)), 110.0);
+        assert_eq!(ramp.tps_at(Duration::from_secs(100)), 110.0);
+    }
+
+    #[test]
+    fn mix_weights() {
+        let mix = BlockMix {
+            send: 2,
+            receive: 1,
+            change: 0,
+            fork: 1,
+        };
+        assert_eq!(mix.kind_at(0), BlockKind::Send);
+        assert_eq!(mix.kind_at(1), BlockKind::Send);
+        assert_eq!(mix.kind_at(2), BlockKind::Receive);
+        assert_eq!(mix.kind_at(3), BlockKind::Fork);
+    }
+}
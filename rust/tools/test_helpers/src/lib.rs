@@ -17,6 +17,7 @@ use rsnano_rpc_client::{NanoRpcClient, Url};
 use rsnano_rpc_server::run_rpc_server;
 use std::{
     net::{IpAddr, Ipv6Addr, SocketAddr, TcpListener},
+    path::PathBuf,
     sync::{
         atomic::{AtomicU16, Ordering},
         Arc, OnceLock,
@@ -25,6 +26,10 @@ use std::{
     time::{Duration, Instant},
 };
 use tracing_subscriber::EnvFilter;
+
+mod simulator;
+pub use simulator::*;
+
 pub struct System {
     runtime: Arc<AsyncRuntime>,
     network_params: NetworkParams,
@@ -63,6 +68,7 @@ impl System {
             config: None,
             flags: None,
             disconnected: false,
+            data_path: None,
         }
     }
 
@@ -79,8 +85,9 @@ impl System {
         config: NodeConfig,
         flags: NodeFlags,
         disconnected: bool,
+        data_path: Option<PathBuf>,
     ) -> Arc<Node> {
-        let node = self.new_node(config, flags);
+        let node = self.new_node(config, flags, data_path);
         let wallet_id = WalletId::random();
         node.wallets.create(wallet_id);
         node.start();
@@ -119,8 +126,13 @@ impl System {
         node
     }
 
-    fn new_node(&self, config: NodeConfig, flags: NodeFlags) -> Arc<Node> {
-        let path = unique_path().expect("Could not get a unique path");
+    fn new_node(
+        &self,
+        config: NodeConfig,
+        flags: NodeFlags,
+        data_path: Option<PathBuf>,
+    ) -> Arc<Node> {
+        let path = data_path.unwrap_or_else(|| unique_path().expect("Could not get a unique path"));
         let node = NodeBuilder::new(self.network_params.network.current_network)
             .runtime(self.runtime.tokio.handle().clone())
             .data_path(path)
@@ -153,6 +165,7 @@ pub struct TestNodeBuilder<'a> {
     config: Option<NodeConfig>,
     flags: Option<NodeFlags>,
     disconnected: bool,
+    data_path: Option<PathBuf>,
 }
 
 impl<'a> TestNodeBuilder<'a> {
@@ -171,10 +184,18 @@ impl<'a> TestNodeBuilder<'a> {
         self
     }
 
+    /// Uses an existing data directory instead of a new one.
+    /// The directory is deleted when the system is dropped
+    pub fn data_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.data_path = Some(path.into());
+        self
+    }
+
     pub fn finish(self) -> Arc<Node> {
         let config = self.config.unwrap_or_else(|| System::default_config());
         let flags = self.flags.unwrap_or_default();
-        self.system.make_node_with(config, flags, self.disconnected)
+        self.system
+            .make_node_with(config, flags, self.disconnected, self.data_path)
     }
 }
 
@@ -0,0 +1,538 @@
+use crate::init_tracing;
+use rsnano_core::{work::WorkPoolImpl, Amount, Networks, WalletId};
+use rsnano_messages::Message;
+use rsnano_network::{ChannelDirection, ChannelId, ChannelInfo, ChannelMode};
+use rsnano_node::{
+    config::{NodeConfig, NodeFlags},
+    unique_path,
+    utils::AsyncRuntime,
+    wallets::WalletsExt,
+    NetworkParams, Node, NodeBuilder, NodeCallbacks, NodeExt,
+};
+use rsnano_nullable_clock::{SteadyClock, Timestamp, VirtualClock};
+use rsnano_nullable_tcp::TcpStream;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    net::{Ipv6Addr, SocketAddrV6},
+    sync::{Arc, Mutex},
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+pub type SimNodeId = usize;
+
+/// Pseudo random numbers (splitmix64). The schedule of a `VirtualNetwork` is fully
+/// described by its seed
+pub struct SimRng(u64);
+
+impl SimRng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniformly distributed in [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinkConfig {
+    pub latency: Duration,
+    /// A random delay between zero and `jitter` is added to the latency
+    pub jitter: Duration,
+    /// Probability that a message gets lost, between 0 and 1
+    pub loss: f64,
+}
+
+impl Default for LinkConfig {
+    fn default() -> Self {
+        Self {
+            latency: Duration::from_millis(50),
+            jitter: Duration::ZERO,
+            loss: 0.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Delivery<T> {
+    pub from: SimNodeId,
+    pub to: SimNodeId,
+    pub payload: T,
+}
+
+/// Routes payloads between simulated nodes on a virtual clock. Latency, loss and
+/// ordering only depend on the seed and on the order of the `send` calls
+pub struct VirtualNetwork<T> {
+    clock: VirtualClock,
+    rng: SimRng,
+    default_link: LinkConfig,
+    links: HashMap<(SimNodeId, SimNodeId), LinkConfig>,
+    partitions: HashMap<SimNodeId, usize>,
+    offline: HashSet<SimNodeId>,
+    in_flight: BTreeMap<(Timestamp, u64), Delivery<T>>,
+    next_sequence: u64,
+    pub delivered: usize,
+    pub dropped: usize,
+}
+
+impl<T> VirtualNetwork<T> {
+    pub fn new(seed: u64, clock: VirtualClock) -> Self {
+        Self {
+            clock,
+            rng: SimRng::new(seed),
+            default_link: LinkConfig::default(),
+            links: HashMap::new(),
+            partitions: HashMap::new(),
+            offline: HashSet::new(),
+            in_flight: BTreeMap::new(),
+            next_sequence: 0,
+            delivered: 0,
+            dropped: 0,
+        }
+    }
+
+    pub fn now(&self) -> Timestamp {
+        self.clock.now()
+    }
+
+    pub fn set_default_link(&mut self, link: LinkConfig) {
+        self.default_link = link;
+    }
+
+    /// Configures the link between `a` and `b` in both directions
+    pub fn set_link(&mut self, a: SimNodeId, b: SimNodeId, link: LinkConfig) {
+        self.links.insert((a, b), link.clone());
+        self.links.insert((b, a), link);
+    }
+
+    /// Nodes can only reach nodes of the same group. Nodes which aren't listed form
+    /// a group of their own
+    pub fn partition(&mut self, groups: &[&[SimNodeId]]) {
+        self.partitions.clear();
+        for (group, nodes) in groups.iter().enumerate() {
+            for node in nodes.iter() {
+                self.partitions.insert(*node, group);
+            }
+        }
+    }
+
+    pub fn heal(&mut self) {
+        self.partitions.clear();
+    }
+
+    /// An offline node neither sends nor receives anything
+    pub fn set_online(&mut self, node: SimNodeId, online: bool) {
+        if online {
+            self.offline.remove(&node);
+        } else {
+            self.offline.insert(node);
+        }
+    }
+
+    pub fn can_reach(&self, from: SimNodeId, to: SimNodeId) -> bool {
+        if self.offline.contains(&from) || self.offline.contains(&to) {
+            return false;
+        }
+        if self.partitions.is_empty() {
+            return true;
+        }
+        match (self.partitions.get(&from), self.partitions.get(&to)) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// Returns false if the payload was lost
+    pub fn send(&mut self, from: SimNodeId, to: SimNodeId, payload: T) -> bool {
+        let link = self
+            .links
+            .get(&(from, to))
+            .unwrap_or(&self.default_link)
+            .clone();
+
+        // Always draw the same amount of random numbers, so that a change of the
+        // topology doesn't change the fate of unrelated messages
+        let loss_roll = self.rng.next_f64();
+        let jitter_roll = self.rng.next_f64();
+
+        if !self.can_reach(from, to) || loss_roll < link.loss {
+            self.dropped += 1;
+            return false;
+        }
+
+        let delay = link.latency + link.jitter.mul_f64(jitter_roll);
+        let deliver_at = self.clock.now() + delay;
+        self.in_flight.insert(
+            (deliver_at, self.next_sequence),
+            Delivery { from, to, payload },
+        );
+        self.next_sequence += 1;
+        true
+    }
+
+    /// Moves the clock forward and returns everything that arrived in the meantime
+    pub fn advance(&mut self, duration: Duration) -> Vec<Delivery<T>> {
+        self.clock.advance(duration);
+        let now = self.clock.now();
+        let mut arrived = Vec::new();
+        while let Some(entry) = self.in_flight.first_entry() {
+            if entry.key().0 > now {
+                break;
+            }
+            let delivery = entry.remove();
+            // The path could have been cut while the message was in flight
+            if self.can_reach(delivery.from, delivery.to) {
+                self.delivered += 1;
+                arrived.push(delivery);
+            } else {
+                self.dropped += 1;
+            }
+        }
+        arrived
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.len()
+    }
+}
+
+struct SimNode {
+    node: Arc<Node>,
+    /// Channels of this node and the simulated nodes they lead to
+    peers: HashMap<ChannelId, SimNodeId>,
+    /// The channel of this node which represents the given simulated node
+    channels: HashMap<SimNodeId, Arc<ChannelInfo>>,
+}
+
+type Outbox = Arc<Mutex<Vec<(SimNodeId, ChannelId, Message)>>>;
+
+/// Upper bound of the real time a step waits for the nodes to process their queues
+const MAX_SETTLE_TIME: Duration = Duration::from_secs(5);
+
+/// Runs real nodes in one process and connects them through a `VirtualNetwork`
+/// instead of TCP. All nodes share a virtual clock which only moves when the
+/// simulation is advanced, and every step waits until the nodes processed what
+/// was delivered to them before the clock moves on. So a slow machine doesn't
+/// change which timeouts fire. Only the network schedule is reproducible from the
+/// seed though: the nodes process messages concurrently on their own threads and
+/// use their own randomness, so the order of events within a step can differ
+/// between runs. Tests should only assert outcomes which don't depend on that order.
+pub struct Simulator {
+    runtime: Arc<AsyncRuntime>,
+    network_params: NetworkParams,
+    work: Arc<WorkPoolImpl>,
+    clock: VirtualClock,
+    network: VirtualNetwork<Message>,
+    nodes: Vec<SimNode>,
+    outbox: Outbox,
+    /// Virtual time which passes per simulation step
+    pub tick: Duration,
+}
+
+impl Simulator {
+    pub fn new(seed: u64) -> Self {
+        init_tracing();
+        let network_params = NetworkParams::new(Networks::NanoDevNetwork);
+        let clock = VirtualClock::new();
+        Self {
+            runtime: Arc::new(AsyncRuntime::default()),
+            work: Arc::new(WorkPoolImpl::new(
+                network_params.work.clone(),
+                1,
+                Duration::ZERO,
+            )),
+            network_params,
+            network: VirtualNetwork::new(seed, clock.clone()),
+            clock,
+            nodes: Vec::new(),
+            outbox: Arc::new(Mutex::new(Vec::new())),
+            tick: Duration::from_millis(10),
+        }
+    }
+
+    pub fn default_config() -> NodeConfig {
+        let network_params = NetworkParams::new(Networks::NanoDevNetwork);
+        // The port is only used for the virtual peer addresses. Incoming connections
+        // are disabled, so no node listens on a real socket
+        let mut config = NodeConfig::new(
+            Some(network_params.network.default_node_port),
+            &network_params,
+            1,
+        );
+        config.tcp_incoming_connections_max = 0;
+        config.representative_vote_weight_minimum = Amount::zero();
+        config.allow_local_peers = true;
+        config
+    }
+
+    pub fn default_flags() -> NodeFlags {
+        // Legacy bootstrap opens its own TCP connections
+        NodeFlags {
+            disable_legacy_bootstrap: true,
+            disable_lazy_bootstrap: true,
+            disable_wallet_bootstrap: true,
+            disable_bootstrap_listener: true,
+            disable_max_peers_per_ip: true,
+            disable_max_peers_per_subnetwork: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn add_node(&mut self) -> SimNodeId {
+        self.add_node_with(Self::default_config(), Self::default_flags())
+    }
+
+    /// Starts a node and connects it to all existing nodes
+    pub fn add_node_with(&mut self, config: NodeConfig, flags: NodeFlags) -> SimNodeId {
+        let id = self.nodes.len();
+        let outbox = self.outbox.clone();
+        let callbacks = NodeCallbacks::builder()
+            .on_publish(move |channel_id, message| {
+                outbox
+                    .lock()
+                    .unwrap()
+                    .push((id, channel_id, message.clone()));
+            })
+            .finish();
+
+        let node = NodeBuilder::new(self.network_params.network.current_network)
+            .runtime(self.runtime.tokio.handle().clone())
+            .data_path(unique_path().expect("Could not get a unique path"))
+            .config(config)
+            .network_params(self.network_params.clone())
+            .flags(flags)
+            .work(self.work.clone())
+            .callbacks(callbacks)
+            .steady_clock(Arc::new(SteadyClock::new_virtual(self.clock.clone())))
+            .finish()
+            .unwrap();
+        let node = Arc::new(node);
+        node.wallets.create(WalletId::random());
+        node.start();
+
+        self.nodes.push(SimNode {
+            node,
+            peers: HashMap::new(),
+            channels: HashMap::new(),
+        });
+
+        for other in 0..id {
+            self.connect(id, other);
+            self.connect(other, id);
+        }
+        id
+    }
+
+    fn virtual_address(id: SimNodeId, node: &Node) -> SocketAddrV6 {
+        SocketAddrV6::new(
+            Ipv6Addr::new(0xfd00, 0, 0, 0, 0, 0, 0, id as u16 + 1),
+            node.config.peering_port.unwrap_or_default(),
+            0,
+            0,
+        )
+    }
+
+    /// Creates the channel of `from` which leads to `to`
+    fn connect(&mut self, from: SimNodeId, to: SimNodeId) {
+        let peer = self.nodes[to].node.clone();
+        let peer_addr = Self::virtual_address(to, &peer);
+        let sim_node = &mut self.nodes[from];
+        let channel = sim_node
+            .node
+            .network
+            .add(
+                TcpStream::new_null_with_peer_addr(peer_addr),
+                ChannelDirection::Outbound,
+                ChannelMode::Realtime,
+            )
+            .expect("could not add simulated channel");
+        channel.info.set_mode(ChannelMode::Realtime);
+        channel.info.set_node_id(peer.node_id.public_key());
+        sim_node.peers.insert(channel.channel_id(), to);
+        sim_node.channels.insert(to, channel.info.clone());
+    }
+
+    pub fn node(&self, id: SimNodeId) -> &Arc<Node> {
+        &self.nodes[id].node
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn network(&mut self) -> &mut VirtualNetwork<Message> {
+        &mut self.network
+    }
+
+    pub fn now(&self) -> Timestamp {
+        self.clock.now()
+    }
+
+    /// Sends everything the nodes published, moves the virtual clock by one tick and
+    /// hands the arrived messages to the receiving nodes
+    pub fn step(&mut self) {
+        let published = std::mem::take(&mut *self.outbox.lock().unwrap());
+        for (from, channel_id, message) in published {
+            if let Some(&to) = self.nodes[from].peers.get(&channel_id) {
+                self.network.send(from, to, message);
+            }
+        }
+
+        for delivery in self.network.advance(self.tick) {
+            let receiver = &self.nodes[delivery.to];
+            if let Some(channel) = receiver.channels.get(&delivery.from) {
+                receiver
+                    .node
+                    .inbound_message_queue
+                    .put(delivery.payload, channel.clone());
+            }
+        }
+
+        self.settle();
+
+        // The links stay up during partitions, only the packets get lost
+        let now = self.clock.now();
+        for sim_node in &self.nodes {
+            for channel in sim_node.channels.values() {
+                channel.set_last_activity(now);
+            }
+        }
+    }
+
+    /// Waits until all nodes drained the queues which are fed by incoming messages.
+    /// A node which is stuck doesn't block the simulation forever
+    fn settle(&self) {
+        let start = Instant::now();
+        while !self.nodes.iter().all(|n| is_idle(&n.node)) && start.elapsed() < MAX_SETTLE_TIME {
+            sleep(Duration::from_micros(100));
+        }
+    }
+
+    /// Steps until the condition holds. Returns false if it didn't hold within
+    /// `timeout` of virtual time
+    pub fn run_until(
+        &mut self,
+        timeout: Duration,
+        mut condition: impl FnMut(&Self) -> bool,
+    ) -> bool {
+        let deadline = self.clock.now() + timeout;
+        while self.clock.now() < deadline {
+            if condition(self) {
+                return true;
+            }
+            self.step();
+        }
+        condition(self)
+    }
+
+    pub fn run_for(&mut self, duration: Duration) {
+        self.run_until(duration, |_| false);
+    }
+}
+
+fn is_idle(node: &Node) -> bool {
+    node.inbound_message_queue.size() == 0
+        && node.block_processor.total_queue_len() == 0
+        && node.vote_processor_queue.is_empty()
+        && node.request_aggregator.len() == 0
+        && node.confirming_set.len() == 0
+}
+
+impl Drop for Simulator {
+    fn drop(&mut self) {
+        for sim_node in &self.nodes {
+            sim_node.node.stop();
+            let _ = std::fs::remove_dir_all(&sim_node.node.data_path);
+        }
+        self.work.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule(seed: u64) -> Vec<(u32, i64)> {
+        let clock = VirtualClock::new();
+        let mut network = VirtualNetwork::new(seed, clock.clone());
+        network.set_default_link(LinkConfig {
+            latency: Duration::from_millis(10),
+            jitter: Duration::from_millis(100),
+            loss: 0.3,
+        });
+        for i in 0..100 {
+            network.send(0, 1, i);
+        }
+        let mut arrived = Vec::new();
+        for _ in 0..20 {
+            for delivery in network.advance(Duration::from_millis(10)) {
+                arrived.push((delivery.payload, i64::from(clock.now())));
+            }
+        }
+        arrived
+    }
+
+    #[test]
+    fn same_seed_same_schedule() {
+        assert_eq!(schedule(1), schedule(1));
+        assert_ne!(schedule(1), schedule(2));
+    }
+
+    #[test]
+    fn loss() {
+        let arrived = schedule(7).len();
+        assert!(arrived > 50 && arrived < 90, "arrived: {arrived}");
+    }
+
+    #[test]
+    fn latency() {
+        let mut network = VirtualNetwork::new(1, VirtualClock::new());
+        network.send(0, 1, "hello");
+        assert!(network.advance(Duration::from_millis(49)).is_empty());
+        assert_eq!(
+            network.advance(Duration::from_millis(1)),
+            vec![Delivery {
+                from: 0,
+                to: 1,
+                payload: "hello"
+            }]
+        );
+    }
+
+    #[test]
+    fn partition() {
+        let mut network = VirtualNetwork::new(1, VirtualClock::new());
+        network.partition(&[&[0, 1], &[2]]);
+        assert!(network.send(0, 1, 1));
+        assert!(!network.send(0, 2, 2));
+        assert!(!network.send(2, 1, 3));
+
+        network.heal();
+        assert!(network.send(2, 1, 4));
+        let arrived: Vec<_> = network
+            .advance(Duration::from_secs(1))
+            .into_iter()
+            .map(|d| d.payload)
+            .collect();
+        assert_eq!(arrived, vec![1, 4]);
+    }
+
+    #[test]
+    fn messages_in_flight_are_lost_when_partitioned() {
+        let mut network = VirtualNetwork::new(1, VirtualClock::new());
+        network.send(0, 1, 1);
+        network.set_online(1, false);
+        assert!(network.advance(Duration::from_secs(1)).is_empty());
+        assert_eq!(network.dropped, 1);
+    }
+}